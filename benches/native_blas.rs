@@ -0,0 +1,70 @@
+#![feature(test)]
+
+extern crate test;
+extern crate collenchyma as co;
+
+use test::Bencher;
+use co::backend::{Backend, BackendConfig};
+use co::framework::IFramework;
+use co::frameworks::Native;
+use co::frameworks::native::libraries::set_threshold;
+use co::libraries::blas::*;
+use co::memory::MemoryType;
+use co::shared_memory::SharedMemory;
+use std::usize;
+
+const LEN: usize = 10_000_000;
+
+fn native_backend() -> Backend<Native> {
+    Backend::new(BackendConfig::all(Native::new())).unwrap()
+}
+
+fn filled(backend: &Backend<Native>, len: usize, value: f32) -> SharedMemory<f32> {
+    let mut mem = SharedMemory::<f32>::new(backend.device(), len);
+    if let &mut MemoryType::Native(ref mut flat) = mem.get_mut(backend.device()).unwrap() {
+        for datum in flat.as_mut_slice::<f32>().iter_mut() {
+            *datum = value;
+        }
+    }
+    mem
+}
+
+fn bench_dot(b: &mut Bencher, threshold: usize) {
+    let backend = native_backend();
+    let mut x = filled(&backend, LEN, 1f32);
+    let mut y = filled(&backend, LEN, 2f32);
+    let mut result = filled(&backend, 1, 0f32);
+    set_threshold(threshold);
+    b.iter(|| backend.dot(&mut x, &mut y, &mut result).unwrap());
+    set_threshold(0);
+}
+
+fn bench_axpy(b: &mut Bencher, threshold: usize) {
+    let backend = native_backend();
+    let mut a = filled(&backend, 1, 2f32);
+    let mut x = filled(&backend, LEN, 1f32);
+    let mut y = filled(&backend, LEN, 0f32);
+    set_threshold(threshold);
+    b.iter(|| backend.axpy(&mut a, &mut x, &mut y).unwrap());
+    set_threshold(0);
+}
+
+#[bench]
+fn bench_10m_dot_single_threaded(b: &mut Bencher) {
+    bench_dot(b, usize::MAX);
+}
+
+#[bench]
+fn bench_10m_dot_multi_threaded(b: &mut Bencher) {
+    bench_dot(b, 0);
+}
+
+#[bench]
+fn bench_10m_axpy_single_threaded(b: &mut Bencher) {
+    bench_axpy(b, usize::MAX);
+}
+
+#[bench]
+fn bench_10m_axpy_multi_threaded(b: &mut Bencher) {
+    bench_axpy(b, 0);
+}
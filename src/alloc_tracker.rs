@@ -0,0 +1,155 @@
+//! Provides the accounting of live device allocations.
+//!
+//! With the `alloc_tracking` feature, every allocation made through
+//! [DeviceType::alloc_memory_labeled][alloc] is recorded together with its size and a label,
+//! until the device memory is freed. Memory, which has been returned to a MemoryPool, stays
+//! listed under the label "pooled", as the device still holds on to it. The live allocations of
+//! a device can be listed with [Backend::memory_report][report], which helps to find out what
+//! holds on to device memory.
+//!
+//! Without the feature, the tracking compiles down to nothing.
+//!
+//! [alloc]: ../device/enum.DeviceType.html#method.alloc_memory_labeled
+//! [report]: ../backend/struct.Backend.html#method.memory_report
+
+use device::DeviceType;
+use memory::MemoryType;
+#[cfg(feature = "alloc_tracking")]
+use device::IDevice;
+#[cfg(feature = "alloc_tracking")]
+use framework::IFramework;
+#[cfg(feature = "alloc_tracking")]
+use frameworks::Native;
+#[cfg(all(feature = "alloc_tracking", feature = "opencl"))]
+use frameworks::OpenCL;
+#[cfg(all(feature = "alloc_tracking", any(feature = "cuda", feature = "cuda-runtime")))]
+use frameworks::Cuda;
+#[cfg(feature = "alloc_tracking")]
+use std::sync::{Mutex, Once, ONCE_INIT};
+
+#[cfg(feature = "alloc_tracking")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Describes a live allocation on a device.
+pub struct AllocInfo {
+    /// The size of the allocation in bytes.
+    pub size: usize,
+    /// Describes what the allocation was made for.
+    pub label: String,
+}
+
+#[cfg(feature = "alloc_tracking")]
+#[derive(Debug)]
+struct Entry {
+    /// The framework and the device address or handle of the allocation.
+    allocation: (&'static str, usize),
+    device: (&'static str, isize),
+    info: AllocInfo,
+}
+
+#[cfg(feature = "alloc_tracking")]
+#[derive(Debug, Default)]
+struct Registry {
+    entries: Vec<Entry>,
+}
+
+#[cfg(feature = "alloc_tracking")]
+static INIT: Once = ONCE_INIT;
+#[cfg(feature = "alloc_tracking")]
+static mut REGISTRY: *const Mutex<Registry> = 0 as *const Mutex<Registry>;
+
+#[cfg(feature = "alloc_tracking")]
+fn registry() -> &'static Mutex<Registry> {
+    unsafe {
+        INIT.call_once(|| {
+            REGISTRY = Box::into_raw(Box::new(Mutex::new(Registry::default())));
+        });
+        &*REGISTRY
+    }
+}
+
+/// Identifies a device by its framework and id, as the DeviceType itself can not be shared
+/// between threads with every framework.
+#[cfg(feature = "alloc_tracking")]
+fn device_key(device: &DeviceType) -> (&'static str, isize) {
+    let id = match *device {
+        DeviceType::Native(ref cpu) => cpu.id(),
+        #[cfg(feature = "opencl")]
+        DeviceType::OpenCL(ref context) => context.id(),
+        #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+        DeviceType::Cuda(ref context) => context.id(),
+    };
+    (device.framework_id(), id)
+}
+
+/// Returns the live allocations on the `device`, largest first.
+#[cfg(feature = "alloc_tracking")]
+pub fn report(device: &DeviceType) -> Vec<AllocInfo> {
+    let key = device_key(device);
+    let registry = registry().lock().unwrap();
+    let mut infos = registry.entries.iter()
+        .filter(|entry| entry.device == key)
+        .map(|entry| entry.info.clone())
+        .collect::<Vec<AllocInfo>>();
+    infos.sort_by(|a, b| b.size.cmp(&a.size));
+    infos
+}
+
+/// Identifies the device allocation, which `memory` and all of its views share.
+#[cfg(feature = "alloc_tracking")]
+fn allocation_key(memory: &MemoryType) -> (&'static str, usize) {
+    match *memory {
+        MemoryType::Native(ref mem) => (Native::ID, mem.allocation_id()),
+        #[cfg(feature = "opencl")]
+        MemoryType::OpenCL(ref mem) => (OpenCL::ID, mem.id_c() as usize),
+        #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+        MemoryType::Cuda(ref mem) => (Cuda::ID, mem.allocation_id()),
+    }
+}
+
+/// Records the allocation of `memory` of `size` bytes on the `device` under the result of `label`.
+///
+/// Replaces the label, if the allocation is tracked already, e.g. as it has been taken from a
+/// MemoryPool.
+#[cfg(feature = "alloc_tracking")]
+pub fn track<F: FnOnce() -> String>(device: &DeviceType, memory: &MemoryType, size: usize, label: F) {
+    let allocation = allocation_key(memory);
+    let info = AllocInfo { size: size, label: label() };
+    let mut registry = registry().lock().unwrap();
+    registry.entries.retain(|entry| entry.allocation != allocation);
+    registry.entries.push(Entry { allocation: allocation, device: device_key(device), info: info });
+}
+
+/// Replaces the label of the allocation of `memory`, if it is tracked.
+#[cfg(feature = "alloc_tracking")]
+pub fn relabel(memory: &MemoryType, label: &str) {
+    let allocation = allocation_key(memory);
+    let mut registry = registry().lock().unwrap();
+    for entry in registry.entries.iter_mut().filter(|entry| entry.allocation == allocation) {
+        entry.info.label = label.to_owned();
+    }
+}
+
+/// Forgets the allocation `id` of the `framework`, as it has been freed.
+///
+/// Called by the memory types of the frameworks, when they free device memory.
+#[cfg(feature = "alloc_tracking")]
+pub fn forget(framework: &'static str, id: usize) {
+    if let Ok(mut registry) = registry().lock() {
+        registry.entries.retain(|entry| entry.allocation != (framework, id));
+    }
+}
+
+/// Records nothing without the `alloc_tracking` feature.
+#[cfg(not(feature = "alloc_tracking"))]
+#[inline(always)]
+pub fn track<F: FnOnce() -> String>(_device: &DeviceType, _memory: &MemoryType, _size: usize, _label: F) {}
+
+/// Records nothing without the `alloc_tracking` feature.
+#[cfg(not(feature = "alloc_tracking"))]
+#[inline(always)]
+pub fn relabel(_memory: &MemoryType, _label: &str) {}
+
+/// Records nothing without the `alloc_tracking` feature.
+#[cfg(not(feature = "alloc_tracking"))]
+#[inline(always)]
+pub fn forget(_framework: &'static str, _id: usize) {}
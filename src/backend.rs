@@ -18,6 +18,25 @@
 //! [framework]: ../framework/index.html
 //! [program]: ../program/index.html
 //!
+//! ## Cloning
+//!
+//! Cloning a Backend does not create a new device. The clones share the Framework and the device
+//! state, such as the OpenCL context and command queue or the Cuda context, which are
+//! refcounted and released, when the last clone goes out of scope. Memory written through one
+//! clone is therefore visible through all the others. Create a new Backend from a BackendConfig
+//! to get a separate device.
+//!
+//! ## Threading
+//!
+//! A Backend is meant to be used from the thread, which created it. The GPU frameworks keep state
+//! per thread, e.g. Cuda makes a context current to the calling thread and OpenCL kernels hold
+//! their arguments between the calls, which set them and launch the kernel. Therefore
+//! `Backend<OpenCL>` and `Backend<Cuda>` are neither `Send` nor `Sync` and moving them to another
+//! thread is rejected at compile time. Create a separate Backend on every thread, which executes
+//! operations, instead.
+//!
+//! A `Backend<Native>` can be moved to another thread.
+//!
 //! ## Examples
 //!
 //! ```
@@ -38,15 +57,61 @@
 //!     let backend = Backend::new(backend_config);
 //! }
 //! ```
+//!
+//! The same Backend can be configured in one go through the BackendBuilder.
+//!
+//! ```
+//! extern crate collenchyma as co;
+//! use co::backend::BackendBuilder;
+//! use co::frameworks::Native;
+//! fn main() {
+//!     // Use the first hardware of the Framework. Without `device_index`, all hardwares are used.
+//!     let backend = BackendBuilder::new().framework(Native::new()).device_index(0).build();
+//! }
+//! ```
 
 use error::Error;
-use framework::IFramework;
-use frameworks::{Native, OpenCL, Cuda};
+use framework::{IFramework, Error as FrameworkError};
+use frameworks::Native;
+#[cfg(feature = "opencl")]
+use frameworks::OpenCL;
+#[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+use frameworks::Cuda;
+#[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+use frameworks::cuda::{ContextFlags, Context as CudaContext};
+#[cfg(feature = "test-util")]
+use frameworks::Mock;
+#[cfg(feature = "test-util")]
+use frameworks::native::Failure;
 use device::{IDevice, DeviceType};
-use libraries::blas::IBlas;
+use libraries::blas::{IBlas, BlasOp, ReduceOp, UnaryFn, BinaryOp, Transpose};
+#[cfg(feature = "opencl")]
+use libraries::blas::BlasNum;
+use libraries::{Element, ILibrary, Blas};
+#[cfg(feature = "f16")]
+use libraries::blas::Error as BlasError;
+#[cfg(feature = "f16")]
+use libraries::f16;
+#[cfg(feature = "f16")]
+use frameworks::native::libraries::half;
+#[cfg(feature = "f16")]
+use validate;
+#[cfg(all(feature = "f16", feature = "opencl"))]
+use frameworks::opencl::libraries::half as opencl_half;
+use shared_memory::SharedMemory;
+#[cfg(feature = "alloc_tracking")]
+use alloc_tracker::{self, AllocInfo};
+#[cfg(feature = "stats")]
+use stats::{self, BackendStats};
+use std::sync::Arc;
+use std::fmt;
+use std::time::Duration;
 
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 /// Defines the main and highest struct of Collenchyma.
+///
+/// Clones of a Backend share its Framework and device, see [Cloning][cloning].
+/// [cloning]: ./index.html#cloning
 pub struct Backend<F: IFramework> {
     /// Provides the Framework.
     ///
@@ -57,10 +122,33 @@ pub struct Backend<F: IFramework> {
     /// Default: [Native][native]
     ///
     /// [native]: ../frameworks/native/index.html
-    framework: Box<F>,
+    framework: Arc<F>,
     /// Provides a device, created from one or many hardwares, which are ready to execute kernel
     /// methods and synchronize memory.
     device: DeviceType,
+    /// Provides the binary, build for the device, which holds the executable operations.
+    binary: F::B,
+}
+
+impl<F: IFramework> Clone for Backend<F> {
+    /// Returns a new handle to the same Backend.
+    ///
+    /// The clone shares the Framework and the device with `self`, instead of initializing them
+    /// again.
+    fn clone(&self) -> Backend<F> {
+        Backend {
+            framework: self.framework.clone(),
+            device: self.device.clone(),
+            binary: self.binary.clone(),
+        }
+    }
+}
+
+impl<F: IFramework> fmt::Display for Backend<F> {
+    /// Prints the framework and the names of the hardwares, e.g. `Backend(CUDA: GeForce GTX 980)`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Backend({}: {})", F::ID, self.device.hardware_names().join(", "))
+    }
 }
 
 /// Defines the functionality of the Backend.
@@ -68,14 +156,52 @@ impl<F: IFramework + Clone> Backend<F> {
     /// Initialize a new native Backend from a BackendConfig.
     pub fn new(config: BackendConfig<F>) -> Result<Backend<F>, Error> {
         let device = try!(config.framework.new_device(config.hardwares));
+        let binary = try!(<Blas as ILibrary<F>>::new_binary(&config.framework, &device));
+        Ok(
+            Backend {
+                framework: Arc::new(config.framework),
+                device: device,
+                binary: binary,
+            }
+        )
+    }
+
+    /// Initializes a new Backend from a BackendConfig, whose device pools freed memory.
+    ///
+    /// Memory copies of a SharedMemory, which is dropped, are kept in a [MemoryPool][pool] of up
+    /// to `max_retained` bytes and handed out again on the next allocation of the same size,
+    /// instead of being freed through the driver. The Native framework is not pooled.
+    /// [pool]: ../memory_pool/index.html
+    pub fn with_pool(config: BackendConfig<F>, max_retained: usize) -> Result<Backend<F>, Error> {
+        let mut device = try!(config.framework.new_device(config.hardwares));
+        device.enable_pool(max_retained);
+        let binary = try!(<Blas as ILibrary<F>>::new_binary(&config.framework, &device));
         Ok(
             Backend {
-                framework: Box::new(config.framework),
+                framework: Arc::new(config.framework),
                 device: device,
+                binary: binary,
             }
         )
     }
 
+    /// Initializes a Backend from a BackendSpec for the Framework `F`.
+    ///
+    /// Returns an error, if the spec describes another Framework, the Framework is not available or
+    /// a selected hardware index is out of range. An empty selection uses all hardwares.
+    pub fn from_spec(spec: &BackendSpec) -> Result<Backend<F>, Error> {
+        if spec.framework != F::ID {
+            return Err(Error::Framework(FrameworkError::InvalidArgument(format!("The spec describes the framework {:?}, not {:?}.", spec.framework, F::ID))))
+        }
+        let framework = try!(F::try_new());
+        let config = if spec.device_indices.is_empty() {
+            BackendConfig::all(framework)
+        } else {
+            try!(BackendConfig::with_devices(framework, &spec.device_indices))
+        };
+        Backend::new(config)
+    }
+
     /// Returns the available hardware.
     pub fn hardwares(&self) -> Vec<F::H> {
         self.framework.hardwares()
@@ -83,7 +209,12 @@ impl<F: IFramework + Clone> Backend<F> {
 
     /// Returns the backend framework.
     pub fn framework(&self) -> Box<F> {
-        self.framework.clone()
+        Box::new((*self.framework).clone())
+    }
+
+    /// Returns the ID of the backend framework.
+    pub fn framework_id(&self) -> &'static str {
+        self.framework.framework_id()
     }
 
     /// Returns the backend device.
@@ -91,9 +222,515 @@ impl<F: IFramework + Clone> Backend<F> {
         &self.device
     }
 
+    /// Returns the number of bytes, which the MemoryPool of the backend device retains.
+    pub fn pooled_memory(&self) -> usize {
+        self.device.pooled_memory()
+    }
+
+    /// Frees all memory, which the MemoryPool of the backend device retains.
+    pub fn drain_pool(&self) {
+        self.device.drain_pool()
+    }
+
+    /// Returns the number of hardwares, which make up the backend device.
+    pub fn device_count(&self) -> usize {
+        self.device.hardware_names().len()
+    }
+
+    /// Returns the index of the hardware of the backend device, on which operations run.
+    pub fn active_device(&self) -> usize {
+        self.device.active_hardware()
+    }
+
+    /// Lets the operations of the Backend run on the hardware at `index` of the backend device.
+    ///
+    /// On OpenCL the operations are issued to the command queue of the selected hardware, for
+    /// which the BLAS binary gets rebuilt, so prefer switching between phases of work over
+    /// switching for every operation. Work on different hardwares is not ordered, synchronize
+    /// before switching, if they share memory. Only affects this Backend, not its clones or
+    /// LibraryBackends, which have been created before.
+    /// Returns an error, if `index` is not smaller than `device_count`.
+    pub fn set_active_device(&mut self, index: usize) -> Result<(), Error> {
+        if index >= self.device_count() {
+            return Err(Error::Framework(FrameworkError::MissingHardware(format!("No device at index {}; the backend device consists of {} hardwares.", index, self.device_count()))))
+        }
+        if index == self.active_device() {
+            return Ok(())
+        }
+        let mut device = self.device.clone();
+        try!(device.set_active_hardware(index));
+        self.binary = try!(<Blas as ILibrary<F>>::new_binary(&self.framework, &device));
+        self.device = device;
+        Ok(())
+    }
+
+    /// Returns the names of the hardwares, which make up the backend device.
+    ///
+    /// Allows to verify, which of the configured hardwares the framework initialized.
+    pub fn device_names(&self) -> Vec<String> {
+        self.device.hardware_names()
+    }
+
     /// Returns the blas binary.
     pub fn binary(&self) -> F::B {
-        self.framework().binary().clone()
+        self.binary.clone()
+    }
+
+    /// Blocks until all work, which was issued to the backend device, has been completed.
+    ///
+    /// Results of asynchronous operations, such as kernel executions and memory copies, are safe
+    /// to observe after `synchronize` returns.
+    pub fn synchronize(&self) -> Result<(), Error> {
+        Ok(try!(self.device.synchronize()))
+    }
+
+    /// Issues all queued work of the backend device to its hardware, without waiting for it.
+    ///
+    /// Ensures, that submitted work gets dispatched, e.g. before tearing down a Backend. Queued
+    /// work can not be cancelled: neither OpenCL nor Cuda allow to drop issued commands, and
+    /// releasing an OpenCL command queue completes its commands first. On Cuda and Native
+    /// `flush` does nothing, as their work is issued immediately.
+    pub fn flush(&self) -> Result<(), Error> {
+        Ok(try!(self.device.flush()))
+    }
+
+    /// Blocks until all work, which was issued to the backend device, has been completed or
+    /// until `timeout` has passed.
+    ///
+    /// Returns `Ok(false)`, if the work did not complete in time, e.g. because a kernel hangs.
+    /// The timeout is best-effort polling: the call may return a little after the deadline and
+    /// the pending work is not cancelled, so the device may stay busy afterwards.
+    pub fn synchronize_timeout(&self, timeout: Duration) -> Result<bool, Error> {
+        Ok(try!(self.device.synchronize_timeout(timeout)))
+    }
+
+    /// Moves the latest version of `mem` from the backend device to the `destination` device.
+    ///
+    /// The `destination` may belong to another Backend, even of another Framework. Without a
+    /// direct path between the devices, e.g. from Native or OpenCL to Cuda, the data gets staged
+    /// through the host. Afterwards `destination` holds the latest version of `mem`.
+    /// Returns an error, if `destination` is the backend device.
+    pub fn transfer<T>(&self, mem: &mut SharedMemory<T>, destination: &DeviceType) -> Result<(), Error> {
+        Ok(try!(mem.transfer(&self.device, destination)))
+    }
+}
+
+impl<F: IFramework + Clone> Backend<F> {
+    /// Builds the binary of the library `L` for the device of the Backend.
+    ///
+    /// The returned LibraryBackend shares the device with the Backend and holds the binary, so
+    /// keep it around instead of building the library for every call. See
+    /// [Writing a library][library].
+    /// [library]: ../libraries/index.html#writing-a-library
+    pub fn library<L: ILibrary<F>>(&self) -> Result<LibraryBackend<F, L>, Error> {
+        let binary = try!(L::new_binary(&self.framework, &self.device));
+        Ok(LibraryBackend { backend: self.clone(), binary: binary })
+    }
+
+    /// Returns whether the Backend implements the BLAS operation `op` for elements of type `T`.
+    ///
+    /// Coverage differs between the frameworks and devices, e.g. OpenCL computes double precision
+    /// only on devices with `cl_khr_fp64`, so portable code can check before calling an operation
+    /// and fall back otherwise, instead of handling the error or panic of the call.
+    ///
+    /// ```
+    /// # extern crate collenchyma;
+    /// use collenchyma::backend::{Backend, BackendConfig};
+    /// use collenchyma::framework::IFramework;
+    /// use collenchyma::frameworks::Native;
+    /// use collenchyma::libraries::blas::BlasOp;
+    /// # fn main() {
+    /// let backend = Backend::new(BackendConfig::all(Native::new())).unwrap();
+    /// assert!(backend.supports::<f32>(BlasOp::Gemm));
+    /// # }
+    /// ```
+    pub fn supports<T: Element>(&self, op: BlasOp) -> bool where Backend<F>: IBlas<T> {
+        IBlas::<T>::supports(self, op)
+    }
+}
+
+#[cfg(feature = "alloc_tracking")]
+impl<F: IFramework + Clone> Backend<F> {
+    /// Returns the live allocations on the backend device, largest first.
+    ///
+    /// Every allocation made through `DeviceType::alloc_memory`, e.g. the memory copies of a
+    /// SharedMemory, is listed with its size in bytes and a label, until the device memory is
+    /// freed. Memory retained by a MemoryPool is listed as "pooled". Views do not allocate and
+    /// are not listed.
+    /// Only available with the `alloc_tracking` feature.
+    pub fn memory_report(&self) -> Vec<AllocInfo> {
+        alloc_tracker::report(&self.device)
+    }
+}
+
+#[cfg(feature = "stats")]
+impl<F: IFramework + Clone> Backend<F> {
+    /// Returns the counters of the bytes transferred between host and devices, the kernel
+    /// launches and the bytes of device memory allocated.
+    ///
+    /// The counters are shared by all Backends of the process. Only available with the `stats`
+    /// feature.
+    pub fn stats(&self) -> BackendStats {
+        stats::snapshot()
+    }
+
+    /// Sets all counters back to zero.
+    ///
+    /// Only available with the `stats` feature.
+    pub fn reset_stats(&self) {
+        stats::reset()
+    }
+}
+
+impl<F: IFramework + Clone> Backend<F> where Backend<F>: IBlas<f32> {
+    /// Launches every single precision BLAS Operation once, so that later launches are hot.
+    ///
+    /// The first launch of a kernel pays for its compilation by the driver and for the lazy
+    /// allocation of device resources, which skews benchmarks and the latency of the first
+    /// request. Call `warm_up` once after initializing the Backend, e.g. during the startup of a
+    /// service. The Operations run on buffers of a single element, whose results are discarded.
+    /// Blocks until all launches have been completed.
+    pub fn warm_up(&self) -> Result<(), Error> {
+        let device = self.device();
+        let mut a = try!(SharedMemory::<f32>::try_new(device, 1));
+        let mut b = try!(SharedMemory::<f32>::try_new(device, 1));
+        let mut c = try!(SharedMemory::<f32>::try_new(device, 1));
+        let mut d = try!(SharedMemory::<f32>::try_new(device, 1));
+        let mut e = try!(SharedMemory::<f32>::try_new(device, 1));
+        let mut index = try!(SharedMemory::<i32>::try_new(device, 1));
+        try!(self.asum(&mut a, &mut b));
+        try!(self.iamax(&mut a, &mut index));
+        try!(self.axpy(&mut a, &mut b, &mut c));
+        try!(self.copy(&mut a, &mut b));
+        try!(self.dot(&mut a, &mut b, &mut c));
+        try!(self.nrm2(&mut a, &mut b));
+        try!(self.scale(&mut a, &mut b));
+        try!(self.swap(&mut a, &mut b));
+        try!(self.gemv(Transpose::NoTrans, &mut a, &mut b, &mut c, &mut d, &mut e, 1, 1));
+        try!(self.gemm(&mut a, Transpose::NoTrans, &mut b, Transpose::NoTrans, &mut c, &mut d, &mut e, 1, 1, 1));
+        try!(self.gemm_batched(&mut a, Transpose::NoTrans, &mut b, Transpose::NoTrans, &mut c, &mut d, &mut e, 1, 1, 1, 1));
+        for op in &[ReduceOp::Sum, ReduceOp::Max, ReduceOp::Min, ReduceOp::Product] {
+            try!(self.reduce(&mut a, &mut b, *op));
+        }
+        for func in &[UnaryFn::Exp, UnaryFn::Log, UnaryFn::Sqrt, UnaryFn::Abs, UnaryFn::Sigmoid, UnaryFn::Tanh, UnaryFn::Relu] {
+            try!(self.map(&mut a, &mut b, *func));
+        }
+        for op in &[BinaryOp::Add, BinaryOp::Sub, BinaryOp::Mul, BinaryOp::Div] {
+            try!(self.elementwise(&mut a, &mut b, &mut c, *op));
+        }
+        self.synchronize()
+    }
+}
+
+#[cfg(feature = "opencl")]
+impl Backend<OpenCL> {
+    /// Blocks until the command of the OpenCL `event` has been completed.
+    ///
+    /// Unlike `synchronize`, work issued to the backend device after the `event` may still be
+    /// running, when `synchronize_event` returns.
+    pub fn synchronize_event(&self, event: &::frameworks::opencl::Event) -> Result<(), Error> {
+        Ok(try!(event.wait().map_err(FrameworkError::OpenCL)))
+    }
+}
+
+#[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+impl Backend<Cuda> {
+    /// Initializes a new Cuda Backend from a BackendConfig, whose context is created with `flags`.
+    ///
+    /// The flags select the scheduling policy, with which the host waits for the device, e.g.
+    /// `CTX_SCHED_BLOCKING_SYNC`, and whether pinned host memory can be mapped, `CTX_MAP_HOST`.
+    /// `Backend::new` uses the default, with which the driver picks the scheduling policy.
+    pub fn with_context_flags(config: BackendConfig<Cuda>, flags: ContextFlags) -> Result<Backend<Cuda>, Error> {
+        let device = try!(config.framework.new_device_with_context_flags(config.hardwares, flags));
+        let binary = try!(<Blas as ILibrary<Cuda>>::new_binary(&config.framework, &device));
+        Ok(
+            Backend {
+                framework: Arc::new(config.framework),
+                device: device,
+                binary: binary,
+            }
+        )
+    }
+
+    /// Initializes a new Cuda Backend, which adopts the existing `context` instead of creating
+    /// its own.
+    ///
+    /// Use [Context::from_primary][primary] to share the primary context of a device, e.g. with
+    /// code using the Cuda runtime API, or [Context::from_raw][raw] to adopt a context, which has
+    /// been created elsewhere in the process.
+    /// [primary]: ../frameworks/cuda/context/struct.Context.html#method.from_primary
+    /// [raw]: ../frameworks/cuda/context/struct.Context.html#method.from_raw
+    pub fn new_with_context(framework: Cuda, context: CudaContext) -> Result<Backend<Cuda>, Error> {
+        let device = DeviceType::Cuda(context);
+        let binary = try!(<Blas as ILibrary<Cuda>>::new_binary(&framework, &device));
+        Ok(
+            Backend {
+                framework: Arc::new(framework),
+                device: device,
+                binary: binary,
+            }
+        )
+    }
+
+    /// Copies a buffer of a single element to the Cuda device and back, so that later transfers
+    /// are hot.
+    ///
+    /// The first transfer pays for the lazy allocation of driver resources, e.g. staging
+    /// buffers, which skews benchmarks and the latency of the first request. Unlike the Native
+    /// and OpenCL `warm_up`, there are no kernels to launch and JIT compile yet, as the Cuda
+    /// binary does not provide BLAS operations.
+    /// Blocks until the copies have been completed.
+    pub fn warm_up(&self) -> Result<(), Error> {
+        let native = Native::new();
+        let host = try!(native.new_device(native.hardwares()));
+        let mut mem = try!(SharedMemory::<f32>::try_new(self.device(), 1));
+        try!(mem.transfer(self.device(), &host));
+        try!(mem.transfer(&host, self.device()));
+        self.synchronize()
+    }
+}
+
+#[cfg(feature = "test-util")]
+impl Backend<Native> {
+    /// Lets the next `failure` of the backend device fail with `error`.
+    ///
+    /// The error is consumed by the next allocation of memory or synchronization of memory from
+    /// the device, which allows to exercise the error handling of code building on Collenchyma.
+    /// Clones of the Backend share the injected errors.
+    /// Only available with the `test-util` feature.
+    pub fn inject_failure(&self, failure: Failure, error: FrameworkError) {
+        if let DeviceType::Native(ref cpu) = self.device {
+            cpu.inject_failure(failure, error)
+        }
+    }
+}
+
+#[cfg(feature = "f16")]
+impl Backend<Native> {
+    /// Computes a half precision vector `x` times a constant `a` plus a vector `y` aka. `a * x + y`.
+    ///
+    /// Saves the resulting vector back into `y`. The arithmetic is carried out in single precision.
+    pub fn axpy_f16(&self, a: &mut SharedMemory<f16>, x: &mut SharedMemory<f16>, y: &mut SharedMemory<f16>) -> Result<(), Error> {
+        try!(validate::same_len(("x", x.capacity()), ("y", y.capacity())));
+        match a.add_device(self.device()) { _ => try!(a.sync(self.device())) }
+        match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+        match y.add_device(self.device()) { _ => try!(y.sync(self.device())) }
+        Ok(try!(half::axpy(
+            try!(a.get(self.device()).ok_or(BlasError::MissingArgument(format!("Unable to resolve memory for `a`")))),
+            try!(x.get(self.device()).ok_or(BlasError::MissingArgument(format!("Unable to resolve memory for `x`")))),
+            try!(y.get_mut(self.device())),
+        )))
+    }
+
+    /// Scales a half precision vector `x` by a constant `a` aka. `a * x`.
+    ///
+    /// Saves the resulting vector back into `x`. The arithmetic is carried out in single precision.
+    pub fn scale_f16(&self, a: &mut SharedMemory<f16>, x: &mut SharedMemory<f16>) -> Result<(), Error> {
+        match a.add_device(self.device()) { _ => try!(a.sync(self.device())) }
+        match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+        Ok(try!(half::scale(
+            try!(a.get(self.device()).ok_or(BlasError::MissingArgument(format!("Unable to resolve memory for `a`")))),
+            try!(x.get_mut(self.device())),
+        )))
+    }
+
+    /// Copies `x.len()` elements of the half precision vector `x` into vector `y`.
+    pub fn copy_f16(&self, x: &mut SharedMemory<f16>, y: &mut SharedMemory<f16>) -> Result<(), Error> {
+        try!(validate::at_least(("x", x.capacity()), ("y", y.capacity())));
+        match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+        match y.add_device(self.device()) { _ => () }
+        Ok(try!(half::copy(
+            try!(x.get(self.device()).ok_or(BlasError::MissingArgument(format!("Unable to resolve memory for `x`")))),
+            try!(if x.capacity() >= y.capacity() { y.get_mut_write_only(self.device()) } else { y.get_mut(self.device()) }),
+        )))
+    }
+}
+
+#[cfg(all(feature = "f16", feature = "opencl"))]
+impl Backend<OpenCL> {
+    /// Computes a half precision vector `x` times a constant `a` plus a vector `y` aka. `a * x + y`.
+    ///
+    /// Saves the resulting vector back into `y`. The arithmetic is carried out in single precision.
+    /// Returns an error, if not all devices support `cl_khr_fp16`.
+    pub fn axpy_f16(&self, a: &mut SharedMemory<f16>, x: &mut SharedMemory<f16>, y: &mut SharedMemory<f16>) -> Result<(), Error> {
+        let kernels = try!(self.binary.blas_half.ok_or(BlasError::InvalidOperation(format!("The OpenCL axpy kernel is not available for f16, as the device does not support cl_khr_fp16."))));
+        try!(validate::same_len(("x", x.capacity()), ("y", y.capacity())));
+        match a.add_device(self.device()) { _ => try!(a.sync(self.device())) }
+        match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+        match y.add_device(self.device()) { _ => try!(y.sync(self.device())) }
+        Ok(try!(opencl_half::axpy(
+            &kernels.axpy,
+            try!(a.get(self.device()).ok_or(BlasError::MissingArgument(format!("Unable to resolve memory for `a`")))),
+            try!(x.get(self.device()).ok_or(BlasError::MissingArgument(format!("Unable to resolve memory for `x`")))),
+            try!(y.get_mut(self.device())),
+        )))
+    }
+
+    /// Scales a half precision vector `x` by a constant `a` aka. `a * x`.
+    ///
+    /// Saves the resulting vector back into `x`. The arithmetic is carried out in single precision.
+    /// Returns an error, if not all devices support `cl_khr_fp16`.
+    pub fn scale_f16(&self, a: &mut SharedMemory<f16>, x: &mut SharedMemory<f16>) -> Result<(), Error> {
+        let kernels = try!(self.binary.blas_half.ok_or(BlasError::InvalidOperation(format!("The OpenCL scale kernel is not available for f16, as the device does not support cl_khr_fp16."))));
+        match a.add_device(self.device()) { _ => try!(a.sync(self.device())) }
+        match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+        Ok(try!(opencl_half::scale(
+            &kernels.scale,
+            try!(a.get(self.device()).ok_or(BlasError::MissingArgument(format!("Unable to resolve memory for `a`")))),
+            try!(x.get_mut(self.device())),
+        )))
+    }
+
+    /// Copies `x.len()` elements of the half precision vector `x` into vector `y`.
+    ///
+    /// Copies are issued on the command queue, so they do not need `cl_khr_fp16`.
+    pub fn copy_f16(&self, x: &mut SharedMemory<f16>, y: &mut SharedMemory<f16>) -> Result<(), Error> {
+        try!(validate::at_least(("x", x.capacity()), ("y", y.capacity())));
+        match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+        match y.add_device(self.device()) { _ => () }
+        Ok(try!(opencl_half::copy(
+            &self.binary.blas_copy,
+            try!(x.get(self.device()).ok_or(BlasError::MissingArgument(format!("Unable to resolve memory for `x`")))),
+            try!(if x.capacity() >= y.capacity() { y.get_mut_write_only(self.device()) } else { y.get_mut(self.device()) }),
+        )))
+    }
+}
+
+/// Default order, in which `BackendType::default` tries the Frameworks.
+pub const DEFAULT_PREFERENCE: [&'static str; 3] = ["CUDA", "OPENCL", "NATIVE"];
+
+#[derive(Debug, Clone)]
+/// Container for a Backend of any of the known Frameworks.
+///
+/// The Framework of a Backend, which was selected at runtime, is not known at compile time.
+pub enum BackendType {
+    /// A Native Backend
+    Native(Backend<Native>),
+    #[cfg(feature = "opencl")]
+    /// A OpenCL Backend
+    OpenCL(Backend<OpenCL>),
+    #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+    /// A Cuda Backend
+    Cuda(Backend<Cuda>),
+}
+
+impl BackendType {
+    /// Initializes a Backend for the best available Framework.
+    ///
+    /// Tries the Frameworks in the order of [DEFAULT_PREFERENCE][pref]: Cuda, OpenCL and finally
+    /// Native. OpenCL and Cuda are only considered when compiled with the `opencl` and `cuda`
+    /// feature respectively.
+    /// [pref]: ./constant.DEFAULT_PREFERENCE.html
+    pub fn default() -> Result<BackendType, Error> {
+        BackendType::with_preference(&DEFAULT_PREFERENCE)
+    }
+
+    /// Initializes a Backend for the first available Framework in `prefer`.
+    ///
+    /// Frameworks are identified by their `IFramework::ID`. A Framework is available, if it
+    /// reports at least one hardware. Unknown IDs are skipped.
+    pub fn with_preference(prefer: &[&str]) -> Result<BackendType, Error> {
+        for id in prefer {
+            match *id {
+                #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+                "CUDA" => if let Some(framework) = probe::<Cuda>() {
+                    return Ok(BackendType::Cuda(try!(Backend::new(BackendConfig::all(framework)))))
+                },
+                #[cfg(feature = "opencl")]
+                "OPENCL" => if let Some(framework) = probe::<OpenCL>() {
+                    return Ok(BackendType::OpenCL(try!(Backend::new(BackendConfig::all(framework)))))
+                },
+                "NATIVE" => if let Some(framework) = probe::<Native>() {
+                    return Ok(BackendType::Native(try!(Backend::new(BackendConfig::all(framework)))))
+                },
+                _ => {}
+            }
+        }
+        Err(Error::Framework(FrameworkError::MissingHardware(format!("None of the frameworks {:?} provides any hardware.", prefer))))
+    }
+
+    /// Returns the ID of the Backend's Framework.
+    pub fn framework_id(&self) -> &'static str {
+        match *self {
+            BackendType::Native(ref backend) => backend.framework_id(),
+            #[cfg(feature = "opencl")]
+            BackendType::OpenCL(ref backend) => backend.framework_id(),
+            #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+            BackendType::Cuda(ref backend) => backend.framework_id(),
+        }
+    }
+
+    /// Returns the backend device.
+    pub fn device(&self) -> &DeviceType {
+        match *self {
+            BackendType::Native(ref backend) => backend.device(),
+            #[cfg(feature = "opencl")]
+            BackendType::OpenCL(ref backend) => backend.device(),
+            #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+            BackendType::Cuda(ref backend) => backend.device(),
+        }
+    }
+
+    /// Blocks until all work, which was issued to the backend device, has been completed.
+    pub fn synchronize(&self) -> Result<(), Error> {
+        Ok(try!(self.device().synchronize()))
+    }
+}
+
+impl BackendType {
+    /// Initializes a Backend from a BackendSpec.
+    ///
+    /// The Framework is resolved by its `IFramework::ID`. Returns an error, if the Framework is
+    /// unknown or not compiled in, or if the selected hardwares are not available.
+    pub fn from_spec(spec: &BackendSpec) -> Result<BackendType, Error> {
+        match &*spec.framework {
+            #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+            "CUDA" => Ok(BackendType::Cuda(try!(Backend::from_spec(spec)))),
+            #[cfg(feature = "opencl")]
+            "OPENCL" => Ok(BackendType::OpenCL(try!(Backend::from_spec(spec)))),
+            "NATIVE" => Ok(BackendType::Native(try!(Backend::from_spec(spec)))),
+            id => Err(Error::Framework(FrameworkError::InvalidArgument(format!("The framework {:?} is unknown or was not compiled in.", id)))),
+        }
+    }
+}
+
+/// Initializes the Framework `F`, if it is available and reports at least one hardware.
+fn probe<F: IFramework>() -> Option<F> {
+    match F::try_new() {
+        Ok(framework) => if framework.hardwares().is_empty() { None } else { Some(framework) },
+        Err(_) => None
+    }
+}
+
+/// Provides the binary of a library, built for the device of a Backend.
+///
+/// Created through `Backend::library`. A library implements its library trait for the
+/// LibraryBackend, just like BLAS implements `IBlas` for the Backend.
+pub struct LibraryBackend<F: IFramework, L: ILibrary<F>> {
+    backend: Backend<F>,
+    binary: L::B,
+}
+
+impl<F: IFramework, L: ILibrary<F>> Clone for LibraryBackend<F, L> {
+    fn clone(&self) -> LibraryBackend<F, L> {
+        LibraryBackend { backend: self.backend.clone(), binary: self.binary.clone() }
+    }
+}
+
+impl<F: IFramework + Clone, L: ILibrary<F>> LibraryBackend<F, L> {
+    /// Returns the Backend, for whose device the library has been built.
+    pub fn backend(&self) -> &Backend<F> {
+        &self.backend
+    }
+
+    /// Returns the binary of the library.
+    pub fn binary(&self) -> L::B {
+        self.binary.clone()
+    }
+
+    /// Returns the device, on which the operations of the library get computed.
+    pub fn device(&self) -> &DeviceType {
+        self.backend.device()
     }
 }
 
@@ -109,15 +746,23 @@ impl IBackend for Backend<Native> {
     type F = Native;
 }
 
+#[cfg(feature = "opencl")]
 impl IBackend for Backend<OpenCL> {
     type F = OpenCL;
 }
 
+#[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
 impl IBackend for Backend<Cuda> {
     type F = Cuda;
 }
 
-impl IBlas<f32> for Backend<OpenCL> {
+#[cfg(feature = "test-util")]
+impl IBackend for Backend<Mock> {
+    type F = Mock;
+}
+
+#[cfg(feature = "opencl")]
+impl<T: BlasNum> IBlas<T> for Backend<OpenCL> {
     type B = ::frameworks::opencl::Program;
 
     fn binary(&self) -> Self::B {
@@ -129,6 +774,7 @@ impl IBlas<f32> for Backend<OpenCL> {
     }
 }
 
+#[cfg(feature = "native")]
 impl IBlas<f32> for Backend<Native> {
     type B = ::frameworks::native::Binary;
 
@@ -141,6 +787,7 @@ impl IBlas<f32> for Backend<Native> {
     }
 }
 
+#[cfg(feature = "native")]
 impl IBlas<f64> for Backend<Native> {
     type B = ::frameworks::native::Binary;
 
@@ -153,6 +800,45 @@ impl IBlas<f64> for Backend<Native> {
     }
 }
 
+#[cfg(feature = "native")]
+impl IBlas<i32> for Backend<Native> {
+    type B = ::frameworks::native::Binary;
+
+    fn binary(&self) -> Self::B {
+        self.binary()
+    }
+
+    fn device(&self) -> &DeviceType {
+        self.device()
+    }
+}
+
+#[cfg(feature = "test-util")]
+impl IBlas<f32> for Backend<Mock> {
+    type B = ::frameworks::mock::Binary;
+
+    fn binary(&self) -> Self::B {
+        self.binary()
+    }
+
+    fn device(&self) -> &DeviceType {
+        self.device()
+    }
+}
+
+#[cfg(feature = "test-util")]
+impl IBlas<f64> for Backend<Mock> {
+    type B = ::frameworks::mock::Binary;
+
+    fn binary(&self) -> Self::B {
+        self.binary()
+    }
+
+    fn device(&self) -> &DeviceType {
+        self.device()
+    }
+}
+
 #[derive(Debug, Clone)]
 /// Provides Backend Configuration.
 ///
@@ -170,4 +856,117 @@ impl<F: IFramework + Clone> BackendConfig<F> {
             hardwares: hardwares,
         }
     }
+
+    /// Creates a new BackendConfig with all the hardwares available through the `framework`.
+    ///
+    /// The hardwares are ordered by `IFramework::hardwares_ranked`, so that frameworks, which
+    /// use the first hardware, pick the most capable one.
+    pub fn all(framework: F) -> BackendConfig<F> {
+        let hardwares = framework.hardwares_ranked();
+        BackendConfig::new(framework, hardwares)
+    }
+
+    /// Creates a new BackendConfig with the hardwares at `indices` of the framework's hardwares.
+    ///
+    /// Returns an error if an index is out of range.
+    pub fn with_devices(framework: F, indices: &[usize]) -> Result<BackendConfig<F>, Error> {
+        let available = framework.hardwares();
+        let mut hardwares = Vec::with_capacity(indices.len());
+        for &index in indices {
+            match available.get(index) {
+                Some(hardware) => hardwares.push(hardware.clone()),
+                None => return Err(Error::Framework(FrameworkError::MissingHardware(format!("No hardware at index {}; the framework provides {} hardwares.", index, available.len())))),
+            }
+        }
+        Ok(BackendConfig::new(framework, hardwares))
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Provides a fluent way to configure and initialize a Backend.
+///
+/// Only the Framework is mandatory. Without any further configuration the Backend is created from
+/// all hardwares of the Framework. All validation happens in `build`.
+pub struct BackendBuilder<F: IFramework> {
+    framework: Option<F>,
+    hardwares: Option<Vec<F::H>>,
+    device_index: Option<usize>,
+}
+
+impl<F: IFramework + Clone> BackendBuilder<F> {
+    /// Creates a new, empty BackendBuilder.
+    pub fn new() -> BackendBuilder<F> {
+        BackendBuilder {
+            framework: None,
+            hardwares: None,
+            device_index: None,
+        }
+    }
+
+    /// Sets the Framework of the Backend.
+    pub fn framework(mut self, framework: F) -> BackendBuilder<F> {
+        self.framework = Some(framework);
+        self
+    }
+
+    /// Sets the hardwares, from which the Backend device is created.
+    ///
+    /// Defaults to all hardwares of the Framework.
+    pub fn hardwares(mut self, hardwares: Vec<F::H>) -> BackendBuilder<F> {
+        self.hardwares = Some(hardwares);
+        self
+    }
+
+    /// Restricts the Backend device to the hardware at `index` of the configured hardwares.
+    pub fn device_index(mut self, index: usize) -> BackendBuilder<F> {
+        self.device_index = Some(index);
+        self
+    }
+
+    /// Initializes the configured Backend.
+    ///
+    /// Returns an error, if no Framework was set, if there are no hardwares to create the device
+    /// from or if the device index is out of range.
+    pub fn build(self) -> Result<Backend<F>, Error> {
+        let framework = try!(self.framework.ok_or(Error::Framework(FrameworkError::InvalidArgument(format!("No framework was configured for the backend.")))));
+        let mut hardwares = match self.hardwares {
+            Some(hardwares) => hardwares,
+            None => framework.hardwares(),
+        };
+        if hardwares.is_empty() {
+            return Err(Error::Framework(FrameworkError::InvalidArgument(format!("No hardwares were configured for the backend."))))
+        }
+        if let Some(index) = self.device_index {
+            if index >= hardwares.len() {
+                return Err(Error::Framework(FrameworkError::InvalidArgument(format!("No hardware at index {}; the backend was configured with {} hardwares.", index, hardwares.len()))))
+            }
+            hardwares = vec![hardwares.swap_remove(index)];
+        }
+        Backend::new(BackendConfig::new(framework, hardwares))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+/// Describes the selection of a Backend, independent of the underlying driver handles.
+///
+/// Can be persisted with the `serialize` feature and turned back into a Backend through
+/// `Backend::from_spec` or `BackendType::from_spec`.
+pub struct BackendSpec {
+    /// The `IFramework::ID` of the Framework, e.g. `"OPENCL"`.
+    pub framework: String,
+    /// The indices of the selected hardwares of the Framework.
+    ///
+    /// An empty selection stands for all hardwares.
+    pub device_indices: Vec<usize>,
+}
+
+impl BackendSpec {
+    /// Creates a new BackendSpec.
+    pub fn new(framework: &str, device_indices: Vec<usize>) -> BackendSpec {
+        BackendSpec {
+            framework: framework.to_owned(),
+            device_indices: device_indices,
+        }
+    }
 }
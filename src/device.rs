@@ -8,8 +8,27 @@
 
 use hardware::IHardware;
 use memory::{IMemory, MemoryType};
+use alloc_tracker;
 use frameworks::native::device::Cpu;
+#[cfg(feature = "opencl")]
 use frameworks::opencl::context::Context;
+#[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+use frameworks::cuda::context::Context as CudaContext;
+#[cfg(feature = "opencl")]
+use frameworks::opencl::{API as OpenCLAPI, Error as OpenCLError};
+#[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+use frameworks::cuda::API as CudaAPI;
+#[cfg(any(feature = "opencl", feature = "cuda", feature = "cuda-runtime"))]
+use libc::c_void;
+#[cfg(any(feature = "opencl", feature = "cuda", feature = "cuda-runtime"))]
+use memory_pool::MemoryPool;
+use stats;
+use std::fmt;
+use std::time::Duration;
+#[cfg(any(feature = "opencl", feature = "cuda", feature = "cuda-runtime"))]
+use std::{cmp, thread};
+#[cfg(any(feature = "opencl", feature = "cuda", feature = "cuda-runtime"))]
+use std::time::Instant;
 
 /// Specifies Hardware behavior accross frameworks.
 pub trait IDevice {
@@ -24,7 +43,9 @@ pub trait IDevice {
     /// Allocate memory on the Device.
     fn alloc_memory(&self, size: usize) -> Self::M;
     /// Synchronize memory from this Device to `dest_device`.
-    fn sync_memory_to(&self, source: &Self::M, dest: &mut MemoryType, dest_device: &DeviceType);
+    fn sync_memory_to(&self, source: &Self::M, dest: &mut MemoryType, dest_device: &DeviceType) -> Result<(), ::framework::Error>;
+    /// Blocks until all work, which was issued to the Device, has been completed.
+    fn synchronize(&self) -> Result<(), ::framework::Error>;
 }
 
 #[derive(Debug, Eq, PartialEq, Hash, Clone)]
@@ -32,6 +53,448 @@ pub trait IDevice {
 pub enum DeviceType {
     /// A native CPU
     Native(Cpu),
+    #[cfg(feature = "opencl")]
     /// A OpenCL Context
     OpenCL(Context),
+    #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+    /// A Cuda Context
+    Cuda(CudaContext),
+}
+
+impl DeviceType {
+    /// Allocates `size` bytes of memory on the Device.
+    ///
+    /// Returns an error, if `size` is zero.
+    pub fn alloc_memory(&self, size: usize) -> Result<MemoryType, ::framework::Error> {
+        self.alloc_memory_labeled(size, || format!("{} bytes", size))
+    }
+
+    /// Allocates `size` bytes of memory on the Device and describes it by the result of `label`.
+    ///
+    /// With the `alloc_tracking` feature, the allocation is listed under the label by
+    /// [Backend::memory_report][report], until the memory is freed. Without it, `label` is never
+    /// called, so building the label costs nothing.
+    /// Returns an error, if `size` is zero.
+    /// [report]: ../backend/struct.Backend.html#method.memory_report
+    pub fn alloc_memory_labeled<F>(&self, size: usize, label: F) -> Result<MemoryType, ::framework::Error>
+        where F: FnOnce() -> String
+    {
+        if size == 0 {
+            return Err(::framework::Error::InvalidMemoryAllocation(format!("Unable to allocate memory of size 0.")))
+        }
+        let memory = match *self {
+            DeviceType::Native(ref cpu) => MemoryType::Native(try!(cpu.try_alloc_memory(size))),
+            #[cfg(feature = "opencl")]
+            DeviceType::OpenCL(ref context) => MemoryType::OpenCL(try!(context.try_alloc_memory(size))),
+            #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+            DeviceType::Cuda(ref context) => MemoryType::Cuda(try!(context.try_alloc_memory(size))),
+        };
+        alloc_tracker::track(self, &memory, size, label);
+        Ok(memory)
+    }
+
+    /// Allocates `size` bytes of memory on the Device, which start at a multiple of `align` bytes.
+    ///
+    /// Native and Cuda pad the allocation, if needed. OpenCL does not expose device addresses
+    /// and only supports alignments up to the `CL_DEVICE_MEM_BASE_ADDR_ALIGN` of its devices.
+    /// Returns an error, if `size` is zero, if `align` is not a power of two or if the Device
+    /// can not guarantee the alignment.
+    pub fn alloc_memory_aligned(&self, size: usize, align: usize) -> Result<MemoryType, ::framework::Error> {
+        if size == 0 {
+            return Err(::framework::Error::InvalidMemoryAllocation(format!("Unable to allocate memory of size 0.")))
+        }
+        if !align.is_power_of_two() {
+            return Err(::framework::Error::InvalidMemoryAllocation(format!("Unable to align memory to {} bytes, which is not a power of two.", align)))
+        }
+        let memory = match *self {
+            DeviceType::Native(ref cpu) => MemoryType::Native(try!(cpu.try_alloc_memory_aligned(size, align))),
+            #[cfg(feature = "opencl")]
+            DeviceType::OpenCL(ref context) => MemoryType::OpenCL(try!(context.alloc_memory_aligned(size, align))),
+            #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+            DeviceType::Cuda(ref context) => MemoryType::Cuda(try!(context.alloc_memory_aligned(size, align))),
+        };
+        alloc_tracker::track(self, &memory, size, || format!("{} bytes aligned to {} bytes", size, align));
+        Ok(memory)
+    }
+
+    /// Returns whether `memory` has been allocated by the framework of the Device.
+    pub fn owns_memory(&self, memory: &MemoryType) -> bool {
+        match *self {
+            DeviceType::Native(_) => memory.as_native().is_some(),
+            #[cfg(feature = "opencl")]
+            DeviceType::OpenCL(_) => memory.as_opencl().is_some(),
+            #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+            DeviceType::Cuda(_) => memory.as_cuda().is_some(),
+        }
+    }
+
+    /// Copies the bytes of `host` into the memory `mem` of the Device.
+    ///
+    /// Returns an error, if the length of `host` does not match the size of `mem` or if `mem`
+    /// does not belong to the framework of the Device.
+    pub fn sync_in(&self, host: &[u8], mem: &mut MemoryType) -> Result<(), ::framework::Error> {
+        if host.len() != mem.size() {
+            return Err(::framework::Error::InvalidMemoryAllocation(format!("Unable to copy {} bytes into memory of size {}.", host.len(), mem.size())))
+        }
+        try!(self.check_memory(mem, "copy into"));
+        co_debug!("Copying {} bytes from the host into {} memory", host.len(), self.framework_id());
+        match *self {
+            DeviceType::Native(_) => {
+                mem.as_mut_native().unwrap().as_mut_slice::<u8>().copy_from_slice(host);
+                Ok(())
+            },
+            #[cfg(feature = "opencl")]
+            DeviceType::OpenCL(ref context) => {
+                let queue = try!(context.queue().ok_or(OpenCLError::InvalidCommandQueue(format!("The OpenCL context has no command queue."))));
+                try!(OpenCLAPI::write_to_memory(queue, mem.as_mut_opencl().unwrap(), host.as_ptr() as *const c_void, host.len()));
+                stats::record_to_device(host.len());
+                Ok(())
+            },
+            #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+            DeviceType::Cuda(ref context) => {
+                try!(CudaAPI::write_to_memory(context, mem.as_mut_cuda().unwrap(), host.as_ptr() as *const c_void, host.len()));
+                stats::record_to_device(host.len());
+                Ok(())
+            },
+        }
+    }
+
+    /// Copies the bytes of the memory `mem` of the Device into `host`.
+    ///
+    /// Returns an error, if the length of `host` does not match the size of `mem` or if `mem`
+    /// does not belong to the framework of the Device.
+    pub fn sync_out(&self, mem: &MemoryType, host: &mut [u8]) -> Result<(), ::framework::Error> {
+        if host.len() != mem.size() {
+            return Err(::framework::Error::InvalidMemoryAllocation(format!("Unable to copy memory of size {} into {} bytes.", mem.size(), host.len())))
+        }
+        self.sync_out_range(mem, 0, host)
+    }
+
+    /// Copies `host.len()` bytes of the memory `mem` of the Device, starting `offset` bytes into
+    /// it, into `host`.
+    ///
+    /// Returns an error, if the range reaches past the end of `mem` or if `mem` does not belong
+    /// to the framework of the Device.
+    pub fn sync_out_range(&self, mem: &MemoryType, offset: usize, host: &mut [u8]) -> Result<(), ::framework::Error> {
+        match offset.checked_add(host.len()) {
+            Some(end) if end <= mem.size() => {},
+            _ => return Err(::framework::Error::InvalidMemoryAllocation(format!("Unable to copy {} bytes at offset {} from memory of size {}.", host.len(), offset, mem.size())))
+        }
+        try!(self.check_memory(mem, "copy from"));
+        co_debug!("Copying {} bytes at offset {} of {} memory to the host", host.len(), offset, self.framework_id());
+        match *self {
+            DeviceType::Native(_) => {
+                host.copy_from_slice(&mem.as_native().unwrap().as_slice::<u8>()[offset..offset + host.len()]);
+                Ok(())
+            },
+            #[cfg(feature = "opencl")]
+            DeviceType::OpenCL(ref context) => {
+                let queue = try!(context.queue().ok_or(OpenCLError::InvalidCommandQueue(format!("The OpenCL context has no command queue."))));
+                try!(OpenCLAPI::read_from_memory_at(queue, mem.as_opencl().unwrap(), offset, host.as_mut_ptr() as *mut c_void, host.len()));
+                stats::record_to_host(host.len());
+                Ok(())
+            },
+            #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+            DeviceType::Cuda(ref context) => {
+                try!(CudaAPI::read_from_memory_at(context, mem.as_cuda().unwrap(), offset, host.as_mut_ptr() as *mut c_void, host.len()));
+                stats::record_to_host(host.len());
+                Ok(())
+            },
+        }
+    }
+
+    /// Copies the memory `src` of the Device into the memory `dst` of the `dst_device`.
+    ///
+    /// If both memories are on the same Device, the copy is issued directly on the Device.
+    /// Otherwise the data is staged through a host buffer.
+    /// Returns an error, if the sizes of `src` and `dst` differ or if a memory does not belong to
+    /// the framework of its Device.
+    pub fn sync_between(&self, src: &MemoryType, dst_device: &DeviceType, dst: &mut MemoryType) -> Result<(), ::framework::Error> {
+        if src.size() != dst.size() {
+            return Err(::framework::Error::InvalidMemoryAllocation(format!("Unable to copy memory of size {} into memory of size {}.", src.size(), dst.size())))
+        }
+        try!(self.check_memory(src, "copy from"));
+        try!(dst_device.check_memory(dst, "copy into"));
+        if self != dst_device {
+            co_warn!("Staging {} bytes through the host to copy from {} to {} memory", src.size(), self.framework_id(), dst_device.framework_id());
+            let mut staging = vec![0u8; src.size()];
+            try!(self.sync_out(src, &mut staging));
+            return dst_device.sync_in(&staging, dst)
+        }
+        match *self {
+            DeviceType::Native(_) => {
+                dst.as_mut_native().unwrap().as_mut_slice::<u8>().copy_from_slice(src.as_native().unwrap().as_slice::<u8>());
+                Ok(())
+            },
+            #[cfg(feature = "opencl")]
+            DeviceType::OpenCL(ref context) => {
+                let queue = try!(context.queue().ok_or(OpenCLError::InvalidCommandQueue(format!("The OpenCL context has no command queue."))));
+                Ok(try!(OpenCLAPI::copy_buffer(queue, src.as_opencl().unwrap(), dst.as_mut_opencl().unwrap(), src.size())))
+            },
+            #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+            DeviceType::Cuda(ref context) => {
+                Ok(try!(CudaAPI::copy_memory(context, src.as_cuda().unwrap(), dst.as_mut_cuda().unwrap(), src.size())))
+            },
+        }
+    }
+
+    /// Frees `memory`, which has been allocated on the Device.
+    ///
+    /// Returns an error, if `memory` does not belong to the framework of the Device.
+    pub fn free_memory(&self, memory: MemoryType) -> Result<(), ::framework::Error> {
+        // The memory releases its allocation, when it goes out of scope.
+        self.check_memory(&memory, "free")
+    }
+
+    /// Attaches a new MemoryPool to the Device, which retains at most `max_retained` bytes of
+    /// freed memory for later allocations of the same size.
+    ///
+    /// Only affects this DeviceType and the clones, which are made afterwards. Native devices
+    /// allocate host memory, which is cheap, and are not pooled.
+    pub fn enable_pool(&mut self, max_retained: usize) {
+        match *self {
+            DeviceType::Native(_) => {},
+            #[cfg(feature = "opencl")]
+            DeviceType::OpenCL(ref mut context) => context.set_pool(Some(MemoryPool::new(max_retained))),
+            #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+            DeviceType::Cuda(ref mut context) => context.set_pool(Some(MemoryPool::new(max_retained))),
+        }
+    }
+
+    /// Returns `memory`, which has been allocated on the Device, to the MemoryPool of the Device.
+    ///
+    /// Frees `memory`, if the Device has no pool, the pool is full or `memory` does not belong
+    /// to the framework of the Device. `memory` must not be aliased by views, as it can be handed
+    /// out by the next allocation of the same size.
+    pub fn recycle_memory(&self, memory: MemoryType) {
+        alloc_tracker::relabel(&memory, "pooled");
+        match (self, memory) {
+            #[cfg(feature = "opencl")]
+            (&DeviceType::OpenCL(ref context), MemoryType::OpenCL(mem)) => context.recycle_memory(mem),
+            #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+            (&DeviceType::Cuda(ref context), MemoryType::Cuda(mem)) => context.recycle_memory(mem),
+            _ => {}
+        }
+    }
+
+    /// Returns the number of bytes, which the MemoryPool of the Device retains.
+    pub fn pooled_memory(&self) -> usize {
+        match *self {
+            DeviceType::Native(_) => 0,
+            #[cfg(feature = "opencl")]
+            DeviceType::OpenCL(ref context) => context.pool().map_or(0, |pool| pool.retained()),
+            #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+            DeviceType::Cuda(ref context) => context.pool().map_or(0, |pool| pool.retained()),
+        }
+    }
+
+    /// Frees all memory, which the MemoryPool of the Device retains.
+    pub fn drain_pool(&self) {
+        match *self {
+            DeviceType::Native(_) => {},
+            #[cfg(feature = "opencl")]
+            DeviceType::OpenCL(ref context) => if let Some(pool) = context.pool() { pool.drain() },
+            #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+            DeviceType::Cuda(ref context) => if let Some(pool) = context.pool() { pool.drain() },
+        }
+    }
+
+    /// Sets every byte of the memory `mem` of the Device to `value`.
+    ///
+    /// The memory is set on the Device, without a round-trip through the host.
+    /// Returns an error, if `mem` does not belong to the framework of the Device.
+    pub fn memset(&self, mem: &mut MemoryType, value: u8) -> Result<(), ::framework::Error> {
+        self.fill_pattern(mem, &[value])
+    }
+
+    /// Sets every single precision element of the memory `mem` of the Device to `value`.
+    ///
+    /// The memory is set on the Device, without a round-trip through the host, e.g. to reset an
+    /// accumulator between iterations.
+    /// Returns an error, if the size of `mem` is not a multiple of the size of a `f32` or if `mem`
+    /// does not belong to the framework of the Device.
+    pub fn fill(&self, mem: &mut MemoryType, value: f32) -> Result<(), ::framework::Error> {
+        let pattern: [u8; 4] = unsafe { ::std::mem::transmute(value) };
+        self.fill_pattern(mem, &pattern)
+    }
+
+    /// Fills the memory `mem` of the Device with the repeated bytes of `pattern`.
+    fn fill_pattern(&self, mem: &mut MemoryType, pattern: &[u8]) -> Result<(), ::framework::Error> {
+        if mem.size() % pattern.len() != 0 {
+            return Err(::framework::Error::InvalidMemoryAllocation(format!("Unable to fill memory of size {} with values of {} bytes.", mem.size(), pattern.len())))
+        }
+        try!(self.check_memory(mem, "fill"));
+        match *self {
+            DeviceType::Native(_) => {
+                for chunk in mem.as_mut_native().unwrap().as_mut_slice::<u8>().chunks_mut(pattern.len()) {
+                    chunk.copy_from_slice(pattern);
+                }
+                Ok(())
+            },
+            #[cfg(feature = "opencl")]
+            DeviceType::OpenCL(ref context) => {
+                Ok(try!(context.fill(mem.as_mut_opencl().unwrap(), pattern)))
+            },
+            #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+            DeviceType::Cuda(ref context) => {
+                let size = mem.size();
+                let cuda_mem = mem.as_mut_cuda().unwrap();
+                match pattern.len() {
+                    1 => Ok(try!(CudaAPI::memset_d8(context, cuda_mem, pattern[0], size))),
+                    4 => {
+                        let word: u32 = unsafe { ::std::mem::transmute([pattern[0], pattern[1], pattern[2], pattern[3]]) };
+                        Ok(try!(CudaAPI::memset_d32(context, cuda_mem, word, size / 4)))
+                    },
+                    len => Err(::framework::Error::NotSupported(format!("Unable to fill Cuda memory with values of {} bytes.", len))),
+                }
+            },
+        }
+    }
+
+    /// Returns an error, if `memory` does not belong to the framework of the Device.
+    ///
+    /// `action` describes the rejected operation in the error message.
+    fn check_memory(&self, memory: &MemoryType, action: &str) -> Result<(), ::framework::Error> {
+        if self.owns_memory(memory) {
+            Ok(())
+        } else {
+            Err(::framework::Error::InvalidMemoryAllocation(format!("Unable to {} {:?}, as it was not allocated by the framework of the device {:?}.", action, memory, self)))
+        }
+    }
+
+    /// Returns the ID of the framework, which created the Device.
+    ///
+    /// Devices of the Mock framework compute on the host and report `NATIVE`.
+    pub fn framework_id(&self) -> &'static str {
+        match *self {
+            DeviceType::Native(_) => "NATIVE",
+            #[cfg(feature = "opencl")]
+            DeviceType::OpenCL(_) => "OPENCL",
+            #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+            DeviceType::Cuda(_) => "CUDA",
+        }
+    }
+
+    /// Returns the names of the hardwares, which define the Device.
+    ///
+    /// Hardwares, which do not report a name, are named after their id.
+    pub fn hardware_names(&self) -> Vec<String> {
+        match *self {
+            DeviceType::Native(ref cpu) => names(cpu.hardwares()),
+            #[cfg(feature = "opencl")]
+            DeviceType::OpenCL(ref context) => names(context.hardwares()),
+            #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+            DeviceType::Cuda(ref context) => names(context.hardwares()),
+        }
+    }
+
+    /// Blocks until all work, which was issued to the Device, has been completed.
+    pub fn synchronize(&self) -> Result<(), ::framework::Error> {
+        match *self {
+            DeviceType::Native(ref cpu) => cpu.synchronize(),
+            #[cfg(feature = "opencl")]
+            DeviceType::OpenCL(ref context) => context.synchronize(),
+            #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+            DeviceType::Cuda(ref context) => context.synchronize(),
+        }
+    }
+
+    /// Blocks until all work, which was issued to the Device, has been completed or until
+    /// `timeout` has passed.
+    ///
+    /// Returns `Ok(false)` on timeout. The completion is polled, so the call may return a little
+    /// after the deadline, and a timeout does not cancel the pending work. OpenCL polls a marker
+    /// on the command queue, Cuda polls the default stream of the context and Native devices
+    /// complete their work, before the call returns.
+    pub fn synchronize_timeout(&self, timeout: Duration) -> Result<bool, ::framework::Error> {
+        match *self {
+            DeviceType::Native(ref cpu) => cpu.synchronize().map(|_| true),
+            #[cfg(feature = "opencl")]
+            DeviceType::OpenCL(ref context) => {
+                let queue = try!(context.queue().ok_or(OpenCLError::InvalidCommandQueue(format!("The OpenCL context has no command queue."))));
+                let marker = try!(queue.marker());
+                Ok(try!(poll_until(timeout, || marker.is_complete())))
+            },
+            #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+            DeviceType::Cuda(ref context) => Ok(try!(poll_until(timeout, || CudaAPI::query_context(context)))),
+        }
+    }
+
+    /// Returns the index of the hardware, on which work of the Device gets issued.
+    pub fn active_hardware(&self) -> usize {
+        match *self {
+            DeviceType::Native(_) => 0,
+            #[cfg(feature = "opencl")]
+            DeviceType::OpenCL(ref context) => context.active_device(),
+            #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+            DeviceType::Cuda(_) => 0,
+        }
+    }
+
+    /// Selects the hardware at `index`, on which work of the Device gets issued from now on.
+    ///
+    /// OpenCL issues work through the command queue of the selected hardware. A Native device
+    /// computes on all its hardwares, and a Cuda device consists of a single hardware, whose
+    /// context is made current by every call into it, so both only accept the index 0.
+    /// Returns an error, if there is no hardware at `index`.
+    pub fn set_active_hardware(&mut self, index: usize) -> Result<(), ::framework::Error> {
+        let count = self.hardware_names().len();
+        if index >= count {
+            return Err(::framework::Error::MissingHardware(format!("No hardware at index {}; the device consists of {} hardwares.", index, count)))
+        }
+        match *self {
+            DeviceType::Native(_) => Ok(()),
+            #[cfg(feature = "opencl")]
+            DeviceType::OpenCL(ref mut context) => Ok(try!(context.set_active_device(index))),
+            #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+            DeviceType::Cuda(_) => Ok(()),
+        }
+    }
+
+    /// Issues all queued work of the Device to its hardware, without waiting for it to complete.
+    ///
+    /// OpenCL may hold commands back on the host, until the queue is flushed. Cuda issues work
+    /// immediately and Native computes synchronously, so for them `flush` does nothing.
+    pub fn flush(&self) -> Result<(), ::framework::Error> {
+        match *self {
+            DeviceType::Native(_) => Ok(()),
+            #[cfg(feature = "opencl")]
+            DeviceType::OpenCL(ref context) => {
+                let queue = try!(context.queue().ok_or(OpenCLError::InvalidCommandQueue(format!("The OpenCL context has no command queue."))));
+                Ok(try!(queue.flush()))
+            },
+            #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+            DeviceType::Cuda(_) => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for DeviceType {
+    /// Prints the framework and the names of the hardwares, e.g. `OPENCL: Tahiti, Tahiti`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.framework_id(), self.hardware_names().join(", "))
+    }
+}
+
+/// Calls `poll` until it returns `true` or until `timeout` has passed.
+///
+/// Returns `Ok(false)` on timeout.
+#[cfg(any(feature = "opencl", feature = "cuda", feature = "cuda-runtime"))]
+fn poll_until<E, P: FnMut() -> Result<bool, E>>(timeout: Duration, mut poll: P) -> Result<bool, E> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        if try!(poll()) {
+            return Ok(true)
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return Ok(false)
+        }
+        thread::sleep(cmp::min(deadline - now, Duration::from_millis(1)));
+    }
+}
+
+/// Returns the names of the `hardwares`.
+fn names<H: IHardware>(hardwares: Vec<H>) -> Vec<String> {
+    hardwares.iter().map(|hardware| hardware.name().unwrap_or_else(|| format!("Hardware {}", hardware.id()))).collect()
 }
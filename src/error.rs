@@ -39,4 +39,12 @@ impl error::Error for Error {
             Error::Operation(ref err) => Some(err),
         }
     }
+
+    fn source(&self) -> Option<&(error::Error + 'static)> {
+        match *self {
+            Error::Framework(ref err) => Some(err),
+            Error::SharedMemory(ref err) => Some(err),
+            Error::Operation(ref err) => Some(err),
+        }
+    }
 }
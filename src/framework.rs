@@ -18,17 +18,26 @@
 //! // let backend: Backend = framework.create_backend();
 //! ```
 
-use hardware::IHardware;
+use hardware::{IHardware, HardwareType};
 use device::{IDevice, DeviceType};
 use binary::IBinary;
+#[cfg(feature = "opencl")]
 use frameworks::opencl::Error as OpenCLError;
+#[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+use frameworks::cuda::Error as CudaError;
+use frameworks::native::Error as NativeError;
 use std::error;
 use std::fmt;
+use std::iter::Cloned;
+use std::slice;
+
+/// Lazily iterates over the hardwares of a Framework.
+pub type HardwareIter<'a, H> = Cloned<slice::Iter<'a, H>>;
 
 /// Defines a Framework.
 pub trait IFramework {
     /// The Hardware representation for this Framework.
-    type H: IHardware;
+    type H: IHardware + Clone;
     /// The Device representation for this Framework.
     type D: IDevice + Clone;
     /// The Binary representation for this Framework.
@@ -40,10 +49,30 @@ pub trait IFramework {
     /// EXAMPLE: OPENCL
     const ID: &'static str;
 
+    /// Returns the ID of the Framework.
+    ///
+    /// Unlike `ID`, it can be called on a Framework value, e.g. in generic code, which only holds
+    /// a reference to it.
+    fn framework_id(&self) -> &'static str {
+        Self::ID
+    }
+
+    /// Initializes a new Framework.
+    ///
+    /// Loads all the available hardwares.
+    /// Panics if the Framework is not available, use `try_new` to handle this case.
+    fn new() -> Self where Self: Sized {
+        match Self::try_new() {
+            Ok(framework) => framework,
+            Err(err) => panic!(err)
+        }
+    }
+
     /// Initializes a new Framework.
     ///
-    /// Loads all the available hardwares
-    fn new() -> Self where Self: Sized;
+    /// Loads all the available hardwares.
+    /// Returns an error if the Framework is not available, e.g. because the driver is missing.
+    fn try_new() -> Result<Self, Error> where Self: Sized;
 
     /// Initializes all the available hardwares.
     fn load_hardwares() -> Result<Vec<Self::H>, Error>;
@@ -51,9 +80,45 @@ pub trait IFramework {
     /// Returns the cached and available hardwares.
     fn hardwares(&self) -> Vec<Self::H>;
 
+    /// Returns a lazy iterator over the cached and available hardwares.
+    ///
+    /// Clones the hardwares one at a time, which allows to stop at the first suitable hardware.
+    fn hardware_iter(&self) -> HardwareIter<Self::H>;
+
+    /// Returns the cached and available hardwares of the provided HardwareType.
+    ///
+    /// Hardwares, which do not report their type, are never returned.
+    fn hardwares_of_type(&self, ty: HardwareType) -> Vec<Self::H> {
+        self.hardware_iter().filter(|hardware| hardware.hardware_type() == Some(ty)).collect()
+    }
+
+    /// Returns the cached and available hardwares, the most capable first.
+    ///
+    /// Ranks the hardwares by their compute units and, on a tie, by their memory. Hardwares,
+    /// which do not report them, rank last. Equally ranked hardwares keep the order of
+    /// `hardwares`. Use `hardwares_ranked_by` for a different heuristic.
+    fn hardwares_ranked(&self) -> Vec<Self::H> {
+        self.hardwares_ranked_by(|hardware| (hardware.compute_units().unwrap_or(0), hardware.memory().unwrap_or(0)))
+    }
+
+    /// Returns the cached and available hardwares, sorted by the descending key, which `rank`
+    /// returns for each hardware.
+    ///
+    /// Equally ranked hardwares keep the order of `hardwares`.
+    fn hardwares_ranked_by<K: Ord, R: Fn(&Self::H) -> K>(&self, rank: R) -> Vec<Self::H> {
+        let mut hardwares = self.hardwares();
+        hardwares.sort_by(|a, b| rank(b).cmp(&rank(a)));
+        hardwares
+    }
+
     /// Returns the initialized binary.
     fn binary(&self) -> Self::B;
 
+    /// Builds a new Binary for the provided device.
+    ///
+    /// The Binary holds the operations, which can be executed on the device.
+    fn new_binary(&self, &DeviceType) -> Result<Self::B, Error>;
+
     /// Initializes a new Device from the provided hardwares.
     fn new_device(&self, Vec<Self::H>) -> Result<DeviceType, Error>;
 }
@@ -61,14 +126,38 @@ pub trait IFramework {
 #[derive(Debug)]
 /// Defines a generic set of Framework Errors.
 pub enum Error {
+    /// Failures related to the Native framework implementation.
+    Native(NativeError),
+    #[cfg(feature = "opencl")]
     /// Failures related to the OpenCL framework implementation.
     OpenCL(OpenCLError),
+    #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+    /// Failures related to the Cuda framework implementation.
+    Cuda(CudaError),
+    /// Failure to find a requested hardware.
+    MissingHardware(String),
+    /// Failure related to an invalid argument, e.g. an incomplete configuration.
+    InvalidArgument(String),
+    /// Failure to allocate or free memory on a device.
+    InvalidMemoryAllocation(String),
+    /// Failure to fulfill a request, which the framework does not support.
+    ///
+    /// Allows to distinguish an absent feature from a failing driver.
+    NotSupported(String),
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
+            Error::Native(ref err) => write!(f, "Native error: {}", err),
+            #[cfg(feature = "opencl")]
             Error::OpenCL(ref err) => write!(f, "OpenCL error: {}", err),
+            #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+            Error::Cuda(ref err) => write!(f, "Cuda error: {}", err),
+            Error::MissingHardware(ref err) => write!(f, "{:?}", err),
+            Error::InvalidArgument(ref err) => write!(f, "{:?}", err),
+            Error::InvalidMemoryAllocation(ref err) => write!(f, "{:?}", err),
+            Error::NotSupported(ref err) => write!(f, "Not supported: {:?}", err),
         }
     }
 }
@@ -76,23 +165,67 @@ impl fmt::Display for Error {
 impl error::Error for Error {
     fn description(&self) -> &str {
         match *self {
+            Error::Native(ref err) => err.description(),
+            #[cfg(feature = "opencl")]
             Error::OpenCL(ref err) => err.description(),
+            #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+            Error::Cuda(ref err) => err.description(),
+            Error::MissingHardware(ref err) => err,
+            Error::InvalidArgument(ref err) => err,
+            Error::InvalidMemoryAllocation(ref err) => err,
+            Error::NotSupported(ref err) => err,
         }
     }
 
     fn cause(&self) -> Option<&error::Error> {
         match *self {
+            Error::Native(ref err) => Some(err),
+            #[cfg(feature = "opencl")]
             Error::OpenCL(ref err) => Some(err),
+            #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+            Error::Cuda(ref err) => Some(err),
+            Error::MissingHardware(_) => None,
+            Error::InvalidArgument(_) => None,
+            Error::InvalidMemoryAllocation(_) => None,
+            Error::NotSupported(_) => None,
         }
     }
+
+    fn source(&self) -> Option<&(error::Error + 'static)> {
+        match *self {
+            Error::Native(ref err) => Some(err),
+            #[cfg(feature = "opencl")]
+            Error::OpenCL(ref err) => Some(err),
+            #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+            Error::Cuda(ref err) => Some(err),
+            Error::MissingHardware(_) => None,
+            Error::InvalidArgument(_) => None,
+            Error::InvalidMemoryAllocation(_) => None,
+            Error::NotSupported(_) => None,
+        }
+    }
+}
+
+impl From<NativeError> for Error {
+    fn from(err: NativeError) -> Error {
+        Error::Native(err)
+    }
 }
 
+#[cfg(feature = "opencl")]
 impl From<OpenCLError> for Error {
     fn from(err: OpenCLError) -> Error {
         Error::OpenCL(err)
     }
 }
 
+#[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+impl From<CudaError> for Error {
+    fn from(err: CudaError) -> Error {
+        Error::Cuda(err)
+    }
+}
+
 impl From<Error> for ::error::Error {
     fn from(err: Error) -> ::error::Error {
         ::error::Error::Framework(err)
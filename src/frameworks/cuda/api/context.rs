@@ -1,41 +1,236 @@
-//! Provides the OpenCL API with its context functionality.
+//! Provides the Cuda API with its context functionality.
 //!
-//! At Collenchyma device can be understood as a synonym to OpenCL's context.
+//! At Collenchyma device can be understood as a synonym to Cuda's context.
 
-use libc;
 use super::{API, Error};
-use frameworks::cuda::Device;
-use super::types as cl;
+use frameworks::cuda::{Context, Device};
+use frameworks::cuda::context::{ContextFlags, CTX_SCHED_SPIN, CTX_SCHED_YIELD, CTX_SCHED_BLOCKING_SYNC};
+use hardware::IHardware;
 use super::ffi::*;
+use std::ptr;
+use std::marker::PhantomData;
+
+#[derive(Debug)]
+/// Keeps a Cuda context current to the calling thread, as long as it is in scope.
+///
+/// Returned by `API::make_current`. Restores the previously current context, when dropped. The
+/// guard can not be sent to another thread, as the context stack belongs to the calling thread.
+pub struct ContextGuard {
+    _marker: PhantomData<*const ()>,
+}
+
+impl Drop for ContextGuard {
+    #[allow(unused_must_use)]
+    fn drop(&mut self) {
+        API::pop_current_context();
+    }
+}
 
 impl API {
-    /// Creates a OpenCL context.
-    ///
-    /// An OpenCL context is created with one or more devices. Contexts are used by the OpenCL
-    /// runtime for managing objects such as command-queues, memory, program and kernel objects
-    /// and for executing kernels on one or more devices specified in the context.
-    /// An OpenCL context is a synonym to a Collenchyma device.
-    pub fn create_context(
-        devices: Vec<Device>,
-        properties: *const cl::context_properties,
-        callback: extern fn (*const libc::c_char, *const libc::c_void, libc::size_t, *mut libc::c_void),
-        user_data: *mut libc::c_void
-    ) -> Result<cl::context_id, Error> {
-        let mut device_ids: Vec<cl::device_id> = devices.iter().map(|device| device.id_c()).collect();
-        Ok(
-            try!(
-                unsafe { API::ffi_create_context(properties, device_ids.len() as u32, device_ids.as_ptr(), callback, user_data) }
-            )
-        )
-    }
-
-    unsafe fn ffi_create_context(
-        properties: *const cl::context_properties,
-        num_devices: cl::uint,
-        devices: *const cl::device_id,
-        pfn_notify: extern fn (*const libc::c_char, *const libc::c_void, libc::size_t, *mut libc::c_void),
-        user_data: *mut libc::c_void
-    ) -> Result<cl::context_id, Error> {
-        unimplemented!()
+    /// Creates a Cuda context.
+    ///
+    /// A Cuda context is created for exactly one device. Contexts are used by the Cuda driver for
+    /// managing objects such as streams, memory, modules and functions. A Cuda context is a
+    /// synonym to a Collenchyma device.
+    /// The context is not left current to the calling thread, use `make_current` before issuing
+    /// driver calls in it.
+    pub fn create_context(device: Device) -> Result<CUcontext, Error> {
+        API::create_context_with_flags(device, ContextFlags::default())
+    }
+
+    /// Creates a Cuda context, whose scheduling policy and host memory mapping are set by `flags`.
+    ///
+    /// Returns an `InvalidValue` error, if `flags` select more than one scheduling policy.
+    pub fn create_context_with_flags(device: Device, flags: ContextFlags) -> Result<CUcontext, Error> {
+        if (flags & (CTX_SCHED_SPIN | CTX_SCHED_YIELD | CTX_SCHED_BLOCKING_SYNC)).bits().count_ones() > 1 {
+            return Err(Error::InvalidValue(format!("The context flags {:?} select more than one scheduling policy.", flags)))
+        }
+        let context = try!(unsafe { API::ffi_create_context(device.id() as CUdevice, flags.bits()) });
+        // cuCtxCreate pushes the new context onto the context stack of the calling thread.
+        try!(unsafe { API::ffi_pop_current_context() });
+        Ok(context)
+    }
+
+    /// Destroys the Cuda context.
+    ///
+    /// Should be called only once, when the last reference to the context is dropped.
+    pub fn destroy_context(context: &mut Context) -> Result<(), Error> {
+        Ok(try!(unsafe { API::ffi_destroy_context(context.id_c()) }))
+    }
+
+    /// Retains the primary context of the `device`.
+    ///
+    /// The primary context is shared with the Cuda runtime API and is created on its first
+    /// retain. Every retain needs to be balanced by `release_primary_context`.
+    pub fn retain_primary_context(device: &Device) -> Result<CUcontext, Error> {
+        try!(API::init());
+        Ok(try!(unsafe { API::ffi_device_primary_ctx_retain(device.id() as CUdevice) }))
+    }
+
+    /// Releases the primary context of the `device`, which has been retained before.
+    pub fn release_primary_context(device: &Device) -> Result<(), Error> {
+        Ok(try!(unsafe { API::ffi_device_primary_ctx_release(device.id() as CUdevice) }))
+    }
+
+    /// Makes the Cuda context current to the calling thread, until the returned guard is dropped.
+    ///
+    /// Driver calls always operate on the current context, so every operation of a context needs
+    /// to hold the guard. Afterwards the previously current context becomes current again, which
+    /// allows to use several contexts from the same thread.
+    pub fn make_current(context: &Context) -> Result<ContextGuard, Error> {
+        API::make_current_c(context.id_c())
+    }
+
+    /// Makes the Cuda context with the C type `context` current to the calling thread, until the
+    /// returned guard is dropped.
+    ///
+    /// Allows to release resources of a context, e.g. from `Drop`, without holding on to the
+    /// Context itself.
+    pub fn make_current_c(context: CUcontext) -> Result<ContextGuard, Error> {
+        try!(unsafe { API::ffi_push_current_context(context) });
+        Ok(ContextGuard { _marker: PhantomData })
+    }
+
+    /// Makes the Cuda context current to the calling thread.
+    ///
+    /// The previously current context is kept on the thread's context stack and becomes current
+    /// again with `pop_current_context`. Prefer `make_current`, which pops the context
+    /// automatically.
+    pub fn push_current_context(context: &Context) -> Result<(), Error> {
+        Ok(try!(unsafe { API::ffi_push_current_context(context.id_c()) }))
+    }
+
+    /// Restores the context, which was current before the last `push_current_context`.
+    pub fn pop_current_context() -> Result<(), Error> {
+        try!(unsafe { API::ffi_pop_current_context() });
+        Ok(())
+    }
+
+    /// Blocks until all work, which was issued in the Cuda context, has been completed.
+    pub fn synchronize_context(context: &Context) -> Result<(), Error> {
+        let _guard = try!(API::make_current(context));
+        unsafe { API::ffi_synchronize_context() }
+    }
+
+    /// Enables direct access from `context` to memory allocations of `peer_context`.
+    ///
+    /// Allows memory copies between the devices of the two contexts to bypass the host.
+    /// Enabling peer access, which is already enabled, is not treated as a failure.
+    pub fn enable_peer_access(context: &Context, peer_context: &Context) -> Result<(), Error> {
+        let _guard = try!(API::make_current(context));
+        unsafe { API::ffi_enable_peer_access(peer_context.id_c()) }
+    }
+
+    /// Disables direct access from `context` to memory allocations of `peer_context`.
+    pub fn disable_peer_access(context: &Context, peer_context: &Context) -> Result<(), Error> {
+        let _guard = try!(API::make_current(context));
+        unsafe { API::ffi_disable_peer_access(peer_context.id_c()) }
+    }
+
+    unsafe fn ffi_create_context(dev: CUdevice, flags: ::libc::c_uint) -> Result<CUcontext, Error> {
+        let mut context: CUcontext = ptr::null_mut();
+        match cuCtxCreate_v2(&mut context, flags, dev) {
+            CUDA_SUCCESS => Ok(context),
+            CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized(format!("Cuda driver is shutting down."))),
+            CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized(format!("Cuda driver has not been initialized."))),
+            CUDA_ERROR_INVALID_CONTEXT => Err(Error::InvalidContext(format!("No valid context is current to the calling thread."))),
+            CUDA_ERROR_INVALID_DEVICE => Err(Error::InvalidDevice(format!("`dev`: {} is not a valid device.", dev))),
+            CUDA_ERROR_INVALID_VALUE => Err(Error::InvalidValue(format!("`flags`: {} are not valid context flags.", flags))),
+            CUDA_ERROR_OUT_OF_MEMORY => Err(Error::OutOfMemory(format!("Failure to allocate resources on the device"))),
+            _ => Err(Error::Other(format!("Unable to create context.")))
+        }
+    }
+
+    unsafe fn ffi_device_primary_ctx_retain(dev: CUdevice) -> Result<CUcontext, Error> {
+        let mut context: CUcontext = ptr::null_mut();
+        match cuDevicePrimaryCtxRetain(&mut context, dev) {
+            CUDA_SUCCESS => Ok(context),
+            CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized(format!("Cuda driver is shutting down."))),
+            CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized(format!("Cuda driver has not been initialized."))),
+            CUDA_ERROR_INVALID_CONTEXT => Err(Error::InvalidContext(format!("No valid context is current to the calling thread."))),
+            CUDA_ERROR_INVALID_DEVICE => Err(Error::InvalidDevice(format!("`dev`: {} is not a valid device.", dev))),
+            CUDA_ERROR_INVALID_VALUE => Err(Error::InvalidValue(format!("Invalid value provided."))),
+            CUDA_ERROR_OUT_OF_MEMORY => Err(Error::OutOfMemory(format!("Failure to allocate resources on the device"))),
+            _ => Err(Error::Other(format!("Unable to retain the primary context.")))
+        }
+    }
+
+    unsafe fn ffi_device_primary_ctx_release(dev: CUdevice) -> Result<(), Error> {
+        match cuDevicePrimaryCtxRelease(dev) {
+            CUDA_SUCCESS => Ok(()),
+            CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized(format!("Cuda driver is shutting down."))),
+            CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized(format!("Cuda driver has not been initialized."))),
+            CUDA_ERROR_INVALID_DEVICE => Err(Error::InvalidDevice(format!("`dev`: {} is not a valid device.", dev))),
+            _ => Err(Error::Other(format!("Unable to release the primary context.")))
+        }
+    }
+
+    unsafe fn ffi_destroy_context(context: CUcontext) -> Result<(), Error> {
+        match cuCtxDestroy_v2(context) {
+            CUDA_SUCCESS => Ok(()),
+            CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized(format!("Cuda driver is shutting down."))),
+            CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized(format!("Cuda driver has not been initialized."))),
+            CUDA_ERROR_INVALID_CONTEXT => Err(Error::InvalidContext(format!("`context`: {:?} is not a valid context.", context))),
+            CUDA_ERROR_INVALID_VALUE => Err(Error::InvalidValue(format!("`context`: {:?} is not a valid context.", context))),
+            _ => Err(Error::Other(format!("Unable to destroy context.")))
+        }
+    }
+
+    unsafe fn ffi_push_current_context(context: CUcontext) -> Result<(), Error> {
+        match cuCtxPushCurrent_v2(context) {
+            CUDA_SUCCESS => Ok(()),
+            CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized(format!("Cuda driver is shutting down."))),
+            CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized(format!("Cuda driver has not been initialized."))),
+            CUDA_ERROR_INVALID_CONTEXT => Err(Error::InvalidContext(format!("`context`: {:?} is not a valid context.", context))),
+            CUDA_ERROR_INVALID_VALUE => Err(Error::InvalidValue(format!("`context`: {:?} is not a valid context.", context))),
+            _ => Err(Error::Other(format!("Unable to push context to the current thread.")))
+        }
+    }
+
+    unsafe fn ffi_pop_current_context() -> Result<CUcontext, Error> {
+        let mut context: CUcontext = ptr::null_mut();
+        match cuCtxPopCurrent_v2(&mut context) {
+            CUDA_SUCCESS => Ok(context),
+            CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized(format!("Cuda driver is shutting down."))),
+            CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized(format!("Cuda driver has not been initialized."))),
+            CUDA_ERROR_INVALID_CONTEXT => Err(Error::InvalidContext(format!("No valid context is current to the calling thread."))),
+            _ => Err(Error::Other(format!("Unable to pop context from the current thread.")))
+        }
+    }
+
+    unsafe fn ffi_synchronize_context() -> Result<(), Error> {
+        match cuCtxSynchronize() {
+            CUDA_SUCCESS => Ok(()),
+            CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized(format!("Cuda driver is shutting down."))),
+            CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized(format!("Cuda driver has not been initialized."))),
+            CUDA_ERROR_INVALID_CONTEXT => Err(Error::InvalidContext(format!("No valid context is current to the calling thread."))),
+            CUDA_ERROR_LAUNCH_FAILED => Err(Error::LaunchFailed(format!("A previously launched kernel failed while executing."))),
+            _ => Err(Error::Other(format!("Unable to synchronize context.")))
+        }
+    }
+
+    unsafe fn ffi_enable_peer_access(peer_context: CUcontext) -> Result<(), Error> {
+        match cuCtxEnablePeerAccess(peer_context, 0) {
+            CUDA_SUCCESS => Ok(()),
+            CUDA_ERROR_PEER_ACCESS_ALREADY_ENABLED => Ok(()),
+            CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized(format!("Cuda driver is shutting down."))),
+            CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized(format!("Cuda driver has not been initialized."))),
+            CUDA_ERROR_INVALID_CONTEXT => Err(Error::InvalidContext(format!("`peer_context`: {:?} is not a valid context.", peer_context))),
+            CUDA_ERROR_INVALID_DEVICE => Err(Error::InvalidDevice(format!("the devices of the current context and `peer_context` are the same."))),
+            CUDA_ERROR_PEER_ACCESS_UNSUPPORTED => Err(Error::PeerAccessUnsupported(format!("peer access is not supported between the devices of the current context and `peer_context`."))),
+            CUDA_ERROR_TOO_MANY_PEERS => Err(Error::TooManyPeers(format!("the device of the current context has reached its maximum number of peers."))),
+            _ => Err(Error::Other(format!("Unable to enable peer access.")))
+        }
+    }
+
+    unsafe fn ffi_disable_peer_access(peer_context: CUcontext) -> Result<(), Error> {
+        match cuCtxDisablePeerAccess(peer_context) {
+            CUDA_SUCCESS => Ok(()),
+            CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized(format!("Cuda driver is shutting down."))),
+            CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized(format!("Cuda driver has not been initialized."))),
+            CUDA_ERROR_INVALID_CONTEXT => Err(Error::InvalidContext(format!("`peer_context`: {:?} is not a valid context.", peer_context))),
+            CUDA_ERROR_PEER_ACCESS_NOT_ENABLED => Err(Error::PeerAccessNotEnabled(format!("peer access to `peer_context`: {:?} has not been enabled.", peer_context))),
+            _ => Err(Error::Other(format!("Unable to disable peer access.")))
+        }
     }
 }
@@ -2,11 +2,13 @@
 
 use libc;
 use super::{API, Error};
-use frameworks::cuda::{Device, DeviceInfo};
+use frameworks::cuda::{Device, DeviceInfo, DeviceAttribute};
+use hardware::IHardware;
 use super::types as cl;
 use super::ffi::*;
 use std::ptr;
 use std::iter::repeat;
+use std::sync::{Once, ONCE_INIT};
 
 impl API {
     /// Returns fully initialized devices for a specific platform.
@@ -14,12 +16,66 @@ impl API {
     /// Combines the fetching of all device ids and the fetching of the individual device
     /// information.
     pub fn load_devices() -> Result<Vec<Device>, Error> {
-        unimplemented!()
+        match API::load_device_list() {
+            Ok(device_list) => {
+                Ok(
+                    device_list.iter().map(|device| {
+                        device.clone()
+                            .load_name()
+                            .load_device_type()
+                            .load_compute_units()
+                            .load_memory()
+                    }).collect()
+                )
+            },
+            Err(err) => Err(err)
+        }
     }
 
-    /// Returns a list of available devices for the provided platform.
+    /// Returns a list of available devices, ordered by their Cuda device ordinal.
+    ///
+    /// Initializes the Cuda driver, if it has not been initialized yet.
     pub fn load_device_list() -> Result<Vec<Device>, Error> {
-        unimplemented!()
+        let num_devices = try!(API::device_count());
+        let mut devices = Vec::with_capacity(num_devices);
+        for ordinal in 0..num_devices {
+            devices.push(try!(API::get_device(ordinal)));
+        }
+        Ok(devices)
+    }
+
+    /// Initializes the Cuda driver.
+    ///
+    /// The driver is initialized exactly once per process, later calls return the result of the
+    /// first initialization. Every other call into the driver fails, until it has been initialized.
+    pub fn init() -> Result<(), Error> {
+        unsafe { API::ffi_init(0) }
+    }
+
+    /// Returns the version of the installed Cuda driver, e.g. `7050` for Cuda 7.5.
+    ///
+    /// The version is encoded as `1000 * major + 10 * minor`. Works without initializing the
+    /// driver and without a context. Collenchyma only links the Cuda driver API, not the runtime
+    /// API, so the version of a Cuda runtime is not available.
+    pub fn driver_version() -> Result<i32, Error> {
+        Ok(try!(unsafe { API::ffi_driver_get_version() }))
+    }
+
+    /// Returns the number of Cuda-capable devices.
+    ///
+    /// Initializes the Cuda driver, if it has not been initialized yet.
+    pub fn device_count() -> Result<usize, Error> {
+        try!(API::init());
+        Ok(try!(unsafe { API::ffi_device_get_count() }) as usize)
+    }
+
+    /// Returns the device with the Cuda device `ordinal`, which is smaller than `device_count`.
+    ///
+    /// Initializes the Cuda driver, if it has not been initialized yet.
+    pub fn get_device(ordinal: usize) -> Result<Device, Error> {
+        try!(API::init());
+        let device = try!(unsafe { API::ffi_device_get(ordinal as ::libc::c_int) });
+        Ok(Device::from_isize(device as isize))
     }
 
     /// Returns the requested DeviceInfo for the provided device.
@@ -36,6 +92,40 @@ impl API {
         Ok(DeviceInfo::new(buf))
     }
 
+    /// Returns whether the device `dev_a` is capable of directly accessing memory of `dev_b`.
+    pub fn can_access_peer(dev_a: &Device, dev_b: &Device) -> Result<bool, Error> {
+        Ok(try!(unsafe { API::ffi_device_can_access_peer(dev_a.id() as CUdevice, dev_b.id() as CUdevice) }))
+    }
+
+    /// Returns the value of the requested `attribute` for the provided device.
+    ///
+    /// The value is returned as reported by the Cuda driver. Its meaning depends on the
+    /// requested attribute.
+    pub fn device_attribute(device: &Device, attribute: DeviceAttribute) -> Result<i32, Error> {
+        Ok(try!(unsafe { API::ffi_device_get_attribute(attribute.as_c(), device.id() as CUdevice) }))
+    }
+
+    /// Returns the name of the provided device.
+    pub fn device_name(device: &Device) -> Result<String, Error> {
+        let mut buf: Vec<u8> = repeat(0u8).take(256).collect();
+        try!(unsafe { API::ffi_device_get_name(buf.as_mut_ptr() as *mut libc::c_char, buf.len() as libc::c_int, device.id() as CUdevice) });
+        let len = buf.iter().position(|&byte| byte == 0).unwrap_or(buf.len());
+        buf.truncate(len);
+        Ok(String::from_utf8_lossy(&buf).into_owned())
+    }
+
+    /// Returns the total amount of memory in bytes of the provided device.
+    pub fn device_total_memory(device: &Device) -> Result<usize, Error> {
+        Ok(try!(unsafe { API::ffi_device_total_mem(device.id() as CUdevice) }))
+    }
+
+    /// Returns the compute capability of the provided device as `(major, minor)`.
+    pub fn compute_capability(device: &Device) -> Result<(i32, i32), Error> {
+        let major = try!(API::device_attribute(device, DeviceAttribute::ComputeCapabilityMajor));
+        let minor = try!(API::device_attribute(device, DeviceAttribute::ComputeCapabilityMinor));
+        Ok((major, minor))
+    }
+
     unsafe fn ffi_get_device_ids(
         platform: cl::platform_id,
         device_type: cl::device_type,
@@ -55,4 +145,94 @@ impl API {
     ) -> Result<(), Error> {
         unimplemented!()
     }
+
+    unsafe fn ffi_init(flags: ::libc::c_uint) -> Result<(), Error> {
+        static INIT: Once = ONCE_INIT;
+        static mut STATUS: CUresult = CUDA_SUCCESS;
+        INIT.call_once(|| STATUS = cuInit(flags));
+        match STATUS {
+            CUDA_SUCCESS => Ok(()),
+            CUDA_ERROR_INVALID_VALUE => Err(Error::InvalidValue(format!("`flags`: {} is not a valid initialization flag.", flags))),
+            CUDA_ERROR_INVALID_DEVICE => Err(Error::InvalidDevice(format!("No valid Cuda device is available."))),
+            CUDA_ERROR_NO_DEVICE => Err(Error::NoDevice(format!("No Cuda-capable device is available."))),
+            _ => Err(Error::Other(format!("Unable to initialize the Cuda driver.")))
+        }
+    }
+
+    unsafe fn ffi_driver_get_version() -> Result<i32, Error> {
+        let mut version: ::libc::c_int = 0;
+        match cuDriverGetVersion(&mut version) {
+            CUDA_SUCCESS => Ok(version),
+            CUDA_ERROR_INVALID_VALUE => Err(Error::InvalidValue(format!("`driverVersion` is NULL."))),
+            _ => Err(Error::Other(format!("Unable to get the driver version.")))
+        }
+    }
+
+    unsafe fn ffi_device_get_count() -> Result<i32, Error> {
+        let mut count: ::libc::c_int = 0;
+        match cuDeviceGetCount(&mut count) {
+            CUDA_SUCCESS => Ok(count),
+            CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized(format!("Cuda driver is shutting down."))),
+            CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized(format!("Cuda driver has not been initialized."))),
+            CUDA_ERROR_INVALID_VALUE => Err(Error::InvalidValue(format!("`count` is NULL."))),
+            _ => Err(Error::Other(format!("Unable to get device count.")))
+        }
+    }
+
+    unsafe fn ffi_device_get(ordinal: ::libc::c_int) -> Result<CUdevice, Error> {
+        let mut device: CUdevice = 0;
+        match cuDeviceGet(&mut device, ordinal) {
+            CUDA_SUCCESS => Ok(device),
+            CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized(format!("Cuda driver is shutting down."))),
+            CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized(format!("Cuda driver has not been initialized."))),
+            CUDA_ERROR_INVALID_VALUE => Err(Error::InvalidValue(format!("`device` is NULL."))),
+            CUDA_ERROR_INVALID_DEVICE => Err(Error::InvalidDevice(format!("`ordinal`: {} is not a valid device ordinal.", ordinal))),
+            _ => Err(Error::Other(format!("Unable to get device.")))
+        }
+    }
+
+    unsafe fn ffi_device_get_name(name: *mut libc::c_char, len: libc::c_int, dev: CUdevice) -> Result<(), Error> {
+        match cuDeviceGetName(name, len, dev) {
+            CUDA_SUCCESS => Ok(()),
+            CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized(format!("Cuda driver is shutting down."))),
+            CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized(format!("Cuda driver has not been initialized."))),
+            CUDA_ERROR_INVALID_VALUE => Err(Error::InvalidValue(format!("`name` is NULL or `len`: {} is not valid.", len))),
+            CUDA_ERROR_INVALID_DEVICE => Err(Error::InvalidDevice(format!("`dev`: {} is not a valid device.", dev))),
+            _ => Err(Error::Other(format!("Unable to get device name.")))
+        }
+    }
+
+    unsafe fn ffi_device_total_mem(dev: CUdevice) -> Result<usize, Error> {
+        let mut bytes: size_t = 0;
+        match cuDeviceTotalMem_v2(&mut bytes, dev) {
+            CUDA_SUCCESS => Ok(bytes as usize),
+            CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized(format!("Cuda driver is shutting down."))),
+            CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized(format!("Cuda driver has not been initialized."))),
+            CUDA_ERROR_INVALID_DEVICE => Err(Error::InvalidDevice(format!("`dev`: {} is not a valid device.", dev))),
+            _ => Err(Error::Other(format!("Unable to get device total memory.")))
+        }
+    }
+
+    unsafe fn ffi_device_can_access_peer(dev: CUdevice, peer_dev: CUdevice) -> Result<bool, Error> {
+        let mut can_access_peer: ::libc::c_int = 0;
+        match cuDeviceCanAccessPeer(&mut can_access_peer, dev, peer_dev) {
+            CUDA_SUCCESS => Ok(can_access_peer == 1),
+            CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized(format!("Cuda driver is shutting down."))),
+            CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized(format!("Cuda driver has not been initialized."))),
+            CUDA_ERROR_INVALID_DEVICE => Err(Error::InvalidDevice(format!("`dev`: {} or `peer_dev`: {} is not a valid device.", dev, peer_dev))),
+            _ => Err(Error::Other(format!("Unable to determine peer access capability.")))
+        }
+    }
+
+    unsafe fn ffi_device_get_attribute(attribute: CUdevice_attribute, dev: CUdevice) -> Result<i32, Error> {
+        let mut value: ::libc::c_int = 0;
+        match cuDeviceGetAttribute(&mut value, attribute, dev) {
+            CUDA_SUCCESS => Ok(value),
+            CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized(format!("Cuda driver is shutting down."))),
+            CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized(format!("Cuda driver has not been initialized."))),
+            CUDA_ERROR_INVALID_VALUE => Err(Error::InvalidValue(format!("`attribute`: {} is not a valid device attribute.", attribute))),
+            CUDA_ERROR_INVALID_DEVICE => Err(Error::InvalidDevice(format!("`dev`: {} is not a valid device.", dev))),
+            _ => Err(Error::Other(format!("Unable to get device attribute.")))
+        }
+    }
 }
@@ -1,126 +1,145 @@
-//! Provides Rust Errors for OpenCL's status.
+//! Provides Rust Errors for Cuda's status.
 
+use super::ffi::*;
 use std::{fmt, error};
 
 #[derive(Debug)]
-/// Defines OpenCL errors.
+/// Defines Cuda errors.
+///
+/// Maps the `CUresult` status codes of the Cuda driver API. With the `cuda-runtime` feature, the
+/// `cudaError_t` status codes of the runtime API are mapped to the variant of the same meaning.
 pub enum Error {
-    /// Failure with provided platform.
-    InvalidPlatform(String),
+    /// Failure with provided param(s).
+    InvalidValue(String),
+    /// Failure to allocate memory on the device.
+    OutOfMemory(String),
+    /// Failure with driver initialization.
+    NotInitialized(String),
+    /// Failure with driver availability, as it is shutting down.
+    Deinitialized(String),
+    /// Failure with device availability.
+    NoDevice(String),
     /// Failure with provided device param.
     InvalidDevice(String),
-    /// Failure with provided platform.
-    InvalidDeviceType(String),
     /// Failure with provided context.
     InvalidContext(String),
-    /// Failure with provided memory object.
-    InvalidMemObject(String),
-    /// Failure with provided command queue.
-    InvalidCommandQueue(String),
-    /// Failure with provided event list.
-    InvalidEventWaitList(String),
-    /// Failure with provided param(s).
-    InvalidValue(String),
-    /// Failure with provided property param.
-    InvalidProperty(String),
-    /// Failure with provided operation param.
-    InvalidOperation(String),
-    /// Failure with provided buffer size.
-    InvalidBufferSize(String),
-    /// Failure with provided host pointer.
-    InvalidHostPtr(String),
-    /// Failure with device availability.
-    DeviceNotFound(String),
-    /// Failure with device availability.
-    DeviceNotAvailable(String),
-    /// Failure to allocate memory.
-    MemObjectAllocationFailure(String),
-    /// Failure with sub buffer offset.
-    MisalignedSubBufferOffset(String),
-    /// Failure with events in wait list.
-    ExecStatusErrorForEventsInWaitList(String),
-    /// Failure to allocate resources on the device.
-    OutOfResources(String),
-    /// Failure to allocate resources on the host.
-    OutOfHostMemory(String),
+    /// Failure with provided handle, e.g. a stream or an event.
+    InvalidHandle(String),
+    /// Failure with an asynchronous operation, which has not completed yet.
+    NotReady(String),
+    /// Failure with peer access support between devices.
+    PeerAccessUnsupported(String),
+    /// Failure with peer access, which has not been enabled.
+    PeerAccessNotEnabled(String),
+    /// Failure to enable peer access, as the device reached its maximum of peers.
+    TooManyPeers(String),
+    /// Failure while executing a kernel.
+    LaunchFailed(String),
+    /// Failure to launch a kernel, as the device lacks the resources, e.g. registers.
+    LaunchOutOfResources(String),
+    /// Failure with a module image, which is not valid for the device.
+    InvalidImage(String),
+    /// Failure to JIT compile PTX.
+    InvalidPtx(String),
+    /// Failure to find a named symbol, e.g. a function of a module.
+    NotFound(String),
+    /// Failure with the operation, which is not supported by the device.
+    NotSupported(String),
     /// Failure not closer defined.
     Other(String),
 }
 
-impl fmt::Display for Error {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+impl Error {
+    fn status(&self) -> Option<(&'static str, CUresult)> {
         match *self {
-            Error::InvalidPlatform(ref err) => write!(f, "{:?}", err),
-            Error::InvalidDevice(ref err) => write!(f, "{:?}", err),
-            Error::InvalidDeviceType(ref err) => write!(f, "{:?}", err),
-            Error::InvalidContext(ref err) => write!(f, "{:?}", err),
-            Error::InvalidMemObject(ref err) => write!(f, "{:?}", err),
-            Error::InvalidCommandQueue(ref err) => write!(f, "{:?}", err),
-            Error::InvalidEventWaitList(ref err) => write!(f, "{:?}", err),
-            Error::InvalidValue(ref err) => write!(f, "{:?}", err),
-            Error::InvalidProperty(ref err) => write!(f, "{:?}", err),
-            Error::InvalidOperation(ref err) => write!(f, "{:?}", err),
-            Error::InvalidBufferSize(ref err) => write!(f, "{:?}", err),
-            Error::InvalidHostPtr(ref err) => write!(f, "{:?}", err),
-            Error::DeviceNotFound(ref err) => write!(f, "{:?}", err),
-            Error::DeviceNotAvailable(ref err) => write!(f, "{:?}", err),
-            Error::MemObjectAllocationFailure(ref err) => write!(f, "{:?}", err),
-            Error::MisalignedSubBufferOffset(ref err) => write!(f, "{:?}", err),
-            Error::ExecStatusErrorForEventsInWaitList(ref err) => write!(f, "{:?}", err),
-            Error::OutOfResources(ref err) => write!(f, "{:?}", err),
-            Error::OutOfHostMemory(ref err) => write!(f, "{:?}", err),
-            Error::Other(ref err) => write!(f, "{:?}", err),
+            Error::InvalidValue(_) => Some(("CUDA_ERROR_INVALID_VALUE", CUDA_ERROR_INVALID_VALUE)),
+            Error::OutOfMemory(_) => Some(("CUDA_ERROR_OUT_OF_MEMORY", CUDA_ERROR_OUT_OF_MEMORY)),
+            Error::NotInitialized(_) => Some(("CUDA_ERROR_NOT_INITIALIZED", CUDA_ERROR_NOT_INITIALIZED)),
+            Error::Deinitialized(_) => Some(("CUDA_ERROR_DEINITIALIZED", CUDA_ERROR_DEINITIALIZED)),
+            Error::NoDevice(_) => Some(("CUDA_ERROR_NO_DEVICE", CUDA_ERROR_NO_DEVICE)),
+            Error::InvalidDevice(_) => Some(("CUDA_ERROR_INVALID_DEVICE", CUDA_ERROR_INVALID_DEVICE)),
+            Error::InvalidContext(_) => Some(("CUDA_ERROR_INVALID_CONTEXT", CUDA_ERROR_INVALID_CONTEXT)),
+            Error::InvalidHandle(_) => Some(("CUDA_ERROR_INVALID_HANDLE", CUDA_ERROR_INVALID_HANDLE)),
+            Error::NotReady(_) => Some(("CUDA_ERROR_NOT_READY", CUDA_ERROR_NOT_READY)),
+            Error::PeerAccessUnsupported(_) => Some(("CUDA_ERROR_PEER_ACCESS_UNSUPPORTED", CUDA_ERROR_PEER_ACCESS_UNSUPPORTED)),
+            Error::PeerAccessNotEnabled(_) => Some(("CUDA_ERROR_PEER_ACCESS_NOT_ENABLED", CUDA_ERROR_PEER_ACCESS_NOT_ENABLED)),
+            Error::TooManyPeers(_) => Some(("CUDA_ERROR_TOO_MANY_PEERS", CUDA_ERROR_TOO_MANY_PEERS)),
+            Error::LaunchFailed(_) => Some(("CUDA_ERROR_LAUNCH_FAILED", CUDA_ERROR_LAUNCH_FAILED)),
+            Error::LaunchOutOfResources(_) => Some(("CUDA_ERROR_LAUNCH_OUT_OF_RESOURCES", CUDA_ERROR_LAUNCH_OUT_OF_RESOURCES)),
+            Error::InvalidImage(_) => Some(("CUDA_ERROR_INVALID_IMAGE", CUDA_ERROR_INVALID_IMAGE)),
+            Error::InvalidPtx(_) => Some(("CUDA_ERROR_INVALID_PTX", CUDA_ERROR_INVALID_PTX)),
+            Error::NotFound(_) => Some(("CUDA_ERROR_NOT_FOUND", CUDA_ERROR_NOT_FOUND)),
+            Error::NotSupported(_) => Some(("CUDA_ERROR_NOT_SUPPORTED", CUDA_ERROR_NOT_SUPPORTED)),
+            Error::Other(_) => None,
         }
     }
-}
 
-impl error::Error for Error {
-    fn description(&self) -> &str {
+    /// Returns the numeric Cuda status code (`CUresult`) of the error.
+    ///
+    /// Returns `None` for `Other`, as its status is not mapped to a variant.
+    pub fn code(&self) -> Option<CUresult> {
+        self.status().map(|(_, code)| code)
+    }
+
+    fn message(&self) -> &str {
         match *self {
-            Error::InvalidPlatform(ref err) => err,
+            Error::InvalidValue(ref err) => err,
+            Error::OutOfMemory(ref err) => err,
+            Error::NotInitialized(ref err) => err,
+            Error::Deinitialized(ref err) => err,
+            Error::NoDevice(ref err) => err,
             Error::InvalidDevice(ref err) => err,
-            Error::InvalidDeviceType(ref err) => err,
             Error::InvalidContext(ref err) => err,
-            Error::InvalidMemObject(ref err) => err,
-            Error::InvalidCommandQueue(ref err) => err,
-            Error::InvalidEventWaitList(ref err) => err,
-            Error::InvalidValue(ref err) => err,
-            Error::InvalidProperty(ref err) => err,
-            Error::InvalidOperation(ref err) => err,
-            Error::InvalidBufferSize(ref err) => err,
-            Error::InvalidHostPtr(ref err) => err,
-            Error::DeviceNotFound(ref err) => err,
-            Error::DeviceNotAvailable(ref err) => err,
-            Error::MemObjectAllocationFailure(ref err) => err,
-            Error::MisalignedSubBufferOffset(ref err) => err,
-            Error::ExecStatusErrorForEventsInWaitList(ref err) => err,
-            Error::OutOfResources(ref err) => err,
-            Error::OutOfHostMemory(ref err) => err,
+            Error::InvalidHandle(ref err) => err,
+            Error::NotReady(ref err) => err,
+            Error::PeerAccessUnsupported(ref err) => err,
+            Error::PeerAccessNotEnabled(ref err) => err,
+            Error::TooManyPeers(ref err) => err,
+            Error::LaunchFailed(ref err) => err,
+            Error::LaunchOutOfResources(ref err) => err,
+            Error::InvalidImage(ref err) => err,
+            Error::InvalidPtx(ref err) => err,
+            Error::NotFound(ref err) => err,
+            Error::NotSupported(ref err) => err,
             Error::Other(ref err) => err,
         }
     }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.status() {
+            Some((name, code)) => write!(f, "{} ({}): {}", name, code, self.message()),
+            None => write!(f, "{:?}", self.message()),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn description(&self) -> &str {
+        self.message()
+    }
 
     fn cause(&self) -> Option<&error::Error> {
         match *self {
-            Error::InvalidPlatform(_) => None,
+            Error::InvalidValue(_) => None,
+            Error::OutOfMemory(_) => None,
+            Error::NotInitialized(_) => None,
+            Error::Deinitialized(_) => None,
+            Error::NoDevice(_) => None,
             Error::InvalidDevice(_) => None,
-            Error::InvalidDeviceType(_) => None,
             Error::InvalidContext(_) => None,
-            Error::InvalidMemObject(_) => None,
-            Error::InvalidCommandQueue(_) => None,
-            Error::InvalidEventWaitList(_) => None,
-            Error::InvalidValue(_) => None,
-            Error::InvalidProperty(_) => None,
-            Error::InvalidOperation(_) => None,
-            Error::InvalidBufferSize(_) => None,
-            Error::InvalidHostPtr(_) => None,
-            Error::DeviceNotFound(_) => None,
-            Error::DeviceNotAvailable(_) => None,
-            Error::MemObjectAllocationFailure(_) => None,
-            Error::MisalignedSubBufferOffset(_) => None,
-            Error::ExecStatusErrorForEventsInWaitList(_) => None,
-            Error::OutOfResources(_) => None,
-            Error::OutOfHostMemory(_) => None,
+            Error::InvalidHandle(_) => None,
+            Error::NotReady(_) => None,
+            Error::PeerAccessUnsupported(_) => None,
+            Error::PeerAccessNotEnabled(_) => None,
+            Error::TooManyPeers(_) => None,
+            Error::LaunchFailed(_) => None,
+            Error::LaunchOutOfResources(_) => None,
+            Error::InvalidImage(_) => None,
+            Error::InvalidPtx(_) => None,
+            Error::NotFound(_) => None,
+            Error::NotSupported(_) => None,
             Error::Other(_) => None,
         }
     }
@@ -0,0 +1,86 @@
+//! Provides the Cuda API with its event functionality.
+
+use super::{API, Error};
+use frameworks::cuda::{Context, Event, EventFlags, Stream};
+use super::ffi::*;
+use std::ptr;
+
+impl API {
+    /// Creates a new Cuda event in the Cuda context.
+    ///
+    /// Pass `EVENT_DISABLE_TIMING` if the event is only used for synchronization, so that no
+    /// timing overhead is introduced.
+    pub fn create_event(context: &Context, flags: EventFlags) -> Result<Event, Error> {
+        let _guard = try!(API::make_current(context));
+        Ok(Event::from_c(try!(unsafe { API::ffi_event_create(flags.bits()) })))
+    }
+
+    /// Destroys the provided Cuda event of the Cuda context.
+    pub fn destroy_event(context: &Context, event: Event) -> Result<(), Error> {
+        let _guard = try!(API::make_current(context));
+        Ok(try!(unsafe { API::ffi_event_destroy(event.id_c()) }))
+    }
+
+    /// Records the provided Cuda event on the `stream`.
+    ///
+    /// The event is completed, once all preceding commands on the stream have been executed.
+    pub fn record_event(event: &Event, stream: &Stream) -> Result<(), Error> {
+        Ok(try!(unsafe { API::ffi_event_record(event.id_c(), stream.id_c()) }))
+    }
+
+    /// Returns the elapsed time between the `start` and `end` event in milliseconds.
+    ///
+    /// Both events need to be completed and must not have been created with
+    /// `EVENT_DISABLE_TIMING`.
+    pub fn elapsed_time(start: &Event, end: &Event) -> Result<f32, Error> {
+        Ok(try!(unsafe { API::ffi_event_elapsed_time(start.id_c(), end.id_c()) }))
+    }
+
+    unsafe fn ffi_event_create(flags: ::libc::c_uint) -> Result<CUevent, Error> {
+        let mut event: CUevent = ptr::null_mut();
+        match cuEventCreate(&mut event, flags) {
+            CUDA_SUCCESS => Ok(event),
+            CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized(format!("Cuda driver is shutting down."))),
+            CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized(format!("Cuda driver has not been initialized."))),
+            CUDA_ERROR_INVALID_CONTEXT => Err(Error::InvalidContext(format!("No valid context is current to the calling thread."))),
+            CUDA_ERROR_INVALID_VALUE => Err(Error::InvalidValue(format!("`flags`: {} is not a valid event flag.", flags))),
+            CUDA_ERROR_OUT_OF_MEMORY => Err(Error::OutOfMemory(format!("Failure to allocate resources on the device"))),
+            _ => Err(Error::Other(format!("Unable to create event.")))
+        }
+    }
+
+    unsafe fn ffi_event_destroy(event: CUevent) -> Result<(), Error> {
+        match cuEventDestroy_v2(event) {
+            CUDA_SUCCESS => Ok(()),
+            CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized(format!("Cuda driver is shutting down."))),
+            CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized(format!("Cuda driver has not been initialized."))),
+            CUDA_ERROR_INVALID_CONTEXT => Err(Error::InvalidContext(format!("No valid context is current to the calling thread."))),
+            CUDA_ERROR_INVALID_HANDLE => Err(Error::InvalidHandle(format!("`event`: {:?} is not a valid event.", event))),
+            _ => Err(Error::Other(format!("Unable to destroy event.")))
+        }
+    }
+
+    unsafe fn ffi_event_record(event: CUevent, stream: CUstream) -> Result<(), Error> {
+        match cuEventRecord(event, stream) {
+            CUDA_SUCCESS => Ok(()),
+            CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized(format!("Cuda driver is shutting down."))),
+            CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized(format!("Cuda driver has not been initialized."))),
+            CUDA_ERROR_INVALID_CONTEXT => Err(Error::InvalidContext(format!("No valid context is current to the calling thread."))),
+            CUDA_ERROR_INVALID_HANDLE => Err(Error::InvalidHandle(format!("`event`: {:?} or `stream`: {:?} is not valid.", event, stream))),
+            _ => Err(Error::Other(format!("Unable to record event.")))
+        }
+    }
+
+    unsafe fn ffi_event_elapsed_time(start: CUevent, end: CUevent) -> Result<f32, Error> {
+        let mut milliseconds: ::libc::c_float = 0.0;
+        match cuEventElapsedTime(&mut milliseconds, start, end) {
+            CUDA_SUCCESS => Ok(milliseconds),
+            CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized(format!("Cuda driver is shutting down."))),
+            CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized(format!("Cuda driver has not been initialized."))),
+            CUDA_ERROR_INVALID_CONTEXT => Err(Error::InvalidContext(format!("No valid context is current to the calling thread."))),
+            CUDA_ERROR_INVALID_HANDLE => Err(Error::InvalidHandle(format!("`start` or `end` is not a valid event or was created with timing disabled."))),
+            CUDA_ERROR_NOT_READY => Err(Error::NotReady(format!("`start` or `end` has not been completed yet."))),
+            _ => Err(Error::Other(format!("Unable to compute the elapsed time between events.")))
+        }
+    }
+}
@@ -677,6 +677,9 @@ pub const CU_EVENT_BLOCKING_SYNC: ::libc::c_uint = 1;
 pub const CU_EVENT_DISABLE_TIMING: ::libc::c_uint = 2;
 pub const CU_EVENT_INTERPROCESS: ::libc::c_uint = 4;
 pub type CUevent_flags = Enum_CUevent_flags_enum;
+pub const CU_MEMHOSTALLOC_PORTABLE: ::libc::c_uint = 1;
+pub const CU_MEMHOSTALLOC_DEVICEMAP: ::libc::c_uint = 2;
+pub const CU_MEMHOSTALLOC_WRITECOMBINED: ::libc::c_uint = 4;
 pub type Enum_CUoccupancy_flags_enum = ::libc::c_uint;
 pub const CU_OCCUPANCY_DEFAULT: ::libc::c_uint = 0;
 pub const CU_OCCUPANCY_DISABLE_CACHING_OVERRIDE: ::libc::c_uint = 1;
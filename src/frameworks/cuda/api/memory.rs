@@ -2,24 +2,95 @@
 
 use libc;
 use super::{API, Error};
-use frameworks::cuda::{Context, Memory};
+use frameworks::cuda::{Context, Memory, Stream, HostPinnedMemory, HostMappedMemory, DeviceAttribute};
+use device::IDevice;
+use hardware::IHardware;
 use super::types as cl;
 use super::ffi::*;
+use std::ptr;
 
 impl API {
-    /// Allocates memory on the OpenCL device.
+    /// Allocates `size` bytes of memory on the device of the Cuda context.
     ///
-    /// A buffer object stores a one-dimensional collection of elements.  Elements of a buffer
-    /// object can be a scalar data type (such as an int, float), vector data type, or a
-    /// user-defined structure.
-    /// Returns a memory id for the created buffer, which can now be writen to.
-    pub fn create_buffer(context: Context) -> Result<cl::memory_id, Error> {
-        unimplemented!()
+    /// Returns the device pointer for the allocated memory, which can now be writen to.
+    /// If the device runs out of memory, the returned error states the requested and the
+    /// available amount of memory.
+    pub fn create_buffer(context: &Context, size: usize) -> Result<CUdeviceptr, Error> {
+        let _guard = try!(API::make_current(context));
+        let result = match unsafe { API::ffi_mem_alloc(size as size_t) } {
+            Err(Error::OutOfMemory(_)) => {
+                match unsafe { API::ffi_mem_get_info() } {
+                    Ok((free, total)) => Err(Error::OutOfMemory(format!("Unable to allocate {} bytes on the device: {} of {} bytes available.", size, free, total))),
+                    Err(_) => Err(Error::OutOfMemory(format!("Unable to allocate {} bytes on the device.", size))),
+                }
+            },
+            result => result,
+        };
+        result
+    }
+
+    /// Returns the free and total amount of memory in bytes of the device of the Cuda context.
+    ///
+    /// The result is returned as `(free_bytes, total_bytes)`.
+    pub fn memory_info(context: &Context) -> Result<(usize, usize), Error> {
+        let _guard = try!(API::make_current(context));
+        unsafe { API::ffi_mem_get_info() }
+    }
+
+    /// Allocates `size` bytes of page-locked host memory.
+    ///
+    /// Copies between pinned host memory and the device are considerably faster than copies
+    /// from pageable host memory and can be issued asynchronously with `mem_cpy_h_to_d_async`.
+    /// The memory is freed, when the returned HostPinnedMemory goes out of scope.
+    pub fn allocate_host_pinned(context: &Context, size: usize) -> Result<HostPinnedMemory, Error> {
+        let _guard = try!(API::make_current(context));
+        let host_ptr = try!(unsafe { API::ffi_mem_alloc_host(size as size_t) });
+        Ok(unsafe { HostPinnedMemory::from_c(host_ptr, size, context.clone()) })
+    }
+
+    /// Allocates `size` bytes of page-locked host memory, which is mapped into the address space
+    /// of the device of the Cuda context.
+    ///
+    /// The device accesses the memory directly through the device pointer of the returned
+    /// HostMappedMemory, without explicit copies. See the [pinned memory module][pinned] for the
+    /// coherence caveats.
+    /// Returns a `NotSupported` error, if the device does not support unified addressing.
+    /// [pinned]: ../../pinned_memory/index.html
+    pub fn allocate_mapped(context: &Context, size: usize) -> Result<HostMappedMemory, Error> {
+        for device in context.hardwares() {
+            if try!(API::device_attribute(&device, DeviceAttribute::UnifiedAddressing)) != 1 {
+                return Err(Error::NotSupported(format!("The device {:?} does not support unified addressing, which is required for mapped memory.", device.name())))
+            }
+        }
+        let _guard = try!(API::make_current(context));
+        let result = unsafe {
+            API::ffi_mem_host_alloc(size as size_t, CU_MEMHOSTALLOC_DEVICEMAP).and_then(|host_ptr| {
+                match API::ffi_mem_host_get_device_pointer(host_ptr) {
+                    Ok(device_ptr) => Ok((host_ptr, device_ptr)),
+                    Err(err) => {
+                        let _ = API::ffi_mem_free_host(host_ptr);
+                        Err(err)
+                    }
+                }
+            })
+        };
+        let (host_ptr, device_ptr) = try!(result);
+        Ok(unsafe { HostMappedMemory::new(HostPinnedMemory::from_c(host_ptr, size, context.clone()), device_ptr) })
     }
 
-    /// Releases allocated memory from the OpenCL device.
-    pub fn release_memory(memory: &mut Memory) -> Result<(), Error> {
-        Ok(try!(unsafe {API::ffi_release_mem_object(memory.id_c())}))
+    /// Frees page-locked host memory, which has been allocated with `allocate_host_pinned`.
+    pub fn free_host_pinned(context: &Context, host_ptr: *mut libc::c_void) -> Result<(), Error> {
+        let _guard = try!(API::make_current(context));
+        unsafe { API::ffi_mem_free_host(host_ptr) }
+    }
+
+    /// Frees the allocated memory at the device pointer `dptr` of the Cuda context `context`.
+    ///
+    /// Gets called, when the last clone of a [Memory][memory] goes out of scope.
+    /// [memory]: ../../memory/struct.Memory.html
+    pub fn release_memory(context: CUcontext, dptr: CUdeviceptr) -> Result<(), Error> {
+        let _guard = try!(API::make_current_c(context));
+        Ok(try!(unsafe { API::ffi_mem_free(dptr) }))
     }
 
     /// Reads from a buffer to the host memory.
@@ -38,6 +109,81 @@ impl API {
         unimplemented!();
     }
 
+    /// Writes `size` bytes from the host memory at `host_ptr` into the Cuda device memory.
+    ///
+    /// Blocks until the data has been written.
+    pub fn write_to_memory(context: &Context, mem: &mut Memory, host_ptr: *const libc::c_void, size: usize) -> Result<(), Error> {
+        let _guard = try!(API::make_current(context));
+        unsafe { API::ffi_mem_cpy_h_to_d(mem.id_c() as CUdeviceptr, host_ptr, size as size_t) }
+    }
+
+    /// Reads `size` bytes from the Cuda device memory into the host memory at `host_ptr`.
+    ///
+    /// Blocks until the data has been read.
+    pub fn read_from_memory(context: &Context, mem: &Memory, host_ptr: *mut libc::c_void, size: usize) -> Result<(), Error> {
+        let _guard = try!(API::make_current(context));
+        unsafe { API::ffi_mem_cpy_d_to_h(host_ptr, mem.id_c() as CUdeviceptr, size as size_t) }
+    }
+
+    /// Reads `size` bytes, starting `offset` bytes into the Cuda device memory, into the host
+    /// memory at `host_ptr`.
+    ///
+    /// Blocks until the data has been read.
+    pub fn read_from_memory_at(context: &Context, mem: &Memory, offset: usize, host_ptr: *mut libc::c_void, size: usize) -> Result<(), Error> {
+        let _guard = try!(API::make_current(context));
+        unsafe { API::ffi_mem_cpy_d_to_h(host_ptr, mem.id_c() as CUdeviceptr + offset as CUdeviceptr, size as size_t) }
+    }
+
+    /// Copies `size` bytes from the `src` to the `dst` Cuda device memory.
+    ///
+    /// The data never leaves the device.
+    pub fn copy_memory(context: &Context, src: &Memory, dst: &mut Memory, size: usize) -> Result<(), Error> {
+        let _guard = try!(API::make_current(context));
+        unsafe { API::ffi_mem_cpy_d_to_d(dst.id_c() as CUdeviceptr, src.id_c() as CUdeviceptr, size as size_t) }
+    }
+
+    /// Sets every byte of the Cuda device memory to `value`.
+    pub fn memset_d8(context: &Context, mem: &mut Memory, value: u8, count: usize) -> Result<(), Error> {
+        let _guard = try!(API::make_current(context));
+        unsafe { API::ffi_memset_d8(mem.id_c() as CUdeviceptr, value, count as size_t) }
+    }
+
+    /// Sets the first `count` 32-bit words of the Cuda device memory to `value`.
+    pub fn memset_d32(context: &Context, mem: &mut Memory, value: u32, count: usize) -> Result<(), Error> {
+        let _guard = try!(API::make_current(context));
+        unsafe { API::ffi_memset_d32(mem.id_c() as CUdeviceptr, value, count as size_t) }
+    }
+
+    /// Copies `size` bytes from the host memory at `host_ptr` to the Cuda device memory asynchronously.
+    ///
+    /// The copy is issued on the provided `stream` and returns immediately. The host memory has
+    /// to stay valid until the stream has been synchronized.
+    pub fn mem_cpy_h_to_d_async(
+        context: &Context,
+        host_ptr: *const libc::c_void,
+        mem: &Memory,
+        size: libc::size_t,
+        stream: &Stream,
+    ) -> Result<(), Error> {
+        let _guard = try!(API::make_current(context));
+        Ok(try!(unsafe { API::ffi_mem_cpy_h_to_d_async(mem.id_c() as CUdeviceptr, host_ptr, size, stream.id_c()) }))
+    }
+
+    /// Copies `size` bytes from the Cuda device memory to the host memory at `host_ptr` asynchronously.
+    ///
+    /// The copy is issued on the provided `stream` and returns immediately. The host memory may
+    /// only be read after the stream has been synchronized.
+    pub fn mem_cpy_d_to_h_async(
+        context: &Context,
+        mem: &Memory,
+        host_ptr: *mut libc::c_void,
+        size: libc::size_t,
+        stream: &Stream,
+    ) -> Result<(), Error> {
+        let _guard = try!(API::make_current(context));
+        Ok(try!(unsafe { API::ffi_mem_cpy_d_to_h_async(host_ptr, mem.id_c() as CUdeviceptr, size, stream.id_c()) }))
+    }
+
     unsafe fn ffi_create_buffer(
         context: cl::context_id,
         flags: cl::mem_flags,
@@ -47,8 +193,15 @@ impl API {
         unimplemented!()
     }
 
-    unsafe fn ffi_release_mem_object(memobj: cl::memory_id) -> Result<(), Error> {
-        unimplemented!()
+    unsafe fn ffi_mem_free(dptr: CUdeviceptr) -> Result<(), Error> {
+        match cuMemFree_v2(dptr) {
+            CUDA_SUCCESS => Ok(()),
+            CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized(format!("Cuda driver is shutting down."))),
+            CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized(format!("Cuda driver has not been initialized."))),
+            CUDA_ERROR_INVALID_CONTEXT => Err(Error::InvalidContext(format!("No valid context is current to the calling thread."))),
+            CUDA_ERROR_INVALID_VALUE => Err(Error::InvalidValue(format!("`dptr`: {} is not a valid device pointer.", dptr))),
+            _ => Err(Error::Other(format!("Unable to free memory.")))
+        }
     }
 
     unsafe fn ffi_enqueue_read_buffer(
@@ -64,4 +217,180 @@ impl API {
     ) -> Result<(), Error> {
         unimplemented!()
     }
+
+    unsafe fn ffi_mem_cpy_h_to_d(
+        dst_device: CUdeviceptr,
+        src_host: *const libc::c_void,
+        byte_count: size_t,
+    ) -> Result<(), Error> {
+        match cuMemcpyHtoD_v2(dst_device, src_host, byte_count) {
+            CUDA_SUCCESS => Ok(()),
+            CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized(format!("Cuda driver is shutting down."))),
+            CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized(format!("Cuda driver has not been initialized."))),
+            CUDA_ERROR_INVALID_CONTEXT => Err(Error::InvalidContext(format!("No valid context is current to the calling thread."))),
+            CUDA_ERROR_INVALID_VALUE => Err(Error::InvalidValue(format!("the region being copied specified by (dst_device, byte_count) is not valid."))),
+            _ => Err(Error::Other(format!("Unable to copy memory from host to device.")))
+        }
+    }
+
+    unsafe fn ffi_mem_cpy_d_to_h(
+        dst_host: *mut libc::c_void,
+        src_device: CUdeviceptr,
+        byte_count: size_t,
+    ) -> Result<(), Error> {
+        match cuMemcpyDtoH_v2(dst_host, src_device, byte_count) {
+            CUDA_SUCCESS => Ok(()),
+            CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized(format!("Cuda driver is shutting down."))),
+            CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized(format!("Cuda driver has not been initialized."))),
+            CUDA_ERROR_INVALID_CONTEXT => Err(Error::InvalidContext(format!("No valid context is current to the calling thread."))),
+            CUDA_ERROR_INVALID_VALUE => Err(Error::InvalidValue(format!("the region being copied specified by (src_device, byte_count) is not valid."))),
+            _ => Err(Error::Other(format!("Unable to copy memory from device to host.")))
+        }
+    }
+
+    unsafe fn ffi_memset_d8(dst_device: CUdeviceptr, uc: u8, n: size_t) -> Result<(), Error> {
+        match cuMemsetD8_v2(dst_device, uc, n) {
+            CUDA_SUCCESS => Ok(()),
+            CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized(format!("Cuda driver is shutting down."))),
+            CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized(format!("Cuda driver has not been initialized."))),
+            CUDA_ERROR_INVALID_CONTEXT => Err(Error::InvalidContext(format!("No valid context is current to the calling thread."))),
+            CUDA_ERROR_INVALID_VALUE => Err(Error::InvalidValue(format!("the region being set specified by (dst_device, n) is not valid."))),
+            _ => Err(Error::Other(format!("Unable to set device memory.")))
+        }
+    }
+
+    unsafe fn ffi_memset_d32(dst_device: CUdeviceptr, ui: u32, n: size_t) -> Result<(), Error> {
+        match cuMemsetD32_v2(dst_device, ui, n) {
+            CUDA_SUCCESS => Ok(()),
+            CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized(format!("Cuda driver is shutting down."))),
+            CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized(format!("Cuda driver has not been initialized."))),
+            CUDA_ERROR_INVALID_CONTEXT => Err(Error::InvalidContext(format!("No valid context is current to the calling thread."))),
+            CUDA_ERROR_INVALID_VALUE => Err(Error::InvalidValue(format!("the region being set specified by (dst_device, n) is not valid or dst_device is not 4 byte aligned."))),
+            _ => Err(Error::Other(format!("Unable to set device memory.")))
+        }
+    }
+
+    unsafe fn ffi_mem_cpy_d_to_d(
+        dst_device: CUdeviceptr,
+        src_device: CUdeviceptr,
+        byte_count: size_t,
+    ) -> Result<(), Error> {
+        match cuMemcpyDtoD_v2(dst_device, src_device, byte_count) {
+            CUDA_SUCCESS => Ok(()),
+            CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized(format!("Cuda driver is shutting down."))),
+            CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized(format!("Cuda driver has not been initialized."))),
+            CUDA_ERROR_INVALID_CONTEXT => Err(Error::InvalidContext(format!("No valid context is current to the calling thread."))),
+            CUDA_ERROR_INVALID_VALUE => Err(Error::InvalidValue(format!("the regions being copied specified by (src_device, dst_device, byte_count) are not valid."))),
+            _ => Err(Error::Other(format!("Unable to copy memory from device to device.")))
+        }
+    }
+
+    unsafe fn ffi_mem_cpy_h_to_d_async(
+        dst_device: CUdeviceptr,
+        src_host: *const libc::c_void,
+        byte_count: libc::size_t,
+        stream: CUstream,
+    ) -> Result<(), Error> {
+        match cuMemcpyHtoDAsync_v2(dst_device, src_host, byte_count as size_t, stream) {
+            CUDA_SUCCESS => Ok(()),
+            CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized(format!("Cuda driver is shutting down."))),
+            CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized(format!("Cuda driver has not been initialized."))),
+            CUDA_ERROR_INVALID_CONTEXT => Err(Error::InvalidContext(format!("No valid context is current to the calling thread."))),
+            CUDA_ERROR_INVALID_VALUE => Err(Error::InvalidValue(format!("the region being copied specified by (dst_device, byte_count) is not valid."))),
+            CUDA_ERROR_INVALID_HANDLE => Err(Error::InvalidHandle(format!("`stream`: {:?} is not a valid stream.", stream))),
+            _ => Err(Error::Other(format!("Unable to copy memory from host to device asynchronously.")))
+        }
+    }
+
+    unsafe fn ffi_mem_cpy_d_to_h_async(
+        dst_host: *mut libc::c_void,
+        src_device: CUdeviceptr,
+        byte_count: libc::size_t,
+        stream: CUstream,
+    ) -> Result<(), Error> {
+        match cuMemcpyDtoHAsync_v2(dst_host, src_device, byte_count as size_t, stream) {
+            CUDA_SUCCESS => Ok(()),
+            CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized(format!("Cuda driver is shutting down."))),
+            CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized(format!("Cuda driver has not been initialized."))),
+            CUDA_ERROR_INVALID_CONTEXT => Err(Error::InvalidContext(format!("No valid context is current to the calling thread."))),
+            CUDA_ERROR_INVALID_VALUE => Err(Error::InvalidValue(format!("the region being copied specified by (src_device, byte_count) is not valid."))),
+            CUDA_ERROR_INVALID_HANDLE => Err(Error::InvalidHandle(format!("`stream`: {:?} is not a valid stream.", stream))),
+            _ => Err(Error::Other(format!("Unable to copy memory from device to host asynchronously.")))
+        }
+    }
+
+    unsafe fn ffi_mem_alloc(bytesize: size_t) -> Result<CUdeviceptr, Error> {
+        let mut dptr: CUdeviceptr = 0;
+        match cuMemAlloc_v2(&mut dptr, bytesize) {
+            CUDA_SUCCESS => Ok(dptr),
+            CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized(format!("Cuda driver is shutting down."))),
+            CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized(format!("Cuda driver has not been initialized."))),
+            CUDA_ERROR_INVALID_CONTEXT => Err(Error::InvalidContext(format!("No valid context is current to the calling thread."))),
+            CUDA_ERROR_INVALID_VALUE => Err(Error::InvalidValue(format!("`bytesize`: {} is not a valid allocation size.", bytesize))),
+            CUDA_ERROR_OUT_OF_MEMORY => Err(Error::OutOfMemory(format!("Failure to allocate resources on the device"))),
+            _ => Err(Error::Other(format!("Unable to allocate memory.")))
+        }
+    }
+
+    unsafe fn ffi_mem_alloc_host(bytesize: size_t) -> Result<*mut libc::c_void, Error> {
+        let mut pp: *mut libc::c_void = ptr::null_mut();
+        match cuMemAllocHost_v2(&mut pp, bytesize) {
+            CUDA_SUCCESS => Ok(pp),
+            CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized(format!("Cuda driver is shutting down."))),
+            CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized(format!("Cuda driver has not been initialized."))),
+            CUDA_ERROR_INVALID_CONTEXT => Err(Error::InvalidContext(format!("No valid context is current to the calling thread."))),
+            CUDA_ERROR_INVALID_VALUE => Err(Error::InvalidValue(format!("`bytesize`: {} is not a valid allocation size.", bytesize))),
+            CUDA_ERROR_OUT_OF_MEMORY => Err(Error::OutOfMemory(format!("Failure to allocate page-locked memory on the host"))),
+            _ => Err(Error::Other(format!("Unable to allocate page-locked host memory.")))
+        }
+    }
+
+    unsafe fn ffi_mem_host_alloc(bytesize: size_t, flags: ::libc::c_uint) -> Result<*mut libc::c_void, Error> {
+        let mut pp: *mut libc::c_void = ptr::null_mut();
+        match cuMemHostAlloc(&mut pp, bytesize, flags) {
+            CUDA_SUCCESS => Ok(pp),
+            CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized(format!("Cuda driver is shutting down."))),
+            CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized(format!("Cuda driver has not been initialized."))),
+            CUDA_ERROR_INVALID_CONTEXT => Err(Error::InvalidContext(format!("No valid context is current to the calling thread."))),
+            CUDA_ERROR_INVALID_VALUE => Err(Error::InvalidValue(format!("`bytesize`: {} or `flags`: {} is not valid.", bytesize, flags))),
+            CUDA_ERROR_OUT_OF_MEMORY => Err(Error::OutOfMemory(format!("Failure to allocate page-locked memory on the host"))),
+            _ => Err(Error::Other(format!("Unable to allocate mapped host memory.")))
+        }
+    }
+
+    unsafe fn ffi_mem_host_get_device_pointer(p: *mut libc::c_void) -> Result<CUdeviceptr, Error> {
+        let mut dptr: CUdeviceptr = 0;
+        match cuMemHostGetDevicePointer_v2(&mut dptr, p, 0) {
+            CUDA_SUCCESS => Ok(dptr),
+            CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized(format!("Cuda driver is shutting down."))),
+            CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized(format!("Cuda driver has not been initialized."))),
+            CUDA_ERROR_INVALID_CONTEXT => Err(Error::InvalidContext(format!("No valid context is current to the calling thread."))),
+            CUDA_ERROR_INVALID_VALUE => Err(Error::InvalidValue(format!("`p`: {:?} is not mapped host memory.", p))),
+            _ => Err(Error::Other(format!("Unable to get the device pointer of mapped host memory.")))
+        }
+    }
+
+    unsafe fn ffi_mem_free_host(p: *mut libc::c_void) -> Result<(), Error> {
+        match cuMemFreeHost(p) {
+            CUDA_SUCCESS => Ok(()),
+            CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized(format!("Cuda driver is shutting down."))),
+            CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized(format!("Cuda driver has not been initialized."))),
+            CUDA_ERROR_INVALID_CONTEXT => Err(Error::InvalidContext(format!("No valid context is current to the calling thread."))),
+            CUDA_ERROR_INVALID_VALUE => Err(Error::InvalidValue(format!("`p`: {:?} is not page-locked host memory.", p))),
+            _ => Err(Error::Other(format!("Unable to free page-locked host memory.")))
+        }
+    }
+
+    unsafe fn ffi_mem_get_info() -> Result<(usize, usize), Error> {
+        let mut free: size_t = 0;
+        let mut total: size_t = 0;
+        match cuMemGetInfo_v2(&mut free, &mut total) {
+            CUDA_SUCCESS => Ok((free as usize, total as usize)),
+            CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized(format!("Cuda driver is shutting down."))),
+            CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized(format!("Cuda driver has not been initialized."))),
+            CUDA_ERROR_INVALID_CONTEXT => Err(Error::InvalidContext(format!("No valid context is current to the calling thread."))),
+            CUDA_ERROR_INVALID_VALUE => Err(Error::InvalidValue(format!("Invalid value for memory info query provided."))),
+            _ => Err(Error::Other(format!("Unable to get memory info.")))
+        }
+    }
 }
@@ -1,14 +1,29 @@
 //! Provides a safe wrapper around Cuda.
 
 pub use self::error::Error;
+#[cfg(feature = "cuda")]
+pub use self::context::ContextGuard;
+#[cfg(feature = "cuda-runtime")]
+pub use self::runtime::ContextGuard;
 
 #[derive(Debug, Copy, Clone)]
 /// Defines the Cuda API.
 pub struct API;
 
 mod error;
+#[cfg(feature = "cuda")]
 mod context;
+#[cfg(feature = "cuda")]
 mod device;
+#[cfg(feature = "cuda")]
 mod memory;
-mod ffi;
+#[cfg(feature = "cuda")]
+mod module;
+#[cfg(feature = "cuda")]
+mod stream;
+#[cfg(feature = "cuda")]
+mod event;
+#[cfg(feature = "cuda-runtime")]
+mod runtime;
+pub mod ffi;
 pub mod types;
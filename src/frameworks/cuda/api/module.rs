@@ -0,0 +1,128 @@
+//! Provides the Cuda API with its module and kernel launch functionality.
+
+use libc;
+use super::{API, Error};
+use frameworks::cuda::{Context, Module, Function, Stream};
+use super::ffi::*;
+use stats;
+use std::ffi::CString;
+use std::ptr;
+use std::iter::repeat;
+
+/// Size of the buffer, which receives the log of a failed JIT compilation.
+const JIT_LOG_SIZE: usize = 8192;
+
+impl API {
+    /// Loads a module from the provided PTX into the Cuda context.
+    ///
+    /// The PTX gets JIT compiled for the device of the context. If the compilation fails, the
+    /// returned error contains the log of the JIT compiler.
+    pub fn load_module_from_ptx(context: &Context, ptx: &str) -> Result<Module, Error> {
+        let image = try!(CString::new(ptx.as_bytes()).map_err(|_| Error::InvalidValue(format!("`ptx` contains a NULL byte."))));
+        let _guard = try!(API::make_current(context));
+        let result = unsafe { API::ffi_module_load_data_ex(image.as_ptr() as *const libc::c_void) };
+        Ok(Module::from_c(try!(result)))
+    }
+
+    /// Unloads the module from its Cuda context.
+    ///
+    /// Functions of the module must not be launched afterwards.
+    pub fn unload_module(context: &Context, module: Module) -> Result<(), Error> {
+        let _guard = try!(API::make_current(context));
+        unsafe { API::ffi_module_unload(module.id_c()) }
+    }
+
+    /// Returns the function `name` of the module.
+    pub fn module_get_function(module: &Module, name: &str) -> Result<Function, Error> {
+        let name = try!(CString::new(name.as_bytes()).map_err(|_| Error::InvalidValue(format!("`name` contains a NULL byte."))));
+        Ok(Function::from_c(try!(unsafe { API::ffi_module_get_function(module.id_c(), name.as_ptr()) })))
+    }
+
+    /// Launches the `function` with a grid of `grid` blocks of `block` threads each.
+    ///
+    /// `args` holds a pointer to the value of each kernel parameter. The launch is issued on the
+    /// `stream` or on the default stream of the context, if `stream` is `None`. Launches are
+    /// asynchronous, use `synchronize_context` to wait for their completion.
+    pub fn launch_kernel(
+        context: &Context,
+        function: &Function,
+        grid: (u32, u32, u32),
+        block: (u32, u32, u32),
+        shared_mem: u32,
+        stream: Option<&Stream>,
+        args: &mut [*mut libc::c_void]
+    ) -> Result<(), Error> {
+        let stream = stream.map_or(ptr::null_mut(), |stream| stream.id_c());
+        co_trace!("Launching Cuda function {:?} with grid {:?}, block {:?} and {} bytes of shared memory", function.id_c(), grid, block, shared_mem);
+        let _guard = try!(API::make_current(context));
+        stats::record_launch();
+        unsafe { API::ffi_launch_kernel(function.id_c(), grid, block, shared_mem, stream, args.as_mut_ptr()) }
+    }
+
+    unsafe fn ffi_module_load_data_ex(image: *const libc::c_void) -> Result<CUmodule, Error> {
+        let mut module: CUmodule = ptr::null_mut();
+        let mut log: Vec<u8> = repeat(0u8).take(JIT_LOG_SIZE).collect();
+        let mut options = [CU_JIT_ERROR_LOG_BUFFER, CU_JIT_ERROR_LOG_BUFFER_SIZE_BYTES];
+        let mut values = [log.as_mut_ptr() as *mut libc::c_void, JIT_LOG_SIZE as *mut libc::c_void];
+        let status = cuModuleLoadDataEx(&mut module, image, options.len() as libc::c_uint, options.as_mut_ptr(), values.as_mut_ptr());
+        let len = log.iter().position(|&byte| byte == 0).unwrap_or(log.len());
+        let log = String::from_utf8_lossy(&log[..len]).into_owned();
+        match status {
+            CUDA_SUCCESS => Ok(module),
+            CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized(format!("Cuda driver is shutting down."))),
+            CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized(format!("Cuda driver has not been initialized."))),
+            CUDA_ERROR_INVALID_CONTEXT => Err(Error::InvalidContext(format!("No valid context is current to the calling thread."))),
+            CUDA_ERROR_INVALID_VALUE => Err(Error::InvalidValue(format!("`image` or the JIT options are not valid."))),
+            CUDA_ERROR_OUT_OF_MEMORY => Err(Error::OutOfMemory(format!("Failure to allocate resources on the device"))),
+            CUDA_ERROR_INVALID_PTX => Err(Error::InvalidPtx(format!("Failure to JIT compile the PTX.\n{}", log))),
+            CUDA_ERROR_INVALID_IMAGE => Err(Error::InvalidImage(format!("The module image is not valid.\n{}", log))),
+            CUDA_ERROR_NO_BINARY_FOR_GPU => Err(Error::InvalidImage(format!("The module image contains no binary for the device.\n{}", log))),
+            _ => Err(Error::Other(format!("Unable to load module.\n{}", log)))
+        }
+    }
+
+    unsafe fn ffi_module_unload(module: CUmodule) -> Result<(), Error> {
+        match cuModuleUnload(module) {
+            CUDA_SUCCESS => Ok(()),
+            CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized(format!("Cuda driver is shutting down."))),
+            CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized(format!("Cuda driver has not been initialized."))),
+            CUDA_ERROR_INVALID_CONTEXT => Err(Error::InvalidContext(format!("No valid context is current to the calling thread."))),
+            CUDA_ERROR_INVALID_VALUE => Err(Error::InvalidValue(format!("`module`: {:?} is not a valid module.", module))),
+            _ => Err(Error::Other(format!("Unable to unload module.")))
+        }
+    }
+
+    unsafe fn ffi_module_get_function(module: CUmodule, name: *const libc::c_char) -> Result<CUfunction, Error> {
+        let mut function: CUfunction = ptr::null_mut();
+        match cuModuleGetFunction(&mut function, module, name) {
+            CUDA_SUCCESS => Ok(function),
+            CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized(format!("Cuda driver is shutting down."))),
+            CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized(format!("Cuda driver has not been initialized."))),
+            CUDA_ERROR_INVALID_CONTEXT => Err(Error::InvalidContext(format!("No valid context is current to the calling thread."))),
+            CUDA_ERROR_INVALID_VALUE => Err(Error::InvalidValue(format!("`module`: {:?} or `name` is not valid.", module))),
+            CUDA_ERROR_NOT_FOUND => Err(Error::NotFound(format!("The module has no function of the provided name."))),
+            _ => Err(Error::Other(format!("Unable to get module function.")))
+        }
+    }
+
+    unsafe fn ffi_launch_kernel(
+        function: CUfunction,
+        grid: (u32, u32, u32),
+        block: (u32, u32, u32),
+        shared_mem: u32,
+        stream: CUstream,
+        args: *mut *mut libc::c_void
+    ) -> Result<(), Error> {
+        match cuLaunchKernel(function, grid.0, grid.1, grid.2, block.0, block.1, block.2, shared_mem, stream, args, ptr::null_mut()) {
+            CUDA_SUCCESS => Ok(()),
+            CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized(format!("Cuda driver is shutting down."))),
+            CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized(format!("Cuda driver has not been initialized."))),
+            CUDA_ERROR_INVALID_CONTEXT => Err(Error::InvalidContext(format!("No valid context is current to the calling thread."))),
+            CUDA_ERROR_INVALID_HANDLE => Err(Error::InvalidHandle(format!("`function`: {:?} or `stream`: {:?} is not valid.", function, stream))),
+            CUDA_ERROR_INVALID_VALUE => Err(Error::InvalidValue(format!("The grid: {:?}, block: {:?} or parameters are not valid.", grid, block))),
+            CUDA_ERROR_LAUNCH_FAILED => Err(Error::LaunchFailed(format!("An exception occurred on the device while executing the kernel."))),
+            CUDA_ERROR_LAUNCH_OUT_OF_RESOURCES => Err(Error::LaunchOutOfResources(format!("The device lacks the resources to launch the kernel with block: {:?}.", block))),
+            _ => Err(Error::Other(format!("Unable to launch kernel.")))
+        }
+    }
+}
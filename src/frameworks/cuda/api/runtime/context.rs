@@ -0,0 +1,170 @@
+//! Provides the Cuda API with its context functionality on top of the Cuda runtime API.
+//!
+//! The runtime API has no context handles. A context is represented by the handle
+//! `ordinal + 1` of its device, so that no context is represented by a NULL handle.
+
+use super::super::{API, Error};
+use super::super::ffi::CUcontext;
+use super::check;
+use super::ffi::*;
+use frameworks::cuda::{Context, Device};
+use frameworks::cuda::context::{ContextFlags, CTX_SCHED_SPIN, CTX_SCHED_YIELD, CTX_SCHED_BLOCKING_SYNC};
+use hardware::IHardware;
+use libc::c_int;
+use std::cell::RefCell;
+use std::marker::PhantomData;
+use std::ptr;
+
+thread_local!(static PREVIOUS_DEVICES: RefCell<Vec<c_int>> = RefCell::new(Vec::new()));
+
+/// Returns the context handle of the device with the Cuda device `ordinal`.
+fn context_of(ordinal: c_int) -> CUcontext {
+    (ordinal as isize + 1) as CUcontext
+}
+
+/// Returns the Cuda device ordinal of the device, which the `context` handle represents.
+fn device_of(context: CUcontext) -> Result<c_int, Error> {
+    match context as isize {
+        id if id > 0 => Ok((id - 1) as c_int),
+        _ => Err(Error::InvalidContext(format!("`context`: {:?} is not a valid context.", context))),
+    }
+}
+
+/// Makes the `device` current to the calling thread, until the returned guard is dropped.
+pub fn make_device_current(device: &Device) -> Result<ContextGuard, Error> {
+    API::make_current_c(context_of(device.id() as c_int))
+}
+
+#[derive(Debug)]
+/// Keeps a Cuda context current to the calling thread, as long as it is in scope.
+///
+/// Returned by `API::make_current`. Restores the previously current device, when dropped. The
+/// guard can not be sent to another thread, as the current device belongs to the calling thread.
+pub struct ContextGuard {
+    _marker: PhantomData<*const ()>,
+}
+
+impl Drop for ContextGuard {
+    #[allow(unused_must_use)]
+    fn drop(&mut self) {
+        API::pop_current_context();
+    }
+}
+
+impl API {
+    /// Creates a Cuda context.
+    ///
+    /// Initializes the primary context of the device, which the runtime API shares with all of
+    /// its users in the process.
+    pub fn create_context(device: Device) -> Result<CUcontext, Error> {
+        API::create_context_with_flags(device, ContextFlags::default())
+    }
+
+    /// Creates a Cuda context, whose scheduling policy and host memory mapping are set by `flags`.
+    ///
+    /// The flags are applied with `cudaSetDeviceFlags`, which fails, if the primary context of
+    /// the device is already active with other flags. Returns an `InvalidValue` error, if `flags`
+    /// select more than one scheduling policy.
+    pub fn create_context_with_flags(device: Device, flags: ContextFlags) -> Result<CUcontext, Error> {
+        if (flags & (CTX_SCHED_SPIN | CTX_SCHED_YIELD | CTX_SCHED_BLOCKING_SYNC)).bits().count_ones() > 1 {
+            return Err(Error::InvalidValue(format!("The context flags {:?} select more than one scheduling policy.", flags)))
+        }
+        let context = context_of(device.id() as c_int);
+        let _guard = try!(API::make_current_c(context));
+        if !flags.is_empty() {
+            try!(check(unsafe { cudaSetDeviceFlags(flags.bits()) }, "set the device flags"));
+        }
+        // Freeing NULL forces the runtime to initialize the primary context.
+        try!(check(unsafe { cudaFree(ptr::null_mut()) }, "initialize the device"));
+        Ok(context)
+    }
+
+    /// Destroys the Cuda context.
+    ///
+    /// Does nothing, as the primary context is shared with the other users of the runtime API.
+    pub fn destroy_context(_context: &mut Context) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Retains the primary context of the `device`.
+    ///
+    /// Same as `create_context`, as the runtime API only uses primary contexts.
+    pub fn retain_primary_context(device: &Device) -> Result<CUcontext, Error> {
+        API::create_context(device.clone())
+    }
+
+    /// Releases the primary context of the `device`, which has been retained before.
+    ///
+    /// Does nothing, as the runtime API keeps the primary context alive until the process exits.
+    pub fn release_primary_context(_device: &Device) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Makes the Cuda context current to the calling thread, until the returned guard is dropped.
+    ///
+    /// Runtime calls always operate on the current device, so every operation of a context needs
+    /// to hold the guard. Afterwards the previously current device becomes current again.
+    pub fn make_current(context: &Context) -> Result<ContextGuard, Error> {
+        API::make_current_c(context.id_c())
+    }
+
+    /// Makes the Cuda context with the C type `context` current to the calling thread, until the
+    /// returned guard is dropped.
+    pub fn make_current_c(context: CUcontext) -> Result<ContextGuard, Error> {
+        try!(API::push_current_device(try!(device_of(context))));
+        Ok(ContextGuard { _marker: PhantomData })
+    }
+
+    /// Makes the Cuda context current to the calling thread.
+    ///
+    /// The previously current device becomes current again with `pop_current_context`. Prefer
+    /// `make_current`, which pops the context automatically.
+    pub fn push_current_context(context: &Context) -> Result<(), Error> {
+        API::push_current_device(try!(device_of(context.id_c())))
+    }
+
+    /// Restores the device, which was current before the last `push_current_context`.
+    pub fn pop_current_context() -> Result<(), Error> {
+        let previous = PREVIOUS_DEVICES.with(|devices| devices.borrow_mut().pop());
+        match previous {
+            Some(ordinal) => check(unsafe { cudaSetDevice(ordinal) }, "restore the current device"),
+            None => Err(Error::InvalidContext(format!("No context has been made current to the calling thread."))),
+        }
+    }
+
+    /// Blocks until all work, which was issued in the Cuda context, has been completed.
+    pub fn synchronize_context(context: &Context) -> Result<(), Error> {
+        let _guard = try!(API::make_current(context));
+        check(unsafe { cudaDeviceSynchronize() }, "synchronize the device")
+    }
+
+    /// Enables direct access from `context` to memory allocations of `peer_context`.
+    ///
+    /// Allows memory copies between the devices of the two contexts to bypass the host.
+    /// Enabling peer access, which is already enabled, is not treated as a failure.
+    pub fn enable_peer_access(context: &Context, peer_context: &Context) -> Result<(), Error> {
+        let peer = try!(device_of(peer_context.id_c()));
+        let _guard = try!(API::make_current(context));
+        let status = unsafe { cudaDeviceEnablePeerAccess(peer, 0) };
+        if status != cudaSuccess && super::status_name(status) == "cudaErrorPeerAccessAlreadyEnabled" {
+            unsafe { cudaGetLastError() };
+            return Ok(())
+        }
+        check(status, "enable peer access")
+    }
+
+    /// Disables direct access from `context` to memory allocations of `peer_context`.
+    pub fn disable_peer_access(context: &Context, peer_context: &Context) -> Result<(), Error> {
+        let peer = try!(device_of(peer_context.id_c()));
+        let _guard = try!(API::make_current(context));
+        check(unsafe { cudaDeviceDisablePeerAccess(peer) }, "disable peer access")
+    }
+
+    fn push_current_device(ordinal: c_int) -> Result<(), Error> {
+        let mut previous: c_int = 0;
+        try!(check(unsafe { cudaGetDevice(&mut previous) }, "get the current device"));
+        try!(check(unsafe { cudaSetDevice(ordinal) }, "set the current device"));
+        PREVIOUS_DEVICES.with(|devices| devices.borrow_mut().push(previous));
+        Ok(())
+    }
+}
@@ -0,0 +1,135 @@
+//! Provides the Cuda API with its device functionality on top of the Cuda runtime API.
+
+use super::super::{API, Error};
+use super::super::types as cl;
+use super::super::ffi::size_t;
+use super::check;
+use super::context::make_device_current;
+use super::ffi::*;
+use frameworks::cuda::{Device, DeviceInfo, DeviceAttribute};
+use hardware::IHardware;
+use libc::{c_int, c_void};
+use std::iter::repeat;
+
+/// Size of the buffer, which receives a `cudaDeviceProp`.
+///
+/// The struct grows with every Cuda version, the buffer leaves room for that. Only its first
+/// field, the name of the device, is read.
+const DEVICE_PROP_SIZE: usize = 4096;
+
+/// Size of the `name` field at the start of a `cudaDeviceProp`.
+const DEVICE_NAME_SIZE: usize = 256;
+
+impl API {
+    /// Returns fully initialized devices for a specific platform.
+    ///
+    /// Combines the fetching of all device ids and the fetching of the individual device
+    /// information.
+    pub fn load_devices() -> Result<Vec<Device>, Error> {
+        let device_list = try!(API::load_device_list());
+        Ok(
+            device_list.iter().map(|device| {
+                device.clone()
+                    .load_name()
+                    .load_device_type()
+                    .load_compute_units()
+                    .load_memory()
+            }).collect()
+        )
+    }
+
+    /// Returns a list of available devices, ordered by their Cuda device ordinal.
+    pub fn load_device_list() -> Result<Vec<Device>, Error> {
+        let num_devices = try!(API::device_count());
+        let mut devices = Vec::with_capacity(num_devices);
+        for ordinal in 0..num_devices {
+            devices.push(try!(API::get_device(ordinal)));
+        }
+        Ok(devices)
+    }
+
+    /// Initializes the Cuda runtime.
+    ///
+    /// The runtime initializes itself on its first call, so this only checks, whether a
+    /// Cuda-capable device is available.
+    pub fn init() -> Result<(), Error> {
+        try!(API::device_count());
+        Ok(())
+    }
+
+    /// Returns the version of the installed Cuda driver, e.g. `7050` for Cuda 7.5.
+    ///
+    /// The version is encoded as `1000 * major + 10 * minor`.
+    pub fn driver_version() -> Result<i32, Error> {
+        let mut version: c_int = 0;
+        try!(check(unsafe { cudaDriverGetVersion(&mut version) }, "get the driver version"));
+        Ok(version)
+    }
+
+    /// Returns the number of Cuda-capable devices.
+    pub fn device_count() -> Result<usize, Error> {
+        let mut count: c_int = 0;
+        try!(check(unsafe { cudaGetDeviceCount(&mut count) }, "get the device count"));
+        Ok(count as usize)
+    }
+
+    /// Returns the device with the Cuda device `ordinal`, which is smaller than `device_count`.
+    pub fn get_device(ordinal: usize) -> Result<Device, Error> {
+        if ordinal >= try!(API::device_count()) {
+            return Err(Error::InvalidDevice(format!("`ordinal`: {} is not a valid device ordinal.", ordinal)))
+        }
+        Ok(Device::from_isize(ordinal as isize))
+    }
+
+    /// Returns the requested DeviceInfo for the provided device.
+    ///
+    /// Returns a `NotSupported` error, as the runtime API has no generic device info query. Use
+    /// `device_attribute` instead.
+    pub fn load_device_info(_device: &Device, info: cl::device_info) -> Result<DeviceInfo, Error> {
+        Err(Error::NotSupported(format!("The device info {} can not be loaded through the Cuda runtime API.", info)))
+    }
+
+    /// Returns whether the device `dev_a` is capable of directly accessing memory of `dev_b`.
+    pub fn can_access_peer(dev_a: &Device, dev_b: &Device) -> Result<bool, Error> {
+        let mut can_access_peer: c_int = 0;
+        try!(check(unsafe { cudaDeviceCanAccessPeer(&mut can_access_peer, dev_a.id() as c_int, dev_b.id() as c_int) }, "determine the peer access capability"));
+        Ok(can_access_peer == 1)
+    }
+
+    /// Returns the value of the requested `attribute` for the provided device.
+    ///
+    /// The value is returned as reported by the Cuda runtime. Its meaning depends on the
+    /// requested attribute.
+    pub fn device_attribute(device: &Device, attribute: DeviceAttribute) -> Result<i32, Error> {
+        let mut value: c_int = 0;
+        // The values of `cudaDeviceAttr` match those of `CUdevice_attribute`.
+        try!(check(unsafe { cudaDeviceGetAttribute(&mut value, attribute.as_c() as c_int, device.id() as c_int) }, "get the device attribute"));
+        Ok(value)
+    }
+
+    /// Returns the name of the provided device.
+    pub fn device_name(device: &Device) -> Result<String, Error> {
+        let mut buf: Vec<u8> = repeat(0u8).take(DEVICE_PROP_SIZE).collect();
+        try!(check(unsafe { cudaGetDeviceProperties(buf.as_mut_ptr() as *mut c_void, device.id() as c_int) }, "get the device properties"));
+        buf.truncate(DEVICE_NAME_SIZE);
+        let len = buf.iter().position(|&byte| byte == 0).unwrap_or(buf.len());
+        buf.truncate(len);
+        Ok(String::from_utf8_lossy(&buf).into_owned())
+    }
+
+    /// Returns the total amount of memory in bytes of the provided device.
+    pub fn device_total_memory(device: &Device) -> Result<usize, Error> {
+        let mut free: size_t = 0;
+        let mut total: size_t = 0;
+        let _guard = try!(make_device_current(device));
+        try!(check(unsafe { cudaMemGetInfo(&mut free, &mut total) }, "get the device total memory"));
+        Ok(total as usize)
+    }
+
+    /// Returns the compute capability of the provided device as `(major, minor)`.
+    pub fn compute_capability(device: &Device) -> Result<(i32, i32), Error> {
+        let major = try!(API::device_attribute(device, DeviceAttribute::ComputeCapabilityMajor));
+        let minor = try!(API::device_attribute(device, DeviceAttribute::ComputeCapabilityMinor));
+        Ok((major, minor))
+    }
+}
@@ -0,0 +1,45 @@
+//! Provides the Cuda API with its event functionality on top of the Cuda runtime API.
+
+use super::super::{API, Error};
+use super::super::ffi::CUevent;
+use super::check;
+use super::ffi::*;
+use frameworks::cuda::{Context, Event, EventFlags, Stream};
+use std::ptr;
+
+impl API {
+    /// Creates a new Cuda event in the Cuda context.
+    ///
+    /// Pass `EVENT_DISABLE_TIMING` if the event is only used for synchronization, so that no
+    /// timing overhead is introduced.
+    pub fn create_event(context: &Context, flags: EventFlags) -> Result<Event, Error> {
+        let _guard = try!(API::make_current(context));
+        let mut event: CUevent = ptr::null_mut();
+        // The values of the `cudaEvent*` flags match those of the `CU_EVENT_*` flags.
+        try!(check(unsafe { cudaEventCreateWithFlags(&mut event, flags.bits()) }, "create event"));
+        Ok(Event::from_c(event))
+    }
+
+    /// Destroys the provided Cuda event of the Cuda context.
+    pub fn destroy_event(context: &Context, event: Event) -> Result<(), Error> {
+        let _guard = try!(API::make_current(context));
+        check(unsafe { cudaEventDestroy(event.id_c()) }, "destroy event")
+    }
+
+    /// Records the provided Cuda event on the `stream`.
+    ///
+    /// The event is completed, once all preceding commands on the stream have been executed.
+    pub fn record_event(event: &Event, stream: &Stream) -> Result<(), Error> {
+        check(unsafe { cudaEventRecord(event.id_c(), stream.id_c()) }, "record event")
+    }
+
+    /// Returns the elapsed time between the `start` and `end` event in milliseconds.
+    ///
+    /// Both events need to be completed and must not have been created with
+    /// `EVENT_DISABLE_TIMING`.
+    pub fn elapsed_time(start: &Event, end: &Event) -> Result<f32, Error> {
+        let mut milliseconds: f32 = 0.0;
+        try!(check(unsafe { cudaEventElapsedTime(&mut milliseconds, start.id_c(), end.id_c()) }, "compute the elapsed time between events"));
+        Ok(milliseconds)
+    }
+}
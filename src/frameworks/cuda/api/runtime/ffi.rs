@@ -0,0 +1,60 @@
+//! Provides the bindings to the Cuda runtime API, which back the `cuda-runtime` feature.
+//!
+//! Streams and events of the runtime API are the same objects as those of the driver API, so
+//! their handles reuse the driver types.
+
+#![allow(non_camel_case_types, non_upper_case_globals, non_snake_case)]
+
+use libc::{c_char, c_int, c_uint, c_void};
+use super::super::ffi::{size_t, CUstream, CUevent};
+
+pub type cudaError_t = c_int;
+pub type cudaStream_t = CUstream;
+pub type cudaEvent_t = CUevent;
+
+pub const cudaSuccess: cudaError_t = 0;
+
+pub const cudaMemcpyHostToDevice: c_int = 1;
+pub const cudaMemcpyDeviceToHost: c_int = 2;
+pub const cudaMemcpyDeviceToDevice: c_int = 3;
+
+pub const cudaHostAllocMapped: c_uint = 2;
+
+extern "C" {
+    pub fn cudaGetErrorName(error: cudaError_t) -> *const c_char;
+    pub fn cudaGetErrorString(error: cudaError_t) -> *const c_char;
+    pub fn cudaGetLastError() -> cudaError_t;
+
+    pub fn cudaDriverGetVersion(driverVersion: *mut c_int) -> cudaError_t;
+    pub fn cudaGetDeviceCount(count: *mut c_int) -> cudaError_t;
+    pub fn cudaGetDevice(device: *mut c_int) -> cudaError_t;
+    pub fn cudaSetDevice(device: c_int) -> cudaError_t;
+    pub fn cudaSetDeviceFlags(flags: c_uint) -> cudaError_t;
+    pub fn cudaDeviceSynchronize() -> cudaError_t;
+    pub fn cudaDeviceGetAttribute(value: *mut c_int, attr: c_int, device: c_int) -> cudaError_t;
+    pub fn cudaGetDeviceProperties(prop: *mut c_void, device: c_int) -> cudaError_t;
+    pub fn cudaDeviceCanAccessPeer(canAccessPeer: *mut c_int, device: c_int, peerDevice: c_int) -> cudaError_t;
+    pub fn cudaDeviceEnablePeerAccess(peerDevice: c_int, flags: c_uint) -> cudaError_t;
+    pub fn cudaDeviceDisablePeerAccess(peerDevice: c_int) -> cudaError_t;
+
+    pub fn cudaMalloc(devPtr: *mut *mut c_void, size: size_t) -> cudaError_t;
+    pub fn cudaFree(devPtr: *mut c_void) -> cudaError_t;
+    pub fn cudaMemGetInfo(free: *mut size_t, total: *mut size_t) -> cudaError_t;
+    pub fn cudaMemcpy(dst: *mut c_void, src: *const c_void, count: size_t, kind: c_int) -> cudaError_t;
+    pub fn cudaMemcpyAsync(dst: *mut c_void, src: *const c_void, count: size_t, kind: c_int, stream: cudaStream_t) -> cudaError_t;
+    pub fn cudaMemset(devPtr: *mut c_void, value: c_int, count: size_t) -> cudaError_t;
+    pub fn cudaMallocHost(ptr: *mut *mut c_void, size: size_t) -> cudaError_t;
+    pub fn cudaHostAlloc(pHost: *mut *mut c_void, size: size_t, flags: c_uint) -> cudaError_t;
+    pub fn cudaHostGetDevicePointer(pDevice: *mut *mut c_void, pHost: *mut c_void, flags: c_uint) -> cudaError_t;
+    pub fn cudaFreeHost(ptr: *mut c_void) -> cudaError_t;
+
+    pub fn cudaStreamCreate(pStream: *mut cudaStream_t) -> cudaError_t;
+    pub fn cudaStreamDestroy(stream: cudaStream_t) -> cudaError_t;
+    pub fn cudaStreamSynchronize(stream: cudaStream_t) -> cudaError_t;
+    pub fn cudaStreamQuery(stream: cudaStream_t) -> cudaError_t;
+
+    pub fn cudaEventCreateWithFlags(event: *mut cudaEvent_t, flags: c_uint) -> cudaError_t;
+    pub fn cudaEventDestroy(event: cudaEvent_t) -> cudaError_t;
+    pub fn cudaEventRecord(event: cudaEvent_t, stream: cudaStream_t) -> cudaError_t;
+    pub fn cudaEventElapsedTime(ms: *mut f32, start: cudaEvent_t, end: cudaEvent_t) -> cudaError_t;
+}
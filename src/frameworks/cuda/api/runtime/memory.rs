@@ -0,0 +1,194 @@
+//! Provides the Cuda API with its memory/buffer functionality on top of the Cuda runtime API.
+
+use libc;
+use super::super::{API, Error};
+use super::super::types as cl;
+use super::super::ffi::{size_t, CUcontext, CUdeviceptr};
+use super::check;
+use super::ffi::*;
+use frameworks::cuda::{Context, Memory, Stream, HostPinnedMemory, HostMappedMemory, DeviceAttribute};
+use device::IDevice;
+use hardware::IHardware;
+use std::ptr;
+
+impl API {
+    /// Allocates `size` bytes of memory on the device of the Cuda context.
+    ///
+    /// Returns the device pointer for the allocated memory, which can now be writen to.
+    /// If the device runs out of memory, the returned error states the requested and the
+    /// available amount of memory.
+    pub fn create_buffer(context: &Context, size: usize) -> Result<CUdeviceptr, Error> {
+        let _guard = try!(API::make_current(context));
+        let mut dptr: *mut libc::c_void = ptr::null_mut();
+        match check(unsafe { cudaMalloc(&mut dptr, size as size_t) }, "allocate memory") {
+            Ok(_) => Ok(dptr as CUdeviceptr),
+            Err(Error::OutOfMemory(_)) => {
+                match API::memory_info(context) {
+                    Ok((free, total)) => Err(Error::OutOfMemory(format!("Unable to allocate {} bytes on the device: {} of {} bytes available.", size, free, total))),
+                    Err(_) => Err(Error::OutOfMemory(format!("Unable to allocate {} bytes on the device.", size))),
+                }
+            },
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Returns the free and total amount of memory in bytes of the device of the Cuda context.
+    ///
+    /// The result is returned as `(free_bytes, total_bytes)`.
+    pub fn memory_info(context: &Context) -> Result<(usize, usize), Error> {
+        let _guard = try!(API::make_current(context));
+        let mut free: size_t = 0;
+        let mut total: size_t = 0;
+        try!(check(unsafe { cudaMemGetInfo(&mut free, &mut total) }, "get the memory info"));
+        Ok((free as usize, total as usize))
+    }
+
+    /// Allocates `size` bytes of page-locked host memory.
+    ///
+    /// Copies between pinned host memory and the device are considerably faster than copies
+    /// from pageable host memory and can be issued asynchronously with `mem_cpy_h_to_d_async`.
+    /// The memory is freed, when the returned HostPinnedMemory goes out of scope.
+    pub fn allocate_host_pinned(context: &Context, size: usize) -> Result<HostPinnedMemory, Error> {
+        let _guard = try!(API::make_current(context));
+        let mut host_ptr: *mut libc::c_void = ptr::null_mut();
+        try!(check(unsafe { cudaMallocHost(&mut host_ptr, size as size_t) }, "allocate pinned host memory"));
+        Ok(unsafe { HostPinnedMemory::from_c(host_ptr, size, context.clone()) })
+    }
+
+    /// Allocates `size` bytes of page-locked host memory, which is mapped into the address space
+    /// of the device of the Cuda context.
+    ///
+    /// The device accesses the memory directly through the device pointer of the returned
+    /// HostMappedMemory, without explicit copies. See the [pinned memory module][pinned] for the
+    /// coherence caveats.
+    /// Returns a `NotSupported` error, if the device does not support unified addressing.
+    /// [pinned]: ../../pinned_memory/index.html
+    pub fn allocate_mapped(context: &Context, size: usize) -> Result<HostMappedMemory, Error> {
+        for device in context.hardwares() {
+            if try!(API::device_attribute(&device, DeviceAttribute::UnifiedAddressing)) != 1 {
+                return Err(Error::NotSupported(format!("The device {:?} does not support unified addressing, which is required for mapped memory.", device.name())))
+            }
+        }
+        let _guard = try!(API::make_current(context));
+        let mut host_ptr: *mut libc::c_void = ptr::null_mut();
+        let mut device_ptr: *mut libc::c_void = ptr::null_mut();
+        try!(check(unsafe { cudaHostAlloc(&mut host_ptr, size as size_t, cudaHostAllocMapped) }, "allocate mapped host memory"));
+        if let Err(err) = check(unsafe { cudaHostGetDevicePointer(&mut device_ptr, host_ptr, 0) }, "get the device pointer of mapped host memory") {
+            unsafe { cudaFreeHost(host_ptr) };
+            return Err(err)
+        }
+        Ok(unsafe { HostMappedMemory::new(HostPinnedMemory::from_c(host_ptr, size, context.clone()), device_ptr as CUdeviceptr) })
+    }
+
+    /// Frees page-locked host memory, which has been allocated with `allocate_host_pinned`.
+    pub fn free_host_pinned(context: &Context, host_ptr: *mut libc::c_void) -> Result<(), Error> {
+        let _guard = try!(API::make_current(context));
+        check(unsafe { cudaFreeHost(host_ptr) }, "free pinned host memory")
+    }
+
+    /// Frees the allocated memory at the device pointer `dptr` of the Cuda context `context`.
+    ///
+    /// Gets called, when the last clone of a [Memory][memory] goes out of scope.
+    /// [memory]: ../../memory/struct.Memory.html
+    pub fn release_memory(context: CUcontext, dptr: CUdeviceptr) -> Result<(), Error> {
+        let _guard = try!(API::make_current_c(context));
+        check(unsafe { cudaFree(dptr as *mut libc::c_void) }, "free memory")
+    }
+
+    /// Reads from a buffer to the host memory.
+    ///
+    /// Returns a `NotSupported` error, use `read_from_memory` instead.
+    pub fn read_from_buffer<T>(
+        _mem: Memory,
+        _blocking_read: cl::boolean,
+        _offset: libc::size_t,
+        _size: libc::size_t,
+        _ptr: *mut libc::c_void,
+        _num_events_in_wait_list: cl::uint,
+        _event_wait_list: *const cl::event,
+        _event: *mut cl::event,
+    ) -> Result<(), Error> {
+        Err(Error::NotSupported(format!("Reading from a buffer with an event wait list is not supported by the Cuda runtime API.")))
+    }
+
+    /// Writes `size` bytes from the host memory at `host_ptr` into the Cuda device memory.
+    ///
+    /// Blocks until the data has been written.
+    pub fn write_to_memory(context: &Context, mem: &mut Memory, host_ptr: *const libc::c_void, size: usize) -> Result<(), Error> {
+        let _guard = try!(API::make_current(context));
+        check(unsafe { cudaMemcpy(mem.id_c() as *mut libc::c_void, host_ptr, size as size_t, cudaMemcpyHostToDevice) }, "copy memory from host to device")
+    }
+
+    /// Reads `size` bytes from the Cuda device memory into the host memory at `host_ptr`.
+    ///
+    /// Blocks until the data has been read.
+    pub fn read_from_memory(context: &Context, mem: &Memory, host_ptr: *mut libc::c_void, size: usize) -> Result<(), Error> {
+        API::read_from_memory_at(context, mem, 0, host_ptr, size)
+    }
+
+    /// Reads `size` bytes, starting `offset` bytes into the Cuda device memory, into the host
+    /// memory at `host_ptr`.
+    ///
+    /// Blocks until the data has been read.
+    pub fn read_from_memory_at(context: &Context, mem: &Memory, offset: usize, host_ptr: *mut libc::c_void, size: usize) -> Result<(), Error> {
+        let _guard = try!(API::make_current(context));
+        let src = (mem.id_c() as CUdeviceptr + offset as CUdeviceptr) as *const libc::c_void;
+        check(unsafe { cudaMemcpy(host_ptr, src, size as size_t, cudaMemcpyDeviceToHost) }, "copy memory from device to host")
+    }
+
+    /// Copies `size` bytes from the `src` to the `dst` Cuda device memory.
+    ///
+    /// The data never leaves the device.
+    pub fn copy_memory(context: &Context, src: &Memory, dst: &mut Memory, size: usize) -> Result<(), Error> {
+        let _guard = try!(API::make_current(context));
+        check(unsafe { cudaMemcpy(dst.id_c() as *mut libc::c_void, src.id_c() as *const libc::c_void, size as size_t, cudaMemcpyDeviceToDevice) }, "copy memory from device to device")
+    }
+
+    /// Sets every byte of the Cuda device memory to `value`.
+    pub fn memset_d8(context: &Context, mem: &mut Memory, value: u8, count: usize) -> Result<(), Error> {
+        let _guard = try!(API::make_current(context));
+        check(unsafe { cudaMemset(mem.id_c() as *mut libc::c_void, value as libc::c_int, count as size_t) }, "set memory")
+    }
+
+    /// Sets the first `count` 32-bit words of the Cuda device memory to `value`.
+    ///
+    /// The runtime API only sets bytes, so a word, whose bytes differ, is written from the host.
+    pub fn memset_d32(context: &Context, mem: &mut Memory, value: u32, count: usize) -> Result<(), Error> {
+        let bytes = [value as u8, (value >> 8) as u8, (value >> 16) as u8, (value >> 24) as u8];
+        if bytes.iter().all(|&byte| byte == bytes[0]) {
+            return API::memset_d8(context, mem, bytes[0], 4 * count)
+        }
+        let words = vec![value; count];
+        API::write_to_memory(context, mem, words.as_ptr() as *const libc::c_void, 4 * count)
+    }
+
+    /// Copies `size` bytes from the host memory at `host_ptr` to the Cuda device memory asynchronously.
+    ///
+    /// The copy is issued on the provided `stream` and returns immediately. The host memory has
+    /// to stay valid until the stream has been synchronized.
+    pub fn mem_cpy_h_to_d_async(
+        context: &Context,
+        host_ptr: *const libc::c_void,
+        mem: &Memory,
+        size: libc::size_t,
+        stream: &Stream,
+    ) -> Result<(), Error> {
+        let _guard = try!(API::make_current(context));
+        check(unsafe { cudaMemcpyAsync(mem.id_c() as *mut libc::c_void, host_ptr, size as size_t, cudaMemcpyHostToDevice, stream.id_c()) }, "copy memory from host to device")
+    }
+
+    /// Copies `size` bytes from the Cuda device memory to the host memory at `host_ptr` asynchronously.
+    ///
+    /// The copy is issued on the provided `stream` and returns immediately. The host memory may
+    /// only be read after the stream has been synchronized.
+    pub fn mem_cpy_d_to_h_async(
+        context: &Context,
+        mem: &Memory,
+        host_ptr: *mut libc::c_void,
+        size: libc::size_t,
+        stream: &Stream,
+    ) -> Result<(), Error> {
+        let _guard = try!(API::make_current(context));
+        check(unsafe { cudaMemcpyAsync(host_ptr, mem.id_c() as *const libc::c_void, size as size_t, cudaMemcpyDeviceToHost, stream.id_c()) }, "copy memory from device to host")
+    }
+}
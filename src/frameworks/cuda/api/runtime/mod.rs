@@ -0,0 +1,55 @@
+//! Provides the Cuda API on top of the Cuda runtime API, instead of the driver API.
+//!
+//! Compiled with the `cuda-runtime` feature. The runtime API manages one context per device, the
+//! primary context, which all of its users in the process share. A Collenchyma context is
+//! therefore identified by its device, and making it current selects the device through
+//! `cudaSetDevice`. PTX modules and kernel launches need the driver API and are not supported.
+
+pub use self::context::ContextGuard;
+
+use super::Error;
+use self::ffi::*;
+use std::ffi::CStr;
+
+mod context;
+mod device;
+mod memory;
+mod module;
+mod stream;
+mod event;
+mod ffi;
+
+/// Returns the name of the runtime `status`, e.g. `cudaErrorInvalidValue`.
+fn status_name(status: cudaError_t) -> String {
+    unsafe { CStr::from_ptr(cudaGetErrorName(status)).to_string_lossy().into_owned() }
+}
+
+/// Maps the runtime `status` of the call, which attempted to `action`, to the Cuda Error.
+///
+/// The numeric codes of `cudaError_t` differ between Cuda versions, so the status is mapped by
+/// its name. The last error of the runtime is reset, so that it does not leak into later calls.
+fn check(status: cudaError_t, action: &str) -> Result<(), Error> {
+    if status == cudaSuccess {
+        return Ok(())
+    }
+    unsafe { cudaGetLastError() };
+    let name = status_name(status);
+    let message = format!("Unable to {}: {}", action, unsafe { CStr::from_ptr(cudaGetErrorString(status)).to_string_lossy() });
+    Err(match &name[..] {
+        "cudaErrorInvalidValue" => Error::InvalidValue(message),
+        "cudaErrorMemoryAllocation" => Error::OutOfMemory(message),
+        "cudaErrorInitializationError" => Error::NotInitialized(message),
+        "cudaErrorCudartUnloading" => Error::Deinitialized(message),
+        "cudaErrorNoDevice" => Error::NoDevice(message),
+        "cudaErrorInvalidDevice" => Error::InvalidDevice(message),
+        "cudaErrorInvalidResourceHandle" => Error::InvalidHandle(message),
+        "cudaErrorNotReady" => Error::NotReady(message),
+        "cudaErrorPeerAccessUnsupported" => Error::PeerAccessUnsupported(message),
+        "cudaErrorPeerAccessNotEnabled" => Error::PeerAccessNotEnabled(message),
+        "cudaErrorTooManyPeers" => Error::TooManyPeers(message),
+        "cudaErrorLaunchFailure" => Error::LaunchFailed(message),
+        "cudaErrorLaunchOutOfResources" => Error::LaunchOutOfResources(message),
+        "cudaErrorNotSupported" => Error::NotSupported(message),
+        _ => Error::Other(format!("{} ({})", message, name)),
+    })
+}
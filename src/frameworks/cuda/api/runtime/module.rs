@@ -0,0 +1,43 @@
+//! Provides the Cuda API with its module and kernel launch functionality on top of the Cuda
+//! runtime API.
+//!
+//! The runtime API launches the kernels, which are compiled into the executable, and can not
+//! load PTX at runtime. Every function returns a `NotSupported` error.
+
+use libc;
+use super::super::{API, Error};
+use frameworks::cuda::{Context, Module, Function, Stream};
+
+impl API {
+    /// Loads a module from the provided PTX into the Cuda context.
+    pub fn load_module_from_ptx(_context: &Context, _ptx: &str) -> Result<Module, Error> {
+        Err(API::modules_not_supported())
+    }
+
+    /// Unloads the module from its Cuda context.
+    pub fn unload_module(_context: &Context, _module: Module) -> Result<(), Error> {
+        Err(API::modules_not_supported())
+    }
+
+    /// Returns the function `name` of the module.
+    pub fn module_get_function(_module: &Module, _name: &str) -> Result<Function, Error> {
+        Err(API::modules_not_supported())
+    }
+
+    /// Launches the `function` with a grid of `grid` blocks of `block` threads each.
+    pub fn launch_kernel(
+        _context: &Context,
+        _function: &Function,
+        _grid: (u32, u32, u32),
+        _block: (u32, u32, u32),
+        _shared_mem: u32,
+        _stream: Option<&Stream>,
+        _args: &mut [*mut libc::c_void]
+    ) -> Result<(), Error> {
+        Err(API::modules_not_supported())
+    }
+
+    fn modules_not_supported() -> Error {
+        Error::NotSupported(format!("The Cuda runtime API can not load PTX modules; use the `cuda` feature instead of `cuda-runtime`."))
+    }
+}
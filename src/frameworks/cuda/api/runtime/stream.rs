@@ -0,0 +1,59 @@
+//! Provides the Cuda API with its stream functionality on top of the Cuda runtime API.
+
+use super::super::{API, Error};
+use super::super::ffi::CUstream;
+use super::{check, status_name};
+use super::ffi::*;
+use frameworks::cuda::{Context, Stream};
+use std::ptr;
+
+impl API {
+    /// Creates a new Cuda stream in the Cuda context.
+    ///
+    /// Commands issued on the stream are executed in order, but may run concurrently with
+    /// commands issued on other streams.
+    pub fn create_stream(context: &Context) -> Result<Stream, Error> {
+        let _guard = try!(API::make_current(context));
+        let mut stream: CUstream = ptr::null_mut();
+        try!(check(unsafe { cudaStreamCreate(&mut stream) }, "create stream"));
+        Ok(Stream::from_c(stream))
+    }
+
+    /// Destroys the provided Cuda stream of the Cuda context.
+    ///
+    /// Work still pending on the stream will be finished before its resources are released.
+    pub fn destroy_stream(context: &Context, stream: Stream) -> Result<(), Error> {
+        let _guard = try!(API::make_current(context));
+        check(unsafe { cudaStreamDestroy(stream.id_c()) }, "destroy stream")
+    }
+
+    /// Blocks until all commands issued on the provided Cuda stream are finished.
+    pub fn synchronize_stream(stream: &Stream) -> Result<(), Error> {
+        check(unsafe { cudaStreamSynchronize(stream.id_c()) }, "synchronize stream")
+    }
+
+    /// Returns whether all commands issued on the provided Cuda stream are finished.
+    ///
+    /// Does not block.
+    pub fn query_stream(stream: &Stream) -> Result<bool, Error> {
+        API::query(stream.id_c())
+    }
+
+    /// Returns whether all commands issued on the default stream of the Cuda context are
+    /// finished.
+    ///
+    /// Does not block. Operations of Collenchyma are issued on the default stream.
+    pub fn query_context(context: &Context) -> Result<bool, Error> {
+        let _guard = try!(API::make_current(context));
+        API::query(ptr::null_mut())
+    }
+
+    fn query(stream: CUstream) -> Result<bool, Error> {
+        let status = unsafe { cudaStreamQuery(stream) };
+        if status != cudaSuccess && status_name(status) == "cudaErrorNotReady" {
+            return Ok(false)
+        }
+        try!(check(status, "query stream"));
+        Ok(true)
+    }
+}
@@ -0,0 +1,93 @@
+//! Provides the Cuda API with its stream functionality.
+
+use super::{API, Error};
+use frameworks::cuda::{Context, Stream};
+use super::ffi::*;
+use std::ptr;
+
+impl API {
+    /// Creates a new Cuda stream in the Cuda context.
+    ///
+    /// Commands issued on the stream are executed in order, but may run concurrently with
+    /// commands issued on other streams.
+    pub fn create_stream(context: &Context) -> Result<Stream, Error> {
+        let _guard = try!(API::make_current(context));
+        Ok(Stream::from_c(try!(unsafe { API::ffi_stream_create(CU_STREAM_DEFAULT) })))
+    }
+
+    /// Destroys the provided Cuda stream of the Cuda context.
+    ///
+    /// Work still pending on the stream will be finished before its resources are released.
+    pub fn destroy_stream(context: &Context, stream: Stream) -> Result<(), Error> {
+        let _guard = try!(API::make_current(context));
+        Ok(try!(unsafe { API::ffi_stream_destroy(stream.id_c()) }))
+    }
+
+    /// Blocks until all commands issued on the provided Cuda stream are finished.
+    pub fn synchronize_stream(stream: &Stream) -> Result<(), Error> {
+        Ok(try!(unsafe { API::ffi_stream_synchronize(stream.id_c()) }))
+    }
+
+    /// Returns whether all commands issued on the provided Cuda stream are finished.
+    ///
+    /// Does not block.
+    pub fn query_stream(stream: &Stream) -> Result<bool, Error> {
+        Ok(try!(unsafe { API::ffi_stream_query(stream.id_c()) }))
+    }
+
+    /// Returns whether all commands issued on the default stream of the Cuda context are
+    /// finished.
+    ///
+    /// Does not block. Operations of Collenchyma are issued on the default stream.
+    pub fn query_context(context: &Context) -> Result<bool, Error> {
+        let _guard = try!(API::make_current(context));
+        Ok(try!(unsafe { API::ffi_stream_query(ptr::null_mut()) }))
+    }
+
+    unsafe fn ffi_stream_create(flags: ::libc::c_uint) -> Result<CUstream, Error> {
+        let mut stream: CUstream = ptr::null_mut();
+        match cuStreamCreate(&mut stream, flags) {
+            CUDA_SUCCESS => Ok(stream),
+            CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized(format!("Cuda driver is shutting down."))),
+            CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized(format!("Cuda driver has not been initialized."))),
+            CUDA_ERROR_INVALID_CONTEXT => Err(Error::InvalidContext(format!("No valid context is current to the calling thread."))),
+            CUDA_ERROR_INVALID_VALUE => Err(Error::InvalidValue(format!("`flags`: {} is not a valid stream flag.", flags))),
+            CUDA_ERROR_OUT_OF_MEMORY => Err(Error::OutOfMemory(format!("Failure to allocate resources on the device"))),
+            _ => Err(Error::Other(format!("Unable to create stream.")))
+        }
+    }
+
+    unsafe fn ffi_stream_destroy(stream: CUstream) -> Result<(), Error> {
+        match cuStreamDestroy_v2(stream) {
+            CUDA_SUCCESS => Ok(()),
+            CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized(format!("Cuda driver is shutting down."))),
+            CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized(format!("Cuda driver has not been initialized."))),
+            CUDA_ERROR_INVALID_CONTEXT => Err(Error::InvalidContext(format!("No valid context is current to the calling thread."))),
+            CUDA_ERROR_INVALID_VALUE => Err(Error::InvalidValue(format!("`stream`: {:?} is not a valid stream.", stream))),
+            _ => Err(Error::Other(format!("Unable to destroy stream.")))
+        }
+    }
+
+    unsafe fn ffi_stream_synchronize(stream: CUstream) -> Result<(), Error> {
+        match cuStreamSynchronize(stream) {
+            CUDA_SUCCESS => Ok(()),
+            CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized(format!("Cuda driver is shutting down."))),
+            CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized(format!("Cuda driver has not been initialized."))),
+            CUDA_ERROR_INVALID_CONTEXT => Err(Error::InvalidContext(format!("No valid context is current to the calling thread."))),
+            CUDA_ERROR_INVALID_HANDLE => Err(Error::InvalidHandle(format!("`stream`: {:?} is not a valid stream.", stream))),
+            _ => Err(Error::Other(format!("Unable to synchronize stream.")))
+        }
+    }
+
+    unsafe fn ffi_stream_query(stream: CUstream) -> Result<bool, Error> {
+        match cuStreamQuery(stream) {
+            CUDA_SUCCESS => Ok(true),
+            CUDA_ERROR_NOT_READY => Ok(false),
+            CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized(format!("Cuda driver is shutting down."))),
+            CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized(format!("Cuda driver has not been initialized."))),
+            CUDA_ERROR_INVALID_CONTEXT => Err(Error::InvalidContext(format!("No valid context is current to the calling thread."))),
+            CUDA_ERROR_INVALID_HANDLE => Err(Error::InvalidHandle(format!("`stream`: {:?} is not a valid stream.", stream))),
+            _ => Err(Error::Other(format!("Unable to query stream.")))
+        }
+    }
+}
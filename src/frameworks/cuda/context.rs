@@ -1,45 +1,217 @@
 //! Provides a Rust wrapper around Cuda's context.
 
 use device::{IDevice, DeviceType};
-use super::api::types as cl;
-use frameworks::opencl::{API, Error, Device};
+use super::api::ffi::CUcontext;
+use super::api::{API, Error};
+use super::Device;
 use super::memory::*;
 use memory::MemoryType;
-use std::{ptr, mem};
+use memory_pool::MemoryPool;
+use std::sync::Arc;
+use libc::c_void;
 use std::hash::{Hash, Hasher};
 
+bitflags! {
+    #[doc = "Defines the scheduling policy and host memory mapping of a Cuda context."]
+    #[doc = ""]
+    #[doc = "Without a scheduling flag the driver picks a policy based on the number of active"]
+    #[doc = "contexts and processors, like `CU_CTX_SCHED_AUTO`."]
+    flags ContextFlags: ::libc::c_uint {
+        #[doc = "Spins actively, while waiting for results from the device."]
+        const CTX_SCHED_SPIN          = 1 << 0,
+        #[doc = "Yields the thread, while waiting for results from the device."]
+        const CTX_SCHED_YIELD         = 1 << 1,
+        #[doc = "Blocks the thread on a synchronization primitive, while waiting for the device."]
+        const CTX_SCHED_BLOCKING_SYNC = 1 << 2,
+        #[doc = "Allows to map pinned host memory into the address space of the device."]
+        const CTX_MAP_HOST            = 1 << 3,
+        #[doc = "Keeps the local memory of the device at its maximum size after resizing it."]
+        const CTX_LMEM_RESIZE_TO_MAX  = 1 << 4,
+    }
+}
+
+impl Default for ContextFlags {
+    fn default() -> ContextFlags {
+        ContextFlags::empty()
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// Defines, what happens to the underlying Cuda context, when the last clone of a Context goes
+/// out of scope.
+pub enum ContextOwnership {
+    /// The context has been created by Collenchyma and is destroyed.
+    Owned,
+    /// The context is the retained primary context of the device and is released.
+    Primary,
+    /// The context is owned outside of Collenchyma and is left alone.
+    Borrowed,
+}
+
 #[derive(Debug, Clone)]
 /// Defines a Cuda Context.
+///
+/// The underlying Cuda context is destroyed or released exactly once, when the last clone of the
+/// Context goes out of scope, depending on its ContextOwnership.
 pub struct Context {
-    id: isize,
+    id: Arc<isize>,
     devices: Vec<Device>,
+    pool: Option<MemoryPool<Memory>>,
+    ownership: ContextOwnership,
+}
+
+// Every API call makes the context current to the calling thread first, so a Context may be
+// used from any thread. The MemoryPool only holds Memory, which no other clone refers to.
+// This keeps `DeviceType`, and with it `Backend<Native>`, `Send` with the Cuda features enabled.
+unsafe impl Send for Context {}
+
+impl Drop for Context {
+    #[allow(unused_must_use)]
+    fn drop(&mut self) {
+        if Arc::get_mut(&mut self.id).is_some() {
+            if let Some(ref pool) = self.pool {
+                pool.drain();
+            }
+            match self.ownership {
+                ContextOwnership::Owned => { API::destroy_context(self); },
+                ContextOwnership::Primary => { API::release_primary_context(&self.devices[0]); },
+                ContextOwnership::Borrowed => {},
+            }
+        }
+    }
 }
 
 impl Context {
-    /// Initializes a new Cuda platform.
-    pub fn new(devices: Vec<Device>) -> Result<Context, Error> {
-        let callback = unsafe { mem::transmute(ptr::null::<fn()>()) };
+    /// Initializes a new Cuda context.
+    pub fn new(device: Device) -> Result<Context, Error> {
         Ok(
             Context::from_c(
-                try!(API::create_context(devices.clone(), ptr::null(), callback, ptr::null_mut())),
-                devices.clone()
+                try!(API::create_context(device.clone())),
+                vec!(device.clone())
             )
         )
     }
 
-    /// Initializes a new Cuda platform from its C type.
-    pub fn from_c(id: cl::context_id, devices: Vec<Device>) -> Context {
-        Context { id: id as isize, devices: devices }
+    /// Initializes a new Cuda context, whose scheduling policy and host memory mapping are set by
+    /// `flags`.
+    ///
+    /// Returns an `InvalidValue` error, if `flags` select more than one scheduling policy.
+    pub fn with_flags(device: Device, flags: ContextFlags) -> Result<Context, Error> {
+        Ok(
+            Context::from_c(
+                try!(API::create_context_with_flags(device.clone(), flags)),
+                vec!(device.clone())
+            )
+        )
+    }
+
+    /// Adopts the primary context of the `device`, which the Cuda runtime API and other users
+    /// of the device share, instead of creating a new context.
+    ///
+    /// The primary context is retained through `cuDevicePrimaryCtxRetain` and released again,
+    /// when the last clone of the Context goes out of scope. With the `cuda-runtime` feature,
+    /// every Context uses the primary context, so this is the same as `new`.
+    pub fn from_primary(device: Device) -> Result<Context, Error> {
+        let id = try!(API::retain_primary_context(&device));
+        let mut context = Context::from_c(id, vec!(device));
+        context.ownership = ContextOwnership::Primary;
+        Ok(context)
+    }
+
+    #[cfg(feature = "cuda")]
+    /// Adopts the existing Cuda context `id` of the `device`, which has been created outside of
+    /// Collenchyma.
+    ///
+    /// With `destroy`, the context is destroyed, when the last clone of the Context goes out of
+    /// scope. Otherwise it is left alone, and its owner needs to keep it alive for the lifetime
+    /// of the Context. Returns an error, if the driver refuses to make `id` current.
+    ///
+    /// ## Safety
+    ///
+    /// `id` must be a live Cuda context of the `device`. The driver can not detect every dangling
+    /// or made-up handle, so making it current is undefined behavior for those. With `destroy`,
+    /// the caller hands over the ownership of the context, which must not be destroyed elsewhere.
+    pub unsafe fn from_raw(id: CUcontext, device: Device, destroy: bool) -> Result<Context, Error> {
+        let mut context = Context::from_c(id, vec!(device));
+        context.ownership = if destroy { ContextOwnership::Owned } else { ContextOwnership::Borrowed };
+        if let Err(err) = API::make_current(&context) {
+            // Do not destroy an invalid context on drop.
+            context.ownership = ContextOwnership::Borrowed;
+            return Err(err)
+        }
+        Ok(context)
+    }
+
+    /// Initializes a new Cuda context from its C type.
+    pub fn from_c(id: CUcontext, devices: Vec<Device>) -> Context {
+        Context { id: Arc::new(id as isize), devices: devices, pool: None, ownership: ContextOwnership::Owned }
+    }
+
+    /// Returns, what happens to the underlying Cuda context, when the last clone of the Context
+    /// goes out of scope.
+    pub fn ownership(&self) -> ContextOwnership {
+        self.ownership
+    }
+
+    /// Lets allocations of the context be served from, and returned to, the `pool`.
+    ///
+    /// Only affects this Context and the clones, which are made afterwards.
+    pub fn set_pool(&mut self, pool: Option<MemoryPool<Memory>>) {
+        self.pool = pool;
+    }
+
+    /// Returns the MemoryPool of the context, if there is one.
+    pub fn pool(&self) -> Option<&MemoryPool<Memory>> {
+        self.pool.as_ref()
+    }
+
+    /// Allocates `size` bytes of memory on the device, or reuses pooled memory of that size.
+    ///
+    /// Unlike `alloc_memory`, returns the error of the driver, e.g. if the device ran out of
+    /// memory.
+    pub fn try_alloc_memory(&self, size: usize) -> Result<Memory, Error> {
+        if let Some(memory) = self.pool.as_ref().and_then(|pool| pool.take(size)) {
+            co_debug!("Reusing {} bytes of pooled Cuda memory", size);
+            return Ok(memory)
+        }
+        Memory::new(self, size)
+    }
+
+    /// Returns the memory `mem` to the MemoryPool of the context.
+    ///
+    /// Frees `mem`, if the context has no pool, the pool is full or `mem` shares its device
+    /// allocation with other Memory.
+    pub fn recycle_memory(&self, mem: Memory) {
+        if let Some(ref pool) = self.pool {
+            if mem.ref_count() == 1 && mem.is_owned() {
+                let size = mem.size();
+                pool.give_back(size, mem);
+            }
+        }
+    }
+
+    /// Allocates `size` bytes of memory, which start at a multiple of `align`.
+    ///
+    /// `cuMemAlloc` aligns to at least 256 bytes. Larger alignments pad the allocation by
+    /// `align - 1` bytes and return a view into it, which starts at the first aligned address.
+    pub fn alloc_memory_aligned(&self, size: usize, align: usize) -> Result<Memory, Error> {
+        if align <= 256 {
+            return self.try_alloc_memory(size)
+        }
+        let padded = try!(size.checked_add(align - 1).ok_or(Error::InvalidValue(format!("Unable to pad {} bytes to an alignment of {} bytes.", size, align))));
+        let memory = try!(Memory::new(self, padded));
+        let offset = (align - memory.id_c() as usize % align) % align;
+        Ok(memory.view(offset, size).unwrap())
     }
 
     /// Returns the id as isize.
     pub fn id(&self) -> isize {
-        self.id
+        *self.id
     }
 
     /// Returns the id as its C type.
-    pub fn id_c(&self) -> cl::context_id {
-        self.id as cl::context_id
+    pub fn id_c(&self) -> CUcontext {
+        *self.id as CUcontext
     }
 }
 
@@ -48,7 +220,7 @@ impl IDevice for Context {
     type M = Memory;
 
     fn id(&self) -> isize {
-        self.id
+        *self.id
     }
 
     fn hardwares(&self) -> Vec<Device> {
@@ -56,20 +228,30 @@ impl IDevice for Context {
     }
 
     fn alloc_memory(&self, size: usize) -> Memory {
-        unimplemented!();
+        match self.try_alloc_memory(size) {
+            Ok(memory) => memory,
+            Err(err) => panic!(err)
+        }
     }
 
-    fn sync_memory_to(&self, source: &Memory, dest: &mut MemoryType, dest_device: &DeviceType) {
-        /*
-        let src = Memory::<Vec<u8>>::from_c(source as cl::memory_id);
-        match dest_device.clone() {
-            DeviceType::Native(cpu) => {
-                unimplemented!();
-            }
-            DeviceType::Cuda(_) => {},
+    fn sync_memory_to(&self, source: &Memory, dest: &mut MemoryType, dest_device: &DeviceType) -> Result<(), ::framework::Error> {
+        match *dest_device {
+            DeviceType::Native(_) => {
+                let data = try!(dest.as_mut_native().ok_or(Error::InvalidValue(format!("The destination is no native memory.")))).as_mut_slice::<u8>();
+                Ok(try!(API::read_from_memory(self, source, data.as_mut_ptr() as *mut c_void, data.len())))
+            },
+            #[cfg(feature = "opencl")]
+            DeviceType::OpenCL(_) => Err(::framework::Error::NotSupported(format!("Syncing memory from a Cuda to an OpenCL device is not supported."))),
+            DeviceType::Cuda(ref context) if context == self => {
+                let memory = try!(dest.as_mut_cuda().ok_or(Error::InvalidValue(format!("The destination is no Cuda memory."))));
+                Ok(try!(API::copy_memory(self, source, memory, source.size())))
+            },
+            DeviceType::Cuda(_) => Err(::framework::Error::NotSupported(format!("Syncing memory between Cuda contexts is not supported."))),
         }
-        */
-        unimplemented!()
+    }
+
+    fn synchronize(&self) -> Result<(), ::framework::Error> {
+        Ok(try!(API::synchronize_context(self)))
     }
 }
 
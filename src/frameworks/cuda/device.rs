@@ -5,6 +5,7 @@ use super::api::types as cl;
 use super::api::API;
 use std::io::Cursor;
 use byteorder::{ByteOrder, LittleEndian, ReadBytesExt};
+use super::api::ffi::*;
 
 #[derive(Debug, Clone)]
 /// Defines a Cuda Device.
@@ -15,6 +16,7 @@ pub struct Device {
     name: Option<String>,
     device_type: Option<HardwareType>,
     compute_units: Option<isize>,
+    memory: Option<usize>,
 }
 
 impl Default for Device {
@@ -24,6 +26,7 @@ impl Default for Device {
             name: None,
             device_type: None,
             compute_units: None,
+            memory: None,
         }
     }
 }
@@ -46,36 +49,34 @@ impl Device {
 
     /// Loads the name of the device via a foreign Cuda call.
     pub fn load_name(&mut self) -> Self {
-        self.name = match API::load_device_info(self, cl::CL_DEVICE_NAME) {
-            Ok(result) => Some(result.to_string()),
+        self.name = match API::device_name(self) {
+            Ok(name) => Some(name),
             Err(_) => None
         };
         self.clone()
     }
 
-    /// Loads the device type via a foreign Cuda call.
+    /// Loads the device type.
+    ///
+    /// Cuda devices are always GPUs.
     pub fn load_device_type(&mut self) -> Self {
-        self.device_type = match API::load_device_info(self, cl::CL_DEVICE_TYPE) {
-            Ok(result) => {
-                let device_type = result.to_device_type();
-                match device_type {
-                    cl::CL_DEVICE_TYPE_CPU => Some(HardwareType::CPU),
-                    cl::CL_DEVICE_TYPE_GPU => Some(HardwareType::GPU),
-                    cl::CL_DEVICE_TYPE_ACCELERATOR => Some(HardwareType::ACCELERATOR),
-                    cl::CL_DEVICE_TYPE_DEFAULT => Some(HardwareType::OTHER),
-                    cl::CL_DEVICE_TYPE_CUSTOM => Some(HardwareType::OTHER),
-                    _ => None
-                }
-            },
+        self.device_type = Some(HardwareType::GPU);
+        self.clone()
+    }
+
+    /// Loads the number of multiprocessors of the device via a foreign Cuda call.
+    pub fn load_compute_units(&mut self) -> Self {
+        self.compute_units = match API::device_attribute(self, DeviceAttribute::MultiprocessorCount) {
+            Ok(result) => Some(result as isize),
             Err(_) => None
         };
         self.clone()
     }
 
-    /// Loads the compute units of the device via a foreign Cuda call.
-    pub fn load_compute_units(&mut self) -> Self {
-        self.compute_units = match API::load_device_info(self, cl::CL_DEVICE_MAX_COMPUTE_UNITS) {
-            Ok(result) => Some(result.to_isize()),
+    /// Loads the total memory of the device via a foreign Cuda call.
+    pub fn load_memory(&mut self) -> Self {
+        self.memory = match API::device_total_memory(self) {
+            Ok(result) => Some(result),
             Err(_) => None
         };
         self.clone()
@@ -114,6 +115,10 @@ impl IHardware for Device {
         self.clone()
     }
 
+    fn memory(&self) -> Option<usize> {
+        self.memory
+    }
+
     #[allow(missing_docs)]
     fn build(self) -> Device {
         Device {
@@ -121,6 +126,7 @@ impl IHardware for Device {
             name: self.name(),
             device_type: self.hardware_type(),
             compute_units: self.compute_units(),
+            memory: self.memory(),
         }
     }
 }
@@ -157,3 +163,42 @@ impl DeviceInfo {
         bytes.read_u32::<LittleEndian>().unwrap() as isize
     }
 }
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+/// Defines the queryable attributes of a Cuda Device.
+///
+/// Use it with `API::device_attribute` to inspect the capabilities of a device.
+pub enum DeviceAttribute {
+    /// Maximum number of threads per block.
+    MaxThreadsPerBlock,
+    /// Number of multiprocessors on the device.
+    MultiprocessorCount,
+    /// Warp size in threads.
+    WarpSize,
+    /// Maximum shared memory available per block in bytes.
+    MaxSharedMemoryPerBlock,
+    /// Major compute capability version number.
+    ComputeCapabilityMajor,
+    /// Minor compute capability version number.
+    ComputeCapabilityMinor,
+    /// Whether the device can map host memory into its address space.
+    CanMapHostMemory,
+    /// Whether the device shares a unified address space with the host.
+    UnifiedAddressing,
+}
+
+impl DeviceAttribute {
+    /// Returns the attribute as its C type.
+    pub fn as_c(&self) -> CUdevice_attribute {
+        match *self {
+            DeviceAttribute::MaxThreadsPerBlock => CU_DEVICE_ATTRIBUTE_MAX_THREADS_PER_BLOCK,
+            DeviceAttribute::MultiprocessorCount => CU_DEVICE_ATTRIBUTE_MULTIPROCESSOR_COUNT,
+            DeviceAttribute::WarpSize => CU_DEVICE_ATTRIBUTE_WARP_SIZE,
+            DeviceAttribute::MaxSharedMemoryPerBlock => CU_DEVICE_ATTRIBUTE_MAX_SHARED_MEMORY_PER_BLOCK,
+            DeviceAttribute::ComputeCapabilityMajor => CU_DEVICE_ATTRIBUTE_COMPUTE_CAPABILITY_MAJOR,
+            DeviceAttribute::ComputeCapabilityMinor => CU_DEVICE_ATTRIBUTE_COMPUTE_CAPABILITY_MINOR,
+            DeviceAttribute::CanMapHostMemory => CU_DEVICE_ATTRIBUTE_CAN_MAP_HOST_MEMORY,
+            DeviceAttribute::UnifiedAddressing => CU_DEVICE_ATTRIBUTE_UNIFIED_ADDRESSING,
+        }
+    }
+}
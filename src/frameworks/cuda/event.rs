@@ -0,0 +1,55 @@
+//! Provides a Rust wrapper around Cuda's event.
+//!
+//! ## Cuda Event
+//!
+//! An event is a marker, which can be recorded on a [stream][stream]. Once all preceding commands
+//! on that stream have been executed, the event is completed. Events can be used for
+//! synchronization and for measuring the elapsed time between two points on a stream.
+//!
+//! [stream]: ../stream/index.html
+
+use super::api::ffi::CUevent;
+
+#[derive(Debug, Copy, Clone)]
+/// Defines a Cuda Event.
+pub struct Event {
+    id: isize,
+}
+
+impl Event {
+    /// Initializes a new Cuda event.
+    pub fn from_isize(id: isize) -> Event {
+        Event { id: id }
+    }
+
+    /// Initializes a new Cuda event from its C type.
+    pub fn from_c(id: CUevent) -> Event {
+        Event { id: id as isize }
+    }
+
+    /// Returns the id as isize.
+    pub fn id(&self) -> isize {
+        self.id
+    }
+
+    /// Returns the id as its C type.
+    pub fn id_c(&self) -> CUevent {
+        self.id as CUevent
+    }
+}
+
+bitflags! {
+    #[doc="Defines the creation flags of a Cuda Event."]
+    flags EventFlags: ::libc::c_uint {
+        #[doc="Event uses blocking synchronization instead of busy waiting."]
+        const EVENT_BLOCKING_SYNC   = 1 << 0,
+        #[doc="Event does not record timing data, which reduces the synchronization overhead."]
+        const EVENT_DISABLE_TIMING  = 1 << 1,
+    }
+}
+
+impl Default for EventFlags {
+    fn default() -> EventFlags {
+        EventFlags::empty()
+    }
+}
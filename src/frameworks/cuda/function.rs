@@ -1,8 +1,7 @@
 //! Provides a Rust wrapper around Cuda's Function.
 
 use operation::IOperation;
-use super::api::types as cl;
-use super::api::API;
+use super::api::ffi::CUfunction;
 
 #[derive(Debug, Copy, Clone)]
 /// Defines a Cuda Function.
@@ -19,14 +18,14 @@ impl Function {
         Function { id: id }
     }
 
-    /// Initializes a new OpenCL device from its C type.
-    pub fn from_c(id: cl::kernel_id) -> Function {
+    /// Initializes a new Cuda function from its C type.
+    pub fn from_c(id: CUfunction) -> Function {
         Function { id: id as isize }
     }
 
     /// Returns the id as its C type.
-    pub fn id_c(&self) -> cl::kernel_id {
-        self.id as cl::kernel_id
+    pub fn id_c(&self) -> CUfunction {
+        self.id as CUfunction
     }
 }
 
@@ -1,24 +1,76 @@
 #![allow(missing_docs)]
 use super::api::types as cl;
+use super::api::ffi::{CUcontext, CUdeviceptr};
 use super::api::{API, Error};
+use super::{Context, Cuda};
+use alloc_tracker;
+use framework::IFramework;
 use memory::*;
+use stats;
 
 use std::ptr;
 
+#[cfg(feature = "thread_safe")]
+/// Reference counter, which is shared between clones of a Cuda memory.
+///
+/// Thread-safe `Arc`, as the `thread_safe` feature is enabled.
+pub type MemoryRc<T> = ::std::sync::Arc<T>;
+
+#[cfg(not(feature = "thread_safe"))]
+/// Reference counter, which is shared between clones of a Cuda memory.
+///
+/// Single-threaded `Rc`, enable the `thread_safe` feature to use an `Arc` instead.
+pub type MemoryRc<T> = ::std::rc::Rc<T>;
+
+#[derive(Debug, Clone)]
 /// Holds a Cuda memory id and manages its deallocation
-#[derive(Debug)]
+///
+/// Cloning a Memory does not copy the device allocation, but shares it between the clones.
+/// The allocation is freed exactly once, when the last clone or view goes out of scope.
 pub struct Memory {
+    handle: MemoryRc<MemoryHandle>,
+    /// The offset of the memory into the device allocation in bytes.
+    offset: usize,
+    /// The size of the memory in bytes.
+    size: usize,
+}
+
+#[derive(Debug)]
+/// Owns the device allocation, which is shared between clones of a Memory.
+struct MemoryHandle {
     /// The underlying memory id>
     memory: cl::memory_id,
     memory_flags: MemoryFlags,
 
     /// Pointer to host memory that is used for pinned host memory.
     host_ptr: *mut u8,
+
+    /// The size of the allocated memory in bytes.
+    size: usize,
+
+    /// Whether the allocation has been made by Collenchyma and is freed on drop.
+    owned: bool,
+
+    /// The Cuda context, which is made current to free the allocation.
+    ///
+    /// Holds the C type instead of a Context, as the MemoryPool of the Context holds Memory.
+    context: CUcontext,
 }
 
-impl Drop for Memory {
+#[cfg(feature = "thread_safe")]
+unsafe impl Send for MemoryHandle {}
+#[cfg(feature = "thread_safe")]
+unsafe impl Sync for MemoryHandle {}
+
+impl Drop for MemoryHandle {
+    #[allow(unused_must_use)]
     fn drop(&mut self) {
-        API::release_memory(self);
+        if !self.owned {
+            return
+        }
+        co_debug!("Releasing {} bytes of Cuda memory {:?}", self.size, self.memory);
+        alloc_tracker::forget(Cuda::ID, self.memory as usize);
+        API::release_memory(self.context, self.memory as CUdeviceptr);
         if self.memory_flags.contains(MEM_USE_HOST_PTR) {
             unsafe {
                 Box::from_raw(self.host_ptr);
@@ -46,15 +98,12 @@ impl Default for MemoryFlags {
 
 #[allow(unused_mut)]
 impl Memory {
-    pub fn new(context: cl::context_id, size: usize) -> Result<Memory, Error> {
-        // Memory::from_c(API::create_buffer(...))
-        /*
-        Memory::<T>::ffi_create_buffer(context,
-                                       MEM_READ_WRITE,
-                                       size,
-                                       ptr::null_mut())
-        */
-        unimplemented!();
+    /// Allocates `size` bytes of memory on the device of the Cuda context.
+    pub fn new(context: &Context, size: usize) -> Result<Memory, Error> {
+        let id = try!(API::create_buffer(context, size)) as cl::memory_id;
+        co_debug!("Allocated {} bytes of Cuda memory {:?}", size, id);
+        stats::record_alloc(size);
+        Ok(Memory::from_c(id, size, context))
     }
 
     pub fn from_box(context: cl::context_id, x: Box<u8>) -> Result<Memory, Error> {
@@ -66,16 +115,87 @@ impl Memory {
     }
 
     pub fn id_c(&self) -> cl::memory_id {
-        self.memory
+        (self.handle.memory as CUdeviceptr + self.offset as CUdeviceptr) as cl::memory_id
     }
 
-    pub fn from_c(id: cl::memory_id) -> Memory {
+    /// Returns a Memory of `size` bytes, which starts `offset` bytes into this Memory.
+    ///
+    /// The view shares the device allocation, its device pointer is offset into it.
+    /// Returns `None` if the view would reach past the end of this Memory.
+    pub fn view(&self, offset: usize, size: usize) -> Option<Memory> {
+        match offset.checked_add(size) {
+            Some(end) if end <= self.size => Some(Memory {
+                handle: self.handle.clone(),
+                offset: self.offset + offset,
+                size: size,
+            }),
+            _ => None
+        }
+    }
+
+    /// Identifies the device allocation, which the Memory and all of its clones and views share.
+    pub fn allocation_id(&self) -> usize {
+        self.handle.memory as usize
+    }
+
+    /// Returns the number of Memory clones, which share the device allocation.
+    pub fn ref_count(&self) -> usize {
+        MemoryRc::strong_count(&self.handle)
+    }
+
+    /// Wraps the device pointer `id` to `size` bytes, which have been allocated in the `context`.
+    ///
+    /// The allocation is freed, when the last clone of the Memory goes out of scope.
+    pub fn from_c(id: cl::memory_id, size: usize, context: &Context) -> Memory {
         Memory {
-            memory: id,
-            memory_flags: MemoryFlags::default(),
-            host_ptr: ptr::null_mut(),
+            handle: MemoryRc::new(MemoryHandle {
+                memory: id,
+                memory_flags: MemoryFlags::default(),
+                host_ptr: ptr::null_mut(),
+                size: size,
+                owned: true,
+                context: context.id_c(),
+            }),
+            offset: 0,
+            size: size,
         }
     }
+
+    /// Wraps the device pointer `device_ptr` to `size` bytes, which have been allocated outside of
+    /// Collenchyma, e.g. by another library.
+    ///
+    /// Cuda allocations are not reference counted, so the Memory borrows the allocation and
+    /// never frees it.
+    ///
+    /// ## Safety
+    ///
+    /// `device_ptr` must point to a live allocation of at least `size` bytes, which the caller
+    /// keeps alive for the lifetime of the Memory and of its clones and views. Every later copy
+    /// or kernel trusts `size`.
+    pub unsafe fn from_raw(device_ptr: CUdeviceptr, size: usize) -> Memory {
+        Memory {
+            handle: MemoryRc::new(MemoryHandle {
+                memory: device_ptr as cl::memory_id,
+                memory_flags: MemoryFlags::default(),
+                host_ptr: ptr::null_mut(),
+                size: size,
+                owned: false,
+                context: ptr::null_mut(),
+            }),
+            offset: 0,
+            size: size,
+        }
+    }
+
+    /// Returns whether the allocation is freed, when the last clone of the Memory is dropped.
+    pub fn is_owned(&self) -> bool {
+        self.handle.owned
+    }
+
+    /// Returns the size of the memory in bytes.
+    pub fn size(&self) -> usize {
+        self.size
+    }
 }
 
 impl IMemory for Memory {}
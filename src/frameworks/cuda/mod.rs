@@ -6,30 +6,65 @@
 //!
 //! Cuda device -> Hardware
 //! Cuda context -> Device
+//!
+//! ## Driver and Runtime API
+//!
+//! With the `cuda` feature, the framework is built on the Cuda driver API (`cu*`). With the
+//! `cuda-runtime` feature, it is built on the Cuda runtime API (`cuda*`) instead, so that it
+//! shares the primary context of each device with other code of the process, which uses the
+//! runtime API. Both provide the same framework, but the runtime API can not load PTX modules
+//! and does not support adopting a context with `Context::from_raw`. The two features are
+//! mutually exclusive.
 
 extern { }
 
-use framework::IFramework;
+use framework::{IFramework, HardwareIter};
 use device::DeviceType;
+use std::marker::PhantomData;
 pub use self::memory::Memory;
-pub use self::context::Context;
+pub use self::pinned_memory::{HostPinnedMemory, HostMappedMemory};
+pub use self::context::{Context, ContextFlags, ContextOwnership};
 pub use self::function::Function;
 pub use self::module::Module;
-pub use self::device::{Device, DeviceInfo};
-pub use self::api::{API, Error};
+pub use self::stream::Stream;
+pub use self::event::{Event, EventFlags};
+pub use self::device::{Device, DeviceInfo, DeviceAttribute};
+pub use self::api::{API, Error, ContextGuard};
+pub use self::api::ffi::CUdeviceptr;
 
 pub mod device;
 pub mod context;
 pub mod function;
 pub mod memory;
+pub mod pinned_memory;
 pub mod module;
+pub mod stream;
+pub mod event;
 mod api;
 
 #[derive(Debug, Clone)]
 /// Provides the Cuda Framework.
+///
+/// Cuda makes a context current to the calling thread, so the framework, and with it a
+/// `Backend<Cuda>`, can not be sent to or shared with another thread.
 pub struct Cuda {
     hardwares: Vec<Device>,
     binary: Module,
+    /// Opts out of `Send` and `Sync`.
+    _thread: PhantomData<*const ()>,
+}
+
+impl Cuda {
+    /// Initializes a new Device from the first of the provided hardwares, whose context is
+    /// created with `flags`.
+    ///
+    /// Use `CTX_SCHED_BLOCKING_SYNC` to let waiting threads sleep instead of spinning, and
+    /// `CTX_MAP_HOST` to allow mapped host memory. Returns an error, if `flags` select more than
+    /// one scheduling policy.
+    pub fn new_device_with_context_flags(&self, hardwares: Vec<Device>, flags: ContextFlags) -> Result<DeviceType, ::framework::Error> {
+        let hardware = try!(hardwares.first().cloned().ok_or(::framework::Error::MissingHardware(format!("Unable to create a Cuda device without hardware."))));
+        Ok(DeviceType::Cuda(try!(Context::with_flags(hardware, flags))))
+    }
 }
 
 impl IFramework for Cuda {
@@ -38,35 +73,41 @@ impl IFramework for Cuda {
     type B = Module;
     const ID: &'static str = "CUDA";
 
-    fn new() -> Cuda {
-        match Cuda::load_hardwares() {
-            Ok(hardwares) => {
-                Cuda {
-                    hardwares: hardwares,
-                    binary: Module::from_isize(1)
-                }
-            },
-            Err(err) => panic!(err)
-        }
+    fn try_new() -> Result<Cuda, ::framework::Error> {
+        let hardwares = try!(Cuda::load_hardwares());
+        Ok(Cuda {
+            hardwares: hardwares,
+            binary: Module::from_isize(1),
+            _thread: PhantomData,
+        })
     }
 
     fn load_hardwares() -> Result<Vec<Device>, ::framework::Error> {
-        unimplemented!()
+        Ok(try!(API::load_devices()))
     }
 
     fn hardwares(&self) -> Vec<Device> {
         self.hardwares.clone()
     }
 
+    fn hardware_iter(&self) -> HardwareIter<Device> {
+        self.hardwares.iter().cloned()
+    }
+
     fn binary(&self) -> Self::B {
         self.binary.clone()
     }
 
+    fn new_binary(&self, _device: &DeviceType) -> Result<Module, ::framework::Error> {
+        Ok(Module::from_isize(1))
+    }
+
     /// Creates a new Cuda device for computation.
     ///
     /// Cuda's device differs from OpenCL's context. Multi device support works different in Cuda.
     /// This function currently suppports only one device, but be a wrapper for multi device support.
     fn new_device(&self, hardwares: Vec<Device>) -> Result<DeviceType, ::framework::Error> {
-        unimplemented!()
+        let hardware = try!(hardwares.first().cloned().ok_or(::framework::Error::MissingHardware(format!("Unable to create a Cuda device without hardware."))));
+        Ok(DeviceType::Cuda(try!(Context::new(hardware))))
     }
 }
@@ -2,8 +2,8 @@
 
 use binary::IBinary;
 use super::function::Function;
-use super::api::types as cl;
-use super::api::API;
+use super::api::ffi::CUmodule;
+use super::api::{API, Error};
 
 #[derive(Debug, Copy, Clone)]
 /// Defines a Cuda Module.
@@ -31,8 +31,8 @@ impl Module {
         }
     }
 
-    /// Initializes a new OpenCL device from its C type.
-    pub fn from_c(id: cl::kernel_id) -> Module {
+    /// Initializes a new Cuda module from its C type.
+    pub fn from_c(id: CUmodule) -> Module {
         Module {
             id: id as isize,
             blas_dot: Function::from_isize(1),
@@ -42,8 +42,13 @@ impl Module {
     }
 
     /// Returns the id as its C type.
-    pub fn id_c(&self) -> cl::kernel_id {
-        self.id as cl::kernel_id
+    pub fn id_c(&self) -> CUmodule {
+        self.id as CUmodule
+    }
+
+    /// Returns the function `name` of the module.
+    pub fn get_function(&self, name: &str) -> Result<Function, Error> {
+        API::module_get_function(self, name)
     }
 }
 
@@ -0,0 +1,129 @@
+//! Provides a Rust wrapper around Cuda's page-locked host memory.
+//!
+//! ## Pinned Host Memory
+//!
+//! Page-locked (pinned) host memory can not be swapped out by the operating system. The device
+//! can therefore access it directly, which makes host-device copies considerably faster than
+//! copies from ordinary pageable host memory and allows them to run asynchronously on a
+//! [stream][stream].
+//! [stream]: ../stream/index.html
+//!
+//! ## Mapped Host Memory
+//!
+//! Pinned host memory can additionally be mapped into the address space of the device. Kernels
+//! then read and write the host memory directly over the bus, which removes the need for explicit
+//! copies. This pays off for small, frequently updated buffers, mostly on integrated GPUs, which
+//! share the physical memory with the host. On discrete GPUs every access crosses the bus, so
+//! buffers that are read repeatedly by kernels are usually faster with explicit copies.
+//!
+//! Mapped memory is not coherent across concurrent accesses: host writes are only guaranteed to
+//! be visible to the device for work issued after the writes, and device writes are only
+//! guaranteed to be visible to the host after the device work has been synchronized.
+//!
+//! Mapped memory is only available through the raw [API][api] for now. A [SharedMemory][shared]
+//! always allocates ordinary device memory and synchronizes it with explicit copies.
+//! [api]: ../api/struct.API.html
+//! [shared]: ../../../shared_memory/struct.SharedMemory.html
+
+use super::api::API;
+use super::api::ffi::CUdeviceptr;
+use super::Context;
+use libc::c_void;
+use std::ops::{Deref, DerefMut};
+use std::slice;
+
+#[derive(Debug)]
+/// Defines page-locked host memory, allocated through Cuda.
+///
+/// Derefs to a byte slice and frees the memory, when it goes out of scope.
+pub struct HostPinnedMemory {
+    ptr: *mut u8,
+    size: usize,
+    context: Context,
+}
+
+impl HostPinnedMemory {
+    /// Initializes a new pinned host memory from its C type.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to `size` bytes of page-locked host memory, which has been allocated in
+    /// the `context` with `cuMemAllocHost` or `cuMemHostAlloc`, or their runtime API counterparts.
+    /// The returned HostPinnedMemory takes ownership of the memory and frees it, when it is
+    /// dropped, so nothing else may free it or access it afterwards.
+    pub unsafe fn from_c(ptr: *mut c_void, size: usize, context: Context) -> HostPinnedMemory {
+        HostPinnedMemory { ptr: ptr as *mut u8, size: size, context: context }
+    }
+
+    /// Returns the size of the memory in bytes.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the pointer as its C type.
+    pub fn as_ptr_c(&self) -> *mut c_void {
+        self.ptr as *mut c_void
+    }
+}
+
+impl Drop for HostPinnedMemory {
+    #[allow(unused_must_use)]
+    fn drop(&mut self) {
+        API::free_host_pinned(&self.context, self.ptr as *mut c_void);
+    }
+}
+
+impl Deref for HostPinnedMemory {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        unsafe { slice::from_raw_parts(self.ptr, self.size) }
+    }
+}
+
+impl DerefMut for HostPinnedMemory {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        unsafe { slice::from_raw_parts_mut(self.ptr, self.size) }
+    }
+}
+
+#[derive(Debug)]
+/// Defines page-locked host memory, which is mapped into the address space of the device.
+///
+/// Derefs to a byte slice on the host, while `device_ptr` is used to access the same memory on
+/// the device. Frees the memory, when it goes out of scope.
+pub struct HostMappedMemory {
+    host: HostPinnedMemory,
+    device_ptr: CUdeviceptr,
+}
+
+impl HostMappedMemory {
+    /// Initializes a new mapped host memory from pinned host memory and its device pointer.
+    ///
+    /// # Safety
+    ///
+    /// `host` must have been allocated as mapped memory and `device_ptr` must be the device
+    /// pointer, which `cuMemHostGetDevicePointer` returned for it.
+    pub unsafe fn new(host: HostPinnedMemory, device_ptr: CUdeviceptr) -> HostMappedMemory {
+        HostMappedMemory { host: host, device_ptr: device_ptr }
+    }
+
+    /// Returns the device pointer, under which the device accesses the memory.
+    pub fn device_ptr(&self) -> CUdeviceptr {
+        self.device_ptr
+    }
+}
+
+impl Deref for HostMappedMemory {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.host
+    }
+}
+
+impl DerefMut for HostMappedMemory {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        &mut self.host
+    }
+}
@@ -0,0 +1,38 @@
+//! Provides a Rust wrapper around Cuda's stream.
+//!
+//! ## Cuda Stream
+//!
+//! A stream is a sequence of commands, which execute in order. Different streams may execute
+//! their commands concurrently or out of order with respect to each other. Issuing memory copies
+//! and kernel launches on separate streams allows overlapping computation with host-device
+//! transfers.
+
+use super::api::ffi::CUstream;
+
+#[derive(Debug, Copy, Clone)]
+/// Defines a Cuda Stream.
+pub struct Stream {
+    id: isize,
+}
+
+impl Stream {
+    /// Initializes a new Cuda stream.
+    pub fn from_isize(id: isize) -> Stream {
+        Stream { id: id }
+    }
+
+    /// Initializes a new Cuda stream from its C type.
+    pub fn from_c(id: CUstream) -> Stream {
+        Stream { id: id as isize }
+    }
+
+    /// Returns the id as isize.
+    pub fn id(&self) -> isize {
+        self.id
+    }
+
+    /// Returns the id as its C type.
+    pub fn id_c(&self) -> CUstream {
+        self.id as CUstream
+    }
+}
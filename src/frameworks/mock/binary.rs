@@ -0,0 +1,89 @@
+//! Provides a binary, which records the Operations issued through it.
+
+use binary::IBinary;
+use operation::IOperation;
+use libraries::blas::Error;
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, PartialEq)]
+/// Describes an Operation, which was issued through a Mock binary.
+pub struct Call {
+    /// The name of the Operation, e.g. `"axpy"`.
+    pub operation: &'static str,
+    /// The contents of the memory arguments in the order of the Operation's signature.
+    ///
+    /// Captured before the Operation was computed and converted to `f64`.
+    pub arguments: Vec<Vec<f64>>,
+    /// The remaining arguments, e.g. the `Transpose` or the dimensions of a matrix, as formatted
+    /// by `Debug`.
+    pub options: Vec<String>,
+}
+
+#[derive(Debug)]
+/// The state, which is shared between the clones of a binary.
+struct Log {
+    calls: Vec<Call>,
+    failure: Option<Error>,
+}
+
+#[derive(Debug, Clone)]
+/// Defines a Mock binary.
+///
+/// Clones of the binary share the recorded calls.
+pub struct Binary {
+    log: Arc<Mutex<Log>>,
+}
+
+impl Binary {
+    /// Initializes a Mock binary without any recorded calls.
+    pub fn new() -> Binary {
+        Binary { log: Arc::new(Mutex::new(Log { calls: Vec::new(), failure: None })) }
+    }
+
+    /// Returns the recorded calls in order.
+    pub fn calls(&self) -> Vec<Call> {
+        self.log.lock().unwrap().calls.clone()
+    }
+
+    /// Forgets the recorded calls.
+    pub fn clear_calls(&self) {
+        self.log.lock().unwrap().calls.clear()
+    }
+
+    /// Lets the next Operation fail with `error`.
+    pub fn fail_next(&self, error: Error) {
+        self.log.lock().unwrap().failure = Some(error)
+    }
+
+    /// Returns the Operation `name`, which records its calls into this binary.
+    pub fn operation(&self, name: &'static str) -> Operation {
+        Operation { name: name, log: self.log.clone() }
+    }
+}
+
+impl IBinary for Binary {}
+
+#[derive(Debug, Clone)]
+/// Defines a Mock Operation.
+///
+/// Records every call and computes it on the host CPU afterwards.
+pub struct Operation {
+    name: &'static str,
+    log: Arc<Mutex<Log>>,
+}
+
+impl Operation {
+    /// Records a call of the Operation.
+    ///
+    /// Returns the injected failure, if there is one.
+    pub fn record(&self, arguments: Vec<Vec<f64>>, options: Vec<String>) -> Result<(), Error> {
+        let mut log = self.log.lock().unwrap();
+        log.calls.push(Call { operation: self.name, arguments: arguments, options: options });
+        match log.failure.take() {
+            Some(error) => Err(error),
+            None => Ok(())
+        }
+    }
+}
+
+impl IOperation for Operation {}
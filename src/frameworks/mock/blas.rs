@@ -0,0 +1,203 @@
+//! Provides BLAS for a Mock backend.
+//!
+//! Every Operation records its call and then delegates the computation to the Native Operation.
+
+use frameworks::native::Function;
+use frameworks::mock::{Binary, Operation};
+use memory::MemoryType;
+use libraries::blas::*;
+use libraries::numeric_helpers::ToPrimitive;
+
+/// Returns the contents of the native memory `mem` converted to `f64`.
+fn values<T: ToPrimitive + Copy>(mem: &MemoryType) -> Vec<f64> {
+    match mem.as_native() {
+        Some(flat) => flat.as_slice::<T>().iter().map(|value| value.to_f64().unwrap_or(::std::f64::NAN)).collect(),
+        None => Vec::new()
+    }
+}
+
+macro_rules! impl_mock_blas(($($t: ident), +) => (
+    $(
+        impl IBlasBinary<$t> for Binary {
+            type Asum = Operation;
+            type Iamax = Operation;
+            type Axpy = Operation;
+            type Copy = Operation;
+            type Dot = Operation;
+            type Nrm2 = Operation;
+            type Scale = Operation;
+            type Swap = Operation;
+            type Gemv = Operation;
+            type Gemm = Operation;
+            type GemmBatched = Operation;
+            type Reduce = Operation;
+            type Map = Operation;
+            type Elementwise = Operation;
+
+            fn asum(&self) -> Self::Asum {
+                self.operation("asum")
+            }
+
+            fn iamax(&self) -> Self::Iamax {
+                self.operation("iamax")
+            }
+
+            fn axpy(&self) -> Self::Axpy {
+                self.operation("axpy")
+            }
+
+            fn copy(&self) -> Self::Copy {
+                self.operation("copy")
+            }
+
+            fn dot(&self) -> Self::Dot {
+                self.operation("dot")
+            }
+
+            fn nrm2(&self) -> Self::Nrm2 {
+                self.operation("nrm2")
+            }
+
+            fn scale(&self) -> Self::Scale {
+                self.operation("scale")
+            }
+
+            fn swap(&self) -> Self::Swap {
+                self.operation("swap")
+            }
+
+            fn gemv(&self) -> Self::Gemv {
+                self.operation("gemv")
+            }
+
+            fn gemm(&self) -> Self::Gemm {
+                self.operation("gemm")
+            }
+
+            fn gemm_batched(&self) -> Self::GemmBatched {
+                self.operation("gemm_batched")
+            }
+
+            fn reduce(&self) -> Self::Reduce {
+                self.operation("reduce")
+            }
+
+            fn map(&self) -> Self::Map {
+                self.operation("map")
+            }
+
+            fn elementwise(&self) -> Self::Elementwise {
+                self.operation("elementwise")
+            }
+        }
+
+        impl IOperationAsum<$t> for Operation {
+            fn compute(&self, x: &MemoryType, result: &mut MemoryType) -> Result<(), Error> {
+                try!(self.record(vec![values::<$t>(x), values::<$t>(result)], vec![]));
+                IOperationAsum::<$t>::compute(&Function, x, result)
+            }
+        }
+
+        impl IOperationIamax<$t> for Operation {
+            fn compute(&self, x: &MemoryType, result: &mut MemoryType) -> Result<(), Error> {
+                try!(self.record(vec![values::<$t>(x), values::<i32>(result)], vec![]));
+                IOperationIamax::<$t>::compute(&Function, x, result)
+            }
+        }
+
+        impl IOperationAxpy<$t> for Operation {
+            fn compute(&self, a: &MemoryType, x: &MemoryType, y: &mut MemoryType) -> Result<(), Error> {
+                try!(self.record(vec![values::<$t>(a), values::<$t>(x), values::<$t>(y)], vec![]));
+                IOperationAxpy::<$t>::compute(&Function, a, x, y)
+            }
+        }
+
+        impl IOperationCopy<$t> for Operation {
+            fn compute(&self, x: &MemoryType, y: &mut MemoryType) -> Result<(), Error> {
+                try!(self.record(vec![values::<$t>(x), values::<$t>(y)], vec![]));
+                IOperationCopy::<$t>::compute(&Function, x, y)
+            }
+        }
+
+        impl IOperationDot<$t> for Operation {
+            fn compute(&self, x: &MemoryType, y: &MemoryType, result: &mut MemoryType) -> Result<(), Error> {
+                try!(self.record(vec![values::<$t>(x), values::<$t>(y), values::<$t>(result)], vec![]));
+                IOperationDot::<$t>::compute(&Function, x, y, result)
+            }
+        }
+
+        impl IOperationNrm2<$t> for Operation {
+            fn compute(&self, x: &MemoryType, result: &mut MemoryType) -> Result<(), Error> {
+                try!(self.record(vec![values::<$t>(x), values::<$t>(result)], vec![]));
+                IOperationNrm2::<$t>::compute(&Function, x, result)
+            }
+        }
+
+        impl IOperationScale<$t> for Operation {
+            fn compute(&self, a: &MemoryType, x: &mut MemoryType) -> Result<(), Error> {
+                try!(self.record(vec![values::<$t>(a), values::<$t>(x)], vec![]));
+                IOperationScale::<$t>::compute(&Function, a, x)
+            }
+        }
+
+        impl IOperationSwap<$t> for Operation {
+            fn compute(&self, x: &mut MemoryType, y: &mut MemoryType) -> Result<(), Error> {
+                try!(self.record(vec![values::<$t>(x), values::<$t>(y)], vec![]));
+                IOperationSwap::<$t>::compute(&Function, x, y)
+            }
+        }
+
+        impl IOperationGemv<$t> for Operation {
+            fn compute(&self, trans: Transpose, alpha: &MemoryType, a: &MemoryType, x: &MemoryType, beta: &MemoryType, y: &mut MemoryType, rows: usize, cols: usize) -> Result<(), Error> {
+                try!(self.record(
+                    vec![values::<$t>(alpha), values::<$t>(a), values::<$t>(x), values::<$t>(beta), values::<$t>(y)],
+                    vec![format!("{:?}", trans), format!("{:?}", rows), format!("{:?}", cols)]
+                ));
+                IOperationGemv::<$t>::compute(&Function, trans, alpha, a, x, beta, y, rows, cols)
+            }
+        }
+
+        impl IOperationGemm<$t> for Operation {
+            fn compute(&self, alpha: &MemoryType, at: Transpose, a: &MemoryType, bt: Transpose, b: &MemoryType, beta: &MemoryType, c: &mut MemoryType, m: usize, n: usize, k: usize) -> Result<(), Error> {
+                try!(self.record(
+                    vec![values::<$t>(alpha), values::<$t>(a), values::<$t>(b), values::<$t>(beta), values::<$t>(c)],
+                    vec![format!("{:?}", at), format!("{:?}", bt), format!("{:?}", m), format!("{:?}", n), format!("{:?}", k)]
+                ));
+                IOperationGemm::<$t>::compute(&Function, alpha, at, a, bt, b, beta, c, m, n, k)
+            }
+        }
+
+        impl IOperationGemmBatched<$t> for Operation {
+            fn compute(&self, alpha: &MemoryType, at: Transpose, a: &MemoryType, bt: Transpose, b: &MemoryType, beta: &MemoryType, c: &mut MemoryType, m: usize, n: usize, k: usize, batch_count: usize) -> Result<(), Error> {
+                try!(self.record(
+                    vec![values::<$t>(alpha), values::<$t>(a), values::<$t>(b), values::<$t>(beta), values::<$t>(c)],
+                    vec![format!("{:?}", at), format!("{:?}", bt), format!("{:?}", m), format!("{:?}", n), format!("{:?}", k), format!("{:?}", batch_count)]
+                ));
+                IOperationGemmBatched::<$t>::compute(&Function, alpha, at, a, bt, b, beta, c, m, n, k, batch_count)
+            }
+        }
+
+        impl IOperationReduce<$t> for Operation {
+            fn compute(&self, x: &MemoryType, result: &mut MemoryType, op: ReduceOp) -> Result<(), Error> {
+                try!(self.record(vec![values::<$t>(x), values::<$t>(result)], vec![format!("{:?}", op)]));
+                IOperationReduce::<$t>::compute(&Function, x, result, op)
+            }
+        }
+
+        impl IOperationMap<$t> for Operation {
+            fn compute(&self, x: &MemoryType, y: &mut MemoryType, func: UnaryFn) -> Result<(), Error> {
+                try!(self.record(vec![values::<$t>(x), values::<$t>(y)], vec![format!("{:?}", func)]));
+                IOperationMap::<$t>::compute(&Function, x, y, func)
+            }
+        }
+
+        impl IOperationElementwise<$t> for Operation {
+            fn compute(&self, a: &MemoryType, b: &MemoryType, c: &mut MemoryType, op: BinaryOp) -> Result<(), Error> {
+                try!(self.record(vec![values::<$t>(a), values::<$t>(b), values::<$t>(c)], vec![format!("{:?}", op)]));
+                IOperationElementwise::<$t>::compute(&Function, a, b, c, op)
+            }
+        }
+    )+
+));
+
+impl_mock_blas!(f32, f64);
@@ -0,0 +1,99 @@
+//! Provides a Framework for testing code, which builds on Collenchyma, without real hardware.
+//!
+//! The Mock Framework computes on the host CPU, just like the [Native][native] Framework, but its
+//! binary records every Operation, which gets issued through it, and can be told to fail the next
+//! Operation. This allows downstream crates to assert, which Operations their code issues, and
+//! to exercise their error handling, e.g. on a CI machine without GPU.
+//!
+//! Only available with the `test-util` feature.
+//!
+//! Keep a clone of the Mock, before passing it on to a [BackendConfig][config], to inspect the
+//! calls of the Backend through `Mock::calls`.
+//!
+//! [config]: ../../backend/struct.BackendConfig.html
+//! [native]: ../native/index.html
+
+use framework::{IFramework, HardwareIter};
+use hardware::{HardwareType, IHardware};
+use device::DeviceType;
+use frameworks::native::Cpu;
+use frameworks::native::hardware::Hardware;
+use libraries::blas::Error;
+pub use self::binary::{Binary, Call, Operation};
+
+pub mod binary;
+mod blas;
+
+#[derive(Debug, Clone)]
+/// Provides the Mock Framework.
+///
+/// Clones of the Mock share their binary and therefore their recorded calls.
+pub struct Mock {
+    hardwares: Vec<Hardware>,
+    binary: Binary,
+}
+
+impl Mock {
+    /// Returns the Operations, which were issued through the binary so far, in order.
+    pub fn calls(&self) -> Vec<Call> {
+        self.binary.calls()
+    }
+
+    /// Forgets the recorded calls.
+    pub fn clear_calls(&self) {
+        self.binary.clear_calls()
+    }
+
+    /// Lets the next Operation, which gets issued through the binary, fail with `error`.
+    ///
+    /// The call is still recorded.
+    pub fn fail_next(&self, error: Error) {
+        self.binary.fail_next(error)
+    }
+}
+
+impl IFramework for Mock {
+    type H = Hardware;
+    type D = Cpu;
+    type B = Binary;
+
+    const ID: &'static str = "MOCK";
+
+    fn try_new() -> Result<Mock, ::framework::Error> {
+        let hardwares = try!(Mock::load_hardwares());
+        Ok(Mock {
+            hardwares: hardwares,
+            binary: Binary::new()
+        })
+    }
+
+    fn load_hardwares() -> Result<Vec<Hardware>, ::framework::Error> {
+        let cpu = Hardware::new(1)
+            .set_name(Some(String::from("Mock CPU")))
+            .set_hardware_type(Some(HardwareType::CPU))
+            .set_compute_units(Some(1))
+            .build();
+        Ok(vec!(cpu))
+    }
+
+    fn hardwares(&self) -> Vec<Hardware> {
+        self.hardwares.clone()
+    }
+
+    fn hardware_iter(&self) -> HardwareIter<Hardware> {
+        self.hardwares.iter().cloned()
+    }
+
+    fn binary(&self) -> Binary {
+        self.binary.clone()
+    }
+
+    /// Returns the binary of the Framework, so that the Framework sees the calls of the Backend.
+    fn new_binary(&self, _device: &DeviceType) -> Result<Binary, ::framework::Error> {
+        Ok(self.binary.clone())
+    }
+
+    fn new_device(&self, hardwares: Vec<Hardware>) -> Result<DeviceType, ::framework::Error> {
+        Ok(DeviceType::Native(Cpu::new(hardwares)))
+    }
+}
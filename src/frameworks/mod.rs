@@ -1,9 +1,17 @@
 //! Exposes the specific Framework implementations.
 
 pub use self::native::Native;
+#[cfg(feature = "opencl")]
 pub use self::opencl::OpenCL;
+#[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
 pub use self::cuda::Cuda;
+#[cfg(feature = "test-util")]
+pub use self::mock::Mock;
 
 pub mod native;
+#[cfg(feature = "opencl")]
 pub mod opencl;
+#[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
 pub mod cuda;
+#[cfg(feature = "test-util")]
+pub mod mock;
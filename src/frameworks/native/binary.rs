@@ -9,6 +9,8 @@ pub struct Binary {
     id: isize,
     /// The initialized Blas Asum Operation.
     pub blas_asum: Function,
+    /// The initialized Blas Iamax Operation.
+    pub blas_iamax: Function,
     /// The initialized Blas Axpy Operation.
     pub blas_axpy: Function,
     /// The initialized Blas Copy Operation.
@@ -21,6 +23,18 @@ pub struct Binary {
     pub blas_scale: Function,
     /// The initialized Blas Swap Operation.
     pub blas_swap: Function,
+    /// The initialized Blas Gemv Operation.
+    pub blas_gemv: Function,
+    /// The initialized Blas Gemm Operation.
+    pub blas_gemm: Function,
+    /// The initialized Blas GemmBatched Operation.
+    pub blas_gemm_batched: Function,
+    /// The initialized Reduce Operation.
+    pub blas_reduce: Function,
+    /// The initialized Map Operation.
+    pub blas_map: Function,
+    /// The initialized Elementwise Operation.
+    pub blas_elementwise: Function,
 }
 
 impl Binary {
@@ -29,12 +43,19 @@ impl Binary {
         Binary {
             id: 0,
             blas_asum: Function::new(),
+            blas_iamax: Function::new(),
             blas_axpy: Function::new(),
             blas_copy: Function::new(),
             blas_dot: Function::new(),
             blas_nrm2: Function::new(),
             blas_scale: Function::new(),
             blas_swap: Function::new(),
+            blas_gemv: Function::new(),
+            blas_gemm: Function::new(),
+            blas_gemm_batched: Function::new(),
+            blas_reduce: Function::new(),
+            blas_map: Function::new(),
+            blas_elementwise: Function::new(),
         }
     }
 }
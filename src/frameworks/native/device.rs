@@ -4,7 +4,28 @@ use device::{IDevice, DeviceType};
 use memory::MemoryType;
 use super::hardware::Hardware;
 use super::flatbox::FlatBox;
+use super::Error;
+#[cfg(feature = "opencl")]
+use frameworks::opencl::{API as OpenCLAPI, Error as OpenCLError};
+#[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+use frameworks::cuda::{API as CudaAPI, Error as CudaError};
+#[cfg(any(feature = "opencl", feature = "cuda", feature = "cuda-runtime"))]
+use libc::c_void;
 use std::hash::{Hash, Hasher};
+#[cfg(feature = "test-util")]
+use std::sync::{Arc, Mutex};
+
+#[cfg(feature = "test-util")]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// Specifies the call of a Native device, which fails with an injected error.
+///
+/// Only available with the `test-util` feature.
+pub enum Failure {
+    /// The next allocation of memory.
+    Alloc,
+    /// The next synchronization of memory from the device.
+    Sync,
+}
 
 #[derive(Debug, Clone)]
 /// Defines the host CPU Hardware.
@@ -12,14 +33,87 @@ use std::hash::{Hash, Hasher};
 /// Can later be transformed into a [Collenchyma hardware][hardware].
 /// [hardware]: ../../hardware/index.html
 pub struct Cpu {
-    hardwares: Vec<Hardware>
+    hardwares: Vec<Hardware>,
+    #[cfg(feature = "test-util")]
+    failures: Arc<Mutex<Vec<(Failure, ::framework::Error)>>>,
 }
 
 impl Cpu {
     /// Initializes a new OpenCL hardware.
+    #[cfg(not(feature = "test-util"))]
     pub fn new(hardwares: Vec<Hardware>) -> Cpu {
         Cpu { hardwares: hardwares }
     }
+
+    /// Initializes a new OpenCL hardware.
+    #[cfg(feature = "test-util")]
+    pub fn new(hardwares: Vec<Hardware>) -> Cpu {
+        Cpu { hardwares: hardwares, failures: Arc::new(Mutex::new(Vec::new())) }
+    }
+
+    /// Allocates `size` bytes of memory on the device.
+    ///
+    /// Unlike `alloc_memory`, returns the injected allocation failure, if there is one, and a
+    /// Native `InvalidMemory` error, if `size` exceeds the address space of the host.
+    pub fn try_alloc_memory(&self, size: usize) -> Result<FlatBox, ::framework::Error> {
+        try!(self.take_failure_alloc());
+        if size > ::std::isize::MAX as usize {
+            return Err(::framework::Error::Native(Error::InvalidMemory(format!("Unable to allocate {} bytes, which exceeds the address space of the host.", size))))
+        }
+        Ok(self.alloc_memory(size))
+    }
+
+    /// Allocates `size` bytes of memory on the device, which start at a multiple of `align`.
+    ///
+    /// Pads the allocation by `align - 1` bytes and returns a view into it, which starts at the
+    /// first aligned address. `align` needs to be a power of two.
+    pub fn try_alloc_memory_aligned(&self, size: usize, align: usize) -> Result<FlatBox, ::framework::Error> {
+        let padded = try!(size.checked_add(align - 1).ok_or(::framework::Error::Native(Error::InvalidMemory(format!("Unable to pad {} bytes to an alignment of {} bytes.", size, align)))));
+        let memory = try!(self.try_alloc_memory(padded));
+        let offset = (align - memory.as_slice::<u8>().as_ptr() as usize % align) % align;
+        Ok(memory.view(offset, size).unwrap())
+    }
+
+    /// Lets the next `failure` of the device, and of all its clones, fail with `error`.
+    ///
+    /// Replaces a previously injected error for the same `failure`.
+    /// Only available with the `test-util` feature.
+    #[cfg(feature = "test-util")]
+    pub fn inject_failure(&self, failure: Failure, error: ::framework::Error) {
+        let mut failures = self.failures.lock().unwrap();
+        failures.retain(|&(injected, _)| injected != failure);
+        failures.push((failure, error));
+    }
+
+    /// Returns and forgets the error, which was injected for `failure`.
+    #[cfg(feature = "test-util")]
+    fn take_failure(&self, failure: Failure) -> Result<(), ::framework::Error> {
+        let mut failures = self.failures.lock().unwrap();
+        match failures.iter().position(|&(injected, _)| injected == failure) {
+            Some(index) => Err(failures.remove(index).1),
+            None => Ok(())
+        }
+    }
+
+    #[cfg(feature = "test-util")]
+    fn take_failure_alloc(&self) -> Result<(), ::framework::Error> {
+        self.take_failure(Failure::Alloc)
+    }
+
+    #[cfg(not(feature = "test-util"))]
+    fn take_failure_alloc(&self) -> Result<(), ::framework::Error> {
+        Ok(())
+    }
+
+    #[cfg(feature = "test-util")]
+    fn take_failure_sync(&self) -> Result<(), ::framework::Error> {
+        self.take_failure(Failure::Sync)
+    }
+
+    #[cfg(not(feature = "test-util"))]
+    fn take_failure_sync(&self) -> Result<(), ::framework::Error> {
+        Ok(())
+    }
 }
 
 impl IDevice for Cpu {
@@ -35,19 +129,39 @@ impl IDevice for Cpu {
     }
 
     fn alloc_memory(&self, size: usize) -> FlatBox {
+        co_debug!("Allocating {} bytes of native memory", size);
         let vec: Vec<u8> = vec![0; size];
         let bx: Box<[u8]> = vec.into_boxed_slice();
         FlatBox::from_box(bx)
     }
 
-    fn sync_memory_to(&self, source: &FlatBox, dest: &mut MemoryType, dest_device: &DeviceType) {
+    fn sync_memory_to(&self, source: &FlatBox, dest: &mut MemoryType, dest_device: &DeviceType) -> Result<(), ::framework::Error> {
+        try!(self.take_failure_sync());
         match dest_device.clone() {
-            DeviceType::Native(_) => {},
-            DeviceType::OpenCL(_) => {
-                unimplemented!();
+            DeviceType::Native(_) => {
+                let memory = try!(dest.as_mut_native().ok_or(::framework::Error::InvalidMemoryAllocation(format!("The destination is no Native memory."))));
+                memory.as_mut_slice::<u8>().copy_from_slice(source.as_slice::<u8>());
+                Ok(())
+            },
+            #[cfg(feature = "opencl")]
+            DeviceType::OpenCL(context) => {
+                let queue = try!(context.queue().ok_or(OpenCLError::InvalidCommandQueue(format!("The OpenCL context has no command queue."))));
+                let memory = try!(dest.as_mut_opencl().ok_or(OpenCLError::InvalidMemObject(format!("The destination is no OpenCL memory."))));
+                let data = source.as_slice::<u8>();
+                Ok(try!(OpenCLAPI::write_to_memory(queue, memory, data.as_ptr() as *const c_void, data.len())))
+            },
+            #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+            DeviceType::Cuda(context) => {
+                let memory = try!(dest.as_mut_cuda().ok_or(CudaError::InvalidValue(format!("The destination is no Cuda memory."))));
+                let data = source.as_slice::<u8>();
+                Ok(try!(CudaAPI::write_to_memory(&context, memory, data.as_ptr() as *const c_void, data.len())))
             }
         }
     }
+
+    fn synchronize(&self) -> Result<(), ::framework::Error> {
+        Ok(())
+    }
 }
 
 impl PartialEq for Cpu {
@@ -0,0 +1,37 @@
+//! Provides Rust Errors for the Native framework.
+
+use std::{fmt, error};
+
+#[derive(Debug)]
+/// Defines Native errors.
+pub enum Error {
+    /// Failure with provided memory.
+    InvalidMemory(String),
+    /// Failure not closer defined.
+    Other(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::InvalidMemory(ref err) => write!(f, "{:?}", err),
+            Error::Other(ref err) => write!(f, "{:?}", err),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn description(&self) -> &str {
+        match *self {
+            Error::InvalidMemory(ref err) => err,
+            Error::Other(ref err) => err,
+        }
+    }
+
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            Error::InvalidMemory(_) => None,
+            Error::Other(_) => None,
+        }
+    }
+}
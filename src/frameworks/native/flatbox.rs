@@ -1,32 +1,78 @@
 //! Provides a Box without any knowledge of its underlying type.
 
+use alloc_tracker;
+use framework::IFramework;
 use memory::*;
+use super::Native;
 use std::fmt;
 use std::mem;
+use std::rc::Rc;
 use std::slice;
 
+/// Owns the boxed memory, which is shared between a FlatBox and its views.
+struct Allocation {
+    raw_box: *mut [u8]
+}
+
+impl Drop for Allocation {
+    fn drop(&mut self) {
+        alloc_tracker::forget(Native::ID, self.raw_box as *mut u8 as usize);
+        unsafe {
+            Box::from_raw(self.raw_box);
+        }
+    }
+}
+
 /// A Box without any knowledge of its underlying type.
+///
+/// A FlatBox either owns its memory or is a view into the memory of another FlatBox, see `view`.
+/// The memory is freed, when the FlatBox and all of its views went out of scope.
 pub struct FlatBox {
     len: usize,
-    raw_box: *mut [u8]
+    ptr: *mut u8,
+    allocation: Rc<Allocation>,
 }
 
 impl FlatBox {
     /// Create FlatBox from Box, consuming it.
     pub fn from_box(b: Box<[u8]>) -> FlatBox {
+        let len = b.len();
+        let raw_box = Box::into_raw(b);
         FlatBox {
-            len: b.len(),
-            raw_box: Box::into_raw(b)
+            len: len,
+            ptr: raw_box as *mut u8,
+            allocation: Rc::new(Allocation { raw_box: raw_box })
+        }
+    }
+
+    /// Returns a FlatBox of `len` bytes, which starts `offset` bytes into this FlatBox.
+    ///
+    /// The view shares the memory with this FlatBox, so writes through one are visible through
+    /// the other.
+    /// Returns `None` if the view would reach past the end of this FlatBox.
+    pub fn view(&self, offset: usize, len: usize) -> Option<FlatBox> {
+        match offset.checked_add(len) {
+            Some(end) if end <= self.len => Some(FlatBox {
+                len: len,
+                ptr: unsafe { self.ptr.offset(offset as isize) },
+                allocation: self.allocation.clone()
+            }),
+            _ => None
         }
     }
 
+    /// Identifies the allocation, which the FlatBox and all of its views share.
+    pub fn allocation_id(&self) -> usize {
+        self.allocation.raw_box as *mut u8 as usize
+    }
+
     /// Access memory as slice.
     ///
     /// The preffered way to access native memory.
     pub fn as_slice<T>(&self) -> &[T] {
         unsafe {
             slice::from_raw_parts_mut(
-                self.raw_box as *mut T,
+                self.ptr as *mut T,
                 self.len / mem::size_of::<T>()
             )
         }
@@ -38,21 +84,13 @@ impl FlatBox {
     pub fn as_mut_slice<T>(&mut self) -> &mut [T] {
         unsafe {
             slice::from_raw_parts_mut(
-                self.raw_box as *mut T,
+                self.ptr as *mut T,
                 self.len / mem::size_of::<T>()
             )
         }
     }
 }
 
-impl Drop for FlatBox {
-    fn drop(&mut self) {
-        unsafe {
-            Box::from_raw(self.raw_box);
-        }
-    }
-}
-
 impl fmt::Debug for FlatBox {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "FlatBox of length {}", &self.len)
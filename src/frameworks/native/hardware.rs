@@ -12,6 +12,7 @@ pub struct Hardware {
     name: Option<String>,
     hardware_type: Option<HardwareType>,
     compute_units: Option<isize>,
+    memory: Option<usize>,
 }
 
 impl Default for Hardware {
@@ -21,6 +22,7 @@ impl Default for Hardware {
             name: None,
             hardware_type: None,
             compute_units: None,
+            memory: None,
         }
     }
 }
@@ -30,6 +32,12 @@ impl Hardware {
     pub fn new(id: isize) -> Hardware {
         Hardware { id: id, ..Hardware::default() }
     }
+
+    /// Defines the memory of the Hardware in bytes.
+    pub fn set_memory(&mut self, memory: Option<usize>) -> Self {
+        self.memory = memory;
+        self.clone()
+    }
 }
 
 impl IHardware for Hardware {
@@ -64,12 +72,17 @@ impl IHardware for Hardware {
         self.clone()
     }
 
+    fn memory(&self) -> Option<usize> {
+        self.memory
+    }
+
     fn build(self) -> Hardware {
         Hardware {
             id: self.id(),
             name: self.name(),
             hardware_type: self.hardware_type(),
             compute_units: self.compute_units(),
+            memory: self.memory(),
         }
     }
 }
@@ -3,23 +3,37 @@
 use frameworks::native::{Function, Binary};
 use memory::MemoryType;
 use libraries::blas::*;
-use blas::{Asum, Axpy, Copy, Dot, Nrm2, Scal, Swap};
+use validate;
+use blas::{Asum, Axpy, Copy, Dot, Scal, Swap};
+use super::parallel;
+use num::traits::Zero;
 
 macro_rules! impl_binary(($($t: ident), +) => (
     $(
         impl IBlasBinary<$t> for Binary {
             type Asum = Function;
+            type Iamax = Function;
             type Axpy = Function;
             type Copy = Function;
             type Dot = Function;
             type Nrm2 = Function;
             type Scale = Function;
             type Swap = Function;
+            type Gemv = Function;
+            type Gemm = Function;
+            type GemmBatched = Function;
+            type Reduce = Function;
+            type Map = Function;
+            type Elementwise = Function;
 
             fn asum(&self) -> Self::Asum {
                 self.blas_asum
             }
 
+            fn iamax(&self) -> Self::Iamax {
+                self.blas_iamax
+            }
+
             fn axpy(&self) -> Self::Axpy {
                 self.blas_axpy
             }
@@ -43,6 +57,30 @@ macro_rules! impl_binary(($($t: ident), +) => (
             fn swap(&self) -> Self::Swap {
                 self.blas_swap
             }
+
+            fn gemv(&self) -> Self::Gemv {
+                self.blas_gemv
+            }
+
+            fn gemm(&self) -> Self::Gemm {
+                self.blas_gemm
+            }
+
+            fn gemm_batched(&self) -> Self::GemmBatched {
+                self.blas_gemm_batched
+            }
+
+            fn reduce(&self) -> Self::Reduce {
+                self.blas_reduce
+            }
+
+            fn map(&self) -> Self::Map {
+                self.blas_map
+            }
+
+            fn elementwise(&self) -> Self::Elementwise {
+                self.blas_elementwise
+            }
         }
     )+
 ));
@@ -53,7 +91,32 @@ macro_rules! impl_asum(($($t: ident), +) => (
             fn compute(&self, x: &MemoryType, result: &mut MemoryType) -> Result<(), Error> {
                 let x_slice = try!(x.as_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `x`.")))).as_slice::<$t>();
                 let mut r_slice = try!(result.as_mut_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `result`.")))).as_mut_slice::<$t>();
-                r_slice[0] = Asum::asum(x_slice);
+                try!(validate::scalar(("result", r_slice.len())));
+                let partials = parallel::map_chunks(x_slice, |x| Asum::asum(x));
+                r_slice[0] = partials.iter().fold(0.0, |sum, partial| sum + *partial);
+                Ok(())
+            }
+        }
+    )+
+));
+
+macro_rules! impl_iamax(($($t: ident), +) => (
+    $(
+        impl IOperationIamax<$t> for Function {
+            fn compute(&self, x: &MemoryType, result: &mut MemoryType) -> Result<(), Error> {
+                let x_slice = try!(x.as_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `x`.")))).as_slice::<$t>();
+                let r_slice = try!(result.as_mut_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `result`.")))).as_mut_slice::<i32>();
+                try!(validate::scalar(("result", r_slice.len())));
+                let mut index = -1;
+                let mut max = Zero::zero();
+                for (i, xi) in x_slice.iter().enumerate() {
+                    // Only a strictly larger magnitude replaces the maximum, so ties keep the lowest index.
+                    if index == -1 || xi.abs() > max {
+                        index = i as i32;
+                        max = xi.abs();
+                    }
+                }
+                r_slice[0] = index;
                 Ok(())
             }
         }
@@ -67,7 +130,10 @@ macro_rules! impl_axpy(($($t: ident), +) => (
                 let a_slice = try!(a.as_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `a`.")))).as_slice::<$t>();
                 let x_slice = try!(x.as_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `x`.")))).as_slice::<$t>();
                 let y_slice = try!(y.as_mut_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `y`.")))).as_mut_slice::<$t>();
-                Axpy::axpy(&a_slice[0], x_slice, y_slice);
+                try!(validate::same_len(("x", x_slice.len()), ("y", y_slice.len())));
+                try!(validate::scalar(("a", a_slice.len())));
+                let a_val = a_slice[0];
+                parallel::for_chunks_zip_mut(x_slice, y_slice, |x, y| Axpy::axpy(&a_val, x, y));
                 Ok(())
             }
         }
@@ -80,6 +146,7 @@ macro_rules! impl_copy(($($t: ident), +) => (
             fn compute(&self, x: &MemoryType, y: &mut MemoryType) -> Result<(), Error> {
                 let x_slice = try!(x.as_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `x`.")))).as_slice::<$t>();
                 let y_slice = try!(y.as_mut_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `y`.")))).as_mut_slice::<$t>();
+                try!(validate::at_least(("x", x_slice.len()), ("y", y_slice.len())));
                 Copy::copy(x_slice, y_slice);
                 Ok(())
             }
@@ -94,7 +161,10 @@ macro_rules! impl_dot(($($t: ident), +) => (
                 let x_slice = try!(x.as_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `x`.")))).as_slice::<$t>();
                 let y_slice = try!(y.as_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `y`.")))).as_slice::<$t>();
                 let mut r_slice = try!(result.as_mut_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `result`.")))).as_mut_slice::<$t>();
-                r_slice[0] = Dot::dot(x_slice, y_slice);
+                try!(validate::scalar(("result", r_slice.len())));
+                try!(validate::same_len(("x", x_slice.len()), ("y", y_slice.len())));
+                let partials = parallel::map_chunks_zip(x_slice, y_slice, |x, y| Dot::dot(x, y));
+                r_slice[0] = partials.iter().fold(0.0, |sum, partial| sum + *partial);
                 Ok(())
             }
         }
@@ -107,7 +177,38 @@ macro_rules! impl_nrm2(($($t: ident), +) => (
             fn compute(&self, x: &MemoryType, result: &mut MemoryType) -> Result<(), Error> {
                 let x_slice = try!(x.as_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `x`.")))).as_slice::<$t>();
                 let mut r_slice = try!(result.as_mut_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `result`.")))).as_mut_slice::<$t>();
-                r_slice[0] = Nrm2::nrm2(x_slice);
+                try!(validate::scalar(("result", r_slice.len())));
+                // Scales by the running maximum, like the reference BLAS, so that the sum of
+                // squares does not overflow for elements with large magnitudes.
+                let partials = parallel::map_chunks(x_slice, |x| {
+                    let mut scale: $t = 0.0;
+                    let mut ssq: $t = 1.0;
+                    for xi in x.iter().filter(|xi| **xi != 0.0) {
+                        let absxi = xi.abs();
+                        if scale < absxi {
+                            let ratio = scale / absxi;
+                            ssq = 1.0 + ssq * ratio * ratio;
+                            scale = absxi;
+                        } else {
+                            let ratio = absxi / scale;
+                            ssq = ssq + ratio * ratio;
+                        }
+                    }
+                    (scale, ssq)
+                });
+                // Merges the scaled sums of squares of the chunks the same way.
+                let (scale, ssq) = partials.iter().fold((0.0 as $t, 1.0 as $t), |(scale, ssq), &(chunk_scale, chunk_ssq)| {
+                    if chunk_scale == 0.0 {
+                        (scale, ssq)
+                    } else if scale < chunk_scale {
+                        let ratio = scale / chunk_scale;
+                        (chunk_scale, chunk_ssq + ssq * ratio * ratio)
+                    } else {
+                        let ratio = chunk_scale / scale;
+                        (scale, ssq + chunk_ssq * ratio * ratio)
+                    }
+                });
+                r_slice[0] = scale * ssq.sqrt();
                 Ok(())
             }
         }
@@ -120,7 +221,9 @@ macro_rules! impl_scale(($($t: ident), +) => (
             fn compute(&self, a: &MemoryType, x: &mut MemoryType) -> Result<(), Error> {
                 let a_slice = try!(a.as_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `a`.")))).as_slice::<$t>();
                 let mut x_slice = try!(x.as_mut_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `x`.")))).as_mut_slice::<$t>();
-                Scal::scal(&a_slice[0], x_slice);
+                try!(validate::scalar(("a", a_slice.len())));
+                let a_val = a_slice[0];
+                parallel::for_chunks_mut(x_slice, |x| Scal::scal(&a_val, x));
                 Ok(())
             }
         }
@@ -133,6 +236,7 @@ macro_rules! impl_swap(($($t: ident), +) => (
             fn compute(&self, x: &mut MemoryType, y: &mut MemoryType) -> Result<(), Error> {
                 let mut x_slice = try!(x.as_mut_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `x`.")))).as_mut_slice::<$t>();
                 let mut y_slice = try!(y.as_mut_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `y`.")))).as_mut_slice::<$t>();
+                try!(validate::same_len(("x", x_slice.len()), ("y", y_slice.len())));
                 Swap::swap(x_slice, y_slice);
                 Ok(())
             }
@@ -140,11 +244,297 @@ macro_rules! impl_swap(($($t: ident), +) => (
     )+
 ));
 
-impl_binary!(f32, f64);
+macro_rules! impl_gemv(($($t: ident), +) => (
+    $(
+        impl IOperationGemv<$t> for Function {
+            fn compute(&self, trans: Transpose, alpha: &MemoryType, a: &MemoryType, x: &MemoryType, beta: &MemoryType, y: &mut MemoryType, rows: usize, cols: usize) -> Result<(), Error> {
+                let alpha_slice = try!(alpha.as_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `alpha`.")))).as_slice::<$t>();
+                let a_slice = try!(a.as_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `a`.")))).as_slice::<$t>();
+                let x_slice = try!(x.as_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `x`.")))).as_slice::<$t>();
+                let beta_slice = try!(beta.as_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `beta`.")))).as_slice::<$t>();
+                let y_slice = try!(y.as_mut_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `y`.")))).as_mut_slice::<$t>();
+                let (x_len, y_len) = if trans.is_transposed() { (rows, cols) } else { (cols, rows) };
+                try!(validate::scalar(("alpha", alpha_slice.len())).and_then(|_| validate::scalar(("beta", beta_slice.len()))));
+                try!(validate::matrix_dims(("a", a_slice.len()), rows, cols)
+                    .and_then(|_| validate::vector_len(("x", x_slice.len()), x_len))
+                    .and_then(|_| validate::vector_len(("y", y_slice.len()), y_len)));
+                for i in 0..y_len {
+                    let mut sum: $t = Zero::zero();
+                    for j in 0..x_len {
+                        let a_val = if trans.is_transposed() { a_slice[j * cols + i] } else { a_slice[i * cols + j] };
+                        sum = sum + a_val * x_slice[j];
+                    }
+                    y_slice[i] = alpha_slice[0] * sum + beta_slice[0] * y_slice[i];
+                }
+                Ok(())
+            }
+        }
+    )+
+));
+
+macro_rules! impl_gemm(($($t: ident), +) => (
+    $(
+        impl IOperationGemm<$t> for Function {
+            fn compute(&self, alpha: &MemoryType, at: Transpose, a: &MemoryType, bt: Transpose, b: &MemoryType, beta: &MemoryType, c: &mut MemoryType, m: usize, n: usize, k: usize) -> Result<(), Error> {
+                let alpha_slice = try!(alpha.as_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `alpha`.")))).as_slice::<$t>();
+                let a_slice = try!(a.as_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `a`.")))).as_slice::<$t>();
+                let b_slice = try!(b.as_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `b`.")))).as_slice::<$t>();
+                let beta_slice = try!(beta.as_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `beta`.")))).as_slice::<$t>();
+                let c_slice = try!(c.as_mut_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `c`.")))).as_mut_slice::<$t>();
+                try!(validate::scalar(("alpha", alpha_slice.len())).and_then(|_| validate::scalar(("beta", beta_slice.len()))));
+                try!(validate::matrix_dims(("a", a_slice.len()), m, k)
+                    .and_then(|_| validate::matrix_dims(("b", b_slice.len()), k, n))
+                    .and_then(|_| validate::matrix_dims(("c", c_slice.len()), m, n)));
+                for row in 0..m {
+                    for col in 0..n {
+                        let mut sum: $t = Zero::zero();
+                        for i in 0..k {
+                            let a_val = if at.is_transposed() { a_slice[i * m + row] } else { a_slice[row * k + i] };
+                            let b_val = if bt.is_transposed() { b_slice[col * k + i] } else { b_slice[i * n + col] };
+                            sum = sum + a_val * b_val;
+                        }
+                        c_slice[row * n + col] = alpha_slice[0] * sum + beta_slice[0] * c_slice[row * n + col];
+                    }
+                }
+                Ok(())
+            }
+        }
+    )+
+));
+
+macro_rules! impl_gemm_batched(($($t: ident), +) => (
+    $(
+        impl IOperationGemmBatched<$t> for Function {
+            fn compute(&self, alpha: &MemoryType, at: Transpose, a: &MemoryType, bt: Transpose, b: &MemoryType, beta: &MemoryType, c: &mut MemoryType, m: usize, n: usize, k: usize, batch_count: usize) -> Result<(), Error> {
+                let alpha_slice = try!(alpha.as_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `alpha`.")))).as_slice::<$t>();
+                let a_slice = try!(a.as_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `a`.")))).as_slice::<$t>();
+                let b_slice = try!(b.as_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `b`.")))).as_slice::<$t>();
+                let beta_slice = try!(beta.as_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `beta`.")))).as_slice::<$t>();
+                let c_slice = try!(c.as_mut_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `c`.")))).as_mut_slice::<$t>();
+                try!(validate::scalar(("alpha", alpha_slice.len())).and_then(|_| validate::scalar(("beta", beta_slice.len()))));
+                try!(validate::batched_matrix_dims(("a", a_slice.len()), batch_count, m, k)
+                    .and_then(|_| validate::batched_matrix_dims(("b", b_slice.len()), batch_count, k, n))
+                    .and_then(|_| validate::batched_matrix_dims(("c", c_slice.len()), batch_count, m, n)));
+                for batch in 0..batch_count {
+                    let a_batch = &a_slice[batch * m * k..(batch + 1) * m * k];
+                    let b_batch = &b_slice[batch * k * n..(batch + 1) * k * n];
+                    let c_batch = &mut c_slice[batch * m * n..(batch + 1) * m * n];
+                    for row in 0..m {
+                        for col in 0..n {
+                            let mut sum: $t = Zero::zero();
+                            for i in 0..k {
+                                let a_val = if at.is_transposed() { a_batch[i * m + row] } else { a_batch[row * k + i] };
+                                let b_val = if bt.is_transposed() { b_batch[col * k + i] } else { b_batch[i * n + col] };
+                                sum = sum + a_val * b_val;
+                            }
+                            c_batch[row * n + col] = alpha_slice[0] * sum + beta_slice[0] * c_batch[row * n + col];
+                        }
+                    }
+                }
+                Ok(())
+            }
+        }
+    )+
+));
+
+macro_rules! impl_reduce(($($t: ident, $min: expr, $max: expr), +) => (
+    $(
+        impl IOperationReduce<$t> for Function {
+            fn compute(&self, x: &MemoryType, result: &mut MemoryType, op: ReduceOp) -> Result<(), Error> {
+                let x_slice = try!(x.as_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `x`.")))).as_slice::<$t>();
+                let mut r_slice = try!(result.as_mut_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `result`.")))).as_mut_slice::<$t>();
+                try!(validate::scalar(("result", r_slice.len())));
+                // Starts every fold with the identity element of the operation.
+                r_slice[0] = match op {
+                    ReduceOp::Sum => x_slice.iter().fold(0 as $t, |acc, xi| acc + *xi),
+                    ReduceOp::Max => x_slice.iter().fold($min, |acc, xi| if *xi > acc { *xi } else { acc }),
+                    ReduceOp::Min => x_slice.iter().fold($max, |acc, xi| if *xi < acc { *xi } else { acc }),
+                    ReduceOp::Product => x_slice.iter().fold(1 as $t, |acc, xi| acc * *xi),
+                };
+                Ok(())
+            }
+        }
+    )+
+));
+
+macro_rules! impl_map(($($t: ident), +) => (
+    $(
+        impl IOperationMap<$t> for Function {
+            fn compute(&self, x: &MemoryType, y: &mut MemoryType, func: UnaryFn) -> Result<(), Error> {
+                let x_slice = try!(x.as_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `x`.")))).as_slice::<$t>();
+                let y_slice = try!(y.as_mut_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `y`.")))).as_mut_slice::<$t>();
+                try!(validate::same_len(("x", x_slice.len()), ("y", y_slice.len())));
+                for (yi, xi) in y_slice.iter_mut().zip(x_slice.iter()) {
+                    *yi = match func {
+                        UnaryFn::Exp => xi.exp(),
+                        UnaryFn::Log => xi.ln(),
+                        UnaryFn::Sqrt => xi.sqrt(),
+                        UnaryFn::Abs => xi.abs(),
+                        UnaryFn::Sigmoid => 1.0 / (1.0 + (-xi).exp()),
+                        UnaryFn::Tanh => xi.tanh(),
+                        UnaryFn::Relu => xi.max(0.0),
+                    };
+                }
+                Ok(())
+            }
+        }
+    )+
+));
+
+macro_rules! impl_elementwise(($($t: ident), +) => (
+    $(
+        impl IOperationElementwise<$t> for Function {
+            fn compute(&self, a: &MemoryType, b: &MemoryType, c: &mut MemoryType, op: BinaryOp) -> Result<(), Error> {
+                let a_slice = try!(a.as_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `a`.")))).as_slice::<$t>();
+                let b_slice = try!(b.as_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `b`.")))).as_slice::<$t>();
+                let c_slice = try!(c.as_mut_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `c`.")))).as_mut_slice::<$t>();
+                try!(validate::same_len(("a", a_slice.len()), ("b", b_slice.len()))
+                    .and_then(|_| validate::same_len(("a", a_slice.len()), ("c", c_slice.len()))));
+                for ((ci, ai), bi) in c_slice.iter_mut().zip(a_slice.iter()).zip(b_slice.iter()) {
+                    *ci = match op {
+                        BinaryOp::Add => *ai + *bi,
+                        BinaryOp::Sub => *ai - *bi,
+                        BinaryOp::Mul => *ai * *bi,
+                        BinaryOp::Div => *ai / *bi,
+                    };
+                }
+                Ok(())
+            }
+        }
+    )+
+));
+
+impl_binary!(f32, f64, i32);
 impl_asum!(f32, f64);
+impl_iamax!(f32, f64, i32);
 impl_axpy!(f32, f64);
 impl_copy!(f32, f64);
 impl_dot!(f32, f64);
 impl_nrm2!(f32, f64);
 impl_scale!(f32, f64);
 impl_swap!(f32, f64);
+impl_gemv!(f32, f64, i32);
+impl_gemm!(f32, f64, i32);
+impl_gemm_batched!(f32, f64, i32);
+impl_reduce!(f32, ::std::f32::NEG_INFINITY, ::std::f32::INFINITY,
+             f64, ::std::f64::NEG_INFINITY, ::std::f64::INFINITY,
+             i32, ::std::i32::MIN, ::std::i32::MAX);
+impl_map!(f32, f64);
+impl_elementwise!(f32, f64);
+
+// The integer operations are not covered by BLAS, so they are computed element-wise.
+
+impl IOperationAsum<i32> for Function {
+    fn compute(&self, x: &MemoryType, result: &mut MemoryType) -> Result<(), Error> {
+        let x_slice = try!(x.as_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `x`.")))).as_slice::<i32>();
+        let mut r_slice = try!(result.as_mut_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `result`.")))).as_mut_slice::<i32>();
+        try!(validate::scalar(("result", r_slice.len())));
+        r_slice[0] = x_slice.iter().fold(0, |sum, xi| sum + xi.abs());
+        Ok(())
+    }
+}
+
+impl IOperationAxpy<i32> for Function {
+    fn compute(&self, a: &MemoryType, x: &MemoryType, y: &mut MemoryType) -> Result<(), Error> {
+        let a_slice = try!(a.as_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `a`.")))).as_slice::<i32>();
+        let x_slice = try!(x.as_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `x`.")))).as_slice::<i32>();
+        let y_slice = try!(y.as_mut_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `y`.")))).as_mut_slice::<i32>();
+        try!(validate::same_len(("x", x_slice.len()), ("y", y_slice.len())));
+        try!(validate::scalar(("a", a_slice.len())));
+        let a_val = a_slice[0];
+        for (yi, xi) in y_slice.iter_mut().zip(x_slice) {
+            *yi = a_val * *xi + *yi;
+        }
+        Ok(())
+    }
+}
+
+impl IOperationCopy<i32> for Function {
+    fn compute(&self, x: &MemoryType, y: &mut MemoryType) -> Result<(), Error> {
+        let x_slice = try!(x.as_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `x`.")))).as_slice::<i32>();
+        let y_slice = try!(y.as_mut_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `y`.")))).as_mut_slice::<i32>();
+        try!(validate::at_least(("x", x_slice.len()), ("y", y_slice.len())));
+        y_slice[..x_slice.len()].copy_from_slice(x_slice);
+        Ok(())
+    }
+}
+
+impl IOperationDot<i32> for Function {
+    fn compute(&self, x: &MemoryType, y: &MemoryType, result: &mut MemoryType) -> Result<(), Error> {
+        let x_slice = try!(x.as_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `x`.")))).as_slice::<i32>();
+        let y_slice = try!(y.as_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `y`.")))).as_slice::<i32>();
+        let mut r_slice = try!(result.as_mut_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `result`.")))).as_mut_slice::<i32>();
+        try!(validate::scalar(("result", r_slice.len())));
+        try!(validate::same_len(("x", x_slice.len()), ("y", y_slice.len())));
+        r_slice[0] = x_slice.iter().zip(y_slice).fold(0, |sum, (xi, yi)| sum + xi * yi);
+        Ok(())
+    }
+}
+
+impl IOperationNrm2<i32> for Function {
+    fn compute(&self, _x: &MemoryType, _result: &mut MemoryType) -> Result<(), Error> {
+        Err(Error::InvalidOperation(format!("The euclidean length of an integer vector is no integer; use f32 or f64 for nrm2.")))
+    }
+}
+
+impl IOperationScale<i32> for Function {
+    fn compute(&self, a: &MemoryType, x: &mut MemoryType) -> Result<(), Error> {
+        let a_slice = try!(a.as_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `a`.")))).as_slice::<i32>();
+        let x_slice = try!(x.as_mut_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `x`.")))).as_mut_slice::<i32>();
+        try!(validate::scalar(("a", a_slice.len())));
+        let a_val = a_slice[0];
+        for xi in x_slice.iter_mut() {
+            *xi = a_val * *xi;
+        }
+        Ok(())
+    }
+}
+
+impl IOperationSwap<i32> for Function {
+    fn compute(&self, x: &mut MemoryType, y: &mut MemoryType) -> Result<(), Error> {
+        let x_slice = try!(x.as_mut_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `x`.")))).as_mut_slice::<i32>();
+        let y_slice = try!(y.as_mut_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `y`.")))).as_mut_slice::<i32>();
+        try!(validate::same_len(("x", x_slice.len()), ("y", y_slice.len())));
+        for (xi, yi) in x_slice.iter_mut().zip(y_slice.iter_mut()) {
+            ::std::mem::swap(xi, yi);
+        }
+        Ok(())
+    }
+}
+
+impl IOperationMap<i32> for Function {
+    fn compute(&self, x: &MemoryType, y: &mut MemoryType, func: UnaryFn) -> Result<(), Error> {
+        let x_slice = try!(x.as_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `x`.")))).as_slice::<i32>();
+        let y_slice = try!(y.as_mut_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `y`.")))).as_mut_slice::<i32>();
+        try!(validate::same_len(("x", x_slice.len()), ("y", y_slice.len())));
+        match func {
+            UnaryFn::Abs => for (yi, xi) in y_slice.iter_mut().zip(x_slice.iter()) { *yi = xi.abs() },
+            UnaryFn::Relu => for (yi, xi) in y_slice.iter_mut().zip(x_slice.iter()) { *yi = if *xi > 0 { *xi } else { 0 } },
+            _ => return Err(Error::InvalidOperation(format!("{:?} is only supported for floating point vectors.", func))),
+        }
+        Ok(())
+    }
+}
+
+impl IOperationElementwise<i32> for Function {
+    fn compute(&self, a: &MemoryType, b: &MemoryType, c: &mut MemoryType, op: BinaryOp) -> Result<(), Error> {
+        let a_slice = try!(a.as_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `a`.")))).as_slice::<i32>();
+        let b_slice = try!(b.as_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `b`.")))).as_slice::<i32>();
+        let c_slice = try!(c.as_mut_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `c`.")))).as_mut_slice::<i32>();
+        try!(validate::same_len(("a", a_slice.len()), ("b", b_slice.len()))
+            .and_then(|_| validate::same_len(("a", a_slice.len()), ("c", c_slice.len()))));
+        // Integers have no representation for infinity, so a division by zero is rejected.
+        if op == BinaryOp::Div && b_slice.iter().any(|bi| *bi == 0) {
+            return Err(Error::InvalidOperation(format!("Unable to divide integers by zero.")))
+        }
+        for ((ci, ai), bi) in c_slice.iter_mut().zip(a_slice.iter()).zip(b_slice.iter()) {
+            *ci = match op {
+                BinaryOp::Add => *ai + *bi,
+                BinaryOp::Sub => *ai - *bi,
+                BinaryOp::Mul => *ai * *bi,
+                BinaryOp::Div => *ai / *bi,
+            };
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+//! Provides half precision operations for a Native backend.
+//!
+//! The host has no half precision arithmetic, so every element is converted to `f32` for the
+//! computation and rounded back to `f16`, when it is stored.
+
+use half::f16;
+use memory::MemoryType;
+use libraries::blas::Error;
+use validate;
+
+/// Computes `a * x + y` and saves the result into `y`.
+pub fn axpy(a: &MemoryType, x: &MemoryType, y: &mut MemoryType) -> Result<(), Error> {
+    let a_slice = try!(a.as_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `a`.")))).as_slice::<f16>();
+    let x_slice = try!(x.as_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `x`.")))).as_slice::<f16>();
+    let y_slice = try!(y.as_mut_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `y`.")))).as_mut_slice::<f16>();
+    try!(validate::same_len(("x", x_slice.len()), ("y", y_slice.len())));
+    try!(validate::scalar(("a", a_slice.len())));
+    let a_val = a_slice[0].to_f32();
+    for (y_val, x_val) in y_slice.iter_mut().zip(x_slice) {
+        *y_val = f16::from_f32(a_val * x_val.to_f32() + y_val.to_f32());
+    }
+    Ok(())
+}
+
+/// Copies the content of `x` into the beginning of `y`.
+pub fn copy(x: &MemoryType, y: &mut MemoryType) -> Result<(), Error> {
+    let x_slice = try!(x.as_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `x`.")))).as_slice::<f16>();
+    let y_slice = try!(y.as_mut_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `y`.")))).as_mut_slice::<f16>();
+    try!(validate::at_least(("x", x_slice.len()), ("y", y_slice.len())));
+    y_slice[..x_slice.len()].copy_from_slice(x_slice);
+    Ok(())
+}
+
+/// Computes `a * x` and saves the result into `x`.
+pub fn scale(a: &MemoryType, x: &mut MemoryType) -> Result<(), Error> {
+    let a_slice = try!(a.as_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `a`.")))).as_slice::<f16>();
+    let x_slice = try!(x.as_mut_native().ok_or(Error::InvalidArgument(format!("Unable to receive native memory for `x`.")))).as_mut_slice::<f16>();
+    try!(validate::scalar(("a", a_slice.len())));
+    let a_val = a_slice[0].to_f32();
+    for x_val in x_slice.iter_mut() {
+        *x_val = f16::from_f32(a_val * x_val.to_f32());
+    }
+    Ok(())
+}
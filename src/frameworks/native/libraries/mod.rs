@@ -1,3 +1,9 @@
 //! Provides support for various libraries for a Native backend.
 
+#[cfg(feature = "native")]
 mod blas;
+mod parallel;
+#[cfg(feature = "f16")]
+pub mod half;
+
+pub use self::parallel::{DEFAULT_THRESHOLD, threshold, set_threshold};
@@ -0,0 +1,163 @@
+//! Provides the multi-threaded execution of Native operations.
+//!
+//! Vectors, which are at least as long as the [threshold][threshold], get split into one chunk
+//! per CPU core and every chunk is processed on its own thread. Shorter vectors are processed on
+//! the calling thread, as spawning threads would cost more than it saves.
+//! [threshold]: ./fn.threshold.html
+
+use std::mem;
+use std::sync::atomic::{AtomicUsize, Ordering, ATOMIC_USIZE_INIT};
+use std::thread;
+
+/// Default length of a vector, from which on operations are split across threads.
+pub const DEFAULT_THRESHOLD: usize = 1 << 16;
+
+/// The tuned threshold. Zero means, that the `DEFAULT_THRESHOLD` is used.
+static THRESHOLD: AtomicUsize = ATOMIC_USIZE_INIT;
+
+/// Returns the length of a vector, from which on operations are split across threads.
+pub fn threshold() -> usize {
+    match THRESHOLD.load(Ordering::Relaxed) {
+        0 => DEFAULT_THRESHOLD,
+        threshold => threshold,
+    }
+}
+
+/// Sets the length of a vector, from which on operations are split across threads.
+///
+/// Use `usize::MAX` to always stay single-threaded and `0` to restore the default.
+pub fn set_threshold(threshold: usize) {
+    THRESHOLD.store(threshold, Ordering::Relaxed);
+}
+
+/// Returns the number of threads, an operation on a vector of length `len` is split across.
+pub fn threads(len: usize) -> usize {
+    if len < threshold() {
+        1
+    } else {
+        host_threads()
+    }
+}
+
+/// Computes `f` for every chunk of `x` and returns the results of the chunks in order.
+///
+/// The chunks are computed on their own threads, if `x` reaches the threshold.
+pub fn map_chunks<T, R, F>(x: &[T], f: F) -> Vec<R>
+    where T: Sync, R: Send + 'static, F: Fn(&[T]) -> R + Sync
+{
+    let len = chunk_len(x.len());
+    if len >= x.len() {
+        return vec![f(x)]
+    }
+    let f = &f;
+    scope(x.chunks(len).map(|x| Box::new(move || f(x)) as Task<R>).collect())
+}
+
+/// Computes `f` for every pair of chunks of `x` and `y` and returns the results in order.
+///
+/// Both vectors are split at the same indices, so `y` needs to be as long as `x`.
+pub fn map_chunks_zip<T, R, F>(x: &[T], y: &[T], f: F) -> Vec<R>
+    where T: Sync, R: Send + 'static, F: Fn(&[T], &[T]) -> R + Sync
+{
+    let len = chunk_len(x.len());
+    if len >= x.len() {
+        return vec![f(x, y)]
+    }
+    let f = &f;
+    scope(x.chunks(len).zip(y.chunks(len)).map(|(x, y)| Box::new(move || f(x, y)) as Task<R>).collect())
+}
+
+/// Applies `f` to every chunk of `x`.
+pub fn for_chunks_mut<T, F>(x: &mut [T], f: F)
+    where T: Send, F: Fn(&mut [T]) + Sync
+{
+    let len = chunk_len(x.len());
+    if len >= x.len() {
+        return f(x)
+    }
+    let f = &f;
+    scope(x.chunks_mut(len).map(|x| Box::new(move || f(x)) as Task<()>).collect());
+}
+
+/// Applies `f` to every pair of chunks of `x` and `y`.
+///
+/// Both vectors are split at the same indices, so `y` needs to be as long as `x`.
+pub fn for_chunks_zip_mut<T, F>(x: &[T], y: &mut [T], f: F)
+    where T: Send + Sync, F: Fn(&[T], &mut [T]) + Sync
+{
+    let len = chunk_len(x.len());
+    if len >= x.len() {
+        return f(x, y)
+    }
+    let f = &f;
+    scope(x.chunks(len).zip(y.chunks_mut(len)).map(|(x, y)| Box::new(move || f(x, y)) as Task<()>).collect());
+}
+
+/// Returns the length of the chunks, a vector of length `len` is split into.
+fn chunk_len(len: usize) -> usize {
+    let threads = ::std::cmp::min(threads(len), len);
+    if threads <= 1 { len } else { (len + threads - 1) / threads }
+}
+
+/// A chunk of work, which may borrow from the caller of `scope`.
+type Task<'a, R> = Box<FnMut() -> R + Send + 'a>;
+
+/// Joins the threads, which have not been joined yet, when dropped.
+///
+/// Keeps the memory borrowed by the tasks alive, even if the calling thread panics.
+struct JoinGuard<R> {
+    handles: Vec<Option<thread::JoinHandle<R>>>,
+}
+
+impl<R> Drop for JoinGuard<R> {
+    fn drop(&mut self) {
+        for handle in self.handles.iter_mut() {
+            if let Some(handle) = handle.take() {
+                let _ = handle.join();
+            }
+        }
+    }
+}
+
+/// Runs the first task on the calling thread and every other task on its own thread.
+///
+/// Returns the results of the tasks in order, after all threads have been joined. Panics, if
+/// one of the tasks panicked.
+fn scope<'a, R>(tasks: Vec<Task<'a, R>>) -> Vec<R> where R: Send + 'static {
+    let mut tasks = tasks.into_iter();
+    let mut first = match tasks.next() {
+        Some(task) => task,
+        None => return Vec::new(),
+    };
+    let mut guard = JoinGuard { handles: Vec::new() };
+    for task in tasks {
+        // The guard joins the thread before `'a` ends, so the task never outlives its borrows.
+        let mut task: Task<'static, R> = unsafe { mem::transmute(task) };
+        guard.handles.push(Some(thread::spawn(move || task())));
+    }
+    let mut results = vec![first()];
+    let joined: Vec<thread::Result<R>> = guard.handles.iter_mut()
+        .filter_map(|handle| handle.take())
+        .map(|handle| handle.join())
+        .collect();
+    for result in joined {
+        match result {
+            Ok(value) => results.push(value),
+            Err(err) => panic!(err),
+        }
+    }
+    results
+}
+
+#[cfg(unix)]
+/// Returns the number of CPU cores of the host.
+fn host_threads() -> usize {
+    let cores = unsafe { ::libc::sysconf(::libc::_SC_NPROCESSORS_ONLN) };
+    if cores > 0 { cores as usize } else { 1 }
+}
+
+#[cfg(not(unix))]
+/// Returns the number of CPU cores of the host.
+fn host_threads() -> usize {
+    1
+}
@@ -4,13 +4,16 @@
 //!
 //!
 
-use framework::IFramework;
+use framework::{IFramework, HardwareIter};
 use hardware::{HardwareType, IHardware};
 use device::DeviceType;
 use self::hardware::Hardware;
 pub use self::device::Cpu;
+#[cfg(feature = "test-util")]
+pub use self::device::Failure;
 pub use self::function::Function;
 pub use self::binary::Binary;
+pub use self::error::Error;
 
 pub mod device;
 pub mod flatbox;
@@ -18,6 +21,7 @@ pub mod hardware;
 pub mod function;
 pub mod libraries;
 pub mod binary;
+pub mod error;
 
 #[derive(Debug, Clone)]
 /// Provides the Native framework.
@@ -42,14 +46,12 @@ impl IFramework for Native {
 
     const ID: &'static str = "NATIVE";
 
-    fn new() -> Native {
-        match Native::load_hardwares() {
-            Ok(hardwares) => Native {
-                hardwares: hardwares,
-                binary: Binary::new()
-            },
-            Err(err) => panic!(err)
-        }
+    fn try_new() -> Result<Native, ::framework::Error> {
+        let hardwares = try!(Native::load_hardwares());
+        Ok(Native {
+            hardwares: hardwares,
+            binary: Binary::new()
+        })
     }
 
     fn load_hardwares() -> Result<Vec<Hardware>, ::framework::Error> {
@@ -57,6 +59,7 @@ impl IFramework for Native {
             .set_name(Some(String::from("Host CPU")))
             .set_hardware_type(Some(HardwareType::CPU))
             .set_compute_units(Some(1))
+            .set_memory(host_memory())
             .build();
         Ok(vec!(cpu))
     }
@@ -65,11 +68,37 @@ impl IFramework for Native {
         self.hardwares.clone()
     }
 
+    fn hardware_iter(&self) -> HardwareIter<Hardware> {
+        self.hardwares.iter().cloned()
+    }
+
     fn binary(&self) -> Binary {
         self.binary.clone()
     }
 
+    fn new_binary(&self, _device: &DeviceType) -> Result<Binary, ::framework::Error> {
+        Ok(Binary::new())
+    }
+
     fn new_device(&self, devices: Vec<Hardware>) -> Result<DeviceType, ::framework::Error> {
         Ok(DeviceType::Native(Cpu::new(devices.to_vec())))
     }
 }
+
+#[cfg(unix)]
+/// Returns the physical memory of the host in bytes.
+fn host_memory() -> Option<usize> {
+    let pages = unsafe { ::libc::sysconf(::libc::_SC_PHYS_PAGES) };
+    let page_size = unsafe { ::libc::sysconf(::libc::_SC_PAGESIZE) };
+    if pages > 0 && page_size > 0 {
+        Some(pages as usize * page_size as usize)
+    } else {
+        None
+    }
+}
+
+#[cfg(not(unix))]
+/// Returns the physical memory of the host in bytes.
+fn host_memory() -> Option<usize> {
+    None
+}
@@ -3,7 +3,7 @@
 //! At Collenchyma device can be understood as a synonym to OpenCL's context.
 
 use libc;
-use frameworks::opencl::{API, Error, Device};
+use frameworks::opencl::{API, Error, Context, Device};
 use super::types as cl;
 use super::ffi::*;
 
@@ -28,6 +28,14 @@ impl API {
         )
     }
 
+    /// Releases the OpenCL context.
+    ///
+    /// The context is destroyed, once all the objects, which were created in it, have been
+    /// released as well.
+    pub fn release_context(context: &Context) -> Result<(), Error> {
+        Ok(try!(unsafe { API::ffi_release_context(context.id_c()) }))
+    }
+
     unsafe fn ffi_create_context(
         properties: *const cl::context_properties,
         num_devices: cl::uint,
@@ -46,7 +54,17 @@ impl API {
             errcode if errcode == cl::Status::DEVICE_NOT_AVAILABLE as i32 => Err(Error::DeviceNotAvailable(format!("a device in devices is currently not available even though the device was returned by clGetDeviceIDs."))),
             errcode if errcode == cl::Status::OUT_OF_RESOURCES as i32 => Err(Error::OutOfResources(format!("Failure to allocate resources on the device"))),
             errcode if errcode == cl::Status::OUT_OF_HOST_MEMORY as i32 => Err(Error::OutOfHostMemory(format!("Failure to allocate resources on the host"))),
-            _ => Err(Error::Other(format!("Unable to create context")))
+            errcode => Err(Error::from_failed_status(errcode, "clCreateContext"))
+        }
+    }
+
+    unsafe fn ffi_release_context(context: cl::context_id) -> Result<(), Error> {
+        match clReleaseContext(context) {
+            cl::Status::SUCCESS => Ok(()),
+            cl::Status::INVALID_CONTEXT => Err(Error::InvalidContext(format!("context: {:?} is not a valid context.", context))),
+            cl::Status::OUT_OF_RESOURCES => Err(Error::OutOfResources(format!("Failure to allocate resources on the device"))),
+            cl::Status::OUT_OF_HOST_MEMORY => Err(Error::OutOfHostMemory(format!("Failure to allocate resources on the host"))),
+            status => Err(Error::from_failed_status(status as i32, "clReleaseContext"))
         }
     }
 }
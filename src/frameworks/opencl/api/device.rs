@@ -24,6 +24,7 @@ impl API {
                             .load_name()
                             .load_device_type()
                             .load_compute_units()
+                            .load_memory()
                     }).collect()
                 )
             },
@@ -61,6 +62,38 @@ impl API {
         Ok(DeviceInfo::new(buf))
     }
 
+    /// Returns fully initialized sub-devices, partitioned from the provided device.
+    ///
+    /// `properties` is a zero terminated list of `cl_device_partition_property` values.
+    pub fn create_sub_devices(device: &Device, properties: &[cl::device_partition_property]) -> Result<Vec<Device>, Error> {
+        let mut num_devices = 0;
+
+        // load how many sub-devices the partition results in
+        try!(unsafe { API::ffi_create_sub_devices(device.id_c(), properties.as_ptr(), 0, ptr::null_mut(), &mut num_devices) });
+
+        // prepare sub-device id list
+        let mut ids: Vec<cl::device_id> = repeat(0 as cl::device_id).take(num_devices as usize).collect();
+
+        // create the sub-devices
+        try!(unsafe { API::ffi_create_sub_devices(device.id_c(), properties.as_ptr(), ids.len() as cl::uint, ids.as_mut_ptr(), ptr::null_mut()) });
+
+        Ok(ids.iter().map(|id| {
+            Device::from_sub_device_c(*id)
+                .load_name()
+                .load_device_type()
+                .load_compute_units()
+                .load_memory()
+        }).collect())
+    }
+
+    /// Releases the OpenCL sub-device.
+    ///
+    /// Gets called, when the last clone of a sub-device, created by `create_sub_devices`, goes
+    /// out of scope. Root devices are not reference counted and are never released.
+    pub fn release_device(device: &Device) -> Result<(), Error> {
+        Ok(try!(unsafe { API::ffi_release_device(device.id_c()) }))
+    }
+
     unsafe fn ffi_get_device_ids(
         platform: cl::platform_id,
         device_type: cl::device_type,
@@ -75,7 +108,7 @@ impl API {
             cl::Status::DEVICE_NOT_FOUND => Err(Error::DeviceNotFound(format!("no devices for `device type`: {} found", device_type))),
             cl::Status::OUT_OF_RESOURCES => Err(Error::OutOfResources(format!("Failure to allocate resources on the device"))),
             cl::Status::OUT_OF_HOST_MEMORY => Err(Error::OutOfHostMemory(format!("Failure to allocate resources on the host"))),
-            _ => Err(Error::Other(format!("Unable to get device ids")))
+            status => Err(Error::from_failed_status(status as i32, "clGetDeviceIDs"))
         }
     }
 
@@ -91,7 +124,36 @@ impl API {
             cl::Status::INVALID_VALUE => Err(Error::InvalidValue(format!("`param_name`: {} is not one of the supported values", param_name))),
             cl::Status::OUT_OF_RESOURCES => Err(Error::OutOfResources(format!("Failure to allocate resources on the device"))),
             cl::Status::OUT_OF_HOST_MEMORY => Err(Error::OutOfHostMemory(format!("Failure to allocate resources on the host"))),
-            _ => Err(Error::Other(format!("Unable to get device info string length")))
+            status => Err(Error::from_failed_status(status as i32, "clGetDeviceInfo"))
+        }
+    }
+
+    unsafe fn ffi_create_sub_devices(
+        in_device: cl::device_id,
+        properties: *const cl::device_partition_property,
+        num_devices: cl::uint,
+        out_devices: *mut cl::device_id,
+        num_devices_ret: *mut cl::uint
+    ) -> Result<(), Error> {
+        match clCreateSubDevices(in_device, properties, num_devices, out_devices, num_devices_ret) {
+            cl::Status::SUCCESS => Ok(()),
+            cl::Status::INVALID_DEVICE => Err(Error::InvalidDevice(format!("`in_device`: {:?} is not a valid device", in_device))),
+            cl::Status::INVALID_VALUE => Err(Error::InvalidValue(format!("`properties` are not valid or `out_devices` is too small"))),
+            cl::Status::DEVICE_PARTITION_FAILED => Err(Error::DevicePartitionFailed(format!("The partition is supported, but the device could not be further partitioned"))),
+            cl::Status::INVALID_DEVICE_PARTITION_COUNT => Err(Error::DevicePartitionFailed(format!("The partition would exceed the maximum number of sub-devices or compute units"))),
+            cl::Status::OUT_OF_RESOURCES => Err(Error::OutOfResources(format!("Failure to allocate resources on the device"))),
+            cl::Status::OUT_OF_HOST_MEMORY => Err(Error::OutOfHostMemory(format!("Failure to allocate resources on the host"))),
+            status => Err(Error::from_failed_status(status as i32, "clCreateSubDevices"))
+        }
+    }
+
+    unsafe fn ffi_release_device(device: cl::device_id) -> Result<(), Error> {
+        match clReleaseDevice(device) {
+            cl::Status::SUCCESS => Ok(()),
+            cl::Status::INVALID_DEVICE => Err(Error::InvalidDevice(format!("`device`: {:?} is not a valid sub-device", device))),
+            cl::Status::OUT_OF_RESOURCES => Err(Error::OutOfResources(format!("Failure to allocate resources on the device"))),
+            cl::Status::OUT_OF_HOST_MEMORY => Err(Error::OutOfHostMemory(format!("Failure to allocate resources on the host"))),
+            status => Err(Error::from_failed_status(status as i32, "clReleaseDevice"))
         }
     }
 }
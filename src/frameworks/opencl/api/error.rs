@@ -1,5 +1,7 @@
 //! Provides Rust Errors for OpenCL's status.
 
+use super::types as cl;
+use enum_primitive::FromPrimitive;
 use std::{fmt, error};
 
 #[derive(Debug)]
@@ -19,6 +21,8 @@ pub enum Error {
     InvalidCommandQueue(String),
     /// Failure with provided event list.
     InvalidEventWaitList(String),
+    /// Failure with provided event.
+    InvalidEvent(String),
     /// Failure with provided param(s).
     InvalidValue(String),
     /// Failure with provided property param.
@@ -43,39 +47,122 @@ pub enum Error {
     OutOfResources(String),
     /// Failure to allocate resources on the host.
     OutOfHostMemory(String),
+    /// Failure to build the program executable.
+    ///
+    /// Contains the build log of the failing devices.
+    BuildProgramFailure(String),
+    /// Failure with compiler availability.
+    CompilerNotAvailable(String),
+    /// Failure at building a program with invalid build options.
+    ///
+    /// Contains the build log of the devices.
+    InvalidBuildOptions(String),
+    /// Failure with provided kernel name.
+    InvalidKernelName(String),
+    /// Failure with kernel argument values, which have not been specified.
+    InvalidKernelArgs(String),
+    /// Failure with provided work-group size.
+    InvalidWorkGroupSize(String),
+    /// Failure at partitioning a device into sub-devices.
+    DevicePartitionFailed(String),
     /// Failure not closer defined.
     Other(String),
 }
 
-impl fmt::Display for Error {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+impl Error {
+    /// Maps the OpenCL status `code` (`cl_int`) of a failed call to an Error.
+    ///
+    /// Returns `None` for `CL_SUCCESS`. The `context`, e.g. the name of the OpenCL call, becomes
+    /// part of the error message. Status codes without a matching variant result in `Other`,
+    /// which contains the numeric status code.
+    pub fn from_status(code: i32, context: &str) -> Option<Error> {
+        let message = format!("{} failed.", context);
+        match cl::Status::from_i32(code) {
+            Some(cl::Status::SUCCESS) => None,
+            Some(cl::Status::INVALID_PLATFORM) => Some(Error::InvalidPlatform(message)),
+            Some(cl::Status::INVALID_DEVICE) => Some(Error::InvalidDevice(message)),
+            Some(cl::Status::INVALID_DEVICE_TYPE) => Some(Error::InvalidDeviceType(message)),
+            Some(cl::Status::INVALID_CONTEXT) => Some(Error::InvalidContext(message)),
+            Some(cl::Status::INVALID_MEM_OBJECT) => Some(Error::InvalidMemObject(message)),
+            Some(cl::Status::INVALID_COMMAND_QUEUE) => Some(Error::InvalidCommandQueue(message)),
+            Some(cl::Status::INVALID_EVENT_WAIT_LIST) => Some(Error::InvalidEventWaitList(message)),
+            Some(cl::Status::INVALID_EVENT) => Some(Error::InvalidEvent(message)),
+            Some(cl::Status::INVALID_VALUE) => Some(Error::InvalidValue(message)),
+            Some(cl::Status::INVALID_PROPERTY) => Some(Error::InvalidProperty(message)),
+            Some(cl::Status::INVALID_OPERATION) => Some(Error::InvalidOperation(message)),
+            Some(cl::Status::INVALID_BUFFER_SIZE) => Some(Error::InvalidBufferSize(message)),
+            Some(cl::Status::INVALID_HOST_PTR) => Some(Error::InvalidHostPtr(message)),
+            Some(cl::Status::DEVICE_NOT_FOUND) => Some(Error::DeviceNotFound(message)),
+            Some(cl::Status::DEVICE_NOT_AVAILABLE) => Some(Error::DeviceNotAvailable(message)),
+            Some(cl::Status::MEM_OBJECT_ALLOCATION_FAILURE) => Some(Error::MemObjectAllocationFailure(message)),
+            Some(cl::Status::MISALIGNED_SUB_BUFFER_OFFSET) => Some(Error::MisalignedSubBufferOffset(message)),
+            Some(cl::Status::EXEC_STATUS_ERROR_FOR_EVENTS_IN_WAIT_LIST) => Some(Error::ExecStatusErrorForEventsInWaitList(message)),
+            Some(cl::Status::OUT_OF_RESOURCES) => Some(Error::OutOfResources(message)),
+            Some(cl::Status::OUT_OF_HOST_MEMORY) => Some(Error::OutOfHostMemory(message)),
+            Some(cl::Status::BUILD_PROGRAM_FAILURE) => Some(Error::BuildProgramFailure(message)),
+            Some(cl::Status::COMPILER_NOT_AVAILABLE) => Some(Error::CompilerNotAvailable(message)),
+            Some(cl::Status::INVALID_BUILD_OPTIONS) => Some(Error::InvalidBuildOptions(message)),
+            Some(cl::Status::INVALID_KERNEL_NAME) => Some(Error::InvalidKernelName(message)),
+            Some(cl::Status::INVALID_KERNEL_ARGS) => Some(Error::InvalidKernelArgs(message)),
+            Some(cl::Status::INVALID_WORK_GROUP_SIZE) => Some(Error::InvalidWorkGroupSize(message)),
+            Some(cl::Status::DEVICE_PARTITION_FAILED) => Some(Error::DevicePartitionFailed(message)),
+            Some(status) => Some(Error::Other(format!("{} failed with CL_{:?} ({}).", context, status, code))),
+            None => Some(Error::Other(format!("{} failed with unknown status {}.", context, code))),
+        }
+    }
+
+    /// Maps the status `code` of a failed call to an Error like `from_status`.
+    ///
+    /// Used by the catch-all arms of the status matchers, which have already matched
+    /// `CL_SUCCESS`.
+    pub fn from_failed_status(code: i32, context: &str) -> Error {
+        match Error::from_status(code, context) {
+            Some(err) => err,
+            None => Error::Other(format!("{} failed, but reported CL_SUCCESS.", context)),
+        }
+    }
+
+    fn status(&self) -> Option<cl::Status> {
         match *self {
-            Error::InvalidPlatform(ref err) => write!(f, "{:?}", err),
-            Error::InvalidDevice(ref err) => write!(f, "{:?}", err),
-            Error::InvalidDeviceType(ref err) => write!(f, "{:?}", err),
-            Error::InvalidContext(ref err) => write!(f, "{:?}", err),
-            Error::InvalidMemObject(ref err) => write!(f, "{:?}", err),
-            Error::InvalidCommandQueue(ref err) => write!(f, "{:?}", err),
-            Error::InvalidEventWaitList(ref err) => write!(f, "{:?}", err),
-            Error::InvalidValue(ref err) => write!(f, "{:?}", err),
-            Error::InvalidProperty(ref err) => write!(f, "{:?}", err),
-            Error::InvalidOperation(ref err) => write!(f, "{:?}", err),
-            Error::InvalidBufferSize(ref err) => write!(f, "{:?}", err),
-            Error::InvalidHostPtr(ref err) => write!(f, "{:?}", err),
-            Error::DeviceNotFound(ref err) => write!(f, "{:?}", err),
-            Error::DeviceNotAvailable(ref err) => write!(f, "{:?}", err),
-            Error::MemObjectAllocationFailure(ref err) => write!(f, "{:?}", err),
-            Error::MisalignedSubBufferOffset(ref err) => write!(f, "{:?}", err),
-            Error::ExecStatusErrorForEventsInWaitList(ref err) => write!(f, "{:?}", err),
-            Error::OutOfResources(ref err) => write!(f, "{:?}", err),
-            Error::OutOfHostMemory(ref err) => write!(f, "{:?}", err),
-            Error::Other(ref err) => write!(f, "{:?}", err),
+            Error::InvalidPlatform(_) => Some(cl::Status::INVALID_PLATFORM),
+            Error::InvalidDevice(_) => Some(cl::Status::INVALID_DEVICE),
+            Error::InvalidDeviceType(_) => Some(cl::Status::INVALID_DEVICE_TYPE),
+            Error::InvalidContext(_) => Some(cl::Status::INVALID_CONTEXT),
+            Error::InvalidMemObject(_) => Some(cl::Status::INVALID_MEM_OBJECT),
+            Error::InvalidCommandQueue(_) => Some(cl::Status::INVALID_COMMAND_QUEUE),
+            Error::InvalidEventWaitList(_) => Some(cl::Status::INVALID_EVENT_WAIT_LIST),
+            Error::InvalidEvent(_) => Some(cl::Status::INVALID_EVENT),
+            Error::InvalidValue(_) => Some(cl::Status::INVALID_VALUE),
+            Error::InvalidProperty(_) => Some(cl::Status::INVALID_PROPERTY),
+            Error::InvalidOperation(_) => Some(cl::Status::INVALID_OPERATION),
+            Error::InvalidBufferSize(_) => Some(cl::Status::INVALID_BUFFER_SIZE),
+            Error::InvalidHostPtr(_) => Some(cl::Status::INVALID_HOST_PTR),
+            Error::DeviceNotFound(_) => Some(cl::Status::DEVICE_NOT_FOUND),
+            Error::DeviceNotAvailable(_) => Some(cl::Status::DEVICE_NOT_AVAILABLE),
+            Error::MemObjectAllocationFailure(_) => Some(cl::Status::MEM_OBJECT_ALLOCATION_FAILURE),
+            Error::MisalignedSubBufferOffset(_) => Some(cl::Status::MISALIGNED_SUB_BUFFER_OFFSET),
+            Error::ExecStatusErrorForEventsInWaitList(_) => Some(cl::Status::EXEC_STATUS_ERROR_FOR_EVENTS_IN_WAIT_LIST),
+            Error::OutOfResources(_) => Some(cl::Status::OUT_OF_RESOURCES),
+            Error::OutOfHostMemory(_) => Some(cl::Status::OUT_OF_HOST_MEMORY),
+            Error::BuildProgramFailure(_) => Some(cl::Status::BUILD_PROGRAM_FAILURE),
+            Error::CompilerNotAvailable(_) => Some(cl::Status::COMPILER_NOT_AVAILABLE),
+            Error::InvalidBuildOptions(_) => Some(cl::Status::INVALID_BUILD_OPTIONS),
+            Error::InvalidKernelName(_) => Some(cl::Status::INVALID_KERNEL_NAME),
+            Error::InvalidKernelArgs(_) => Some(cl::Status::INVALID_KERNEL_ARGS),
+            Error::InvalidWorkGroupSize(_) => Some(cl::Status::INVALID_WORK_GROUP_SIZE),
+            Error::DevicePartitionFailed(_) => Some(cl::Status::DEVICE_PARTITION_FAILED),
+            Error::Other(_) => None,
         }
     }
-}
 
-impl error::Error for Error {
-    fn description(&self) -> &str {
+    /// Returns the numeric OpenCL status code (`cl_int`) of the error.
+    ///
+    /// Returns `None` for `Other`, as its status is not mapped to a variant.
+    pub fn code(&self) -> Option<i32> {
+        self.status().map(|status| status as i32)
+    }
+
+    fn message(&self) -> &str {
         match *self {
             Error::InvalidPlatform(ref err) => err,
             Error::InvalidDevice(ref err) => err,
@@ -84,6 +171,7 @@ impl error::Error for Error {
             Error::InvalidMemObject(ref err) => err,
             Error::InvalidCommandQueue(ref err) => err,
             Error::InvalidEventWaitList(ref err) => err,
+            Error::InvalidEvent(ref err) => err,
             Error::InvalidValue(ref err) => err,
             Error::InvalidProperty(ref err) => err,
             Error::InvalidOperation(ref err) => err,
@@ -96,9 +184,31 @@ impl error::Error for Error {
             Error::ExecStatusErrorForEventsInWaitList(ref err) => err,
             Error::OutOfResources(ref err) => err,
             Error::OutOfHostMemory(ref err) => err,
+            Error::BuildProgramFailure(ref err) => err,
+            Error::CompilerNotAvailable(ref err) => err,
+            Error::InvalidBuildOptions(ref err) => err,
+            Error::InvalidKernelName(ref err) => err,
+            Error::InvalidKernelArgs(ref err) => err,
+            Error::InvalidWorkGroupSize(ref err) => err,
+            Error::DevicePartitionFailed(ref err) => err,
             Error::Other(ref err) => err,
         }
     }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.status() {
+            Some(status) => write!(f, "CL_{:?} ({}): {}", status, status as i32, self.message()),
+            None => write!(f, "{:?}", self.message()),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn description(&self) -> &str {
+        self.message()
+    }
 
     fn cause(&self) -> Option<&error::Error> {
         match *self {
@@ -109,6 +219,7 @@ impl error::Error for Error {
             Error::InvalidMemObject(_) => None,
             Error::InvalidCommandQueue(_) => None,
             Error::InvalidEventWaitList(_) => None,
+            Error::InvalidEvent(_) => None,
             Error::InvalidValue(_) => None,
             Error::InvalidProperty(_) => None,
             Error::InvalidOperation(_) => None,
@@ -121,6 +232,13 @@ impl error::Error for Error {
             Error::ExecStatusErrorForEventsInWaitList(_) => None,
             Error::OutOfResources(_) => None,
             Error::OutOfHostMemory(_) => None,
+            Error::BuildProgramFailure(_) => None,
+            Error::CompilerNotAvailable(_) => None,
+            Error::InvalidBuildOptions(_) => None,
+            Error::InvalidKernelName(_) => None,
+            Error::InvalidKernelArgs(_) => None,
+            Error::InvalidWorkGroupSize(_) => None,
+            Error::DevicePartitionFailed(_) => None,
             Error::Other(_) => None,
         }
     }
@@ -0,0 +1,85 @@
+//! Provides the OpenCL API with its event functionality.
+
+use frameworks::opencl::{API, Error, Event};
+use super::types as cl;
+use super::ffi::*;
+use libc;
+use std::{mem, ptr};
+
+impl API {
+    /// Blocks until the commands of all the `events` have been completed.
+    pub fn wait_for_events(events: &[&Event]) -> Result<(), Error> {
+        if events.is_empty() {
+            return Ok(())
+        }
+        let ids = API::event_ids(events);
+        Ok(try!(unsafe { API::ffi_wait_for_events(ids.len() as cl::uint, ids.as_ptr()) }))
+    }
+
+    /// Returns whether the command of the `event` has been completed.
+    ///
+    /// Does not block. Returns an error, if the command terminated abnormally.
+    pub fn event_complete(event: &Event) -> Result<bool, Error> {
+        let mut status: cl::int = 0;
+        try!(unsafe { API::ffi_get_event_info(event.id_c(), cl::CL_EVENT_COMMAND_EXECUTION_STATUS, mem::size_of::<cl::int>() as libc::size_t, &mut status as *mut cl::int as *mut libc::c_void, ptr::null_mut()) });
+        if status < 0 {
+            return Err(Error::ExecStatusErrorForEventsInWaitList(format!("The command of the event terminated abnormally with status {}.", status)))
+        }
+        Ok(status == cl::CL_COMPLETE as cl::int)
+    }
+
+    /// Releases the `event`.
+    pub fn release_event(event: &mut Event) -> Result<(), Error> {
+        Ok(try!(unsafe { API::ffi_release_event(event.id_c()) }))
+    }
+
+    /// Returns the C types of the `events`, which can be passed as an event wait list.
+    pub fn event_ids(events: &[&Event]) -> Vec<cl::event> {
+        events.iter().map(|event| event.id_c()).collect()
+    }
+
+    /// Returns a pointer to the event wait list `ids`, which is NULL for an empty list.
+    pub fn event_wait_list(ids: &[cl::event]) -> *const cl::event {
+        if ids.is_empty() { ptr::null() } else { ids.as_ptr() }
+    }
+
+    unsafe fn ffi_wait_for_events(num_events: cl::uint, event_list: *const cl::event) -> Result<(), Error> {
+        match clWaitForEvents(num_events, event_list) {
+            cl::Status::SUCCESS => Ok(()),
+            cl::Status::INVALID_VALUE => Err(Error::InvalidValue(format!("`num_events` is zero or `event_list` is NULL."))),
+            cl::Status::INVALID_CONTEXT => Err(Error::InvalidContext(format!("the events in `event_list` do not belong to the same context."))),
+            cl::Status::INVALID_EVENT => Err(Error::InvalidEvent(format!("the events in `event_list` are not valid event objects."))),
+            cl::Status::EXEC_STATUS_ERROR_FOR_EVENTS_IN_WAIT_LIST => Err(Error::ExecStatusErrorForEventsInWaitList(format!("the execution status of any of the events in `event_list` is a negative integer value."))),
+            cl::Status::OUT_OF_RESOURCES => Err(Error::OutOfResources(format!("Failure to allocate resources on the device"))),
+            cl::Status::OUT_OF_HOST_MEMORY => Err(Error::OutOfHostMemory(format!("Failure to allocate resources on the host"))),
+            status => Err(Error::from_failed_status(status as i32, "clWaitForEvents"))
+        }
+    }
+
+    unsafe fn ffi_get_event_info(
+        event: cl::event,
+        param_name: cl::event_info,
+        param_value_size: libc::size_t,
+        param_value: *mut libc::c_void,
+        param_value_size_ret: *mut libc::size_t
+    ) -> Result<(), Error> {
+        match clGetEventInfo(event, param_name, param_value_size, param_value, param_value_size_ret) {
+            cl::Status::SUCCESS => Ok(()),
+            cl::Status::INVALID_VALUE => Err(Error::InvalidValue(format!("`param_name` is not valid or `param_value_size` is too small."))),
+            cl::Status::INVALID_EVENT => Err(Error::InvalidEvent(format!("event: {:?} is not a valid event object.", event))),
+            cl::Status::OUT_OF_RESOURCES => Err(Error::OutOfResources(format!("Failure to allocate resources on the device"))),
+            cl::Status::OUT_OF_HOST_MEMORY => Err(Error::OutOfHostMemory(format!("Failure to allocate resources on the host"))),
+            status => Err(Error::from_failed_status(status as i32, "clGetEventInfo"))
+        }
+    }
+
+    unsafe fn ffi_release_event(event: cl::event) -> Result<(), Error> {
+        match clReleaseEvent(event) {
+            cl::Status::SUCCESS => Ok(()),
+            cl::Status::INVALID_EVENT => Err(Error::InvalidEvent(format!("event: {:?} is not a valid event object.", event))),
+            cl::Status::OUT_OF_RESOURCES => Err(Error::OutOfResources(format!("Failure to allocate resources on the device"))),
+            cl::Status::OUT_OF_HOST_MEMORY => Err(Error::OutOfHostMemory(format!("Failure to allocate resources on the host"))),
+            status => Err(Error::from_failed_status(status as i32, "clReleaseEvent"))
+        }
+    }
+}
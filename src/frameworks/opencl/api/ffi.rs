@@ -36,6 +36,15 @@ extern
         param_value: *mut libc::c_void,
         param_value_size_ret: *mut libc::size_t) -> cl::Status;
 
+    pub fn clCreateSubDevices(
+        in_device: cl::device_id,
+        properties: *const cl::device_partition_property,
+        num_devices: cl::uint,
+        out_devices: *mut cl::device_id,
+        num_devices_ret: *mut cl::uint) -> cl::Status;
+
+    pub fn clReleaseDevice(device: cl::device_id) -> cl::Status;
+
     /* Context APIs */
     pub fn clCreateContext(
         properties: *const cl::context_properties,
@@ -366,6 +375,17 @@ extern
         event_wait_list: *const cl::event,
         event: *mut cl::event) -> cl::Status;
 
+    pub fn clEnqueueFillBuffer(
+        command_queue: cl::queue_id,
+        buffer: cl::memory_id,
+        pattern: *const libc::c_void,
+        pattern_size: libc::size_t,
+        offset: libc::size_t,
+        size: libc::size_t,
+        num_events_in_wait_list: cl::uint,
+        event_wait_list: *const cl::event,
+        event: *mut cl::event) -> cl::Status;
+
     pub fn clEnqueueCopyBufferRect(
         command_queue: cl::queue_id,
         src_buffer: cl::memory_id,
@@ -0,0 +1,190 @@
+//! Provides the OpenCL API with its kernel functionality.
+
+use libc;
+use frameworks::opencl::{API, Error, Kernel, Queue, Device, Event};
+use super::types as cl;
+use super::ffi::*;
+use std::ffi::CString;
+use stats;
+use std::{mem, ptr};
+
+impl API {
+    /// Creates a OpenCL kernel for the function `name` of the build `program`.
+    pub fn create_kernel(program: cl::program, name: &str) -> Result<cl::kernel_id, Error> {
+        let name = CString::new(name.as_bytes()).unwrap();
+        Ok(try!(unsafe { API::ffi_create_kernel(program, name.as_ptr()) }))
+    }
+
+    /// Releases the OpenCL kernel.
+    pub fn release_kernel(kernel: &Kernel) -> Result<(), Error> {
+        Ok(try!(unsafe { API::ffi_release_kernel(kernel.id_c()) }))
+    }
+
+    /// Sets the value of the kernel argument at `index`.
+    ///
+    /// `value` points to `size` bytes, which get copied as the argument value. Pass a NULL
+    /// `value` to allocate `size` bytes of local memory for the argument.
+    pub fn set_kernel_arg(kernel: &Kernel, index: cl::uint, size: libc::size_t, value: *const libc::c_void) -> Result<(), Error> {
+        Ok(try!(unsafe { API::ffi_set_kernel_arg(kernel.id_c(), index, size, value) }))
+    }
+
+    /// Returns the number of arguments of the `kernel`.
+    pub fn kernel_num_args(kernel: &Kernel) -> Result<u32, Error> {
+        let mut num_args: cl::uint = 0;
+        try!(unsafe { API::ffi_get_kernel_info(kernel.id_c(), cl::CL_KERNEL_NUM_ARGS, mem::size_of::<cl::uint>() as libc::size_t, &mut num_args as *mut cl::uint as *mut libc::c_void, ptr::null_mut()) });
+        Ok(num_args)
+    }
+
+    /// Returns the maximum work-group size, with which the `kernel` can be executed on the `device`.
+    ///
+    /// The limit depends on the resources the kernel requires and is never larger than the
+    /// maximum work-group size of the device.
+    pub fn kernel_work_group_size(kernel: &Kernel, device: &Device) -> Result<usize, Error> {
+        let mut size: libc::size_t = 0;
+        try!(unsafe { API::ffi_get_kernel_work_group_info(kernel.id_c(), device.id_c(), cl::CL_KERNEL_WORK_GROUP_SIZE, mem::size_of::<libc::size_t>() as libc::size_t, &mut size as *mut libc::size_t as *mut libc::c_void, ptr::null_mut()) });
+        Ok(size as usize)
+    }
+
+    /// Enqueues the execution of the `kernel` on the `queue`.
+    ///
+    /// The number of dimensions is defined by the length of `global_work_size`.
+    /// `local_work_size` needs to have the same length or be empty, to let the OpenCL
+    /// implementation choose the work-group size.
+    pub fn enqueue_kernel(queue: &Queue, kernel: &Kernel, global_work_size: &[usize], local_work_size: &[usize]) -> Result<(), Error> {
+        let global: Vec<libc::size_t> = global_work_size.iter().map(|s| *s as libc::size_t).collect();
+        let local: Vec<libc::size_t> = local_work_size.iter().map(|s| *s as libc::size_t).collect();
+        let local_ptr = if local.is_empty() { ptr::null() } else { local.as_ptr() };
+        co_trace!("Enqueuing OpenCL kernel {:?} with global work size {:?} and local work size {:?}", kernel.id_c(), global_work_size, local_work_size);
+        stats::record_launch();
+        Ok(try!(unsafe {
+            API::ffi_enqueue_nd_range_kernel(queue.id_c(), kernel.id_c(), global.len() as cl::uint, ptr::null(), global.as_ptr(), local_ptr, 0, ptr::null(), ptr::null_mut())
+        }))
+    }
+
+    /// Enqueues the execution of the `kernel` on the `queue`, which starts after all the commands
+    /// of the `wait_list` have been completed.
+    ///
+    /// Returns the Event of the execution, which can be waited on or passed to further commands.
+    pub fn enqueue_kernel_with_events(queue: &Queue, kernel: &Kernel, global_work_size: &[usize], local_work_size: &[usize], wait_list: &[&Event]) -> Result<Event, Error> {
+        let global: Vec<libc::size_t> = global_work_size.iter().map(|s| *s as libc::size_t).collect();
+        let local: Vec<libc::size_t> = local_work_size.iter().map(|s| *s as libc::size_t).collect();
+        let local_ptr = if local.is_empty() { ptr::null() } else { local.as_ptr() };
+        co_trace!("Enqueuing OpenCL kernel {:?} with global work size {:?} and local work size {:?}", kernel.id_c(), global_work_size, local_work_size);
+        stats::record_launch();
+        let wait_ids = API::event_ids(wait_list);
+        let mut event: cl::event = ptr::null_mut();
+        try!(unsafe {
+            API::ffi_enqueue_nd_range_kernel(queue.id_c(), kernel.id_c(), global.len() as cl::uint, ptr::null(), global.as_ptr(), local_ptr, wait_ids.len() as cl::uint, API::event_wait_list(&wait_ids), &mut event)
+        });
+        Ok(Event::from_c(event))
+    }
+
+    unsafe fn ffi_create_kernel(program: cl::program, kernel_name: *const libc::c_char) -> Result<cl::kernel_id, Error> {
+        let mut errcode: i32 = 0;
+        let kernel_id = clCreateKernel(program, kernel_name, &mut errcode);
+        match errcode {
+            errcode if errcode == cl::Status::SUCCESS as i32 => Ok(kernel_id),
+            errcode if errcode == cl::Status::INVALID_PROGRAM as i32 => Err(Error::Other(format!("program: {:?} is not a valid program object.", program))),
+            errcode if errcode == cl::Status::INVALID_PROGRAM_EXECUTABLE as i32 => Err(Error::Other(format!("there is no successfully built executable for program."))),
+            errcode if errcode == cl::Status::INVALID_KERNEL_NAME as i32 => Err(Error::InvalidKernelName(format!("kernel_name is not found in program."))),
+            errcode if errcode == cl::Status::INVALID_VALUE as i32 => Err(Error::InvalidValue(format!("kernel_name is NULL."))),
+            errcode if errcode == cl::Status::OUT_OF_RESOURCES as i32 => Err(Error::OutOfResources(format!("Failure to allocate resources on the device"))),
+            errcode if errcode == cl::Status::OUT_OF_HOST_MEMORY as i32 => Err(Error::OutOfHostMemory(format!("Failure to allocate resources on the host"))),
+            errcode => Err(Error::from_failed_status(errcode, "clCreateKernel"))
+        }
+    }
+
+    unsafe fn ffi_release_kernel(kernel: cl::kernel_id) -> Result<(), Error> {
+        match clReleaseKernel(kernel) {
+            cl::Status::SUCCESS => Ok(()),
+            cl::Status::INVALID_KERNEL => Err(Error::Other(format!("kernel: {:?} is not a valid kernel object.", kernel))),
+            cl::Status::OUT_OF_RESOURCES => Err(Error::OutOfResources(format!("Failure to allocate resources on the device"))),
+            cl::Status::OUT_OF_HOST_MEMORY => Err(Error::OutOfHostMemory(format!("Failure to allocate resources on the host"))),
+            status => Err(Error::from_failed_status(status as i32, "clReleaseKernel"))
+        }
+    }
+
+    unsafe fn ffi_set_kernel_arg(
+        kernel: cl::kernel_id,
+        arg_index: cl::uint,
+        arg_size: libc::size_t,
+        arg_value: *const libc::c_void
+    ) -> Result<(), Error> {
+        match clSetKernelArg(kernel, arg_index, arg_size, arg_value) {
+            cl::Status::SUCCESS => Ok(()),
+            cl::Status::INVALID_KERNEL => Err(Error::Other(format!("kernel: {:?} is not a valid kernel object.", kernel))),
+            cl::Status::INVALID_ARG_INDEX => Err(Error::InvalidValue(format!("arg_index: {} is not a valid argument index.", arg_index))),
+            cl::Status::INVALID_ARG_VALUE => Err(Error::InvalidValue(format!("arg_value specified is not a valid value for argument {}.", arg_index))),
+            cl::Status::INVALID_MEM_OBJECT => Err(Error::InvalidMemObject(format!("the argument {} is a memory object and arg_value is not a valid memory object.", arg_index))),
+            cl::Status::INVALID_ARG_SIZE => Err(Error::InvalidValue(format!("arg_size: {} does not match the size of the data type for argument {}.", arg_size, arg_index))),
+            cl::Status::OUT_OF_RESOURCES => Err(Error::OutOfResources(format!("Failure to allocate resources on the device"))),
+            cl::Status::OUT_OF_HOST_MEMORY => Err(Error::OutOfHostMemory(format!("Failure to allocate resources on the host"))),
+            status => Err(Error::from_failed_status(status as i32, "clSetKernelArg"))
+        }
+    }
+
+    unsafe fn ffi_get_kernel_info(
+        kernel: cl::kernel_id,
+        param_name: cl::kernel_info,
+        param_value_size: libc::size_t,
+        param_value: *mut libc::c_void,
+        param_value_size_ret: *mut libc::size_t
+    ) -> Result<(), Error> {
+        match clGetKernelInfo(kernel, param_name, param_value_size, param_value, param_value_size_ret) {
+            cl::Status::SUCCESS => Ok(()),
+            cl::Status::INVALID_VALUE => Err(Error::InvalidValue(format!("`param_name`: {} is not valid, or the size of the param value is too small.", param_name))),
+            cl::Status::INVALID_KERNEL => Err(Error::Other(format!("kernel: {:?} is not a valid kernel object.", kernel))),
+            cl::Status::OUT_OF_RESOURCES => Err(Error::OutOfResources(format!("Failure to allocate resources on the device"))),
+            cl::Status::OUT_OF_HOST_MEMORY => Err(Error::OutOfHostMemory(format!("Failure to allocate resources on the host"))),
+            status => Err(Error::from_failed_status(status as i32, "clGetKernelInfo"))
+        }
+    }
+
+    unsafe fn ffi_get_kernel_work_group_info(
+        kernel: cl::kernel_id,
+        device: cl::device_id,
+        param_name: cl::kernel_work_group_info,
+        param_value_size: libc::size_t,
+        param_value: *mut libc::c_void,
+        param_value_size_ret: *mut libc::size_t
+    ) -> Result<(), Error> {
+        match clGetKernelWorkGroupInfo(kernel, device, param_name, param_value_size, param_value, param_value_size_ret) {
+            cl::Status::SUCCESS => Ok(()),
+            cl::Status::INVALID_DEVICE => Err(Error::InvalidDevice(format!("`device`: {:?} is not associated with the kernel.", device))),
+            cl::Status::INVALID_VALUE => Err(Error::InvalidValue(format!("`param_name`: {} is not valid, or the size of the param value is too small.", param_name))),
+            cl::Status::INVALID_KERNEL => Err(Error::Other(format!("kernel: {:?} is not a valid kernel object.", kernel))),
+            cl::Status::OUT_OF_RESOURCES => Err(Error::OutOfResources(format!("Failure to allocate resources on the device"))),
+            cl::Status::OUT_OF_HOST_MEMORY => Err(Error::OutOfHostMemory(format!("Failure to allocate resources on the host"))),
+            status => Err(Error::from_failed_status(status as i32, "clGetKernelWorkGroupInfo"))
+        }
+    }
+
+    unsafe fn ffi_enqueue_nd_range_kernel(
+        command_queue: cl::queue_id,
+        kernel: cl::kernel_id,
+        work_dim: cl::uint,
+        global_work_offset: *const libc::size_t,
+        global_work_size: *const libc::size_t,
+        local_work_size: *const libc::size_t,
+        num_events_in_wait_list: cl::uint,
+        event_wait_list: *const cl::event,
+        event: *mut cl::event
+    ) -> Result<(), Error> {
+        match clEnqueueNDRangeKernel(command_queue, kernel, work_dim, global_work_offset, global_work_size, local_work_size, num_events_in_wait_list, event_wait_list, event) {
+            cl::Status::SUCCESS => Ok(()),
+            cl::Status::INVALID_PROGRAM_EXECUTABLE => Err(Error::Other(format!("there is no successfully built program executable available for device associated with command_queue."))),
+            cl::Status::INVALID_COMMAND_QUEUE => Err(Error::InvalidCommandQueue(format!("command_queue: {:?} is not a valid command-queue", command_queue))),
+            cl::Status::INVALID_KERNEL => Err(Error::Other(format!("kernel: {:?} is not a valid kernel object.", kernel))),
+            cl::Status::INVALID_CONTEXT => Err(Error::InvalidContext(format!("context associated with command_queue and kernel are not the same."))),
+            cl::Status::INVALID_KERNEL_ARGS => Err(Error::InvalidKernelArgs(format!("the kernel argument values have not been specified."))),
+            cl::Status::INVALID_WORK_DIMENSION => Err(Error::InvalidValue(format!("work_dim: {} is not a valid value.", work_dim))),
+            cl::Status::INVALID_WORK_GROUP_SIZE => Err(Error::InvalidWorkGroupSize(format!("local_work_size is not valid for the provided global_work_size and kernel."))),
+            cl::Status::INVALID_WORK_ITEM_SIZE => Err(Error::InvalidValue(format!("the number of work-items specified in any of local_work_size is greater than the device maximum."))),
+            cl::Status::INVALID_EVENT_WAIT_LIST => Err(Error::InvalidEventWaitList(format!("event_wait_list is NULL and num_events_in_wait_list > 0, or event_wait_list is not NULL and num_events_in_wait_list is 0, or if event objects in event_wait_list are not valid events."))),
+            cl::Status::MEM_OBJECT_ALLOCATION_FAILURE => Err(Error::MemObjectAllocationFailure(format!("there is a failure to allocate memory for data store associated with image or buffer objects specified as arguments to kernel."))),
+            cl::Status::OUT_OF_RESOURCES => Err(Error::OutOfResources(format!("Failure to allocate resources on the device"))),
+            cl::Status::OUT_OF_HOST_MEMORY => Err(Error::OutOfHostMemory(format!("Failure to allocate resources on the host"))),
+            status => Err(Error::from_failed_status(status as i32, "clEnqueueNDRangeKernel"))
+        }
+    }
+}
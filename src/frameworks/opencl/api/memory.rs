@@ -3,9 +3,11 @@
 //! At Collenchyma device can be understood as a synonym to OpenCL's context.
 
 use libc;
-use frameworks::opencl::{API, Error, Context, Memory, Queue};
+use frameworks::opencl::{API, Error, Context, Memory, Queue, Event};
+use frameworks::opencl::memory::MemoryFlags;
 use super::types as cl;
 use super::ffi::*;
+use std::{mem, ptr};
 
 impl API {
     /// Allocates memory on the OpenCL device.
@@ -14,8 +16,32 @@ impl API {
     /// object can be a scalar data type (such as an int, float), vector data type, or a
     /// user-defined structure.
     /// Returns a memory id for the created buffer, which can now be writen to.
-    pub fn create_buffer(context: Context) -> Result<cl::memory_id, Error> {
-        unimplemented!()
+    pub fn create_buffer(context: &Context, flags: MemoryFlags, size: usize) -> Result<cl::memory_id, Error> {
+        Ok(try!(unsafe { API::ffi_create_buffer(context.id_c(), flags.bits(), size as libc::size_t, ptr::null_mut()) }))
+    }
+
+    /// Creates a sub-buffer of `size` bytes, which starts `offset` bytes into the `buffer`.
+    ///
+    /// The sub-buffer shares the data store of the `buffer`. The `offset` needs to be aligned to
+    /// `CL_DEVICE_MEM_BASE_ADDR_ALIGN` of the devices of the context.
+    /// Returns a memory id for the created sub-buffer, which needs to be released on its own.
+    pub fn create_sub_buffer(buffer: &Memory, flags: MemoryFlags, offset: usize, size: usize) -> Result<cl::memory_id, Error> {
+        let mut region = cl::buffer_region { origin: offset as libc::size_t, size: size as libc::size_t };
+        Ok(try!(unsafe { API::ffi_create_sub_buffer(buffer.id_c(), flags.bits(), &mut region) }))
+    }
+
+    /// Increments the reference count of the memory object `memory`.
+    ///
+    /// Every retain needs to be balanced by a release.
+    pub fn retain_memory(memory: cl::memory_id) -> Result<(), Error> {
+        Ok(try!(unsafe { API::ffi_retain_mem_object(memory) }))
+    }
+
+    /// Returns the size of the data store of the memory object `memory` in bytes.
+    pub fn memory_size(memory: cl::memory_id) -> Result<usize, Error> {
+        let mut size: libc::size_t = 0;
+        try!(unsafe { API::ffi_get_mem_object_info(memory, cl::CL_MEM_SIZE, mem::size_of::<libc::size_t>() as libc::size_t, &mut size as *mut libc::size_t as *mut libc::c_void, ptr::null_mut()) });
+        Ok(size as usize)
     }
 
     /// Releases allocated memory from the OpenCL device.
@@ -23,21 +49,77 @@ impl API {
         Ok(try!(unsafe {API::ffi_release_mem_object(memory.id_c())}))
     }
 
-    /// Reads from a buffer to the host memory.
+    /// Enqueues a copy of `size` bytes from the `src` buffer to the `dst` buffer.
     ///
-    /// With write_to_buffer you can do the opposite, write from the host memory to a buffer.
-    pub fn read_from_buffer<T>(
-        queue: Queue,
-        mem: Memory,
-        blocking_read: cl::boolean,
-        offset: libc::size_t,
-        size: libc::size_t,
-        ptr: *mut libc::c_void,
-        num_events_in_wait_list: cl::uint,
-        event_wait_list: *const cl::event,
-        event: *mut cl::event,
-    ) -> Result<(), Error> {
-        unimplemented!();
+    /// Both buffers need to belong to the context of the `queue`. The data never leaves the
+    /// device.
+    pub fn copy_buffer(queue: &Queue, src: &Memory, dst: &Memory, size: usize) -> Result<(), Error> {
+        Ok(try!(unsafe { API::ffi_enqueue_copy_buffer(queue.id_c(), src.id_c(), dst.id_c(), 0, 0, size as libc::size_t, 0, ptr::null(), ptr::null_mut()) }))
+    }
+
+    /// Enqueues a copy of `size` bytes from the `src` buffer to the `dst` buffer, which starts
+    /// after all the commands of the `wait_list` have been completed.
+    ///
+    /// Returns the Event of the copy, which can be waited on or passed to further commands.
+    pub fn enqueue_copy_buffer(queue: &Queue, src: &Memory, dst: &Memory, size: usize, wait_list: &[&Event]) -> Result<Event, Error> {
+        let wait_ids = API::event_ids(wait_list);
+        let mut event: cl::event = ptr::null_mut();
+        try!(unsafe { API::ffi_enqueue_copy_buffer(queue.id_c(), src.id_c(), dst.id_c(), 0, 0, size as libc::size_t, wait_ids.len() as cl::uint, API::event_wait_list(&wait_ids), &mut event) });
+        Ok(Event::from_c(event))
+    }
+
+    /// Reads `size` bytes from the memory into the host memory at `host_ptr`.
+    ///
+    /// Blocks until the data has been read. With `write_to_memory` you can do the opposite,
+    /// write from the host memory into the memory.
+    pub fn read_from_memory(queue: &Queue, mem: &Memory, host_ptr: *mut libc::c_void, size: usize) -> Result<(), Error> {
+        Ok(try!(unsafe { API::ffi_enqueue_read_buffer(queue.id_c(), mem.id_c(), cl::CL_TRUE, 0, size as libc::size_t, host_ptr, 0, ptr::null(), ptr::null_mut()) }))
+    }
+
+    /// Reads `size` bytes, starting `offset` bytes into the memory, into the host memory at
+    /// `host_ptr`.
+    ///
+    /// Blocks until the data has been read.
+    pub fn read_from_memory_at(queue: &Queue, mem: &Memory, offset: usize, host_ptr: *mut libc::c_void, size: usize) -> Result<(), Error> {
+        Ok(try!(unsafe { API::ffi_enqueue_read_buffer(queue.id_c(), mem.id_c(), cl::CL_TRUE, offset as libc::size_t, size as libc::size_t, host_ptr, 0, ptr::null(), ptr::null_mut()) }))
+    }
+
+    /// Writes `size` bytes from the host memory at `host_ptr` into the memory.
+    ///
+    /// Blocks until the data has been written.
+    pub fn write_to_memory(queue: &Queue, mem: &mut Memory, host_ptr: *const libc::c_void, size: usize) -> Result<(), Error> {
+        Ok(try!(unsafe { API::ffi_enqueue_write_buffer(queue.id_c(), mem.id_c(), cl::CL_TRUE, 0, size as libc::size_t, host_ptr, 0, ptr::null(), ptr::null_mut()) }))
+    }
+
+    /// Enqueues a read of `size` bytes from the memory into the host memory at `host_ptr`.
+    ///
+    /// Returns immediately with the Event of the read. The host memory may only be accessed
+    /// after the Event has been completed.
+    pub fn enqueue_read_from_memory(queue: &Queue, mem: &Memory, host_ptr: *mut libc::c_void, size: usize) -> Result<Event, Error> {
+        let mut event: cl::event = ptr::null_mut();
+        try!(unsafe { API::ffi_enqueue_read_buffer(queue.id_c(), mem.id_c(), cl::CL_FALSE, 0, size as libc::size_t, host_ptr, 0, ptr::null(), &mut event) });
+        Ok(Event::from_c(event))
+    }
+
+    /// Enqueues a write of `size` bytes from the host memory at `host_ptr` into the memory.
+    ///
+    /// Returns immediately with the Event of the write. The host memory has to stay valid and
+    /// unchanged until the Event has been completed.
+    pub fn enqueue_write_to_memory(queue: &Queue, mem: &mut Memory, host_ptr: *const libc::c_void, size: usize) -> Result<Event, Error> {
+        let mut event: cl::event = ptr::null_mut();
+        try!(unsafe { API::ffi_enqueue_write_buffer(queue.id_c(), mem.id_c(), cl::CL_FALSE, 0, size as libc::size_t, host_ptr, 0, ptr::null(), &mut event) });
+        Ok(Event::from_c(event))
+    }
+
+    /// Fills the memory with the repeated bytes of `pattern`.
+    ///
+    /// Blocks until the memory has been filled. The size of the memory needs to be a multiple of
+    /// the length of the `pattern`, which needs to be 1, 2, 4, 8, 16, 32, 64 or 128 bytes long.
+    /// Requires OpenCL 1.2.
+    pub fn fill_buffer(queue: &Queue, mem: &mut Memory, pattern: &[u8]) -> Result<(), Error> {
+        let mut event: cl::event = ptr::null_mut();
+        try!(unsafe { API::ffi_enqueue_fill_buffer(queue.id_c(), mem.id_c(), pattern.as_ptr() as *const libc::c_void, pattern.len() as libc::size_t, 0, mem.size() as libc::size_t, 0, ptr::null(), &mut event) });
+        Event::from_c(event).wait()
     }
 
     unsafe fn ffi_create_buffer(
@@ -57,7 +139,54 @@ impl API {
             errcode if errcode == cl::Status::MEM_OBJECT_ALLOCATION_FAILURE as i32 => Err(Error::MemObjectAllocationFailure(format!("failure toallocate memory for buffer object."))),
             errcode if errcode == cl::Status::OUT_OF_RESOURCES as i32 => Err(Error::OutOfResources(format!("Failure to allocate resources on the device"))),
             errcode if errcode == cl::Status::OUT_OF_HOST_MEMORY as i32 => Err(Error::OutOfHostMemory(format!("Failure to allocate resources on the host"))),
-            _ => Err(Error::Other(format!("Unable to create memory buffer.")))
+            errcode => Err(Error::from_failed_status(errcode, "clCreateBuffer"))
+        }
+    }
+
+    unsafe fn ffi_create_sub_buffer(
+        buffer: cl::memory_id,
+        flags: cl::mem_flags,
+        region: *mut cl::buffer_region
+    ) -> Result<cl::memory_id, Error> {
+        let mut errcode: i32 = 0;
+        let memory_id = clCreateSubBuffer(buffer, flags, cl::CL_BUFFER_CREATE_TYPE_REGION, region as *mut libc::c_void, &mut errcode);
+        match errcode {
+            errcode if errcode == cl::Status::SUCCESS as i32 => Ok(memory_id),
+            errcode if errcode == cl::Status::INVALID_MEM_OBJECT as i32 => Err(Error::InvalidMemObject(format!("buffer: {:?} is not a valid buffer object or is a sub-buffer object.", buffer))),
+            errcode if errcode == cl::Status::INVALID_VALUE as i32 => Err(Error::InvalidValue(format!("values specified in flags are not valid or the region specified by (origin, size) is out of bounds in buffer."))),
+            errcode if errcode == cl::Status::INVALID_BUFFER_SIZE as i32 => Err(Error::InvalidBufferSize(format!("size of the region is 0."))),
+            errcode if errcode == cl::Status::MISALIGNED_SUB_BUFFER_OFFSET as i32 => Err(Error::MisalignedSubBufferOffset(format!("there are no devices in context associated with buffer for which the origin value is aligned to the CL_DEVICE_MEM_BASE_ADDR_ALIGN value."))),
+            errcode if errcode == cl::Status::MEM_OBJECT_ALLOCATION_FAILURE as i32 => Err(Error::MemObjectAllocationFailure(format!("failure to allocate memory for sub-buffer object."))),
+            errcode if errcode == cl::Status::OUT_OF_RESOURCES as i32 => Err(Error::OutOfResources(format!("Failure to allocate resources on the device"))),
+            errcode if errcode == cl::Status::OUT_OF_HOST_MEMORY as i32 => Err(Error::OutOfHostMemory(format!("Failure to allocate resources on the host"))),
+            errcode => Err(Error::from_failed_status(errcode, "clCreateSubBuffer"))
+        }
+    }
+
+    unsafe fn ffi_retain_mem_object(memobj: cl::memory_id) -> Result<(), Error> {
+        match clRetainMemObject(memobj) {
+            cl::Status::SUCCESS => Ok(()),
+            cl::Status::INVALID_MEM_OBJECT => Err(Error::InvalidMemObject(format!("memobj: {:?} is not a valid memory object.", memobj))),
+            cl::Status::OUT_OF_RESOURCES => Err(Error::OutOfResources(format!("Failure to allocate resources on the device"))),
+            cl::Status::OUT_OF_HOST_MEMORY => Err(Error::OutOfHostMemory(format!("Failure to allocate resources on the host"))),
+            status => Err(Error::from_failed_status(status as i32, "clRetainMemObject"))
+        }
+    }
+
+    unsafe fn ffi_get_mem_object_info(
+        memobj: cl::memory_id,
+        param_name: cl::mem_info,
+        param_value_size: libc::size_t,
+        param_value: *mut libc::c_void,
+        param_value_size_ret: *mut libc::size_t
+    ) -> Result<(), Error> {
+        match clGetMemObjectInfo(memobj, param_name, param_value_size, param_value, param_value_size_ret) {
+            cl::Status::SUCCESS => Ok(()),
+            cl::Status::INVALID_VALUE => Err(Error::InvalidValue(format!("`param_name` is not valid or `param_value_size` is too small."))),
+            cl::Status::INVALID_MEM_OBJECT => Err(Error::InvalidMemObject(format!("memobj: {:?} is not a valid memory object.", memobj))),
+            cl::Status::OUT_OF_RESOURCES => Err(Error::OutOfResources(format!("Failure to allocate resources on the device"))),
+            cl::Status::OUT_OF_HOST_MEMORY => Err(Error::OutOfHostMemory(format!("Failure to allocate resources on the host"))),
+            status => Err(Error::from_failed_status(status as i32, "clGetMemObjectInfo"))
         }
     }
 
@@ -67,7 +196,7 @@ impl API {
             cl::Status::INVALID_MEM_OBJECT => Err(Error::InvalidMemObject(format!("memobj: {:?} is not a valid memory object.", memobj))),
             cl::Status::OUT_OF_RESOURCES => Err(Error::OutOfResources(format!("Failure to allocate resources on the device"))),
             cl::Status::OUT_OF_HOST_MEMORY => Err(Error::OutOfHostMemory(format!("Failure to allocate resources on the host"))),
-            _ => Err(Error::Other(format!("Unable to release memory object.")))
+            status => Err(Error::from_failed_status(status as i32, "clReleaseMemObject"))
         }
     }
 
@@ -95,7 +224,88 @@ impl API {
             cl::Status::INVALID_OPERATION => Err(Error::InvalidOperation(format!("called on buffer which has been created with CL_MEM_HOST_WRITE_ONLY or CL_MEM_HOST_NO_ACCESS."))),
             cl::Status::OUT_OF_RESOURCES => Err(Error::OutOfResources(format!("Failure to allocate resources on the device"))),
             cl::Status::OUT_OF_HOST_MEMORY => Err(Error::OutOfHostMemory(format!("Failure to allocate resources on the host"))),
-            _ => Err(Error::Other(format!("Unable to enqueue read buffer.")))
+            status => Err(Error::from_failed_status(status as i32, "clEnqueueReadBuffer"))
+        }
+    }
+
+    unsafe fn ffi_enqueue_copy_buffer(
+        command_queue: cl::queue_id,
+        src_buffer: cl::memory_id,
+        dst_buffer: cl::memory_id,
+        src_offset: libc::size_t,
+        dst_offset: libc::size_t,
+        cb: libc::size_t,
+        num_events_in_wait_list: cl::uint,
+        event_wait_list: *const cl::event,
+        event: *mut cl::event
+    ) -> Result<(), Error> {
+        match clEnqueueCopyBuffer(command_queue, src_buffer, dst_buffer, src_offset, dst_offset, cb, num_events_in_wait_list, event_wait_list, event) {
+            cl::Status::SUCCESS => Ok(()),
+            cl::Status::INVALID_COMMAND_QUEUE => Err(Error::InvalidCommandQueue(format!("command_queue: {:?} is not a valid command-queue", command_queue))),
+            cl::Status::INVALID_CONTEXT => Err(Error::InvalidContext(format!("the context associated with command_queue, src_buffer and dst_buffer are not the same."))),
+            cl::Status::INVALID_MEM_OBJECT => Err(Error::InvalidMemObject(format!("src_buffer and dst_buffer are not valid buffer objects."))),
+            cl::Status::INVALID_VALUE => Err(Error::InvalidValue(format!("src_offset, dst_offset, cb, src_offset + cb or dst_offset + cb require accessing elements outside the src_buffer and dst_buffer buffer objects."))),
+            cl::Status::INVALID_EVENT_WAIT_LIST => Err(Error::InvalidEventWaitList(format!("event_wait_list is NULL and num_events_in_wait_list > 0, or event_wait_list is not NULL and num_events_in_wait_list is 0, or if event objects in event_wait_list are not valid events."))),
+            cl::Status::MISALIGNED_SUB_BUFFER_OFFSET => Err(Error::MisalignedSubBufferOffset(format!("src_buffer or dst_buffer is a sub-buffer object and offset specified when the sub-buffer object is created is not aligned to CL_DEVICE_MEM_BASE_ADDR_ALIGN value for device associated with queue."))),
+            cl::Status::MEM_COPY_OVERLAP => Err(Error::InvalidValue(format!("src_buffer and dst_buffer are the same buffer object and the source and destination regions overlap."))),
+            cl::Status::MEM_OBJECT_ALLOCATION_FAILURE => Err(Error::MemObjectAllocationFailure(format!("there is a failure to allocate memory for data store associated with src_buffer or dst_buffer."))),
+            cl::Status::OUT_OF_RESOURCES => Err(Error::OutOfResources(format!("Failure to allocate resources on the device"))),
+            cl::Status::OUT_OF_HOST_MEMORY => Err(Error::OutOfHostMemory(format!("Failure to allocate resources on the host"))),
+            status => Err(Error::from_failed_status(status as i32, "clEnqueueCopyBuffer"))
+        }
+    }
+
+    unsafe fn ffi_enqueue_write_buffer(
+        command_queue: cl::queue_id,
+        buffer: cl::memory_id,
+        blocking_write: cl::boolean,
+        offset: libc::size_t,
+        cb: libc::size_t,
+        ptr: *const libc::c_void,
+        num_events_in_wait_list: cl::uint,
+        event_wait_list: *const cl::event,
+        event: *mut cl::event
+    ) -> Result<(), Error> {
+        match clEnqueueWriteBuffer(command_queue, buffer, blocking_write, offset, cb, ptr, num_events_in_wait_list, event_wait_list, event) {
+            cl::Status::SUCCESS => Ok(()),
+            cl::Status::INVALID_COMMAND_QUEUE => Err(Error::InvalidCommandQueue(format!("command_queue: {:?} is not a valid command-queue", command_queue))),
+            cl::Status::INVALID_CONTEXT => Err(Error::InvalidContext(format!("the context associated with command_queue and buffer are not the same or if the context associated with command_queue and events in event_wait_list are not the same."))),
+            cl::Status::INVALID_MEM_OBJECT => Err(Error::InvalidMemObject(format!("buffer: {:?} is not a valid memory object.", buffer))),
+            cl::Status::INVALID_VALUE => Err(Error::InvalidValue(format!("the region being written specified by (offset, size) is out of bounds or if ptr is a NULL value or if size is 0."))),
+            cl::Status::INVALID_EVENT_WAIT_LIST => Err(Error::InvalidEventWaitList(format!("event_wait_list is NULL and num_events_in_wait_list > 0, or event_wait_list is not NULL and num_events_in_wait_list is 0, or if event objects in event_wait_list are not valid events."))),
+            cl::Status::MISALIGNED_SUB_BUFFER_OFFSET => Err(Error::MisalignedSubBufferOffset(format!("buffer is a sub-buffer object and offset specified when the sub-buffer object is created is not aligned to CL_DEVICE_MEM_BASE_ADDR_ALIGN value for device associated with queue."))),
+            cl::Status::EXEC_STATUS_ERROR_FOR_EVENTS_IN_WAIT_LIST => Err(Error::ExecStatusErrorForEventsInWaitList(format!("the write operation is blocking and the execution status of any of the events in event_wait_list is a negative integer value."))),
+            cl::Status::MEM_OBJECT_ALLOCATION_FAILURE => Err(Error::MemObjectAllocationFailure(format!("there is a failure to allocate memory for data store associated with buffer."))),
+            cl::Status::INVALID_OPERATION => Err(Error::InvalidOperation(format!("called on buffer which has been created with CL_MEM_HOST_READ_ONLY or CL_MEM_HOST_NO_ACCESS."))),
+            cl::Status::OUT_OF_RESOURCES => Err(Error::OutOfResources(format!("Failure to allocate resources on the device"))),
+            cl::Status::OUT_OF_HOST_MEMORY => Err(Error::OutOfHostMemory(format!("Failure to allocate resources on the host"))),
+            status => Err(Error::from_failed_status(status as i32, "clEnqueueWriteBuffer"))
+        }
+    }
+
+    unsafe fn ffi_enqueue_fill_buffer(
+        command_queue: cl::queue_id,
+        buffer: cl::memory_id,
+        pattern: *const libc::c_void,
+        pattern_size: libc::size_t,
+        offset: libc::size_t,
+        size: libc::size_t,
+        num_events_in_wait_list: cl::uint,
+        event_wait_list: *const cl::event,
+        event: *mut cl::event
+    ) -> Result<(), Error> {
+        match clEnqueueFillBuffer(command_queue, buffer, pattern, pattern_size, offset, size, num_events_in_wait_list, event_wait_list, event) {
+            cl::Status::SUCCESS => Ok(()),
+            cl::Status::INVALID_COMMAND_QUEUE => Err(Error::InvalidCommandQueue(format!("command_queue: {:?} is not a valid command-queue", command_queue))),
+            cl::Status::INVALID_CONTEXT => Err(Error::InvalidContext(format!("the context associated with command_queue and buffer are not the same or if the context associated with command_queue and events in event_wait_list are not the same."))),
+            cl::Status::INVALID_MEM_OBJECT => Err(Error::InvalidMemObject(format!("buffer: {:?} is not a valid buffer object.", buffer))),
+            cl::Status::INVALID_VALUE => Err(Error::InvalidValue(format!("offset or offset + size require accessing elements outside the buffer, pattern is NULL, pattern_size is not one of {{1, 2, 4, 8, 16, 32, 64, 128}} or offset and size are not a multiple of pattern_size."))),
+            cl::Status::INVALID_EVENT_WAIT_LIST => Err(Error::InvalidEventWaitList(format!("event_wait_list is NULL and num_events_in_wait_list > 0, or event_wait_list is not NULL and num_events_in_wait_list is 0, or if event objects in event_wait_list are not valid events."))),
+            cl::Status::MISALIGNED_SUB_BUFFER_OFFSET => Err(Error::MisalignedSubBufferOffset(format!("buffer is a sub-buffer object and offset specified when the sub-buffer object is created is not aligned to CL_DEVICE_MEM_BASE_ADDR_ALIGN value for device associated with queue."))),
+            cl::Status::MEM_OBJECT_ALLOCATION_FAILURE => Err(Error::MemObjectAllocationFailure(format!("there is a failure to allocate memory for data store associated with buffer."))),
+            cl::Status::OUT_OF_RESOURCES => Err(Error::OutOfResources(format!("Failure to allocate resources on the device"))),
+            cl::Status::OUT_OF_HOST_MEMORY => Err(Error::OutOfHostMemory(format!("Failure to allocate resources on the host"))),
+            status => Err(Error::from_failed_status(status as i32, "clEnqueueFillBuffer"))
         }
     }
 }
@@ -10,6 +10,10 @@ mod error;
 mod context;
 mod device;
 mod memory;
+mod queue;
+mod program;
+mod kernel;
 mod platform;
+mod event;
 mod ffi;
 pub mod types;
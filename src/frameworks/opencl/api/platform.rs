@@ -2,6 +2,7 @@
 
 use frameworks::opencl::{API, Error};
 use frameworks::opencl::Platform;
+use libc;
 use super::types as cl;
 use super::ffi::*;
 use std::ptr;
@@ -36,6 +37,26 @@ impl API {
         Ok(ids.iter().map(|id| Platform::from_c(*id) ).collect())
     }
 
+    /// Returns the information `info` of the `platform` as a String.
+    ///
+    /// Use it with `CL_PLATFORM_NAME`, `CL_PLATFORM_VENDOR`, `CL_PLATFORM_VERSION`, etc.
+    pub fn load_platform_info(platform: &Platform, info: cl::platform_info) -> Result<String, Error> {
+        let mut size = 0;
+
+        try!(unsafe {API::ffi_get_platform_info(platform.id_c(), info, 0, ptr::null_mut(), &mut size)});
+
+        let mut buf: Vec<u8> = repeat(0u8).take(size).collect();
+        let buf_ptr = buf.as_mut_ptr() as *mut libc::c_void;
+
+        try!(unsafe {API::ffi_get_platform_info(platform.id_c(), info, size, buf_ptr, ptr::null_mut())});
+
+        // The returned string is null-terminated.
+        while buf.last() == Some(&0) {
+            buf.pop();
+        }
+        Ok(String::from_utf8_lossy(&buf).into_owned())
+    }
+
     unsafe fn ffi_get_platform_ids(
         num_entries: cl::uint,
         platforms: *mut cl::platform_id,
@@ -45,7 +66,23 @@ impl API {
             cl::Status::SUCCESS => Ok(()),
             cl::Status::INVALID_VALUE => Err(Error::InvalidValue(format!("`num_entries` is equal to zero and `platforms` is not NULL or if both `num_platforms` and `platforms` are NULL"))),
             cl::Status::OUT_OF_HOST_MEMORY => Err(Error::OutOfHostMemory(format!("Failure to allocate resources on the host"))),
-            _ => Err(Error::Other(format!("Unable to get platform ids")))
+            status => Err(Error::from_failed_status(status as i32, "clGetPlatformIDs"))
+        }
+    }
+
+    unsafe fn ffi_get_platform_info(
+        platform: cl::platform_id,
+        param_name: cl::platform_info,
+        param_value_size: libc::size_t,
+        param_value: *mut libc::c_void,
+        param_value_size_ret: *mut libc::size_t
+    ) -> Result<(), Error> {
+        match clGetPlatformInfo(platform, param_name, param_value_size, param_value, param_value_size_ret) {
+            cl::Status::SUCCESS => Ok(()),
+            cl::Status::INVALID_PLATFORM => Err(Error::InvalidPlatform(format!("`platform`: {:?} is not a valid platform", platform))),
+            cl::Status::INVALID_VALUE => Err(Error::InvalidValue(format!("`param_name`: {} is not one of the supported values or `param_value_size` is too small", param_name))),
+            cl::Status::OUT_OF_HOST_MEMORY => Err(Error::OutOfHostMemory(format!("Failure to allocate resources on the host"))),
+            status => Err(Error::from_failed_status(status as i32, "clGetPlatformInfo"))
         }
     }
 }
@@ -0,0 +1,127 @@
+//! Provides the OpenCL API with its program functionality.
+
+use libc;
+use frameworks::opencl::{API, Error, Context, Device};
+use super::types as cl;
+use super::ffi::*;
+use std::ffi::CString;
+use std::{ptr, mem};
+use std::iter::repeat;
+
+impl API {
+    /// Creates a OpenCL program from the provided source strings.
+    ///
+    /// The program still needs to be build, before kernels can be created from it.
+    pub fn create_program_with_source(context: &Context, program_source: Vec<&str>) -> Result<cl::program, Error> {
+        let src_strings: Vec<CString> = program_source.iter().map(|s| CString::new(s.as_bytes()).unwrap()).collect();
+        let lengths: Vec<libc::size_t> = program_source.iter().map(|s| s.len() as libc::size_t).collect();
+        let src_ptrs: Vec<*const libc::c_char> = src_strings.iter().map(|s| s.as_ptr()).collect();
+        Ok(try!(unsafe { API::ffi_create_program_with_source(context.id_c(), src_ptrs.len() as cl::uint, src_ptrs.as_ptr(), lengths.as_ptr()) }))
+    }
+
+    /// Builds the OpenCL program for the provided devices.
+    ///
+    /// `options` are passed on to the OpenCL compiler, e.g. `-cl-fast-relaxed-math`.
+    /// If the build fails, the returned `BuildProgramFailure` or `InvalidBuildOptions` contains
+    /// the build logs of the devices.
+    pub fn build_program(program: cl::program, devices: &[Device], options: &str) -> Result<(), Error> {
+        let device_ids: Vec<cl::device_id> = devices.iter().map(|device| device.id_c()).collect();
+        let c_options = try!(CString::new(options.as_bytes()).map_err(|_| Error::InvalidBuildOptions(format!("The build options {:?} contain a NUL byte.", options))));
+        let callback = unsafe { mem::transmute(ptr::null::<fn()>()) };
+        match unsafe { API::ffi_build_program(program, device_ids.len() as cl::uint, device_ids.as_ptr(), c_options.as_ptr(), callback, ptr::null_mut()) } {
+            Err(Error::BuildProgramFailure(err)) => {
+                Err(Error::BuildProgramFailure(format!("{}\n{}", err, API::program_build_logs(program, devices))))
+            },
+            Err(Error::InvalidBuildOptions(err)) => {
+                Err(Error::InvalidBuildOptions(format!("{} Options: {:?}\n{}", err, options, API::program_build_logs(program, devices))))
+            },
+            result => result
+        }
+    }
+
+    /// Returns the joined build logs of the `program` for the provided devices.
+    fn program_build_logs(program: cl::program, devices: &[Device]) -> String {
+        let logs: Vec<String> = devices.iter().map(|device| {
+            API::program_build_log(program, device).unwrap_or_else(|log_err| format!("Unable to get build log: {}", log_err))
+        }).collect();
+        logs.join("\n")
+    }
+
+    /// Returns the build log of the `program` for the provided device.
+    pub fn program_build_log(program: cl::program, device: &Device) -> Result<String, Error> {
+        let mut size = 0;
+
+        try!(unsafe { API::ffi_get_program_build_info(program, device.id_c(), cl::CL_PROGRAM_BUILD_LOG, 0, ptr::null_mut(), &mut size) });
+
+        let mut buf: Vec<u8> = repeat(0u8).take(size).collect();
+        let buf_ptr = buf.as_mut_ptr() as *mut libc::c_void;
+
+        try!(unsafe { API::ffi_get_program_build_info(program, device.id_c(), cl::CL_PROGRAM_BUILD_LOG, size, buf_ptr, ptr::null_mut()) });
+
+        // the build log is NULL-terminated
+        while buf.last() == Some(&0u8) {
+            buf.pop();
+        }
+        Ok(String::from_utf8_lossy(&buf).into_owned())
+    }
+
+    unsafe fn ffi_create_program_with_source(
+        context: cl::context_id,
+        count: cl::uint,
+        strings: *const *const libc::c_char,
+        lengths: *const libc::size_t,
+    ) -> Result<cl::program, Error> {
+        let mut errcode: i32 = 0;
+        let program = clCreateProgramWithSource(context, count, strings, lengths, &mut errcode);
+        match errcode {
+            errcode if errcode == cl::Status::SUCCESS as i32 => Ok(program),
+            errcode if errcode == cl::Status::INVALID_CONTEXT as i32 => Err(Error::InvalidContext(format!("context: {:?} is not a valid context.", context))),
+            errcode if errcode == cl::Status::INVALID_VALUE as i32 => Err(Error::InvalidValue(format!("count is zero or strings or any entry in strings is NULL."))),
+            errcode if errcode == cl::Status::OUT_OF_RESOURCES as i32 => Err(Error::OutOfResources(format!("Failure to allocate resources on the device"))),
+            errcode if errcode == cl::Status::OUT_OF_HOST_MEMORY as i32 => Err(Error::OutOfHostMemory(format!("Failure to allocate resources on the host"))),
+            errcode => Err(Error::from_failed_status(errcode, "clCreateProgramWithSource"))
+        }
+    }
+
+    unsafe fn ffi_build_program(
+        program: cl::program,
+        num_devices: cl::uint,
+        device_list: *const cl::device_id,
+        options: *const libc::c_char,
+        pfn_notify: extern fn (cl::program, *mut libc::c_void),
+        user_data: *mut libc::c_void
+    ) -> Result<(), Error> {
+        match clBuildProgram(program, num_devices, device_list, options, pfn_notify, user_data) {
+            cl::Status::SUCCESS => Ok(()),
+            cl::Status::INVALID_PROGRAM => Err(Error::Other(format!("program: {:?} is not a valid program object.", program))),
+            cl::Status::INVALID_VALUE => Err(Error::InvalidValue(format!("device_list is NULL and num_devices is greater than zero, or device_list is not NULL and num_devices is zero."))),
+            cl::Status::INVALID_DEVICE => Err(Error::InvalidDevice(format!("devices listed in device_list are not in the list of devices associated with program."))),
+            cl::Status::INVALID_BUILD_OPTIONS => Err(Error::InvalidBuildOptions(format!("the build options specified by options are invalid."))),
+            cl::Status::INVALID_OPERATION => Err(Error::InvalidOperation(format!("the build of a program executable for any of the devices listed in device_list by a previous call to clBuildProgram for program has not completed."))),
+            cl::Status::COMPILER_NOT_AVAILABLE => Err(Error::CompilerNotAvailable(format!("program is created with source and a compiler is not available."))),
+            cl::Status::BUILD_PROGRAM_FAILURE => Err(Error::BuildProgramFailure(format!("there is a failure to build the program executable."))),
+            cl::Status::OUT_OF_RESOURCES => Err(Error::OutOfResources(format!("Failure to allocate resources on the device"))),
+            cl::Status::OUT_OF_HOST_MEMORY => Err(Error::OutOfHostMemory(format!("Failure to allocate resources on the host"))),
+            status => Err(Error::from_failed_status(status as i32, "clBuildProgram"))
+        }
+    }
+
+    unsafe fn ffi_get_program_build_info(
+        program: cl::program,
+        device: cl::device_id,
+        param_name: cl::program_build_info,
+        param_value_size: libc::size_t,
+        param_value: *mut libc::c_void,
+        param_value_size_ret: *mut libc::size_t
+    ) -> Result<(), Error> {
+        match clGetProgramBuildInfo(program, device, param_name, param_value_size, param_value, param_value_size_ret) {
+            cl::Status::SUCCESS => Ok(()),
+            cl::Status::INVALID_DEVICE => Err(Error::InvalidDevice(format!("`device`: {:?} is not in the list of devices associated with program.", device))),
+            cl::Status::INVALID_VALUE => Err(Error::InvalidValue(format!("`param_name`: {} is not valid, or the size of the param value is too small.", param_name))),
+            cl::Status::INVALID_PROGRAM => Err(Error::Other(format!("program: {:?} is not a valid program object.", program))),
+            cl::Status::OUT_OF_RESOURCES => Err(Error::OutOfResources(format!("Failure to allocate resources on the device"))),
+            cl::Status::OUT_OF_HOST_MEMORY => Err(Error::OutOfHostMemory(format!("Failure to allocate resources on the host"))),
+            status => Err(Error::from_failed_status(status as i32, "clGetProgramBuildInfo"))
+        }
+    }
+}
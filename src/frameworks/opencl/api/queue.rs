@@ -0,0 +1,137 @@
+//! Provides the OpenCL API with its command queue functionality.
+
+use frameworks::opencl::{API, Error, Context, Device, Queue, Event};
+use super::types as cl;
+use super::ffi::*;
+use libc;
+use std::{mem, ptr};
+
+impl API {
+    /// Creates a OpenCL command queue on the provided `device` of the `context`.
+    ///
+    /// All commands, such as kernel executions and memory transfers, are issued through a
+    /// command queue.
+    pub fn create_queue(context: &Context, device: &Device, properties: cl::command_queue_properties) -> Result<cl::queue_id, Error> {
+        Ok(try!(unsafe { API::ffi_create_command_queue(context.id_c(), device.id_c(), properties) }))
+    }
+
+    /// Releases the OpenCL command queue.
+    ///
+    /// Commands, which are still queued, get completed before the queue is destroyed.
+    pub fn release_queue(queue: &Queue) -> Result<(), Error> {
+        Ok(try!(unsafe { API::ffi_release_command_queue(queue.id_c()) }))
+    }
+
+    /// Issues all previously queued commands on the `queue` to its device.
+    ///
+    /// Returns without waiting for the commands to complete.
+    pub fn flush(queue: &Queue) -> Result<(), Error> {
+        Ok(try!(unsafe { API::ffi_flush(queue.id_c()) }))
+    }
+
+    /// Blocks until all previously queued commands on the `queue` have been completed.
+    pub fn finish(queue: &Queue) -> Result<(), Error> {
+        Ok(try!(unsafe { API::ffi_finish(queue.id_c()) }))
+    }
+
+    /// Enqueues a marker, whose Event completes once all previously queued commands on the
+    /// `queue` have been completed.
+    ///
+    /// Unlike `finish`, returns immediately, so that the completion can be polled.
+    pub fn enqueue_marker(queue: &Queue) -> Result<Event, Error> {
+        let mut event: cl::event = ptr::null_mut();
+        try!(unsafe { API::ffi_enqueue_marker(queue.id_c(), &mut event) });
+        Ok(Event::from_c(event))
+    }
+
+    /// Returns the device, on which the commands of the `queue` are executed.
+    pub fn queue_device(queue: &Queue) -> Result<Device, Error> {
+        let mut device: cl::device_id = ptr::null_mut();
+        try!(unsafe { API::ffi_get_command_queue_info(queue.id_c(), cl::CL_QUEUE_DEVICE, mem::size_of::<cl::device_id>() as libc::size_t, &mut device as *mut cl::device_id as *mut libc::c_void, ptr::null_mut()) });
+        Ok(Device::from_c(device))
+    }
+
+    /// Returns the properties, with which the `queue` was created.
+    pub fn queue_properties(queue: &Queue) -> Result<cl::command_queue_properties, Error> {
+        let mut properties: cl::command_queue_properties = 0;
+        try!(unsafe { API::ffi_get_command_queue_info(queue.id_c(), cl::CL_QUEUE_PROPERTIES, mem::size_of::<cl::command_queue_properties>() as libc::size_t, &mut properties as *mut cl::command_queue_properties as *mut libc::c_void, ptr::null_mut()) });
+        Ok(properties)
+    }
+
+    unsafe fn ffi_create_command_queue(
+        context: cl::context_id,
+        device: cl::device_id,
+        properties: cl::command_queue_properties,
+    ) -> Result<cl::queue_id, Error> {
+        let mut errcode: i32 = 0;
+        let queue_id = clCreateCommandQueue(context, device, properties, &mut errcode);
+        match errcode {
+            errcode if errcode == cl::Status::SUCCESS as i32 => Ok(queue_id),
+            errcode if errcode == cl::Status::INVALID_CONTEXT as i32 => Err(Error::InvalidContext(format!("context: {:?} is not a valid context.", context))),
+            errcode if errcode == cl::Status::INVALID_DEVICE as i32 => Err(Error::InvalidDevice(format!("device: {:?} is not a valid device or is not associated with context.", device))),
+            errcode if errcode == cl::Status::INVALID_VALUE as i32 => Err(Error::InvalidValue(format!("values specified in properties are not valid."))),
+            errcode if errcode == cl::Status::INVALID_QUEUE_PROPERTIES as i32 => Err(Error::InvalidValue(format!("values specified in properties are valid but are not supported by the device."))),
+            errcode if errcode == cl::Status::OUT_OF_RESOURCES as i32 => Err(Error::OutOfResources(format!("Failure to allocate resources on the device"))),
+            errcode if errcode == cl::Status::OUT_OF_HOST_MEMORY as i32 => Err(Error::OutOfHostMemory(format!("Failure to allocate resources on the host"))),
+            errcode => Err(Error::from_failed_status(errcode, "clCreateCommandQueue"))
+        }
+    }
+
+    unsafe fn ffi_release_command_queue(command_queue: cl::queue_id) -> Result<(), Error> {
+        match clReleaseCommandQueue(command_queue) {
+            cl::Status::SUCCESS => Ok(()),
+            cl::Status::INVALID_COMMAND_QUEUE => Err(Error::InvalidCommandQueue(format!("command_queue: {:?} is not a valid command queue.", command_queue))),
+            cl::Status::OUT_OF_RESOURCES => Err(Error::OutOfResources(format!("Failure to allocate resources on the device"))),
+            cl::Status::OUT_OF_HOST_MEMORY => Err(Error::OutOfHostMemory(format!("Failure to allocate resources on the host"))),
+            status => Err(Error::from_failed_status(status as i32, "clReleaseCommandQueue"))
+        }
+    }
+
+    unsafe fn ffi_get_command_queue_info(
+        command_queue: cl::queue_id,
+        param_name: cl::command_queue_info,
+        param_value_size: libc::size_t,
+        param_value: *mut libc::c_void,
+        param_value_size_ret: *mut libc::size_t
+    ) -> Result<(), Error> {
+        match clGetCommandQueueInfo(command_queue, param_name, param_value_size, param_value, param_value_size_ret) {
+            cl::Status::SUCCESS => Ok(()),
+            cl::Status::INVALID_COMMAND_QUEUE => Err(Error::InvalidCommandQueue(format!("command_queue: {:?} is not a valid command-queue", command_queue))),
+            cl::Status::INVALID_VALUE => Err(Error::InvalidValue(format!("`param_name`: {} is not valid, or the size of the param value is too small.", param_name))),
+            cl::Status::OUT_OF_RESOURCES => Err(Error::OutOfResources(format!("Failure to allocate resources on the device"))),
+            cl::Status::OUT_OF_HOST_MEMORY => Err(Error::OutOfHostMemory(format!("Failure to allocate resources on the host"))),
+            status => Err(Error::from_failed_status(status as i32, "clGetCommandQueueInfo"))
+        }
+    }
+
+    unsafe fn ffi_enqueue_marker(command_queue: cl::queue_id, event: *mut cl::event) -> Result<(), Error> {
+        match clEnqueueMarker(command_queue, event) {
+            cl::Status::SUCCESS => Ok(()),
+            cl::Status::INVALID_COMMAND_QUEUE => Err(Error::InvalidCommandQueue(format!("command_queue: {:?} is not a valid command-queue", command_queue))),
+            cl::Status::INVALID_VALUE => Err(Error::InvalidValue(format!("`event` is NULL."))),
+            cl::Status::OUT_OF_RESOURCES => Err(Error::OutOfResources(format!("Failure to allocate resources on the device"))),
+            cl::Status::OUT_OF_HOST_MEMORY => Err(Error::OutOfHostMemory(format!("Failure to allocate resources on the host"))),
+            status => Err(Error::from_failed_status(status as i32, "clEnqueueMarker"))
+        }
+    }
+
+    unsafe fn ffi_flush(command_queue: cl::queue_id) -> Result<(), Error> {
+        match clFlush(command_queue) {
+            cl::Status::SUCCESS => Ok(()),
+            cl::Status::INVALID_COMMAND_QUEUE => Err(Error::InvalidCommandQueue(format!("command_queue: {:?} is not a valid command-queue", command_queue))),
+            cl::Status::OUT_OF_RESOURCES => Err(Error::OutOfResources(format!("Failure to allocate resources on the device"))),
+            cl::Status::OUT_OF_HOST_MEMORY => Err(Error::OutOfHostMemory(format!("Failure to allocate resources on the host"))),
+            status => Err(Error::from_failed_status(status as i32, "clFlush"))
+        }
+    }
+
+    unsafe fn ffi_finish(command_queue: cl::queue_id) -> Result<(), Error> {
+        match clFinish(command_queue) {
+            cl::Status::SUCCESS => Ok(()),
+            cl::Status::INVALID_COMMAND_QUEUE => Err(Error::InvalidCommandQueue(format!("command_queue: {:?} is not a valid command-queue", command_queue))),
+            cl::Status::OUT_OF_RESOURCES => Err(Error::OutOfResources(format!("Failure to allocate resources on the device"))),
+            cl::Status::OUT_OF_HOST_MEMORY => Err(Error::OutOfHostMemory(format!("Failure to allocate resources on the host"))),
+            status => Err(Error::from_failed_status(status as i32, "clFinish"))
+        }
+    }
+}
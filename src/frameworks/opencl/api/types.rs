@@ -37,6 +37,8 @@ pub type device_mem_cache_type       = uint;
 pub type device_local_mem_type       = uint;
 pub type device_exec_capabilities    = bitfield;
 pub type command_queue_properties    = bitfield;
+pub type device_partition_property   = libc::intptr_t;
+pub type device_affinity_domain      = bitfield;
 
 pub type context_properties          = libc::intptr_t;
 pub type context_info                = uint;
@@ -67,16 +69,17 @@ pub struct image_format {
     image_channel_data_type:    channel_type
 }
 
+#[repr(C)]
 pub struct buffer_region {
-    origin:     libc::size_t,
-    size:       libc::size_t
+    pub origin:     libc::size_t,
+    pub size:       libc::size_t
 }
 
 
 
 enum_from_primitive! {
 /// OpenCL error codes.
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Copy, Clone)]
 #[repr(C)]
 pub enum Status {
     SUCCESS = 0,
@@ -94,6 +97,7 @@ pub enum Status {
     MAP_FAILURE = -12,
     MISALIGNED_SUB_BUFFER_OFFSET = -13,
     EXEC_STATUS_ERROR_FOR_EVENTS_IN_WAIT_LIST = -14,
+    DEVICE_PARTITION_FAILED = -18,
     INVALID_VALUE = -30,
     INVALID_DEVICE_TYPE = -31,
     INVALID_PLATFORM = -32,
@@ -129,6 +133,7 @@ pub enum Status {
     INVALID_MIP_LEVEL = -62,
     INVALID_GLOBAL_WORK_SIZE = -63,
     INVALID_PROPERTY = -64,
+    INVALID_DEVICE_PARTITION_COUNT = -68,
     PLATFORM_NOT_FOUND_KHR = -1001,
 }
 }
@@ -240,6 +245,20 @@ pub static CL_DEVICE_NATIVE_VECTOR_WIDTH_DOUBLE:         uint = 0x103B;
 pub static CL_DEVICE_NATIVE_VECTOR_WIDTH_HALF:           uint = 0x103C;
 pub static CL_DEVICE_OPENCL_C_VERSION:                   uint = 0x103D;
 
+/* cl_device_partition_property */
+pub static CL_DEVICE_PARTITION_EQUALLY:                  device_partition_property = 0x1086;
+pub static CL_DEVICE_PARTITION_BY_COUNTS:                device_partition_property = 0x1087;
+pub static CL_DEVICE_PARTITION_BY_COUNTS_LIST_END:       device_partition_property = 0x0;
+pub static CL_DEVICE_PARTITION_BY_AFFINITY_DOMAIN:       device_partition_property = 0x1088;
+
+/* cl_device_affinity_domain */
+pub static CL_DEVICE_AFFINITY_DOMAIN_NUMA:               device_affinity_domain = 1 << 0;
+pub static CL_DEVICE_AFFINITY_DOMAIN_L4_CACHE:           device_affinity_domain = 1 << 1;
+pub static CL_DEVICE_AFFINITY_DOMAIN_L3_CACHE:           device_affinity_domain = 1 << 2;
+pub static CL_DEVICE_AFFINITY_DOMAIN_L2_CACHE:           device_affinity_domain = 1 << 3;
+pub static CL_DEVICE_AFFINITY_DOMAIN_L1_CACHE:           device_affinity_domain = 1 << 4;
+pub static CL_DEVICE_AFFINITY_DOMAIN_NEXT_PARTITIONABLE: device_affinity_domain = 1 << 5;
+
 /* cl_device_fp_config - bitfield */
 pub static CL_FP_DENORM:                                 bitfield = 1;
 pub static CL_FP_INF_NAN:                                bitfield = 1 << 1;
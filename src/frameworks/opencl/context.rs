@@ -2,44 +2,233 @@
 
 use device::{IDevice, DeviceType};
 use super::api::types as cl;
-use super::{API, Error, Device};
+use super::{API, Error, Device, Queue, QueueFlags, Program, Kernel};
 use super::memory::*;
 use memory::MemoryType;
+use memory_pool::MemoryPool;
 use std::{ptr, mem};
+use libc::c_void;
 use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+
+/// The OpenCL source of the kernel, which fills buffers on devices without `clEnqueueFillBuffer`.
+///
+/// The bytes of the pattern are passed in little-endian order, independent of the host.
+const FILL_SOURCE: &'static str = "
+__kernel void fill(const uint n, const uint pattern, const uint pattern_size, __global uchar *x)
+{
+    uint i = get_global_id(0);
+    if (i < n) {
+        x[i] = (uchar)(pattern >> (8 * (i % pattern_size)));
+    }
+}
+";
 
 #[derive(Debug, Clone)]
 /// Defines a OpenCL Context.
+///
+/// Clones of the Context share the underlying OpenCL context and its command queues, one per
+/// device. They are released exactly once, when the last clone of the Context goes out of scope.
+/// Every clone selects its own active device, on whose queue work gets issued.
 pub struct Context {
-    id: isize,
+    id: Arc<isize>,
     devices: Vec<Device>,
+    queues: Vec<Queue>,
+    active: usize,
+    pool: Option<MemoryPool<Memory>>,
+    /// The fill kernel, once it has been built for a device without `clEnqueueFillBuffer`.
+    fill_kernel: Arc<Mutex<Option<isize>>>,
+}
+
+impl Drop for Context {
+    #[allow(unused_must_use)]
+    fn drop(&mut self) {
+        if Arc::get_mut(&mut self.id).is_some() {
+            if let Some(ref pool) = self.pool {
+                pool.drain();
+            }
+            if let Ok(fill_kernel) = self.fill_kernel.lock() {
+                if let Some(id) = *fill_kernel {
+                    API::release_kernel(&Kernel::from_isize(id));
+                }
+            }
+            for queue in &self.queues {
+                API::release_queue(queue);
+            }
+            API::release_context(self);
+        }
+    }
 }
 
 impl Context {
     /// Initializes a new OpenCL platform.
+    ///
+    /// Creates an in-order command queue on every device. Kernels of this context get executed
+    /// through the queue of the active device, which is the first device.
     pub fn new(devices: Vec<Device>) -> Result<Context, Error> {
+        Context::with_queue_flags(devices, QueueFlags::empty())
+    }
+
+    /// Initializes a new OpenCL platform, whose command queue is created with `flags`.
+    ///
+    /// Use `QUEUE_OUT_OF_ORDER_EXEC_MODE_ENABLE` to let independent commands overlap. Returns an
+    /// error, if a device does not support the `flags`.
+    pub fn with_queue_flags(devices: Vec<Device>, flags: QueueFlags) -> Result<Context, Error> {
         let callback = unsafe { mem::transmute(ptr::null::<fn()>()) };
-        Ok(
-            Context::from_c(
-                try!(API::create_context(devices.clone(), ptr::null(), callback, ptr::null_mut())),
-                devices.clone()
-            )
-        )
+        let mut context = Context::from_c(
+            try!(API::create_context(devices.clone(), ptr::null(), callback, ptr::null_mut())),
+            devices.clone()
+        );
+        for device in &devices {
+            let queue = try!(Queue::new(&context, device, flags));
+            context.queues.push(queue);
+        }
+        Ok(context)
     }
 
     /// Initializes a new OpenCL platform from its C type.
     pub fn from_c(id: cl::context_id, devices: Vec<Device>) -> Context {
-        Context { id: id as isize, devices: devices }
+        Context { id: Arc::new(id as isize), devices: devices, queues: Vec::new(), active: 0, pool: None, fill_kernel: Arc::new(Mutex::new(None)) }
+    }
+
+    /// Returns the command queue of the active device of the context.
+    pub fn queue(&self) -> Option<&Queue> {
+        self.queues.get(self.active)
+    }
+
+    /// Returns the index of the active device, on whose command queue work gets issued.
+    pub fn active_device(&self) -> usize {
+        self.active
+    }
+
+    /// Selects the device at `index`, on whose command queue work gets issued from now on.
+    ///
+    /// Only affects this Context and the clones, which are made afterwards. Commands on the
+    /// queues of different devices are not ordered, so synchronize before switching, if the
+    /// devices work on the same memory. Returns an error, if there is no device at `index`.
+    pub fn set_active_device(&mut self, index: usize) -> Result<(), Error> {
+        if index >= self.devices.len() {
+            return Err(Error::InvalidDevice(format!("No device at index {}; the context holds {} devices.", index, self.devices.len())))
+        }
+        self.active = index;
+        Ok(())
+    }
+
+    /// Lets allocations of the context be served from, and returned to, the `pool`.
+    ///
+    /// Only affects this Context and the clones, which are made afterwards.
+    pub fn set_pool(&mut self, pool: Option<MemoryPool<Memory>>) {
+        self.pool = pool;
+    }
+
+    /// Returns the MemoryPool of the context, if there is one.
+    pub fn pool(&self) -> Option<&MemoryPool<Memory>> {
+        self.pool.as_ref()
+    }
+
+    /// Allocates `size` bytes of memory on the device, or reuses pooled memory of that size.
+    ///
+    /// Unlike `alloc_memory`, returns the error of the driver, e.g. if the device ran out of
+    /// memory.
+    pub fn try_alloc_memory(&self, size: usize) -> Result<Memory, Error> {
+        if let Some(memory) = self.pool.as_ref().and_then(|pool| pool.take(size)) {
+            co_debug!("Reusing {} bytes of pooled OpenCL memory", size);
+            return Ok(memory)
+        }
+        Memory::new(self, size)
+    }
+
+    /// Returns the memory `mem` to the MemoryPool of the context.
+    ///
+    /// Frees `mem`, if the context has no pool or the pool is full.
+    pub fn recycle_memory(&self, mem: Memory) {
+        if let Some(ref pool) = self.pool {
+            if mem.is_owned() {
+                let size = mem.size();
+                pool.give_back(size, mem);
+            }
+        }
+    }
+
+    /// Fills the memory `mem` with the repeated bytes of `pattern`.
+    ///
+    /// Blocks until the memory has been filled. Uses `clEnqueueFillBuffer`, if all devices of
+    /// the context support OpenCL 1.2, and otherwise a fill kernel, which supports patterns of up
+    /// to four bytes. The kernel is built on the first fill and shared by all clones of the
+    /// Context.
+    pub fn fill(&self, mem: &mut Memory, pattern: &[u8]) -> Result<(), Error> {
+        let queue = try!(self.queue().ok_or(Error::InvalidCommandQueue(format!("The OpenCL context has no command queue."))));
+        if pattern.is_empty() || mem.size() % pattern.len() != 0 {
+            return Err(Error::InvalidValue(format!("Unable to fill memory of size {} with a pattern of {} bytes.", mem.size(), pattern.len())))
+        }
+        if mem.size() == 0 {
+            return Ok(())
+        }
+        if self.devices.iter().all(|device| device.supports_fill_buffer()) {
+            return API::fill_buffer(queue, mem, pattern)
+        }
+        if pattern.len() > 4 {
+            return Err(Error::InvalidValue(format!("The fill kernel supports patterns of up to 4 bytes, but got {} bytes.", pattern.len())))
+        }
+        let word = pattern.iter().enumerate().fold(0u32, |word, (i, byte)| word | (*byte as u32) << (8 * i));
+        // Held until the kernel is enqueued, so that clones don't overwrite each others arguments.
+        let mut fill_kernel = self.fill_kernel.lock().unwrap();
+        if fill_kernel.is_none() {
+            let program = try!(Program::from_source(self, FILL_SOURCE, &["fill"]));
+            let kernel = try!(program.kernel("fill").ok_or(Error::InvalidKernelName(format!("The fill program has no kernel `fill`."))));
+            *fill_kernel = Some(kernel.id_c() as isize);
+        }
+        let kernel = Kernel::from_isize(fill_kernel.unwrap());
+        try!(kernel.set_arg(0, &(mem.size() as u32)));
+        try!(kernel.set_arg(1, &word));
+        try!(kernel.set_arg(2, &(pattern.len() as u32)));
+        try!(kernel.set_arg_memory(3, mem));
+        try!(API::enqueue_kernel(queue, &kernel, &[mem.size()], &[]));
+        queue.finish()
+    }
+
+    /// Returns a view of `size` bytes, which starts `offset` bytes into the memory `mem`.
+    ///
+    /// The view is a sub-buffer, which shares the data store of `mem`. Its `offset` needs to be
+    /// a multiple of the `CL_DEVICE_MEM_BASE_ADDR_ALIGN` of every device of the context.
+    pub fn view_memory(&self, mem: &Memory, offset: usize, size: usize) -> Result<Memory, Error> {
+        match offset.checked_add(size) {
+            Some(end) if end <= mem.size() => (),
+            _ => return Err(Error::InvalidValue(format!("A view of {} bytes at offset {} exceeds the memory of {} bytes.", size, offset, mem.size())))
+        }
+        for device in &self.devices {
+            if let Some(align) = device.mem_base_addr_align() {
+                if align != 0 && offset % align != 0 {
+                    return Err(Error::MisalignedSubBufferOffset(format!("The offset {} is not aligned to the {} bytes of CL_DEVICE_MEM_BASE_ADDR_ALIGN of device {:?}.", offset, align, device.name())))
+                }
+            }
+        }
+        mem.sub_buffer(offset, size)
+    }
+
+    /// Allocates `size` bytes of memory, which start at a multiple of `align`.
+    ///
+    /// OpenCL does not expose the device address of a buffer, so the allocation can not be padded
+    /// to an alignment. Buffers are aligned to the `CL_DEVICE_MEM_BASE_ADDR_ALIGN` of their
+    /// device, therefore returns an error, if `align` exceeds it on any device of the context.
+    pub fn alloc_memory_aligned(&self, size: usize, align: usize) -> Result<Memory, Error> {
+        for device in &self.devices {
+            match device.mem_base_addr_align() {
+                Some(base_align) if align <= base_align => {},
+                _ => return Err(Error::InvalidValue(format!("Unable to align memory to {} bytes, which exceeds the CL_DEVICE_MEM_BASE_ADDR_ALIGN of device {:?}.", align, device.name())))
+            }
+        }
+        self.try_alloc_memory(size)
     }
 
     /// Returns the id as isize.
     pub fn id(&self) -> isize {
-        self.id
+        *self.id
     }
 
     /// Returns the id as its C type.
     pub fn id_c(&self) -> cl::context_id {
-        self.id as cl::context_id
+        *self.id as cl::context_id
     }
 }
 
@@ -48,7 +237,7 @@ impl IDevice for Context {
     type M = Memory;
 
     fn id(&self) -> isize {
-        self.id
+        *self.id
     }
 
     fn hardwares(&self) -> Vec<Device> {
@@ -56,20 +245,28 @@ impl IDevice for Context {
     }
 
     fn alloc_memory(&self, size: usize) -> Memory {
-        unimplemented!();
+        match self.try_alloc_memory(size) {
+            Ok(memory) => memory,
+            Err(err) => panic!(err)
+        }
     }
 
-    fn sync_memory_to(&self, source: &Memory, dest: &mut MemoryType, dest_device: &DeviceType) {
-        /*
-        let src = Memory::<Vec<u8>>::from_c(source as cl::memory_id);
-        match dest_device.clone() {
-            DeviceType::Native(cpu) => {
-                unimplemented!();
-            }
-            DeviceType::OpenCL(_) => {},
+    fn sync_memory_to(&self, source: &Memory, dest: &mut MemoryType, dest_device: &DeviceType) -> Result<(), ::framework::Error> {
+        match *dest_device {
+            DeviceType::Native(_) => {
+                let queue = try!(self.queue().ok_or(Error::InvalidCommandQueue(format!("The OpenCL context has no command queue."))));
+                let data = try!(dest.as_mut_native().ok_or(Error::InvalidHostPtr(format!("The destination is no native memory.")))).as_mut_slice::<u8>();
+                Ok(try!(API::read_from_memory(queue, source, data.as_mut_ptr() as *mut c_void, data.len())))
+            },
+            DeviceType::OpenCL(_) => Err(::framework::Error::NotSupported(format!("Syncing memory between OpenCL devices is not supported."))),
+            #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+            DeviceType::Cuda(_) => Err(::framework::Error::NotSupported(format!("Syncing memory from an OpenCL to a Cuda device is not supported."))),
         }
-        */
-        unimplemented!()
+    }
+
+    fn synchronize(&self) -> Result<(), ::framework::Error> {
+        let queue = try!(self.queue().ok_or(Error::InvalidCommandQueue(format!("The OpenCL context has no command queue."))));
+        Ok(try!(queue.finish()))
     }
 }
 
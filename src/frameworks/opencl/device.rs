@@ -1,30 +1,47 @@
 //! Provides a Rust wrapper around OpenCL's device.
 
-use hardware::{IHardware, HardwareType};
+use hardware::{IHardware, HardwareType, Partition, AffinityDomain};
+use framework::Error;
 use super::api::types as cl;
 use super::api::API;
+use super::queue::QueueFlags;
+use libraries::blas::BlasNum;
 use std::io::Cursor;
+use std::sync::Arc;
 use byteorder::{ByteOrder, LittleEndian, ReadBytesExt};
 
 #[derive(Debug, Clone)]
 /// Defines a OpenCL Device.
 ///
 /// Can later be transformed into a [Collenchyma hardware][hardware].
+/// A sub-device, created by `create_sub_devices`, is released exactly once, when the last clone
+/// of the Device goes out of scope.
 /// [hardware]: ../../hardware/index.html
 pub struct Device {
     id: isize,
     name: Option<String>,
     device_type: Option<HardwareType>,
     compute_units: Option<isize>,
+    memory: Option<usize>,
+    /// Counts the clones of a sub-device. Root devices are not reference counted.
+    sub_device: Option<Arc<isize>>,
 }
 
 impl Default for Device {
     fn default() -> Self {
-        Device {
-            id: -1,
-            name: None,
-            device_type: None,
-            compute_units: None,
+        Device::from_isize(-1)
+    }
+}
+
+impl Drop for Device {
+    #[allow(unused_must_use)]
+    fn drop(&mut self) {
+        let last = match self.sub_device {
+            Some(ref mut sub_device) => Arc::get_mut(sub_device).is_some(),
+            None => false
+        };
+        if last {
+            API::release_device(self);
         }
     }
 }
@@ -32,12 +49,34 @@ impl Default for Device {
 impl Device {
     /// Initializes a new OpenCL device.
     pub fn from_isize(id: isize) -> Device {
-        Device { id: id, ..Device::default() }
+        Device {
+            id: id,
+            name: None,
+            device_type: None,
+            compute_units: None,
+            memory: None,
+            sub_device: None,
+        }
     }
 
     /// Initializes a new OpenCL device from its C type.
     pub fn from_c(id: cl::device_id) -> Device {
-        Device { id: id as isize, ..Device::default() }
+        Device::from_isize(id as isize)
+    }
+
+    /// Initializes a new OpenCL sub-device from its C type.
+    ///
+    /// Takes over the reference, which `clCreateSubDevices` returned, and releases it, when the
+    /// last clone of the Device goes out of scope.
+    pub fn from_sub_device_c(id: cl::device_id) -> Device {
+        let mut device = Device::from_c(id);
+        device.sub_device = Some(Arc::new(id as isize));
+        device
+    }
+
+    /// Returns whether the device is a sub-device, which gets released on drop.
+    pub fn is_sub_device(&self) -> bool {
+        self.sub_device.is_some()
     }
 
     /// Returns the id as its C type.
@@ -73,6 +112,94 @@ impl Device {
         self.clone()
     }
 
+    /// Returns the space separated list of extensions, supported by the device.
+    pub fn extensions(&self) -> Option<String> {
+        match API::load_device_info(self, cl::CL_DEVICE_EXTENSIONS) {
+            Ok(result) => Some(result.to_string()),
+            Err(_) => None
+        }
+    }
+
+    /// Returns whether the device supports the OpenCL `extension`.
+    pub fn supports_extension(&self, extension: &str) -> bool {
+        match self.extensions() {
+            Some(extensions) => extensions.split_whitespace().any(|ext| ext.trim_matches('\0') == extension),
+            None => false
+        }
+    }
+
+    /// Returns whether the device supports double precision through the `cl_khr_fp64` extension.
+    pub fn supports_fp64(&self) -> bool {
+        self.supports_element::<f64>()
+    }
+
+    /// Returns whether the device supports the BLAS element type `T`.
+    pub fn supports_element<T: BlasNum>(&self) -> bool {
+        T::EXTENSION.map_or(true, |extension| self.supports_extension(extension))
+    }
+
+    /// Returns the command queue properties, supported by the device.
+    pub fn queue_flags(&self) -> Option<QueueFlags> {
+        match API::load_device_info(self, cl::CL_DEVICE_QUEUE_PROPERTIES) {
+            Ok(result) => result.to_usize().map(|flags| QueueFlags::from_bits_truncate(flags as cl::bitfield)),
+            Err(_) => None
+        }
+    }
+
+    /// Returns whether the device supports half precision through the `cl_khr_fp16` extension.
+    pub fn supports_fp16(&self) -> bool {
+        match self.extensions() {
+            Some(extensions) => extensions.split_whitespace().any(|ext| ext.trim_matches('\0') == "cl_khr_fp16"),
+            None => false
+        }
+    }
+
+    /// Returns the OpenCL version of the device as `(major, minor)`.
+    ///
+    /// Parsed from the version string, which has the form `OpenCL <major>.<minor> <vendor info>`.
+    pub fn version(&self) -> Option<(u32, u32)> {
+        let version = match API::load_device_info(self, cl::CL_DEVICE_VERSION) {
+            Ok(result) => result.to_string(),
+            Err(_) => return None
+        };
+        let number = match version.trim_matches('\0').split_whitespace().nth(1) {
+            Some(number) => number.to_owned(),
+            None => return None
+        };
+        let mut parts = number.split('.').map(|part| part.parse::<u32>());
+        match (parts.next(), parts.next()) {
+            (Some(Ok(major)), Some(Ok(minor))) => Some((major, minor)),
+            _ => None
+        }
+    }
+
+    /// Returns whether the device supports filling buffers through `clEnqueueFillBuffer`.
+    ///
+    /// The command has been introduced with OpenCL 1.2.
+    pub fn supports_fill_buffer(&self) -> bool {
+        match self.version() {
+            Some(version) => version >= (1, 2),
+            None => false
+        }
+    }
+
+    /// Returns the maximum number of work-items in a work-group, supported by the device.
+    pub fn max_work_group_size(&self) -> Option<usize> {
+        match API::load_device_info(self, cl::CL_DEVICE_MAX_WORK_GROUP_SIZE) {
+            Ok(result) => result.to_usize(),
+            Err(_) => None
+        }
+    }
+
+    /// Returns the alignment in bytes, which the offset of a sub-buffer needs on the device.
+    pub fn mem_base_addr_align(&self) -> Option<usize> {
+        match API::load_device_info(self, cl::CL_DEVICE_MEM_BASE_ADDR_ALIGN) {
+            // The device reports the alignment in bits.
+            Ok(result) => Some(result.to_isize() as usize / 8),
+            Err(_) => None
+        }
+    }
+
     /// Loads the compute units of the device via a foreign OpenCL call.
     pub fn load_compute_units(&mut self) -> Self {
         self.compute_units = match API::load_device_info(self, cl::CL_DEVICE_MAX_COMPUTE_UNITS) {
@@ -81,6 +208,15 @@ impl Device {
         };
         self.clone()
     }
+
+    /// Loads the global memory size of the device via a foreign OpenCL call.
+    pub fn load_memory(&mut self) -> Self {
+        self.memory = match API::load_device_info(self, cl::CL_DEVICE_GLOBAL_MEM_SIZE) {
+            Ok(result) => result.to_usize(),
+            Err(_) => None
+        };
+        self.clone()
+    }
 }
 
 impl IHardware for Device {
@@ -115,6 +251,10 @@ impl IHardware for Device {
         self.clone()
     }
 
+    fn memory(&self) -> Option<usize> {
+        self.memory
+    }
+
     #[allow(missing_docs)]
     fn build(self) -> Device {
         Device {
@@ -122,8 +262,42 @@ impl IHardware for Device {
             name: self.name(),
             device_type: self.hardware_type(),
             compute_units: self.compute_units(),
+            memory: self.memory(),
+            sub_device: self.sub_device.clone(),
         }
     }
+
+    /// Partitions the device into sub-devices via `clCreateSubDevices`.
+    ///
+    /// Requires OpenCL 1.2. The sub-devices can be used like any other device.
+    fn create_sub_devices(&self, partition: Partition) -> Result<Vec<Device>, Error> {
+        let mut properties: Vec<cl::device_partition_property> = Vec::new();
+        match partition {
+            Partition::Equally(n) => {
+                properties.push(cl::CL_DEVICE_PARTITION_EQUALLY);
+                properties.push(n as cl::device_partition_property);
+            },
+            Partition::ByCounts(counts) => {
+                properties.push(cl::CL_DEVICE_PARTITION_BY_COUNTS);
+                properties.extend(counts.iter().map(|count| *count as cl::device_partition_property));
+                properties.push(cl::CL_DEVICE_PARTITION_BY_COUNTS_LIST_END);
+            },
+            Partition::ByAffinityDomain(domain) => {
+                let domain = match domain {
+                    AffinityDomain::Numa => cl::CL_DEVICE_AFFINITY_DOMAIN_NUMA,
+                    AffinityDomain::L4Cache => cl::CL_DEVICE_AFFINITY_DOMAIN_L4_CACHE,
+                    AffinityDomain::L3Cache => cl::CL_DEVICE_AFFINITY_DOMAIN_L3_CACHE,
+                    AffinityDomain::L2Cache => cl::CL_DEVICE_AFFINITY_DOMAIN_L2_CACHE,
+                    AffinityDomain::L1Cache => cl::CL_DEVICE_AFFINITY_DOMAIN_L1_CACHE,
+                    AffinityDomain::NextPartitionable => cl::CL_DEVICE_AFFINITY_DOMAIN_NEXT_PARTITIONABLE,
+                };
+                properties.push(cl::CL_DEVICE_PARTITION_BY_AFFINITY_DOMAIN);
+                properties.push(domain as cl::device_partition_property);
+            },
+        }
+        properties.push(0);
+        Ok(try!(API::create_sub_devices(self, &properties)))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -157,4 +331,19 @@ impl DeviceInfo {
         let mut bytes = Cursor::new(&self.info);
         bytes.read_u32::<LittleEndian>().unwrap() as isize
     }
+
+    /// Reads the info as an unsigned integer of the size, which the device returned.
+    ///
+    /// Infos of type `size_t` are four bytes long on 32-bit hosts, `cl_ulong` and bitfields are
+    /// always eight bytes long. Returns `None` for any other size, or if the value does not fit
+    /// into a `usize`.
+    pub fn to_usize(self) -> Option<usize> {
+        let mut bytes = Cursor::new(&self.info);
+        let value = match self.info.len() {
+            4 => bytes.read_u32::<LittleEndian>().ok().map(|value| value as u64),
+            8 => bytes.read_u64::<LittleEndian>().ok(),
+            _ => None
+        };
+        value.and_then(|value| if value <= ::std::usize::MAX as u64 { Some(value as usize) } else { None })
+    }
 }
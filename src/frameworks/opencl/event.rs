@@ -0,0 +1,61 @@
+//! Provides a Rust wrapper around OpenCL's event.
+//!
+//! ## OpenCL Event
+//!
+//! An event identifies a command, such as a kernel execution or a memory copy, which has been
+//! enqueued on a [command queue][queue]. Passing events as a wait list to further commands
+//! expresses dependencies between them, which is required for queues with out-of-order execution.
+//!
+//! [queue]: ../queue/index.html
+
+use super::api::types as cl;
+use super::api::{API, Error};
+
+#[derive(Debug)]
+/// Defines a OpenCL Event.
+///
+/// The underlying OpenCL event is released, when the Event goes out of scope.
+pub struct Event {
+    id: isize,
+}
+
+impl Drop for Event {
+    #[allow(unused_must_use)]
+    fn drop(&mut self) {
+        API::release_event(self);
+    }
+}
+
+impl Event {
+    /// Initializes a new OpenCL event from its C type.
+    ///
+    /// Takes ownership of the reference to the event, which gets released on drop.
+    pub fn from_c(id: cl::event) -> Event {
+        Event { id: id as isize }
+    }
+
+    /// Returns the id as isize.
+    pub fn id(&self) -> isize {
+        self.id
+    }
+
+    /// Returns the id as its C type.
+    pub fn id_c(&self) -> cl::event {
+        self.id as cl::event
+    }
+
+    /// Returns whether the command of the event has been completed, without blocking.
+    pub fn is_complete(&self) -> Result<bool, Error> {
+        API::event_complete(self)
+    }
+
+    /// Blocks until the command of the event has been completed.
+    pub fn wait(&self) -> Result<(), Error> {
+        API::wait_for_events(&[self])
+    }
+
+    /// Blocks until the commands of all the `events` have been completed.
+    pub fn wait_all(events: &[&Event]) -> Result<(), Error> {
+        API::wait_for_events(events)
+    }
+}
@@ -2,32 +2,133 @@
 
 use operation::IOperation;
 use super::api::types as cl;
-use super::api::API;
+use super::api::{API, Error};
+use super::{Memory, Queue, Event};
+use memory::MemoryType;
+use libc;
+use std::{mem, ptr};
+use std::marker::PhantomData;
 
 #[derive(Debug, Copy, Clone)]
 /// Defines a OpenCL Kernel.
 ///
 /// A Kernel is OpenCL's version of Collenchyma's [operation][operation].
 /// [operation]: ../../operation/index.html
+///
+/// Setting the arguments and enqueuing a Kernel are separate calls, so two threads launching the
+/// same Kernel would overwrite each others arguments. Therefore a Kernel can not be sent to or
+/// shared with another thread.
 pub struct Kernel {
     id: isize,
+    queue: Option<Queue>,
+    /// Opts out of `Send` and `Sync`.
+    _thread: PhantomData<*const ()>,
+}
+
+#[derive(Debug, Copy, Clone)]
+/// Defines a typed argument of a OpenCL Kernel.
+pub enum KernelArg<'a> {
+    /// A OpenCL memory, provided as a `__global` pointer.
+    Mem(&'a MemoryType),
+    /// A single precision float.
+    Scalar(f32),
+    /// A 32 bit integer.
+    ScalarI32(i32),
+    /// `size` bytes of `__local` memory.
+    Local(usize),
 }
 
 impl Kernel {
+    /// Creates the OpenCL kernel `name` from a build program.
+    ///
+    /// The kernel gets executed on the provided `queue`.
+    pub fn new(program: cl::program, name: &str, queue: Queue) -> Result<Kernel, Error> {
+        let id = try!(API::create_kernel(program, name));
+        Ok(Kernel { id: id as isize, queue: Some(queue), _thread: PhantomData })
+    }
+
     /// Initializes a new OpenCL device.
     pub fn from_isize(id: isize) -> Kernel {
-        Kernel { id: id }
+        Kernel { id: id, queue: None, _thread: PhantomData }
     }
 
     /// Initializes a new OpenCL device from its C type.
     pub fn from_c(id: cl::kernel_id) -> Kernel {
-        Kernel { id: id as isize }
+        Kernel { id: id as isize, queue: None, _thread: PhantomData }
     }
 
     /// Returns the id as its C type.
     pub fn id_c(&self) -> cl::kernel_id {
         self.id as cl::kernel_id
     }
+
+    /// Sets a plain value, such as an integer, as the kernel argument at `index`.
+    pub fn set_arg<T: Copy>(&self, index: u32, value: &T) -> Result<(), Error> {
+        API::set_kernel_arg(self, index, mem::size_of::<T>() as libc::size_t, value as *const T as *const libc::c_void)
+    }
+
+    /// Sets the OpenCL memory as the kernel argument at `index`.
+    pub fn set_arg_memory(&self, index: u32, memory: &Memory) -> Result<(), Error> {
+        let id = memory.id_c();
+        API::set_kernel_arg(self, index, mem::size_of::<cl::memory_id>() as libc::size_t, &id as *const cl::memory_id as *const libc::c_void)
+    }
+
+    /// Allocates `size` bytes of local memory as the kernel argument at `index`.
+    pub fn set_arg_local(&self, index: u32, size: usize) -> Result<(), Error> {
+        API::set_kernel_arg(self, index, size as libc::size_t, ptr::null())
+    }
+
+    /// Sets all the arguments of the kernel.
+    ///
+    /// Returns an `InvalidKernelArgs` error, if the number of `args` does not match the number
+    /// of arguments of the kernel, or an `InvalidMemObject` error for a memory, which is no
+    /// OpenCL memory.
+    pub fn set_args(&self, args: &[KernelArg]) -> Result<(), Error> {
+        let num_args = try!(API::kernel_num_args(self));
+        if num_args as usize != args.len() {
+            return Err(Error::InvalidKernelArgs(format!("Kernel expects {} arguments, but {} were provided.", num_args, args.len())))
+        }
+        for (index, arg) in args.iter().enumerate() {
+            let index = index as u32;
+            try!(match *arg {
+                KernelArg::Mem(memory) => {
+                    let memory = try!(memory.as_opencl().ok_or(Error::InvalidMemObject(format!("Argument {} is no OpenCL memory.", index))));
+                    self.set_arg_memory(index, memory)
+                },
+                KernelArg::Scalar(value) => self.set_arg(index, &value),
+                KernelArg::ScalarI32(value) => self.set_arg(index, &value),
+                KernelArg::Local(size) => self.set_arg_local(index, size),
+            });
+        }
+        Ok(())
+    }
+
+    /// Clamps the `requested` work-group size to the maximum of the kernel on the device of its
+    /// command queue.
+    pub fn clamp_work_group_size(&self, requested: usize) -> Result<usize, Error> {
+        let queue = try!(self.queue.ok_or(Error::InvalidCommandQueue(format!("Kernel has no command queue to determine its device."))));
+        let max = try!(API::kernel_work_group_size(self, &try!(queue.device())));
+        Ok(if requested < max { requested } else { max })
+    }
+
+    /// Enqueues the kernel for execution on its command queue.
+    pub fn enqueue(&self, global_work_size: &[usize], local_work_size: &[usize]) -> Result<(), Error> {
+        match self.queue {
+            Some(ref queue) => API::enqueue_kernel(queue, self, global_work_size, local_work_size),
+            None => Err(Error::InvalidCommandQueue(format!("Kernel has no command queue to be enqueued on."))),
+        }
+    }
+
+    /// Enqueues the kernel for execution on its command queue, once all the commands of the
+    /// `wait_list` have been completed.
+    ///
+    /// Returns the Event of the execution, which can be waited on or passed to further commands.
+    pub fn enqueue_with_events(&self, global_work_size: &[usize], local_work_size: &[usize], wait_list: &[&Event]) -> Result<Event, Error> {
+        match self.queue {
+            Some(ref queue) => API::enqueue_kernel_with_events(queue, self, global_work_size, local_work_size, wait_list),
+            None => Err(Error::InvalidCommandQueue(format!("Kernel has no command queue to be enqueued on."))),
+        }
+    }
 }
 
 impl IOperation for Kernel {}
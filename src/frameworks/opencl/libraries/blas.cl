@@ -0,0 +1,391 @@
+// BLAS kernel template for the OpenCL backend.
+//
+// Instantiated per element type by `templated_source`, which replaces $T with the OpenCL C type
+// and $P with the BLAS prefix of the type. Reductions run in a single work-group, whose size has
+// to be a power of two.
+
+__kernel void $Paxpy(const uint n,
+                    __global const $T *a,
+                    __global const $T *x,
+                    __global $T *y)
+{
+    uint i = get_global_id(0);
+    if (i < n) {
+        y[i] = a[0] * x[i] + y[i];
+    }
+}
+
+__kernel void $Pasum(const uint n,
+                    __global const $T *x,
+                    __global $T *result,
+                    __local $T *scratch)
+{
+    uint lid = get_local_id(0);
+    uint lsize = get_local_size(0);
+
+    $T sum = ($T)0;
+    for (uint i = lid; i < n; i += lsize) {
+        sum += fabs(x[i]);
+    }
+    scratch[lid] = sum;
+    barrier(CLK_LOCAL_MEM_FENCE);
+
+    for (uint offset = lsize / 2; offset > 0; offset >>= 1) {
+        if (lid < offset) {
+            scratch[lid] += scratch[lid + offset];
+        }
+        barrier(CLK_LOCAL_MEM_FENCE);
+    }
+
+    if (lid == 0) {
+        result[0] = scratch[0];
+    }
+}
+
+__kernel void $Pdot(const uint n,
+                   __global const $T *x,
+                   __global const $T *y,
+                   __global $T *result,
+                   __local $T *scratch)
+{
+    uint lid = get_local_id(0);
+    uint lsize = get_local_size(0);
+
+    $T sum = ($T)0;
+    for (uint i = lid; i < n; i += lsize) {
+        sum += x[i] * y[i];
+    }
+    scratch[lid] = sum;
+    barrier(CLK_LOCAL_MEM_FENCE);
+
+    for (uint offset = lsize / 2; offset > 0; offset >>= 1) {
+        if (lid < offset) {
+            scratch[lid] += scratch[lid + offset];
+        }
+        barrier(CLK_LOCAL_MEM_FENCE);
+    }
+
+    if (lid == 0) {
+        result[0] = scratch[0];
+    }
+}
+
+// Carries the index along with the magnitude through the reduction. On equal magnitudes the lower
+// index wins, so the result matches a linear scan. An empty input results in -1.
+__kernel void $Piamax(const uint n,
+                     __global const $T *x,
+                     __global int *result,
+                     __local $T *values,
+                     __local int *indices)
+{
+    uint lid = get_local_id(0);
+    uint lsize = get_local_size(0);
+
+    $T best = ($T)-1;
+    int index = -1;
+    for (uint i = lid; i < n; i += lsize) {
+        $T value = fabs(x[i]);
+        if (value > best) {
+            best = value;
+            index = i;
+        }
+    }
+    values[lid] = best;
+    indices[lid] = index;
+    barrier(CLK_LOCAL_MEM_FENCE);
+
+    for (uint offset = lsize / 2; offset > 0; offset >>= 1) {
+        if (lid < offset) {
+            $T other = values[lid + offset];
+            int other_index = indices[lid + offset];
+            if (other_index != -1 && (other > values[lid] || (other == values[lid] && other_index < indices[lid]))) {
+                values[lid] = other;
+                indices[lid] = other_index;
+            }
+        }
+        barrier(CLK_LOCAL_MEM_FENCE);
+    }
+
+    if (lid == 0) {
+        result[0] = indices[0];
+    }
+}
+
+__kernel void $Pnrm2(const uint n,
+                    __global const $T *x,
+                    __global $T *result,
+                    __local $T *scratch)
+{
+    uint lid = get_local_id(0);
+    uint lsize = get_local_size(0);
+
+    // First pass: find the largest magnitude, to scale the squares by.
+    $T local_max = ($T)0;
+    for (uint i = lid; i < n; i += lsize) {
+        local_max = fmax(local_max, fabs(x[i]));
+    }
+    scratch[lid] = local_max;
+    barrier(CLK_LOCAL_MEM_FENCE);
+
+    for (uint offset = lsize / 2; offset > 0; offset >>= 1) {
+        if (lid < offset) {
+            scratch[lid] = fmax(scratch[lid], scratch[lid + offset]);
+        }
+        barrier(CLK_LOCAL_MEM_FENCE);
+    }
+    $T scale = scratch[0];
+    barrier(CLK_LOCAL_MEM_FENCE);
+
+    if (scale == ($T)0) {
+        if (lid == 0) {
+            result[0] = ($T)0;
+        }
+        return;
+    }
+
+    // Second pass: sum up the scaled squares.
+    $T ssq = ($T)0;
+    for (uint i = lid; i < n; i += lsize) {
+        $T ratio = x[i] / scale;
+        ssq += ratio * ratio;
+    }
+    scratch[lid] = ssq;
+    barrier(CLK_LOCAL_MEM_FENCE);
+
+    for (uint offset = lsize / 2; offset > 0; offset >>= 1) {
+        if (lid < offset) {
+            scratch[lid] += scratch[lid + offset];
+        }
+        barrier(CLK_LOCAL_MEM_FENCE);
+    }
+
+    if (lid == 0) {
+        result[0] = scale * sqrt(scratch[0]);
+    }
+}
+
+__kernel void $Pscal(const uint n,
+                    __global const $T *a,
+                    __global $T *x)
+{
+    uint i = get_global_id(0);
+    if (i < n) {
+        x[i] = a[0] * x[i];
+    }
+}
+
+__kernel void $Pswap(const uint n,
+                    __global $T *x,
+                    __global $T *y)
+{
+    uint i = get_global_id(0);
+    if (i < n) {
+        $T tmp = x[i];
+        x[i] = y[i];
+        y[i] = tmp;
+    }
+}
+
+__kernel void $Pgemv(const uint rows,
+                    const uint cols,
+                    const uint trans,
+                    __global const $T *alpha,
+                    __global const $T *a,
+                    __global const $T *x,
+                    __global const $T *beta,
+                    __global $T *y)
+{
+    uint i = get_global_id(0);
+    uint x_len = trans ? rows : cols;
+    uint y_len = trans ? cols : rows;
+    if (i < y_len) {
+        $T sum = ($T)0;
+        for (uint j = 0; j < x_len; j++) {
+            $T a_val = trans ? a[j * cols + i] : a[i * cols + j];
+            sum += a_val * x[j];
+        }
+        y[i] = alpha[0] * sum + beta[0] * y[i];
+    }
+}
+
+// The largest edge length of the tiles. The square work-groups can be smaller on devices with a
+// smaller work-group limit, the tiles then only use a part of the local memory.
+#define GEMM_TILE 16
+
+// Multiplies the matrices a and b of one gemm into c. The tiles get declared by the calling
+// kernel, as local memory can not be allocated in a regular function.
+void $Pgemm_tiles(const uint m,
+                 const uint n,
+                 const uint k,
+                 const uint trans_a,
+                 const uint trans_b,
+                 __global const $T *alpha,
+                 __global const $T *a,
+                 __global const $T *b,
+                 __global const $T *beta,
+                 __global $T *c,
+                 __local $T a_tile[GEMM_TILE][GEMM_TILE],
+                 __local $T b_tile[GEMM_TILE][GEMM_TILE])
+{
+    uint col = get_global_id(0);
+    uint row = get_global_id(1);
+    uint lcol = get_local_id(0);
+    uint lrow = get_local_id(1);
+    uint tile_size = get_local_size(0);
+
+    $T sum = ($T)0;
+    for (uint tile = 0; tile < (k + tile_size - 1) / tile_size; tile++) {
+        uint a_col = tile * tile_size + lcol;
+        uint b_row = tile * tile_size + lrow;
+        if (row < m && a_col < k) {
+            a_tile[lrow][lcol] = trans_a ? a[a_col * m + row] : a[row * k + a_col];
+        } else {
+            a_tile[lrow][lcol] = ($T)0;
+        }
+        if (b_row < k && col < n) {
+            b_tile[lrow][lcol] = trans_b ? b[col * k + b_row] : b[b_row * n + col];
+        } else {
+            b_tile[lrow][lcol] = ($T)0;
+        }
+        barrier(CLK_LOCAL_MEM_FENCE);
+
+        for (uint i = 0; i < tile_size; i++) {
+            sum += a_tile[lrow][i] * b_tile[i][lcol];
+        }
+        barrier(CLK_LOCAL_MEM_FENCE);
+    }
+
+    if (row < m && col < n) {
+        c[row * n + col] = alpha[0] * sum + beta[0] * c[row * n + col];
+    }
+}
+
+__kernel void $Pgemm(const uint m,
+                    const uint n,
+                    const uint k,
+                    const uint trans_a,
+                    const uint trans_b,
+                    __global const $T *alpha,
+                    __global const $T *a,
+                    __global const $T *b,
+                    __global const $T *beta,
+                    __global $T *c)
+{
+    __local $T a_tile[GEMM_TILE][GEMM_TILE];
+    __local $T b_tile[GEMM_TILE][GEMM_TILE];
+
+    $Pgemm_tiles(m, n, k, trans_a, trans_b, alpha, a, b, beta, c, a_tile, b_tile);
+}
+
+// Every work-group along the third dimension computes the product of one matrix of the batch.
+__kernel void $Pgemm_batched(const uint m,
+                            const uint n,
+                            const uint k,
+                            const uint trans_a,
+                            const uint trans_b,
+                            __global const $T *alpha,
+                            __global const $T *a,
+                            __global const $T *b,
+                            __global const $T *beta,
+                            __global $T *c)
+{
+    __local $T a_tile[GEMM_TILE][GEMM_TILE];
+    __local $T b_tile[GEMM_TILE][GEMM_TILE];
+
+    uint batch = get_group_id(2);
+    $Pgemm_tiles(m, n, k, trans_a, trans_b, alpha,
+                 a + batch * m * k, b + batch * k * n, beta, c + batch * m * n,
+                 a_tile, b_tile);
+}
+
+// Generic reductions, specialized for every operation through the REDUCE macro. An empty input
+// results in the identity element of the operation.
+#define REDUCE(name, identity, combine)                                 \
+__kernel void name(const uint n,                                        \
+                   __global const $T *x,                                \
+                   __global $T *result,                                 \
+                   __local $T *scratch)                                 \
+{                                                                       \
+    uint lid = get_local_id(0);                                         \
+    uint lsize = get_local_size(0);                                     \
+                                                                        \
+    $T acc = identity;                                                  \
+    for (uint i = lid; i < n; i += lsize) {                             \
+        acc = combine(acc, x[i]);                                       \
+    }                                                                   \
+    scratch[lid] = acc;                                                 \
+    barrier(CLK_LOCAL_MEM_FENCE);                                       \
+                                                                        \
+    for (uint offset = lsize / 2; offset > 0; offset >>= 1) {           \
+        if (lid < offset) {                                             \
+            scratch[lid] = combine(scratch[lid], scratch[lid + offset]);\
+        }                                                               \
+        barrier(CLK_LOCAL_MEM_FENCE);                                   \
+    }                                                                   \
+                                                                        \
+    if (lid == 0) {                                                     \
+        result[0] = scratch[0];                                         \
+    }                                                                   \
+}
+
+#define REDUCE_SUM(a, b) ((a) + (b))
+#define REDUCE_PRODUCT(a, b) ((a) * (b))
+
+REDUCE($Preduce_sum, ($T)0, REDUCE_SUM)
+REDUCE($Preduce_max, -INFINITY, fmax)
+REDUCE($Preduce_min, INFINITY, fmin)
+REDUCE($Preduce_product, ($T)1, REDUCE_PRODUCT)
+
+// The macro differs between the instances of the template.
+#undef REDUCE
+
+// Identifiers of the functions, which map can apply. Need to match `unary_fn_id` of the Rust
+// side.
+#define UNARY_EXP 0
+#define UNARY_LOG 1
+#define UNARY_SQRT 2
+#define UNARY_ABS 3
+#define UNARY_SIGMOID 4
+#define UNARY_TANH 5
+#define UNARY_RELU 6
+
+__kernel void $Pmap(const uint n,
+                   const uint func,
+                   __global const $T *x,
+                   __global $T *y)
+{
+    uint i = get_global_id(0);
+    if (i < n) {
+        $T xi = x[i];
+        switch (func) {
+            case UNARY_EXP: y[i] = exp(xi); break;
+            case UNARY_LOG: y[i] = log(xi); break;
+            case UNARY_SQRT: y[i] = sqrt(xi); break;
+            case UNARY_ABS: y[i] = fabs(xi); break;
+            case UNARY_SIGMOID: y[i] = ($T)1 / (($T)1 + exp(-xi)); break;
+            case UNARY_TANH: y[i] = tanh(xi); break;
+            case UNARY_RELU: y[i] = fmax(xi, ($T)0); break;
+        }
+    }
+}
+
+// Element-wise operations on two vectors, specialized for every operation through the
+// ELEMENTWISE macro. Division by zero follows IEEE 754.
+#define ELEMENTWISE(name, op)                                           \
+__kernel void name(const uint n,                                        \
+                   __global const $T *a,                                \
+                   __global const $T *b,                                \
+                   __global $T *c)                                      \
+{                                                                       \
+    uint i = get_global_id(0);                                          \
+    if (i < n) {                                                        \
+        c[i] = a[i] op b[i];                                            \
+    }                                                                   \
+}
+
+ELEMENTWISE($Padd, +)
+ELEMENTWISE($Psub, -)
+ELEMENTWISE($Pmul, *)
+ELEMENTWISE($Pdiv, /)
+
+#undef ELEMENTWISE
@@ -1,87 +1,526 @@
 //! Provides BLAS for a OpenCL backend.
 
-use frameworks::opencl::Kernel;
-use frameworks::opencl::Program;
+use frameworks::opencl::{Kernel, Program, Queue, Reduction, Elementwise, API};
 use memory::MemoryType;
 use libraries::blas::*;
-use num::traits::Float;
+use validate;
+use std::mem::size_of;
 
-impl IBlasBinary<f32> for Program {
-    type Asum = Kernel;
-    type Axpy = Kernel;
-    type Copy = Kernel;
-    type Dot = Kernel;
-    type Nrm2 = Kernel;
-    type Scale = Kernel;
-    type Swap = Kernel;
+/// The requested work-group size of the reduction kernels.
+///
+/// Gets clamped to the maximum of the kernel on its device, see `reduction_work_group_size`.
+const WORK_GROUP_SIZE: usize = 64;
+
+/// The largest edge length of the square tiles of the gemm kernel.
+///
+/// Gets reduced on devices with a smaller work-group limit, see `gemm_tile_size`.
+const GEMM_TILE: usize = 16;
+
+/// Rounds `size` up to the next multiple of `multiple`.
+fn round_up(size: usize, multiple: usize) -> usize {
+    (size + multiple - 1) / multiple * multiple
+}
+
+/// Returns the work-group size for the reduction `kernel`.
+///
+/// The reduction kernels need a power of two, so the clamped size is rounded down to one.
+fn reduction_work_group_size(kernel: &Kernel) -> Result<usize, ::frameworks::opencl::Error> {
+    let max = try!(kernel.clamp_work_group_size(WORK_GROUP_SIZE));
+    let mut size = 1;
+    while size * 2 <= max {
+        size *= 2;
+    }
+    Ok(size)
+}
+
+/// Returns the edge length of the square work-groups of the gemm `kernel`.
+///
+/// The largest tile, whose work-group does not exceed the clamped size, up to `GEMM_TILE`.
+fn gemm_tile_size(kernel: &Kernel) -> Result<usize, ::frameworks::opencl::Error> {
+    let max = try!(kernel.clamp_work_group_size(GEMM_TILE * GEMM_TILE));
+    let mut tile = GEMM_TILE;
+    while tile > 1 && tile * tile > max {
+        tile -= 1;
+    }
+    Ok(tile)
+}
+
+/// Returns the `kernel` of the operation `op` for `T` or an error, if there is none, as the
+/// device does not support `T`.
+fn kernel_for<'a, T: BlasNum, K>(kernel: &'a Option<K>, op: &str) -> Result<&'a K, Error> {
+    kernel.as_ref().ok_or(Error::InvalidOperation(format!("The OpenCL {} kernel is not available for {}, as the device does not support {}.", op, T::CL_TYPE, T::EXTENSION.unwrap_or(T::CL_TYPE))))
+}
+
+/// Launches the reduction `kernel`, computing the dot product of `x` and `y` into `result`.
+fn dot<T: BlasNum>(kernel: &Kernel, x: &MemoryType, y: &MemoryType, result: &mut MemoryType) -> Result<(), Error> {
+    let x_mem = try!(x.as_opencl().ok_or(Error::MissingArgument(format!("Unable to receive OpenCL memory for `x`"))));
+    let y_mem = try!(y.as_opencl().ok_or(Error::MissingArgument(format!("Unable to receive OpenCL memory for `y`"))));
+    let r_mem = try!(result.as_opencl().ok_or(Error::MissingArgument(format!("Unable to receive OpenCL memory for `result`"))));
+    try!(validate::same_len(("x", x_mem.size() / T::SIZE), ("y", y_mem.size() / T::SIZE)));
+    let n = (x_mem.size() / T::SIZE) as u32;
+    let res = reduction_work_group_size(kernel).and_then(|local| {
+        kernel.set_arg(0, &n)
+            .and_then(|_| kernel.set_arg_memory(1, x_mem))
+            .and_then(|_| kernel.set_arg_memory(2, y_mem))
+            .and_then(|_| kernel.set_arg_memory(3, r_mem))
+            .and_then(|_| kernel.set_arg_local(4, local * T::SIZE))
+            .and_then(|_| kernel.enqueue(&[local], &[local]))
+    });
+    match res {
+        Ok(_) => Ok(()),
+        Err(err) => Err(Error::Dot(format!("Unable to execute the OpenCL dot kernel: {}", err)))
+    }
+}
+
+/// Launches the reduction `kernel`, reducing the vector `x` into the scalar `result`.
+fn reduce<T: BlasNum>(kernel: &Kernel, x: &MemoryType, result: &mut MemoryType) -> Result<(), Error> {
+    let x_mem = try!(x.as_opencl().ok_or(Error::MissingArgument(format!("Unable to receive OpenCL memory for `x`"))));
+    let r_mem = try!(result.as_opencl().ok_or(Error::MissingArgument(format!("Unable to receive OpenCL memory for `result`"))));
+    let n = (x_mem.size() / T::SIZE) as u32;
+    let res = reduction_work_group_size(kernel).and_then(|local| {
+        kernel.set_arg(0, &n)
+            .and_then(|_| kernel.set_arg_memory(1, x_mem))
+            .and_then(|_| kernel.set_arg_memory(2, r_mem))
+            .and_then(|_| kernel.set_arg_local(3, local * T::SIZE))
+            .and_then(|_| kernel.enqueue(&[local], &[local]))
+    });
+    match res {
+        Ok(_) => Ok(()),
+        Err(err) => Err(Error::InvalidOperation(format!("Unable to execute the OpenCL reduction kernel: {}", err)))
+    }
+}
+
+/// Launches the reduction `kernel`, writing the index of the largest magnitude of `x` into `result`.
+fn iamax<T: BlasNum>(kernel: &Kernel, x: &MemoryType, result: &mut MemoryType) -> Result<(), Error> {
+    let x_mem = try!(x.as_opencl().ok_or(Error::MissingArgument(format!("Unable to receive OpenCL memory for `x`"))));
+    let r_mem = try!(result.as_opencl().ok_or(Error::MissingArgument(format!("Unable to receive OpenCL memory for `result`"))));
+    let n = (x_mem.size() / T::SIZE) as u32;
+    let res = reduction_work_group_size(kernel).and_then(|local| {
+        kernel.set_arg(0, &n)
+            .and_then(|_| kernel.set_arg_memory(1, x_mem))
+            .and_then(|_| kernel.set_arg_memory(2, r_mem))
+            .and_then(|_| kernel.set_arg_local(3, local * T::SIZE))
+            .and_then(|_| kernel.set_arg_local(4, local * size_of::<i32>()))
+            .and_then(|_| kernel.enqueue(&[local], &[local]))
+    });
+    match res {
+        Ok(_) => Ok(()),
+        Err(err) => Err(Error::InvalidOperation(format!("Unable to execute the OpenCL iamax kernel: {}", err)))
+    }
+}
+
+/// Launches the `kernel`, computing `y = a * x + y`.
+fn axpy<T: BlasNum>(kernel: &Kernel, a: &MemoryType, x: &MemoryType, y: &mut MemoryType) -> Result<(), Error> {
+    let a_mem = try!(a.as_opencl().ok_or(Error::MissingArgument(format!("Unable to receive OpenCL memory for `a`"))));
+    let x_mem = try!(x.as_opencl().ok_or(Error::MissingArgument(format!("Unable to receive OpenCL memory for `x`"))));
+    let y_mem = try!(y.as_opencl().ok_or(Error::MissingArgument(format!("Unable to receive OpenCL memory for `y`"))));
+    try!(validate::scalar(("a", a_mem.size() / T::SIZE))
+        .and_then(|_| validate::same_len(("x", x_mem.size() / T::SIZE), ("y", y_mem.size() / T::SIZE))));
+    let n = x_mem.size() / T::SIZE;
+    if n == 0 {
+        return Ok(())
+    }
+    let res = kernel.set_arg(0, &(n as u32))
+        .and_then(|_| kernel.set_arg_memory(1, a_mem))
+        .and_then(|_| kernel.set_arg_memory(2, x_mem))
+        .and_then(|_| kernel.set_arg_memory(3, y_mem))
+        .and_then(|_| kernel.enqueue(&[n], &[]));
+    match res {
+        Ok(_) => Ok(()),
+        Err(err) => Err(Error::InvalidOperation(format!("Unable to execute the OpenCL axpy kernel: {}", err)))
+    }
+}
+
+/// Launches the `kernel`, computing `x = a * x`.
+fn scale<T: BlasNum>(kernel: &Kernel, a: &MemoryType, x: &mut MemoryType) -> Result<(), Error> {
+    let a_mem = try!(a.as_opencl().ok_or(Error::MissingArgument(format!("Unable to receive OpenCL memory for `a`"))));
+    let x_mem = try!(x.as_opencl().ok_or(Error::MissingArgument(format!("Unable to receive OpenCL memory for `x`"))));
+    try!(validate::scalar(("a", a_mem.size() / T::SIZE)));
+    let n = x_mem.size() / T::SIZE;
+    // some OpenCL implementations reject a global work size of zero.
+    if n == 0 {
+        return Ok(())
+    }
+    let res = kernel.set_arg(0, &(n as u32))
+        .and_then(|_| kernel.set_arg_memory(1, a_mem))
+        .and_then(|_| kernel.set_arg_memory(2, x_mem))
+        .and_then(|_| kernel.enqueue(&[n], &[]));
+    match res {
+        Ok(_) => Ok(()),
+        Err(err) => Err(Error::InvalidOperation(format!("Unable to execute the OpenCL scale kernel: {}", err)))
+    }
+}
+
+/// Launches the `kernel`, exchanging the elements of `x` and `y`.
+fn swap<T: BlasNum>(kernel: &Kernel, x: &mut MemoryType, y: &mut MemoryType) -> Result<(), Error> {
+    let x_mem = try!(x.as_opencl().ok_or(Error::MissingArgument(format!("Unable to receive OpenCL memory for `x`"))));
+    let y_mem = try!(y.as_opencl().ok_or(Error::MissingArgument(format!("Unable to receive OpenCL memory for `y`"))));
+    try!(validate::same_len(("x", x_mem.size() / T::SIZE), ("y", y_mem.size() / T::SIZE)));
+    let n = x_mem.size() / T::SIZE;
+    if n == 0 {
+        return Ok(())
+    }
+    let res = kernel.set_arg(0, &(n as u32))
+        .and_then(|_| kernel.set_arg_memory(1, x_mem))
+        .and_then(|_| kernel.set_arg_memory(2, y_mem))
+        .and_then(|_| kernel.enqueue(&[n], &[]));
+    match res {
+        Ok(_) => Ok(()),
+        Err(err) => Err(Error::InvalidOperation(format!("Unable to execute the OpenCL swap kernel: {}", err)))
+    }
+}
+
+/// Launches the `kernel`, computing `y = alpha * op(a) * x + beta * y`.
+fn gemv<T: BlasNum>(kernel: &Kernel, trans: Transpose, alpha: &MemoryType, a: &MemoryType, x: &MemoryType, beta: &MemoryType, y: &mut MemoryType, rows: usize, cols: usize) -> Result<(), Error> {
+    let alpha_mem = try!(alpha.as_opencl().ok_or(Error::MissingArgument(format!("Unable to receive OpenCL memory for `alpha`"))));
+    let a_mem = try!(a.as_opencl().ok_or(Error::MissingArgument(format!("Unable to receive OpenCL memory for `a`"))));
+    let x_mem = try!(x.as_opencl().ok_or(Error::MissingArgument(format!("Unable to receive OpenCL memory for `x`"))));
+    let beta_mem = try!(beta.as_opencl().ok_or(Error::MissingArgument(format!("Unable to receive OpenCL memory for `beta`"))));
+    let y_mem = try!(y.as_opencl().ok_or(Error::MissingArgument(format!("Unable to receive OpenCL memory for `y`"))));
+    let (x_len, y_len) = if trans.is_transposed() { (rows, cols) } else { (cols, rows) };
+    try!(validate::scalar(("alpha", alpha_mem.size() / T::SIZE))
+        .and_then(|_| validate::scalar(("beta", beta_mem.size() / T::SIZE)))
+        .and_then(|_| validate::matrix_dims(("a", a_mem.size() / T::SIZE), rows, cols))
+        .and_then(|_| validate::vector_len(("x", x_mem.size() / T::SIZE), x_len))
+        .and_then(|_| validate::vector_len(("y", y_mem.size() / T::SIZE), y_len)));
+    if y_len == 0 {
+        return Ok(())
+    }
+    let res = kernel.set_arg(0, &(rows as u32))
+        .and_then(|_| kernel.set_arg(1, &(cols as u32)))
+        .and_then(|_| kernel.set_arg(2, &(trans.is_transposed() as u32)))
+        .and_then(|_| kernel.set_arg_memory(3, alpha_mem))
+        .and_then(|_| kernel.set_arg_memory(4, a_mem))
+        .and_then(|_| kernel.set_arg_memory(5, x_mem))
+        .and_then(|_| kernel.set_arg_memory(6, beta_mem))
+        .and_then(|_| kernel.set_arg_memory(7, y_mem))
+        .and_then(|_| kernel.enqueue(&[y_len], &[]));
+    match res {
+        Ok(_) => Ok(()),
+        Err(err) => Err(Error::InvalidOperation(format!("Unable to execute the OpenCL gemv kernel: {}", err)))
+    }
+}
+
+/// Launches the gemm `kernel` for `batch_count` products of the matrices in `a` and `b`.
+///
+/// A `batch_count` of 1 launches the two dimensional gemm kernel, otherwise the batched one.
+fn gemm<T: BlasNum>(kernel: &Kernel, name: &str, alpha: &MemoryType, at: Transpose, a: &MemoryType, bt: Transpose, b: &MemoryType, beta: &MemoryType, c: &mut MemoryType, m: usize, n: usize, k: usize, batch_count: Option<usize>) -> Result<(), Error> {
+    let alpha_mem = try!(alpha.as_opencl().ok_or(Error::MissingArgument(format!("Unable to receive OpenCL memory for `alpha`"))));
+    let a_mem = try!(a.as_opencl().ok_or(Error::MissingArgument(format!("Unable to receive OpenCL memory for `a`"))));
+    let b_mem = try!(b.as_opencl().ok_or(Error::MissingArgument(format!("Unable to receive OpenCL memory for `b`"))));
+    let beta_mem = try!(beta.as_opencl().ok_or(Error::MissingArgument(format!("Unable to receive OpenCL memory for `beta`"))));
+    let c_mem = try!(c.as_opencl().ok_or(Error::MissingArgument(format!("Unable to receive OpenCL memory for `c`"))));
+    try!(validate::scalar(("alpha", alpha_mem.size() / T::SIZE))
+        .and_then(|_| validate::scalar(("beta", beta_mem.size() / T::SIZE))));
+    try!(match batch_count {
+        Some(batch_count) => validate::batched_matrix_dims(("a", a_mem.size() / T::SIZE), batch_count, m, k)
+            .and_then(|_| validate::batched_matrix_dims(("b", b_mem.size() / T::SIZE), batch_count, k, n))
+            .and_then(|_| validate::batched_matrix_dims(("c", c_mem.size() / T::SIZE), batch_count, m, n)),
+        None => validate::matrix_dims(("a", a_mem.size() / T::SIZE), m, k)
+            .and_then(|_| validate::matrix_dims(("b", b_mem.size() / T::SIZE), k, n))
+            .and_then(|_| validate::matrix_dims(("c", c_mem.size() / T::SIZE), m, n)),
+    });
+    if m == 0 || n == 0 || batch_count == Some(0) {
+        return Ok(())
+    }
+    let res = gemm_tile_size(kernel).and_then(|tile| {
+        kernel.set_arg(0, &(m as u32))
+            .and_then(|_| kernel.set_arg(1, &(n as u32)))
+            .and_then(|_| kernel.set_arg(2, &(k as u32)))
+            .and_then(|_| kernel.set_arg(3, &(at.is_transposed() as u32)))
+            .and_then(|_| kernel.set_arg(4, &(bt.is_transposed() as u32)))
+            .and_then(|_| kernel.set_arg_memory(5, alpha_mem))
+            .and_then(|_| kernel.set_arg_memory(6, a_mem))
+            .and_then(|_| kernel.set_arg_memory(7, b_mem))
+            .and_then(|_| kernel.set_arg_memory(8, beta_mem))
+            .and_then(|_| kernel.set_arg_memory(9, c_mem))
+            .and_then(|_| match batch_count {
+                Some(batch_count) => kernel.enqueue(&[round_up(n, tile), round_up(m, tile), batch_count], &[tile, tile, 1]),
+                None => kernel.enqueue(&[round_up(n, tile), round_up(m, tile)], &[tile, tile]),
+            })
+    });
+    match res {
+        Ok(_) => Ok(()),
+        Err(err) => Err(Error::InvalidOperation(format!("Unable to execute the OpenCL {} kernel: {}", name, err)))
+    }
+}
+
+/// Launches the `kernel`, applying `func` to every element of `x` into `y`.
+fn map<T: BlasNum>(kernel: &Kernel, x: &MemoryType, y: &mut MemoryType, func: UnaryFn) -> Result<(), Error> {
+    let x_mem = try!(x.as_opencl().ok_or(Error::MissingArgument(format!("Unable to receive OpenCL memory for `x`"))));
+    let y_mem = try!(y.as_opencl().ok_or(Error::MissingArgument(format!("Unable to receive OpenCL memory for `y`"))));
+    try!(validate::same_len(("x", x_mem.size() / T::SIZE), ("y", y_mem.size() / T::SIZE)));
+    let n = x_mem.size() / T::SIZE;
+    if n == 0 {
+        return Ok(())
+    }
+    let res = kernel.set_arg(0, &(n as u32))
+        .and_then(|_| kernel.set_arg(1, &unary_fn_id(func)))
+        .and_then(|_| kernel.set_arg_memory(2, x_mem))
+        .and_then(|_| kernel.set_arg_memory(3, y_mem))
+        .and_then(|_| kernel.enqueue(&[n], &[]));
+    match res {
+        Ok(_) => Ok(()),
+        Err(err) => Err(Error::InvalidOperation(format!("Unable to execute the OpenCL map kernel: {}", err)))
+    }
+}
+
+/// Launches the `kernel`, combining the elements of `a` and `b` into `c`.
+fn elementwise<T: BlasNum>(kernel: &Kernel, a: &MemoryType, b: &MemoryType, c: &mut MemoryType) -> Result<(), Error> {
+    let a_mem = try!(a.as_opencl().ok_or(Error::MissingArgument(format!("Unable to receive OpenCL memory for `a`"))));
+    let b_mem = try!(b.as_opencl().ok_or(Error::MissingArgument(format!("Unable to receive OpenCL memory for `b`"))));
+    let c_mem = try!(c.as_opencl().ok_or(Error::MissingArgument(format!("Unable to receive OpenCL memory for `c`"))));
+    try!(validate::same_len(("a", a_mem.size() / T::SIZE), ("b", b_mem.size() / T::SIZE))
+        .and_then(|_| validate::same_len(("a", a_mem.size() / T::SIZE), ("c", c_mem.size() / T::SIZE))));
+    let n = a_mem.size() / T::SIZE;
+    if n == 0 {
+        return Ok(())
+    }
+    let res = kernel.set_arg(0, &(n as u32))
+        .and_then(|_| kernel.set_arg_memory(1, a_mem))
+        .and_then(|_| kernel.set_arg_memory(2, b_mem))
+        .and_then(|_| kernel.set_arg_memory(3, c_mem))
+        .and_then(|_| kernel.enqueue(&[n], &[]));
+    match res {
+        Ok(_) => Ok(()),
+        Err(err) => Err(Error::InvalidOperation(format!("Unable to execute the OpenCL element-wise kernel: {}", err)))
+    }
+}
+
+/// Returns the identifier of `func`, as defined by the `UNARY_*` constants of the kernels.
+fn unary_fn_id(func: UnaryFn) -> u32 {
+    match func {
+        UnaryFn::Exp => 0,
+        UnaryFn::Log => 1,
+        UnaryFn::Sqrt => 2,
+        UnaryFn::Abs => 3,
+        UnaryFn::Sigmoid => 4,
+        UnaryFn::Tanh => 5,
+        UnaryFn::Relu => 6,
+    }
+}
+
+/// Copies the content of `x` into the beginning of `y` on the device.
+fn copy<T: BlasNum>(queue: &Queue, x: &MemoryType, y: &mut MemoryType) -> Result<(), Error> {
+    let x_mem = try!(x.as_opencl().ok_or(Error::MissingArgument(format!("Unable to receive OpenCL memory for `x`"))));
+    let y_mem = try!(y.as_opencl().ok_or(Error::MissingArgument(format!("Unable to receive OpenCL memory for `y`"))));
+    try!(validate::at_least(("x", x_mem.size() / T::SIZE), ("y", y_mem.size() / T::SIZE)));
+    if x_mem.size() == 0 {
+        return Ok(())
+    }
+    match API::copy_buffer(queue, x_mem, y_mem, x_mem.size()) {
+        Ok(_) => Ok(()),
+        Err(err) => Err(Error::InvalidOperation(format!("Unable to copy the OpenCL buffer: {}", err)))
+    }
+}
+
+/// The kernels of every operation are `None`, if the Program has not been built for `T`, as the
+/// devices do not support it.
+impl<T: BlasNum> IBlasBinary<T> for Program {
+    type Asum = Option<Kernel>;
+    type Iamax = Option<Kernel>;
+    type Axpy = Option<Kernel>;
+    type Copy = Queue;
+    type Dot = Option<Kernel>;
+    type Nrm2 = Option<Kernel>;
+    type Scale = Option<Kernel>;
+    type Swap = Option<Kernel>;
+    type Gemv = Option<Kernel>;
+    type Gemm = Option<Kernel>;
+    type GemmBatched = Option<Kernel>;
+    type Reduce = Option<Reduction>;
+    type Map = Option<Kernel>;
+    type Elementwise = Option<Elementwise>;
 
     fn asum(&self) -> Self::Asum {
-        unimplemented!()
+        self.blas_kernels::<T>().map(|kernels| kernels.asum)
+    }
+
+    fn iamax(&self) -> Self::Iamax {
+        self.blas_kernels::<T>().map(|kernels| kernels.iamax)
     }
 
     fn axpy(&self) -> Self::Axpy {
-        self.blas_axpy
+        self.blas_kernels::<T>().map(|kernels| kernels.axpy)
     }
 
     fn copy(&self) -> Self::Copy {
-        unimplemented!()
+        self.blas_copy
     }
 
     fn dot(&self) -> Self::Dot {
-        self.blas_dot
+        self.blas_kernels::<T>().map(|kernels| kernels.dot)
     }
 
     fn nrm2(&self) -> Self::Nrm2 {
-        unimplemented!()
+        self.blas_kernels::<T>().map(|kernels| kernels.nrm2)
     }
 
     fn scale(&self) -> Self::Scale {
-        self.blas_scale
+        self.blas_kernels::<T>().map(|kernels| kernels.scale)
     }
 
     fn swap(&self) -> Self::Swap {
-        unimplemented!()
+        self.blas_kernels::<T>().map(|kernels| kernels.swap)
+    }
+
+    fn gemv(&self) -> Self::Gemv {
+        self.blas_kernels::<T>().map(|kernels| kernels.gemv)
+    }
+
+    fn gemm(&self) -> Self::Gemm {
+        self.blas_kernels::<T>().map(|kernels| kernels.gemm)
+    }
+
+    fn gemm_batched(&self) -> Self::GemmBatched {
+        self.blas_kernels::<T>().map(|kernels| kernels.gemm_batched)
+    }
+
+    fn reduce(&self) -> Self::Reduce {
+        self.blas_kernels::<T>().map(|kernels| kernels.reduce)
+    }
+
+    fn map(&self) -> Self::Map {
+        self.blas_kernels::<T>().map(|kernels| kernels.map)
+    }
+
+    fn elementwise(&self) -> Self::Elementwise {
+        self.blas_kernels::<T>().map(|kernels| kernels.elementwise)
+    }
+}
+
+impl<T: BlasNum> IOperationAsum<T> for Option<Kernel> {
+    fn compute(&self, x: &MemoryType, result: &mut MemoryType) -> Result<(), Error> {
+        reduce::<T>(try!(kernel_for::<T, _>(self, "asum")), x, result)
+    }
+
+    fn is_available(&self) -> bool {
+        self.is_some()
     }
 }
 
-impl IOperationAsum<f32> for Kernel {
+impl<T: BlasNum> IOperationIamax<T> for Option<Kernel> {
     fn compute(&self, x: &MemoryType, result: &mut MemoryType) -> Result<(), Error> {
-        unimplemented!()
+        iamax::<T>(try!(kernel_for::<T, _>(self, "iamax")), x, result)
+    }
+
+    fn is_available(&self) -> bool {
+        self.is_some()
     }
 }
 
-impl IOperationAxpy<f32> for Kernel {
+impl<T: BlasNum> IOperationAxpy<T> for Option<Kernel> {
     fn compute(&self, a: &MemoryType, x: &MemoryType, y: &mut MemoryType) -> Result<(), Error> {
-        unimplemented!()
+        axpy::<T>(try!(kernel_for::<T, _>(self, "axpy")), a, x, y)
+    }
+
+    fn is_available(&self) -> bool {
+        self.is_some()
     }
 }
 
-impl IOperationCopy<f32> for Kernel {
+impl<T: BlasNum> IOperationCopy<T> for Queue {
     fn compute(&self, x: &MemoryType, y: &mut MemoryType) -> Result<(), Error> {
-        unimplemented!()
+        copy::<T>(self, x, y)
     }
 }
 
-impl IOperationDot<f32> for Kernel {
+impl<T: BlasNum> IOperationDot<T> for Option<Kernel> {
     fn compute(&self, x: &MemoryType, y: &MemoryType, result: &mut MemoryType) -> Result<(), Error> {
-        unimplemented!()
+        dot::<T>(try!(kernel_for::<T, _>(self, "dot")), x, y, result)
+    }
+
+    fn is_available(&self) -> bool {
+        self.is_some()
     }
 }
 
-impl IOperationNrm2<f32> for Kernel {
+impl<T: BlasNum> IOperationNrm2<T> for Option<Kernel> {
     fn compute(&self, x: &MemoryType, result: &mut MemoryType) -> Result<(), Error> {
-        unimplemented!()
+        reduce::<T>(try!(kernel_for::<T, _>(self, "nrm2")), x, result)
+    }
+
+    fn is_available(&self) -> bool {
+        self.is_some()
     }
 }
 
-impl IOperationScale<f32> for Kernel {
+impl<T: BlasNum> IOperationScale<T> for Option<Kernel> {
     fn compute(&self, a: &MemoryType, x: &mut MemoryType) -> Result<(), Error> {
-        unimplemented!()
+        scale::<T>(try!(kernel_for::<T, _>(self, "scale")), a, x)
+    }
+
+    fn is_available(&self) -> bool {
+        self.is_some()
     }
 }
 
-impl IOperationSwap<f32> for Kernel {
+impl<T: BlasNum> IOperationSwap<T> for Option<Kernel> {
     fn compute(&self, x: &mut MemoryType, y: &mut MemoryType) -> Result<(), Error> {
-        unimplemented!()
+        swap::<T>(try!(kernel_for::<T, _>(self, "swap")), x, y)
+    }
+
+    fn is_available(&self) -> bool {
+        self.is_some()
+    }
+}
+
+impl<T: BlasNum> IOperationGemv<T> for Option<Kernel> {
+    fn compute(&self, trans: Transpose, alpha: &MemoryType, a: &MemoryType, x: &MemoryType, beta: &MemoryType, y: &mut MemoryType, rows: usize, cols: usize) -> Result<(), Error> {
+        gemv::<T>(try!(kernel_for::<T, _>(self, "gemv")), trans, alpha, a, x, beta, y, rows, cols)
+    }
+
+    fn is_available(&self) -> bool {
+        self.is_some()
+    }
+}
+
+impl<T: BlasNum> IOperationGemm<T> for Option<Kernel> {
+    fn compute(&self, alpha: &MemoryType, at: Transpose, a: &MemoryType, bt: Transpose, b: &MemoryType, beta: &MemoryType, c: &mut MemoryType, m: usize, n: usize, k: usize) -> Result<(), Error> {
+        gemm::<T>(try!(kernel_for::<T, _>(self, "gemm")), "gemm", alpha, at, a, bt, b, beta, c, m, n, k, None)
+    }
+
+    fn is_available(&self) -> bool {
+        self.is_some()
+    }
+}
+
+impl<T: BlasNum> IOperationGemmBatched<T> for Option<Kernel> {
+    fn compute(&self, alpha: &MemoryType, at: Transpose, a: &MemoryType, bt: Transpose, b: &MemoryType, beta: &MemoryType, c: &mut MemoryType, m: usize, n: usize, k: usize, batch_count: usize) -> Result<(), Error> {
+        gemm::<T>(try!(kernel_for::<T, _>(self, "gemm_batched")), "gemm_batched", alpha, at, a, bt, b, beta, c, m, n, k, Some(batch_count))
+    }
+
+    fn is_available(&self) -> bool {
+        self.is_some()
+    }
+}
+
+impl<T: BlasNum> IOperationReduce<T> for Option<Reduction> {
+    fn compute(&self, x: &MemoryType, result: &mut MemoryType, op: ReduceOp) -> Result<(), Error> {
+        reduce::<T>(try!(kernel_for::<T, _>(self, "reduction")).kernel(op), x, result)
+    }
+
+    fn is_available(&self) -> bool {
+        self.is_some()
+    }
+}
+
+impl<T: BlasNum> IOperationMap<T> for Option<Kernel> {
+    fn compute(&self, x: &MemoryType, y: &mut MemoryType, func: UnaryFn) -> Result<(), Error> {
+        map::<T>(try!(kernel_for::<T, _>(self, "map")), x, y, func)
+    }
+
+    fn is_available(&self) -> bool {
+        self.is_some()
+    }
+}
+
+impl<T: BlasNum> IOperationElementwise<T> for Option<Elementwise> {
+    fn compute(&self, a: &MemoryType, b: &MemoryType, c: &mut MemoryType, op: BinaryOp) -> Result<(), Error> {
+        elementwise::<T>(try!(kernel_for::<T, _>(self, "element-wise")).kernel(op), a, b, c)
+    }
+
+    fn is_available(&self) -> bool {
+        self.is_some()
     }
 }
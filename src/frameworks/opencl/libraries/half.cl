@@ -0,0 +1,27 @@
+// Half precision kernels for the OpenCL backend.
+//
+// Only compiled, if all devices support cl_khr_fp16. Like on the Native backend, the arithmetic is
+// carried out in single precision and the results are rounded to half precision on store.
+
+#pragma OPENCL EXTENSION cl_khr_fp16 : enable
+
+__kernel void haxpy(const uint n,
+                    __global const half *a,
+                    __global const half *x,
+                    __global half *y)
+{
+    uint i = get_global_id(0);
+    if (i < n) {
+        y[i] = (half)((float)a[0] * (float)x[i] + (float)y[i]);
+    }
+}
+
+__kernel void hscal(const uint n,
+                    __global const half *a,
+                    __global half *x)
+{
+    uint i = get_global_id(0);
+    if (i < n) {
+        x[i] = (half)((float)a[0] * (float)x[i]);
+    }
+}
@@ -0,0 +1,66 @@
+//! Provides half precision operations for a OpenCL backend.
+//!
+//! The kernels only exist, if all devices support `cl_khr_fp16`. They convert every element to
+//! `float` for the computation and round it back to `half`, when it is stored.
+
+use half::f16;
+use frameworks::opencl::{Kernel, Queue, API};
+use memory::MemoryType;
+use libraries::blas::Error;
+use validate;
+use std::mem::size_of;
+
+/// Computes `a * x + y` with the axpy `kernel` and saves the result into `y`.
+pub fn axpy(kernel: &Kernel, a: &MemoryType, x: &MemoryType, y: &mut MemoryType) -> Result<(), Error> {
+    let a_mem = try!(a.as_opencl().ok_or(Error::MissingArgument(format!("Unable to receive OpenCL memory for `a`"))));
+    let x_mem = try!(x.as_opencl().ok_or(Error::MissingArgument(format!("Unable to receive OpenCL memory for `x`"))));
+    let y_mem = try!(y.as_opencl().ok_or(Error::MissingArgument(format!("Unable to receive OpenCL memory for `y`"))));
+    try!(validate::scalar(("a", a_mem.size() / size_of::<f16>()))
+        .and_then(|_| validate::same_len(("x", x_mem.size() / size_of::<f16>()), ("y", y_mem.size() / size_of::<f16>()))));
+    let n = x_mem.size() / size_of::<f16>();
+    if n == 0 {
+        return Ok(())
+    }
+    let res = kernel.set_arg(0, &(n as u32))
+        .and_then(|_| kernel.set_arg_memory(1, a_mem))
+        .and_then(|_| kernel.set_arg_memory(2, x_mem))
+        .and_then(|_| kernel.set_arg_memory(3, y_mem))
+        .and_then(|_| kernel.enqueue(&[n], &[]));
+    match res {
+        Ok(_) => Ok(()),
+        Err(err) => Err(Error::InvalidOperation(format!("Unable to execute the OpenCL half precision axpy kernel: {}", err)))
+    }
+}
+
+/// Copies the content of `x` into the beginning of `y` on the `queue`.
+pub fn copy(queue: &Queue, x: &MemoryType, y: &mut MemoryType) -> Result<(), Error> {
+    let x_mem = try!(x.as_opencl().ok_or(Error::MissingArgument(format!("Unable to receive OpenCL memory for `x`"))));
+    let y_mem = try!(y.as_opencl().ok_or(Error::MissingArgument(format!("Unable to receive OpenCL memory for `y`"))));
+    try!(validate::at_least(("x", x_mem.size() / size_of::<f16>()), ("y", y_mem.size() / size_of::<f16>())));
+    if x_mem.size() == 0 {
+        return Ok(())
+    }
+    match API::copy_buffer(queue, x_mem, y_mem, x_mem.size()) {
+        Ok(_) => Ok(()),
+        Err(err) => Err(Error::InvalidOperation(format!("Unable to copy the OpenCL buffer: {}", err)))
+    }
+}
+
+/// Computes `a * x` with the scale `kernel` and saves the result into `x`.
+pub fn scale(kernel: &Kernel, a: &MemoryType, x: &mut MemoryType) -> Result<(), Error> {
+    let a_mem = try!(a.as_opencl().ok_or(Error::MissingArgument(format!("Unable to receive OpenCL memory for `a`"))));
+    let x_mem = try!(x.as_opencl().ok_or(Error::MissingArgument(format!("Unable to receive OpenCL memory for `x`"))));
+    try!(validate::scalar(("a", a_mem.size() / size_of::<f16>())));
+    let n = x_mem.size() / size_of::<f16>();
+    if n == 0 {
+        return Ok(())
+    }
+    let res = kernel.set_arg(0, &(n as u32))
+        .and_then(|_| kernel.set_arg_memory(1, a_mem))
+        .and_then(|_| kernel.set_arg_memory(2, x_mem))
+        .and_then(|_| kernel.enqueue(&[n], &[]));
+    match res {
+        Ok(_) => Ok(()),
+        Err(err) => Err(Error::InvalidOperation(format!("Unable to execute the OpenCL half precision scale kernel: {}", err)))
+    }
+}
@@ -1,3 +1,5 @@
 //! Provides support for various libraries for a OpenCL backend.
 
 mod blas;
+#[cfg(feature = "f16")]
+pub mod half;
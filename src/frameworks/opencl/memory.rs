@@ -1,7 +1,11 @@
 #![allow(missing_docs)]
 use super::api::types as cl;
 use super::api::{API, Error};
+use super::{Context, OpenCL};
+use alloc_tracker;
+use framework::IFramework;
 use memory::*;
+use stats;
 
 use std::ptr;
 
@@ -14,10 +18,24 @@ pub struct Memory {
 
     /// Pointer to host memory that is used for pinned host memory.
     host_ptr: *mut u8,
+
+    /// The size of the allocated memory in bytes.
+    size: usize,
+
+    /// Whether the Memory holds a reference to the memory object, which it releases on drop.
+    owned: bool,
 }
 
+// OpenCL memory objects may be released from any thread. The host memory is owned by the Memory.
+unsafe impl Send for Memory {}
+
 impl Drop for Memory {
     fn drop(&mut self) {
+        if !self.owned {
+            return
+        }
+        co_debug!("Releasing {} bytes of OpenCL memory {:?}", self.size, self.memory);
+        alloc_tracker::forget(OpenCL::ID, self.memory as usize);
         API::release_memory(self);
         if self.memory_flags.contains(MEM_USE_HOST_PTR) {
             unsafe {
@@ -46,15 +64,11 @@ impl Default for MemoryFlags {
 
 #[allow(unused_mut)]
 impl Memory {
-    pub fn new(context: cl::context_id, size: usize) -> Result<Memory, Error> {
-        // Memory::from_c(API::create_buffer(...))
-        /*
-        Memory::<T>::ffi_create_buffer(context,
-                                       MEM_READ_WRITE,
-                                       size,
-                                       ptr::null_mut())
-        */
-        unimplemented!();
+    pub fn new(context: &Context, size: usize) -> Result<Memory, Error> {
+        let id = try!(API::create_buffer(context, MEM_READ_WRITE, size));
+        co_debug!("Allocated {} bytes of OpenCL memory {:?}", size, id);
+        stats::record_alloc(size);
+        Ok(Memory::from_c(id, size))
     }
 
     pub fn from_box(context: cl::context_id, x: Box<u8>) -> Result<Memory, Error> {
@@ -65,17 +79,64 @@ impl Memory {
         unimplemented!();
     }
 
+    /// Creates a sub-buffer of `size` bytes, which starts `offset` bytes into this Memory.
+    ///
+    /// The sub-buffer shares the data store of this Memory. See `Context::view_memory` for a
+    /// version, which checks the alignment of `offset` up front.
+    pub fn sub_buffer(&self, offset: usize, size: usize) -> Result<Memory, Error> {
+        let id = try!(API::create_sub_buffer(self, MEM_READ_WRITE, offset, size));
+        Ok(Memory::from_c(id, size))
+    }
+
     pub fn id_c(&self) -> cl::memory_id {
         self.memory
     }
 
-    pub fn from_c(id: cl::memory_id) -> Memory {
+    pub fn from_c(id: cl::memory_id, size: usize) -> Memory {
         Memory {
             memory: id,
             memory_flags: MemoryFlags::default(),
             host_ptr: ptr::null_mut(),
+            size: size,
+            owned: true,
         }
     }
+
+    /// Wraps the memory object `id` of `size` bytes, which has been created outside of
+    /// Collenchyma, e.g. by another library.
+    ///
+    /// With `retain`, the Memory retains the memory object through `clRetainMemObject` and
+    /// releases it again on drop, so that it stays valid as long as the Memory lives. Otherwise
+    /// the Memory borrows the memory object and never releases it, so the caller needs to keep
+    /// it alive for the lifetime of the Memory.
+    /// Returns an `InvalidBufferSize` error, if `size` exceeds the size of the memory object.
+    ///
+    /// ## Safety
+    ///
+    /// `id` must be a live OpenCL memory object. The driver can not detect every dangling or
+    /// made-up handle, so querying or retaining those is undefined behavior.
+    pub unsafe fn from_raw(id: cl::memory_id, size: usize, retain: bool) -> Result<Memory, Error> {
+        let available = try!(API::memory_size(id));
+        if size > available {
+            return Err(Error::InvalidBufferSize(format!("Unable to wrap {} bytes of a memory object of {} bytes.", size, available)))
+        }
+        if retain {
+            try!(API::retain_memory(id));
+        }
+        let mut memory = Memory::from_c(id, size);
+        memory.owned = retain;
+        Ok(memory)
+    }
+
+    /// Returns whether the Memory releases its memory object, when it is dropped.
+    pub fn is_owned(&self) -> bool {
+        self.owned
+    }
+
+    /// Returns the size of the allocated memory in bytes.
+    pub fn size(&self) -> usize {
+        self.size
+    }
 }
 
 impl IMemory for Memory {}
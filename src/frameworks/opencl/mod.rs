@@ -11,14 +11,17 @@
 #[cfg(target_os = "linux")]
 extern { }
 
-use framework::IFramework;
+use framework::{IFramework, HardwareIter};
 use device::DeviceType;
-pub use self::platform::Platform;
+pub use self::platform::{Platform, PlatformInfo};
 pub use self::context::Context;
 pub use self::memory::Memory;
-pub use self::queue::Queue;
-pub use self::kernel::Kernel;
-pub use self::program::Program;
+pub use self::queue::{Queue, QueueFlags};
+pub use self::event::Event;
+pub use self::kernel::{Kernel, KernelArg};
+pub use self::program::{Program, BlasKernels, Reduction, Elementwise};
+#[cfg(feature = "f16")]
+pub use self::program::HalfKernels;
 pub use self::device::{Device, DeviceInfo};
 pub use self::api::{API, Error};
 
@@ -27,6 +30,7 @@ pub mod platform;
 pub mod context;
 pub mod memory;
 pub mod queue;
+pub mod event;
 pub mod kernel;
 pub mod program;
 pub mod libraries;
@@ -37,6 +41,7 @@ mod api;
 pub struct OpenCL {
     hardwares: Vec<Device>,
     binary: Program,
+    build_options: String,
 }
 
 /// Provides the OpenCL framework trait for explicit Backend behaviour.
@@ -46,22 +51,66 @@ pub trait IOpenCL {}
 
 impl IOpenCL for OpenCL {}
 
+impl OpenCL {
+    /// Initializes a new OpenCL Framework with the hardwares of the platform at `index` only.
+    ///
+    /// The indices correspond to the order of `OpenCL::platforms`. `OpenCL::new` keeps loading the
+    /// hardwares of all platforms.
+    pub fn with_platform(index: usize) -> Result<OpenCL, ::framework::Error> {
+        let platforms = try!(API::load_platforms());
+        let platform = try!(platforms.get(index).ok_or(::framework::Error::MissingHardware(format!("No OpenCL platform at index {}; {} platforms are available.", index, platforms.len()))));
+        Ok(OpenCL {
+            hardwares: try!(API::load_devices(platform)),
+            binary: Program::from_isize(1),
+            build_options: String::new(),
+        })
+    }
+
+    /// Returns the compiler flags, with which the binary of a new device is built.
+    pub fn build_options(&self) -> &str {
+        &self.build_options
+    }
+
+    /// Defines the compiler flags, with which the binary of a new device is built.
+    ///
+    /// Defaults to no flags. Use e.g. `-cl-fast-relaxed-math` to trade accuracy for speed of the
+    /// BLAS Operations. Invalid flags are reported, when the Backend gets initialized.
+    pub fn set_build_options(&mut self, build_options: &str) {
+        self.build_options = build_options.to_owned();
+    }
+
+    /// Initializes a new Device from the provided hardwares, whose command queue is created with
+    /// `flags`.
+    ///
+    /// Returns an error, if the hardwares do not support the `flags`, e.g. out-of-order execution.
+    pub fn new_device_with_queue_flags(&self, hardwares: Vec<Device>, flags: QueueFlags) -> Result<DeviceType, ::framework::Error> {
+        Ok(DeviceType::OpenCL(try!(Context::with_queue_flags(hardwares, flags))))
+    }
+
+    /// Returns the name, vendor and version of every available OpenCL platform.
+    pub fn platforms() -> Result<Vec<PlatformInfo>, ::framework::Error> {
+        let platforms = try!(API::load_platforms());
+        let mut infos = Vec::with_capacity(platforms.len());
+        for platform in &platforms {
+            infos.push(try!(platform.load_info()));
+        }
+        Ok(infos)
+    }
+}
+
 impl IFramework for OpenCL {
     type H = Device;
     type D = Context;
     type B = Program;
     const ID: &'static str = "OPENCL";
 
-    fn new() -> OpenCL {
-        match OpenCL::load_hardwares() {
-            Ok(hardwares) => {
-                OpenCL {
-                    hardwares: hardwares,
-                    binary: Program::from_isize(1)
-                }
-            },
-            Err(err) => panic!(err)
-        }
+    fn try_new() -> Result<OpenCL, ::framework::Error> {
+        let hardwares = try!(OpenCL::load_hardwares());
+        Ok(OpenCL {
+            hardwares: hardwares,
+            binary: Program::from_isize(1),
+            build_options: String::new(),
+        })
     }
 
     fn load_hardwares() -> Result<Vec<Device>, ::framework::Error> {
@@ -80,10 +129,24 @@ impl IFramework for OpenCL {
         self.hardwares.clone()
     }
 
+    fn hardware_iter(&self) -> HardwareIter<Device> {
+        self.hardwares.iter().cloned()
+    }
+
     fn binary(&self) -> Self::B {
         self.binary.clone()
     }
 
+    /// Builds the OpenCL program with all kernels for the context of the `device`.
+    fn new_binary(&self, device: &DeviceType) -> Result<Program, ::framework::Error> {
+        match *device {
+            DeviceType::OpenCL(ref context) => Ok(try!(Program::with_build_options(context, &self.build_options))),
+            DeviceType::Native(_) => Err(::framework::Error::OpenCL(Error::InvalidContext(format!("Unable to build an OpenCL program for a Native device.")))),
+            #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+            DeviceType::Cuda(_) => Err(::framework::Error::OpenCL(Error::InvalidContext(format!("Unable to build an OpenCL program for a Cuda device.")))),
+        }
+    }
+
     /// Creates a new OpenCL context over one or many devices ready for computation.
     ///
     /// Contexts are used by the OpenCL runtime for managing objects such as command-queues,
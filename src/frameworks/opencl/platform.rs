@@ -1,6 +1,7 @@
 //! Provides a Rust wrapper around OpenCL's platform.
 
 use super::api::types as cl;
+use super::api::{API, Error};
 
 #[derive(Debug, Copy, Clone)]
 /// Defines a OpenCL Platform.
@@ -28,4 +29,26 @@ impl Platform {
     pub fn id_c(&self) -> cl::platform_id {
         self.id as cl::platform_id
     }
+
+    /// Loads the name, vendor and version of the platform via foreign OpenCL calls.
+    pub fn load_info(&self) -> Result<PlatformInfo, Error> {
+        Ok(PlatformInfo {
+            name: try!(API::load_platform_info(self, cl::CL_PLATFORM_NAME)),
+            vendor: try!(API::load_platform_info(self, cl::CL_PLATFORM_VENDOR)),
+            version: try!(API::load_platform_info(self, cl::CL_PLATFORM_VERSION)),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Describes a OpenCL Platform.
+///
+/// Allows to tell the installed OpenCL implementations, e.g. of Intel and NVIDIA, apart.
+pub struct PlatformInfo {
+    /// The name of the platform.
+    pub name: String,
+    /// The vendor of the platform.
+    pub vendor: String,
+    /// The OpenCL version, which is supported by the platform.
+    pub version: String,
 }
@@ -1,33 +1,195 @@
 //! Provides a Rust wrapper around OpenCL's Program.
 
 use binary::IBinary;
-use frameworks::opencl::Kernel;
+use device::IDevice;
+use frameworks::opencl::{Kernel, KernelArg, Context, Device, Queue, Event};
 use super::api::types as cl;
-use super::api::API;
+use super::api::{API, Error};
+use libraries::blas::{BinaryOp, ReduceOp, BlasNum};
+use std::collections::HashMap;
 
-#[derive(Debug, Copy, Clone)]
+/// The OpenCL source template of the BLAS kernels, see `templated_source`.
+///
+/// The double precision instance only gets compiled, if all devices support `cl_khr_fp64`.
+const BLAS_TEMPLATE: &'static str = include_str!("libraries/blas.cl");
+
+/// The OpenCL source of the half precision kernels.
+///
+/// Only gets compiled, if all devices support `cl_khr_fp16`.
+#[cfg(feature = "f16")]
+const HALF_SOURCE: &'static str = include_str!("libraries/half.cl");
+
+/// Instantiates the kernel source `template` for the element type `T`.
+///
+/// Replaces `$T` with the OpenCL C name of `T` and `$P` with its BLAS prefix, and enables the
+/// extension, which `T` requires.
+pub fn templated_source<T: BlasNum>(template: &str) -> String {
+    let source = template.replace("$T", T::CL_TYPE).replace("$P", T::PREFIX);
+    match T::EXTENSION {
+        Some(extension) => format!("#pragma OPENCL EXTENSION {} : enable\n{}", extension, source),
+        None => source,
+    }
+}
+
+/// Adds the half precision kernels to the `sources`, if all devices of the `context` support them.
+///
+/// Returns whether the kernels have been added.
+#[cfg(feature = "f16")]
+fn push_half_source<'a>(context: &Context, sources: &mut Vec<&'a str>) -> bool {
+    let fp16 = context.hardwares().iter().all(|device| device.supports_fp16());
+    if fp16 {
+        sources.push(HALF_SOURCE);
+    } else {
+        co_warn!("Not all OpenCL devices support cl_khr_fp16, building without half precision kernels");
+    }
+    fp16
+}
+
+#[derive(Debug, Clone)]
 /// Defines a OpenCL Program.
 ///
 /// A Program is OpenCL's version of Collenchyma's [binary][binary].
 /// [binary]: ../../binary/index.html
 pub struct Program {
     id: isize,
-    /// The initialized BLAS dot Operation.
-    pub blas_dot: Kernel,
-    /// The initialized BLAS scale Operation.
-    pub blas_scale: Kernel,
-    /// The initialized BLAS axpy Operation.
-    pub blas_axpy: Kernel,
+    /// The initialized single precision BLAS Operations.
+    pub blas_single: BlasKernels,
+    /// The initialized double precision BLAS Operations.
+    ///
+    /// `None` if the devices do not support double precision.
+    pub blas_double: Option<BlasKernels>,
+    /// The initialized half precision Operations.
+    ///
+    /// `None` if the devices do not support half precision.
+    #[cfg(feature = "f16")]
+    pub blas_half: Option<HalfKernels>,
+    /// The initialized BLAS copy Operation.
+    ///
+    /// Copies are issued directly on the command queue, without a kernel.
+    pub blas_copy: Queue,
+    /// The kernels of a Program, which was build from custom source.
+    kernels: HashMap<String, Kernel>,
 }
 
 impl Program {
+    /// Builds the OpenCL kernels for all devices of the `context`.
+    ///
+    /// The kernels get executed on the command queue of the context.
+    pub fn new(context: &Context) -> Result<Program, Error> {
+        Program::with_build_options(context, "")
+    }
+
+    /// Builds the OpenCL kernels for all devices of the `context` with the compiler flags
+    /// `build_options`.
+    ///
+    /// Flags such as `-cl-fast-relaxed-math` or `-cl-mad-enable` trade accuracy for speed of the
+    /// BLAS Operations. Returns an `InvalidBuildOptions` error with the build logs, if the
+    /// compiler rejects the flags.
+    pub fn with_build_options(context: &Context, build_options: &str) -> Result<Program, Error> {
+        let queue = try!(context.queue().cloned().ok_or(Error::InvalidCommandQueue(format!("Context has no command queue."))));
+        let fp64 = context.hardwares().iter().all(|device| device.supports_element::<f64>());
+        let single = templated_source::<f32>(BLAS_TEMPLATE);
+        let double = templated_source::<f64>(BLAS_TEMPLATE);
+        let mut sources = vec![&single[..]];
+        if fp64 {
+            sources.push(&double);
+        } else {
+            co_warn!("Not all OpenCL devices support cl_khr_fp64, building without double precision kernels");
+        }
+        #[cfg(feature = "f16")]
+        let fp16 = push_half_source(context, &mut sources);
+        let id = try!(API::create_program_with_source(context, sources));
+        try!(API::build_program(id, &context.hardwares(), build_options));
+        Ok(Program {
+            id: id as isize,
+            blas_single: try!(BlasKernels::new::<f32>(id, queue)),
+            blas_double: if fp64 { Some(try!(BlasKernels::new::<f64>(id, queue))) } else { None },
+            #[cfg(feature = "f16")]
+            blas_half: if fp16 { Some(try!(HalfKernels::new(id, queue))) } else { None },
+            blas_copy: queue,
+            kernels: HashMap::new(),
+        })
+    }
+
+    /// Builds custom OpenCL C `source` for all devices of the `context`.
+    ///
+    /// Creates the kernels `kernel_names`, which can then be launched through `run`. The BLAS
+    /// Operations of the Program are not initialized.
+    /// If the build fails, the returned `BuildProgramFailure` contains the build logs.
+    pub fn from_source(context: &Context, source: &str, kernel_names: &[&str]) -> Result<Program, Error> {
+        let queue = try!(context.queue().cloned().ok_or(Error::InvalidCommandQueue(format!("Context has no command queue."))));
+        let id = try!(API::create_program_with_source(context, vec![source]));
+        try!(API::build_program(id, &context.hardwares(), ""));
+        let mut kernels = HashMap::new();
+        for name in kernel_names {
+            kernels.insert(name.to_string(), try!(Kernel::new(id, name, queue)));
+        }
+        let mut program = Program::from_c(id);
+        program.kernels = kernels;
+        Ok(program)
+    }
+
+    /// Returns the BLAS kernels for the element type `T`.
+    ///
+    /// Returns `None`, if the kernels have not been built for `T`, as not all devices support it.
+    pub fn blas_kernels<T: BlasNum>(&self) -> Option<BlasKernels> {
+        match T::PREFIX {
+            "s" => Some(self.blas_single),
+            "d" => self.blas_double,
+            _ => None,
+        }
+    }
+
+    /// Returns the custom kernel `name`.
+    pub fn kernel(&self, name: &str) -> Option<&Kernel> {
+        self.kernels.get(name)
+    }
+
+    /// Returns the maximum work-group size, with which the `kernel` can be executed on the `device`.
+    pub fn kernel_work_group_size(&self, kernel: &Kernel, device: &Device) -> Result<usize, Error> {
+        API::kernel_work_group_size(kernel, device)
+    }
+
+    /// Sets all the arguments of the custom kernel `kernel_name`.
+    ///
+    /// Returns an `InvalidKernelName` error, if the Program has no kernel `kernel_name`.
+    pub fn set_args(&self, kernel_name: &str, args: &[KernelArg]) -> Result<(), Error> {
+        try!(self.custom_kernel(kernel_name)).set_args(args)
+    }
+
+    /// Sets the `args` and enqueues the custom kernel `kernel_name` for execution.
+    ///
+    /// Returns an `InvalidKernelName` error, if the Program has no kernel `kernel_name`.
+    pub fn run(&self, kernel_name: &str, args: &[KernelArg], global_work_size: &[usize], local_work_size: &[usize]) -> Result<(), Error> {
+        let kernel = try!(self.custom_kernel(kernel_name));
+        try!(kernel.set_args(args));
+        kernel.enqueue(global_work_size, local_work_size)
+    }
+
+    /// Sets the `args` and enqueues the custom kernel `kernel_name`, once all the commands of the
+    /// `wait_list` have been completed.
+    ///
+    /// Returns the Event of the execution, which can be waited on or passed to further commands.
+    pub fn run_with_events(&self, kernel_name: &str, args: &[KernelArg], global_work_size: &[usize], local_work_size: &[usize], wait_list: &[&Event]) -> Result<Event, Error> {
+        let kernel = try!(self.custom_kernel(kernel_name));
+        try!(kernel.set_args(args));
+        kernel.enqueue_with_events(global_work_size, local_work_size, wait_list)
+    }
+
+    fn custom_kernel(&self, name: &str) -> Result<&Kernel, Error> {
+        self.kernel(name).ok_or(Error::InvalidKernelName(format!("Program has no kernel {:?}.", name)))
+    }
+
     /// Initializes a new OpenCL device.
     pub fn from_isize(id: isize) -> Program {
         Program {
             id: id,
-            blas_dot: Kernel::from_isize(1),
-            blas_scale: Kernel::from_isize(1),
-            blas_axpy: Kernel::from_isize(1),
+            blas_single: BlasKernels::from_isize(1),
+            blas_double: None,
+            #[cfg(feature = "f16")]
+            blas_half: None,
+            blas_copy: Queue::from_isize(1),
+            kernels: HashMap::new(),
         }
     }
 
@@ -35,9 +197,12 @@ impl Program {
     pub fn from_c(id: cl::kernel_id) -> Program {
         Program {
             id: id as isize,
-            blas_dot: Kernel::from_isize(1),
-            blas_scale: Kernel::from_isize(1),
-            blas_axpy: Kernel::from_isize(1),
+            blas_single: BlasKernels::from_isize(1),
+            blas_double: None,
+            #[cfg(feature = "f16")]
+            blas_half: None,
+            blas_copy: Queue::from_isize(1),
+            kernels: HashMap::new(),
         }
     }
 
@@ -48,3 +213,184 @@ impl Program {
 }
 
 impl IBinary for Program {}
+
+#[derive(Debug, Copy, Clone)]
+/// Defines the OpenCL kernels of the BLAS Operations for one element type.
+///
+/// Every element type gets its own instance of the templated kernel source.
+pub struct BlasKernels {
+    /// The initialized BLAS asum Operation.
+    pub asum: Kernel,
+    /// The initialized BLAS iamax Operation.
+    pub iamax: Kernel,
+    /// The initialized BLAS dot Operation.
+    pub dot: Kernel,
+    /// The initialized BLAS nrm2 Operation.
+    pub nrm2: Kernel,
+    /// The initialized BLAS scale Operation.
+    pub scale: Kernel,
+    /// The initialized BLAS axpy Operation.
+    pub axpy: Kernel,
+    /// The initialized BLAS swap Operation.
+    pub swap: Kernel,
+    /// The initialized BLAS gemv Operation.
+    pub gemv: Kernel,
+    /// The initialized BLAS gemm Operation.
+    pub gemm: Kernel,
+    /// The initialized BLAS gemm_batched Operation.
+    pub gemm_batched: Kernel,
+    /// The initialized Reduce Operation.
+    pub reduce: Reduction,
+    /// The initialized Map Operation.
+    pub map: Kernel,
+    /// The initialized Elementwise Operation.
+    pub elementwise: Elementwise,
+}
+
+impl BlasKernels {
+    /// Creates the kernels for the element type `T` of the built program `id`.
+    fn new<T: BlasNum>(id: cl::program, queue: Queue) -> Result<BlasKernels, Error> {
+        let kernel = |name: &str| Kernel::new(id, &format!("{}{}", T::PREFIX, name), queue);
+        Ok(BlasKernels {
+            asum: try!(kernel("asum")),
+            iamax: try!(kernel("iamax")),
+            dot: try!(kernel("dot")),
+            nrm2: try!(kernel("nrm2")),
+            scale: try!(kernel("scal")),
+            axpy: try!(kernel("axpy")),
+            swap: try!(kernel("swap")),
+            gemv: try!(kernel("gemv")),
+            gemm: try!(kernel("gemm")),
+            gemm_batched: try!(kernel("gemm_batched")),
+            reduce: Reduction {
+                sum: try!(kernel("reduce_sum")),
+                max: try!(kernel("reduce_max")),
+                min: try!(kernel("reduce_min")),
+                product: try!(kernel("reduce_product")),
+            },
+            map: try!(kernel("map")),
+            elementwise: Elementwise {
+                add: try!(kernel("add")),
+                sub: try!(kernel("sub")),
+                mul: try!(kernel("mul")),
+                div: try!(kernel("div")),
+            },
+        })
+    }
+
+    /// Initializes new BlasKernels, whose kernels are all `id`.
+    pub fn from_isize(id: isize) -> BlasKernels {
+        BlasKernels {
+            asum: Kernel::from_isize(id),
+            iamax: Kernel::from_isize(id),
+            dot: Kernel::from_isize(id),
+            nrm2: Kernel::from_isize(id),
+            scale: Kernel::from_isize(id),
+            axpy: Kernel::from_isize(id),
+            swap: Kernel::from_isize(id),
+            gemv: Kernel::from_isize(id),
+            gemm: Kernel::from_isize(id),
+            gemm_batched: Kernel::from_isize(id),
+            reduce: Reduction::from_isize(id),
+            map: Kernel::from_isize(id),
+            elementwise: Elementwise::from_isize(id),
+        }
+    }
+}
+
+#[cfg(feature = "f16")]
+#[derive(Debug, Copy, Clone)]
+/// Defines the OpenCL kernels of the half precision Operations.
+///
+/// Copies of half precision memory are issued on the command queue, like for the BLAS copy.
+pub struct HalfKernels {
+    /// The initialized half precision scale Operation.
+    pub scale: Kernel,
+    /// The initialized half precision axpy Operation.
+    pub axpy: Kernel,
+}
+
+#[cfg(feature = "f16")]
+impl HalfKernels {
+    /// Creates the half precision kernels of the built program `id`.
+    fn new(id: cl::program, queue: Queue) -> Result<HalfKernels, Error> {
+        Ok(HalfKernels {
+            scale: try!(Kernel::new(id, "hscal", queue)),
+            axpy: try!(Kernel::new(id, "haxpy", queue)),
+        })
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+/// Defines the OpenCL kernels of the Reduce Operation.
+///
+/// Every `ReduceOp` is compiled into its own specialization of the reduction kernel.
+pub struct Reduction {
+    /// Adds up the elements.
+    pub sum: Kernel,
+    /// Takes the largest element.
+    pub max: Kernel,
+    /// Takes the smallest element.
+    pub min: Kernel,
+    /// Multiplies the elements.
+    pub product: Kernel,
+}
+
+impl Reduction {
+    /// Initializes a new Reduction, whose kernels are all `id`.
+    pub fn from_isize(id: isize) -> Reduction {
+        Reduction {
+            sum: Kernel::from_isize(id),
+            max: Kernel::from_isize(id),
+            min: Kernel::from_isize(id),
+            product: Kernel::from_isize(id),
+        }
+    }
+
+    /// Returns the kernel, which reduces with `op`.
+    pub fn kernel(&self, op: ReduceOp) -> &Kernel {
+        match op {
+            ReduceOp::Sum => &self.sum,
+            ReduceOp::Max => &self.max,
+            ReduceOp::Min => &self.min,
+            ReduceOp::Product => &self.product,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+/// Defines the OpenCL kernels of the Elementwise Operation.
+///
+/// Every `BinaryOp` is compiled into its own specialization of the element-wise kernel.
+pub struct Elementwise {
+    /// Adds the elements.
+    pub add: Kernel,
+    /// Subtracts the elements.
+    pub sub: Kernel,
+    /// Multiplies the elements.
+    pub mul: Kernel,
+    /// Divides the elements.
+    pub div: Kernel,
+}
+
+impl Elementwise {
+    /// Initializes a new Elementwise, whose kernels are all `id`.
+    pub fn from_isize(id: isize) -> Elementwise {
+        Elementwise {
+            add: Kernel::from_isize(id),
+            sub: Kernel::from_isize(id),
+            mul: Kernel::from_isize(id),
+            div: Kernel::from_isize(id),
+        }
+    }
+
+    /// Returns the kernel, which combines the elements with `op`.
+    pub fn kernel(&self, op: BinaryOp) -> &Kernel {
+        match op {
+            BinaryOp::Add => &self.add,
+            BinaryOp::Sub => &self.sub,
+            BinaryOp::Mul => &self.mul,
+            BinaryOp::Div => &self.div,
+        }
+    }
+}
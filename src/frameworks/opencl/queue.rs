@@ -11,6 +11,25 @@
 //! synchronization.
 
 use super::api::types as cl;
+use super::api::{API, Error};
+use super::{Context, Device, Event};
+use hardware::IHardware;
+
+bitflags! {
+    #[doc = "Defines the execution properties of a OpenCL command queue."]
+    flags QueueFlags: cl::bitfield {
+        #[doc = "Allows the commands of the queue to be executed out of order."]
+        const QUEUE_OUT_OF_ORDER_EXEC_MODE_ENABLE = 1 << 0,
+        #[doc = "Enables the profiling of the commands of the queue."]
+        const QUEUE_PROFILING_ENABLE              = 1 << 1,
+    }
+}
+
+impl Default for QueueFlags {
+    fn default() -> QueueFlags {
+        QueueFlags::empty()
+    }
+}
 
 #[derive(Debug, Copy, Clone)]
 /// Defines a OpenCL Queue.
@@ -19,6 +38,17 @@ pub struct Queue {
 }
 
 impl Queue {
+    /// Creates a new OpenCL command queue on the `device` of the `context`.
+    ///
+    /// Returns an error, if the `device` does not support the requested `flags`.
+    pub fn new(context: &Context, device: &Device, flags: QueueFlags) -> Result<Queue, Error> {
+        let supported = device.queue_flags().unwrap_or(QueueFlags::empty());
+        if !supported.contains(flags) {
+            return Err(Error::InvalidProperty(format!("The device {:?} does not support the queue properties {:?}.", device.name(), flags - supported)))
+        }
+        Ok(Queue::from_c(try!(API::create_queue(context, device, flags.bits()))))
+    }
+
     /// Initializes a new OpenCL command queue.
     pub fn from_isize(id: isize) -> Queue {
         Queue { id: id }
@@ -38,4 +68,30 @@ impl Queue {
     pub fn id_c(&self) -> cl::queue_id {
         self.id as cl::queue_id
     }
+
+    /// Returns the device, on which the commands of the queue are executed.
+    pub fn device(&self) -> Result<Device, Error> {
+        API::queue_device(self)
+    }
+
+    /// Returns the properties, with which the queue was created.
+    pub fn flags(&self) -> Result<QueueFlags, Error> {
+        Ok(QueueFlags::from_bits_truncate(try!(API::queue_properties(self))))
+    }
+
+    /// Issues all commands of the queue to its device, without waiting for them to complete.
+    pub fn flush(&self) -> Result<(), Error> {
+        API::flush(self)
+    }
+
+    /// Blocks until all commands issued to the queue have been completed.
+    pub fn finish(&self) -> Result<(), Error> {
+        API::finish(self)
+    }
+
+    /// Enqueues a marker, whose Event completes once all previously issued commands have been
+    /// completed.
+    pub fn marker(&self) -> Result<Event, Error> {
+        API::enqueue_marker(self)
+    }
 }
@@ -6,6 +6,14 @@
 //! To execute code on hardware, turn hardware into a [device][device].
 //!
 //! [device]: ../device/index.html
+//!
+//! Frameworks, which support device fission, can split a hardware into sub-hardwares via
+//! `create_sub_devices`. The sub-hardwares are hardwares like any other and can be passed on to a
+//! [BackendConfig][backend_config].
+//!
+//! [backend_config]: ../backend/struct.BackendConfig.html
+
+use framework::Error;
 
 #[derive(Debug, Copy, Clone, PartialEq, Hash)]
 /// Specifies the available Hardware types.
@@ -20,6 +28,34 @@ pub enum HardwareType {
     OTHER
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Specifies how a Hardware gets partitioned into sub-hardwares.
+pub enum Partition {
+    /// Splits the Hardware into as many sub-hardwares as possible, with `n` compute units each.
+    Equally(usize),
+    /// Splits the Hardware into one sub-hardware per entry, with the given number of compute units.
+    ByCounts(Vec<usize>),
+    /// Splits the Hardware along the compute units, which share the given affinity domain.
+    ByAffinityDomain(AffinityDomain),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+/// Specifies the affinity domain, along which a Hardware gets partitioned.
+pub enum AffinityDomain {
+    /// Compute units sharing a NUMA node.
+    Numa,
+    /// Compute units sharing a level 4 data cache.
+    L4Cache,
+    /// Compute units sharing a level 3 data cache.
+    L3Cache,
+    /// Compute units sharing a level 2 data cache.
+    L2Cache,
+    /// Compute units sharing a level 1 data cache.
+    L1Cache,
+    /// The next partitionable affinity domain, from NUMA down to the level 1 cache.
+    NextPartitionable,
+}
+
 /// Specifies Hardware behavior accross frameworks.
 pub trait IHardware {
     /// Returns the ID of the Hardware
@@ -43,6 +79,20 @@ pub trait IHardware {
     /// Defines the compute_units of the Hardware
     fn set_compute_units(&mut self, compute_units: Option<isize>) -> Self;
 
+    /// Returns the memory of the Hardware in bytes
+    ///
+    /// Defaults to `None` for Hardware, which does not report its memory.
+    fn memory(&self) -> Option<usize> {
+        None
+    }
+
     /// Build an inmutable Hardware
     fn build(self) -> Self;
+
+    /// Partitions the Hardware into sub-hardwares.
+    ///
+    /// Defaults to a `NotSupported` error for Hardware, which can not be partitioned.
+    fn create_sub_devices(&self, partition: Partition) -> Result<Vec<Self>, Error> where Self: Sized {
+        Err(Error::NotSupported(format!("Hardware can not be partitioned by {:?}", partition)))
+    }
 }
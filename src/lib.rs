@@ -104,6 +104,26 @@
 //! }
 //! ```
 //!
+//! ## Features
+//!
+//! The OpenCL and Cuda frameworks are compiled with the `opencl` (default) and `cuda` features.
+//! The `cuda-runtime` feature compiles the Cuda framework on top of the Cuda runtime API instead
+//! of the driver API, for applications, which use the runtime API elsewhere. It can not be
+//! combined with the `cuda` feature.
+//! The Native framework is compiled unconditionally, as SharedMemory keeps its host copies on it,
+//! but its BLAS operations, which link against a system BLAS, need the `native` (default) feature.
+//! At least one of `native`, `opencl`, `cuda` and `cuda-runtime` has to be enabled, or the crate
+//! fails to compile, as it would not provide any usable Backend.
+//!
+//! The `serialize` feature implements serde's `Serialize` and `Deserialize` for the
+//! [BackendSpec][spec], so that a selection of hardwares can be persisted across runs.
+//!
+//! The `f16` feature adds half precision [SharedMemory][shared-memory] and the half precision
+//! `axpy_f16`, `scale_f16` and `copy_f16` operations on the Native and OpenCL Backends. On
+//! OpenCL, `axpy_f16` and `scale_f16` need all devices to support `cl_khr_fp16`.
+//!
+//! [spec]: ./backend/struct.BackendSpec.html
+//!
 //! ## Development
 //!
 //! At the moment Collenchyma itself will provide Rust APIs for the important frameworks - OpenCL
@@ -133,6 +153,8 @@
 //! [shared-memory]: ./shared-memory/index.html
 #![cfg_attr(lint, feature(plugin))]
 #![cfg_attr(lint, plugin(clippy))]
+#![cfg_attr(feature = "serialize", feature(custom_derive, plugin))]
+#![cfg_attr(feature = "serialize", plugin(serde_macros))]
 #![allow(dead_code)]
 #![feature(associated_consts)]
 #![feature(associated_type_defaults)]
@@ -150,7 +172,24 @@ extern crate bitflags;
 extern crate enum_primitive;
 extern crate num;
 extern crate byteorder;
+#[cfg(feature = "native")]
 extern crate rblas as blas;
+#[cfg(feature = "serialize")]
+extern crate serde;
+#[cfg(feature = "f16")]
+extern crate half;
+#[cfg(feature = "logging")]
+#[macro_use]
+extern crate log;
+
+#[cfg(not(any(feature = "native", feature = "opencl", feature = "cuda", feature = "cuda-runtime")))]
+compile_error!("Collenchyma needs at least one of the `native`, `opencl`, `cuda` or `cuda-runtime` features to provide a usable Backend.");
+#[cfg(all(feature = "cuda", feature = "cuda-runtime"))]
+compile_error!("The `cuda` and `cuda-runtime` features are mutually exclusive, as they build the Cuda framework on the driver and the runtime API respectively.");
+
+#[macro_use]
+mod logging;
+mod validate;
 
 pub mod backend;
 pub mod device;
@@ -160,6 +199,9 @@ pub mod frameworks;
 pub mod memory;
 pub mod libraries;
 pub mod shared_memory;
+pub mod alloc_tracker;
+pub mod memory_pool;
+pub mod stats;
 pub mod operation;
 pub mod binary;
 pub mod error;
@@ -16,10 +16,67 @@ use memory::MemoryType;
 use shared_memory::SharedMemory;
 use binary::IBinary;
 use device::DeviceType;
-use libraries::Float;
+use libraries::Element;
+use validate;
+
+/// Restricts the implementations of `BlasNum` to this crate.
+mod sealed {
+    /// Implemented for the element types, which implement `BlasNum`.
+    pub trait Sealed {}
+
+    impl Sealed for f32 {}
+    impl Sealed for f64 {}
+}
+
+/// Describes the element types of BLAS operands, for which kernels are generated.
+///
+/// Carries the name of the type in OpenCL C and the extension, which a device needs to support
+/// it, so that one kernel source can be instantiated for every element type. The trait is sealed,
+/// as a wrong type name would only surface, when the kernels get compiled for a device.
+pub trait BlasNum: Element + sealed::Sealed {
+    /// The name of the type in OpenCL C.
+    const CL_TYPE: &'static str;
+    /// The prefix of the BLAS routines of the type, e.g. `s` for `sdot`.
+    const PREFIX: &'static str;
+    /// The OpenCL extension, which a device needs to support the type, if any.
+    const EXTENSION: Option<&'static str>;
+    /// The size of the type in bytes.
+    const SIZE: usize;
+}
+
+impl BlasNum for f32 {
+    const CL_TYPE: &'static str = "float";
+    const PREFIX: &'static str = "s";
+    const EXTENSION: Option<&'static str> = None;
+    const SIZE: usize = 4;
+}
+
+impl BlasNum for f64 {
+    const CL_TYPE: &'static str = "double";
+    const PREFIX: &'static str = "d";
+    const EXTENSION: Option<&'static str> = Some("cl_khr_fp64");
+    const SIZE: usize = 8;
+}
+
+/// Describes the lengths of the `(name, length)` operands of an operation for an error context.
+fn describe(operands: &[(&str, usize)]) -> String {
+    operands.iter().map(|&(name, len)| format!("`{}` holds {} elements", name, len)).collect::<Vec<String>>().join(", ")
+}
+
+/// Returns the copy on `device` of the output operand `mem`, whose first `written` elements the
+/// operation overwrites.
+///
+/// An outdated copy is only synchronized, if some of its elements are kept.
+fn output<'a, T>(mem: &'a mut SharedMemory<T>, device: &DeviceType, written: usize) -> Result<&'a mut MemoryType, ::shared_memory::Error> {
+    if written >= mem.capacity() {
+        mem.get_mut_write_only(device)
+    } else {
+        mem.get_mut(device)
+    }
+}
 
 /// Provides the functionality for a backend to support Basic Linear Algebra Subprogram operations.
-pub trait IBlas<F: Float> {
+pub trait IBlas<F: Element> {
     /// The Binary representation for this Library.
     type B: IBlasBinary<F> + IBinary;
 
@@ -28,13 +85,35 @@ pub trait IBlas<F: Float> {
     /// Saves the result to `result`.
     /// This is a Level 1 BLAS operation.
     fn asum(&self, x: &mut SharedMemory<F>, result: &mut SharedMemory<F>) -> Result<(), ::error::Error> {
+        let operands = [("x", x.capacity()), ("result", result.capacity())];
+        let detail = || describe(&operands);
+        try!(validate::scalar(("result", result.capacity())).map_err(|err| err.with_context("asum", detail())));
         match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
         match result.add_device(self.device()) { _ => () }
         Ok(try!(
             self.binary().asum().compute(
                 try!(x.get(self.device()).ok_or(Error::MissingArgument(format!("Unable to resolve memory for `x`")))),
-                try!(result.get_mut(self.device()).ok_or(Error::MissingArgument(format!("Unable to resolve memory for `result`")))),
-            )
+                try!(output(result, self.device(), 1)),
+            ).map_err(|err| err.with_context("asum", detail()))
+        ))
+    }
+
+    /// Finds the index of the element of vector `x` with the largest absolute value.
+    ///
+    /// Saves the zero-based index to `result`, or `-1` if `x` is empty. Ties resolve to the lowest
+    /// index, like `i?amax` of the reference BLAS, which however counts from one.
+    /// This is a Level 1 BLAS operation.
+    fn iamax(&self, x: &mut SharedMemory<F>, result: &mut SharedMemory<i32>) -> Result<(), ::error::Error> {
+        let operands = [("x", x.capacity()), ("result", result.capacity())];
+        let detail = || describe(&operands);
+        try!(validate::scalar(("result", result.capacity())).map_err(|err| err.with_context("iamax", detail())));
+        match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+        match result.add_device(self.device()) { _ => () }
+        Ok(try!(
+            self.binary().iamax().compute(
+                try!(x.get(self.device()).ok_or(Error::MissingArgument(format!("Unable to resolve memory for `x`")))),
+                try!(output(result, self.device(), 1)),
+            ).map_err(|err| err.with_context("iamax", detail()))
         ))
     }
 
@@ -43,6 +122,11 @@ pub trait IBlas<F: Float> {
     /// Saves the resulting vector back into `y`.
     /// This is a Level 1 BLAS operation.
     fn axpy(&self, a: &mut SharedMemory<F>, x: &mut SharedMemory<F>, y: &mut SharedMemory<F>) -> Result<(), ::error::Error> {
+        let operands = [("a", a.capacity()), ("x", x.capacity()), ("y", y.capacity())];
+        let detail = || describe(&operands);
+        try!(validate::scalar(("a", a.capacity()))
+            .and_then(|_| validate::same_len(("x", x.capacity()), ("y", y.capacity())))
+            .map_err(|err| err.with_context("axpy", detail())));
         match a.add_device(self.device()) { _ => try!(a.sync(self.device())) }
         match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
         match y.add_device(self.device()) { _ => try!(y.sync(self.device())) }
@@ -50,8 +134,8 @@ pub trait IBlas<F: Float> {
             self.binary().axpy().compute(
                 try!(a.get(self.device()).ok_or(Error::MissingArgument(format!("Unable to resolve memory for `a`")))),
                 try!(x.get(self.device()).ok_or(Error::MissingArgument(format!("Unable to resolve memory for `x`")))),
-                try!(y.get_mut(self.device()).ok_or(Error::MissingArgument(format!("Unable to resolve memory for `y`")))),
-            )
+                try!(y.get_mut(self.device())),
+            ).map_err(|err| err.with_context("axpy", detail()))
         ))
     }
 
@@ -60,22 +144,30 @@ pub trait IBlas<F: Float> {
     /// Saves the result to `y`.
     /// This is a Level 1 BLAS operation.
     fn copy(&self, x: &mut SharedMemory<F>, y: &mut SharedMemory<F>) -> Result<(), ::error::Error> {
+        let operands = [("x", x.capacity()), ("y", y.capacity())];
+        let detail = || describe(&operands);
+        try!(validate::at_least(("x", x.capacity()), ("y", y.capacity())).map_err(|err| err.with_context("copy", detail())));
         match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
         match y.add_device(self.device()) { _ => () }
         Ok(try!(
             self.binary().copy().compute(
                 try!(x.get(self.device()).ok_or(Error::MissingArgument(format!("Unable to resolve memory for `x`")))),
-                try!(y.get_mut(self.device()).ok_or(Error::MissingArgument(format!("Unable to resolve memory for `y`")))),
-            )
+                try!(output(y, self.device(), x.capacity())),
+            ).map_err(|err| err.with_context("copy", detail()))
         ))
     }
 
     /// Computes the [dot product][dot-product] over x and y.
     /// [dot-product]: https://en.wikipedia.org/wiki/Dot_product
     ///
-    /// Saves the resulting value into `result`.
+    /// `x` and `y` need to have the same length. Saves the resulting value into `result`.
     /// This is a Level 1 BLAS operation.
     fn dot(&self, x: &mut SharedMemory<F>, y: &mut SharedMemory<F>, result: &mut SharedMemory<F>) -> Result<(), ::error::Error> {
+        let operands = [("x", x.capacity()), ("y", y.capacity()), ("result", result.capacity())];
+        let detail = || describe(&operands);
+        try!(validate::same_len(("x", x.capacity()), ("y", y.capacity()))
+            .and_then(|_| validate::scalar(("result", result.capacity())))
+            .map_err(|err| err.with_context("dot", detail())));
         match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
         match y.add_device(self.device()) { _ => try!(y.sync(self.device())) }
         match result.add_device(self.device()) { _ => () }
@@ -83,8 +175,8 @@ pub trait IBlas<F: Float> {
             self.binary().dot().compute(
                 try!(x.get(self.device()).ok_or(Error::MissingArgument(format!("Unable to resolve memory for `x`")))),
                 try!(y.get(self.device()).ok_or(Error::MissingArgument(format!("Unable to resolve memory for `y`")))),
-                try!(result.get_mut(self.device()).ok_or(Error::MissingArgument(format!("Unable to resolve memory for `result`")))),
-            )
+                try!(output(result, self.device(), 1)),
+            ).map_err(|err| err.with_context("dot", detail()))
         ))
     }
 
@@ -93,13 +185,16 @@ pub trait IBlas<F: Float> {
     /// Saves the result to `result`.
     /// This is a Level 1 BLAS operation.
     fn nrm2(&self, x: &mut SharedMemory<F>, result: &mut SharedMemory<F>) -> Result<(), ::error::Error> {
+        let operands = [("x", x.capacity()), ("result", result.capacity())];
+        let detail = || describe(&operands);
+        try!(validate::scalar(("result", result.capacity())).map_err(|err| err.with_context("nrm2", detail())));
         match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
         match result.add_device(self.device()) { _ => () }
         Ok(try!(
             self.binary().nrm2().compute(
                 try!(x.get(self.device()).ok_or(Error::MissingArgument(format!("Unable to resolve memory for `x`")))),
-                try!(result.get_mut(self.device()).ok_or(Error::MissingArgument(format!("Unable to resolve memory for `result`")))),
-            )
+                try!(output(result, self.device(), 1)),
+            ).map_err(|err| err.with_context("nrm2", detail()))
         ))
     }
 
@@ -108,13 +203,16 @@ pub trait IBlas<F: Float> {
     /// Saves the resulting vector back into `x`.
     /// This is a Level 1 BLAS operation.
     fn scale(&self, a: &mut SharedMemory<F>, x: &mut SharedMemory<F>) -> Result<(), ::error::Error> {
+        let operands = [("a", a.capacity()), ("x", x.capacity())];
+        let detail = || describe(&operands);
+        try!(validate::scalar(("a", a.capacity())).map_err(|err| err.with_context("scale", detail())));
         match a.add_device(self.device()) { _ => try!(a.sync(self.device())) }
         match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
         Ok(try!(
             self.binary().scale().compute(
                 try!(a.get(self.device()).ok_or(Error::MissingArgument(format!("Unable to resolve memory for `a`")))),
-                try!(x.get_mut(self.device()).ok_or(Error::MissingArgument(format!("Unable to resolve memory for `x`")))),
-            )
+                try!(x.get_mut(self.device())),
+            ).map_err(|err| err.with_context("scale", detail()))
         ))
     }
 
@@ -123,16 +221,197 @@ pub trait IBlas<F: Float> {
     /// Saves the resulting vector back into `x`.
     /// This is a Level 1 BLAS operation.
     fn swap(&self, x: &mut SharedMemory<F>, y: &mut SharedMemory<F>) -> Result<(), ::error::Error> {
+        let operands = [("x", x.capacity()), ("y", y.capacity())];
+        let detail = || describe(&operands);
+        try!(validate::same_len(("x", x.capacity()), ("y", y.capacity())).map_err(|err| err.with_context("swap", detail())));
         match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
         match y.add_device(self.device()) { _ => try!(y.sync(self.device())) }
         Ok(try!(
             self.binary().swap().compute(
-                try!(x.get_mut(self.device()).ok_or(Error::MissingArgument(format!("Unable to resolve memory for `x`")))),
-                try!(y.get_mut(self.device()).ok_or(Error::MissingArgument(format!("Unable to resolve memory for `y`")))),
-            )
+                try!(x.get_mut(self.device())),
+                try!(y.get_mut(self.device())),
+            ).map_err(|err| err.with_context("swap", detail()))
         ))
     }
 
+    /// Computes a matrix-vector product with a general matrix aka. `alpha * op(A) * x + beta * y`.
+    ///
+    /// `A` is a `rows` x `cols` matrix stored in row-major order. `trans` defines whether `A` gets
+    /// transposed, which determines the expected lengths of `x` and `y`.
+    /// Saves the resulting vector into `y`.
+    /// This is a Level 2 BLAS operation.
+    fn gemv(&self, trans: Transpose, alpha: &mut SharedMemory<F>, a: &mut SharedMemory<F>, x: &mut SharedMemory<F>, beta: &mut SharedMemory<F>, y: &mut SharedMemory<F>, rows: usize, cols: usize) -> Result<(), ::error::Error> {
+        let operands = [("alpha", alpha.capacity()), ("a", a.capacity()), ("x", x.capacity()), ("beta", beta.capacity()), ("y", y.capacity())];
+        let detail = || format!("{} for {} rows and {} columns", describe(&operands), rows, cols);
+        let (x_len, y_len) = if trans.is_transposed() { (rows, cols) } else { (cols, rows) };
+        try!(validate::scalar(("alpha", alpha.capacity()))
+            .and_then(|_| validate::scalar(("beta", beta.capacity())))
+            .and_then(|_| validate::matrix_dims(("a", a.capacity()), rows, cols))
+            .and_then(|_| validate::vector_len(("x", x.capacity()), x_len))
+            .and_then(|_| validate::vector_len(("y", y.capacity()), y_len))
+            .map_err(|err| err.with_context("gemv", detail())));
+        match alpha.add_device(self.device()) { _ => try!(alpha.sync(self.device())) }
+        match a.add_device(self.device()) { _ => try!(a.sync(self.device())) }
+        match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+        match beta.add_device(self.device()) { _ => try!(beta.sync(self.device())) }
+        match y.add_device(self.device()) { _ => try!(y.sync(self.device())) }
+        Ok(try!(
+            self.binary().gemv().compute(
+                trans,
+                try!(alpha.get(self.device()).ok_or(Error::MissingArgument(format!("Unable to resolve memory for `alpha`")))),
+                try!(a.get(self.device()).ok_or(Error::MissingArgument(format!("Unable to resolve memory for `a`")))),
+                try!(x.get(self.device()).ok_or(Error::MissingArgument(format!("Unable to resolve memory for `x`")))),
+                try!(beta.get(self.device()).ok_or(Error::MissingArgument(format!("Unable to resolve memory for `beta`")))),
+                try!(y.get_mut(self.device())),
+                rows, cols,
+            ).map_err(|err| err.with_context("gemv", detail()))
+        ))
+    }
+
+    /// Computes a matrix-matrix product with general matrices aka. `alpha * op(A) * op(B) + beta * C`.
+    ///
+    /// `op(A)` is a `m` x `k` matrix, `op(B)` is a `k` x `n` matrix and `C` is a `m` x `n` matrix,
+    /// all stored in row-major order. `at` and `bt` define whether `A` and `B` get transposed.
+    /// Saves the resulting matrix into `c`.
+    /// This is a Level 3 BLAS operation.
+    fn gemm(&self, alpha: &mut SharedMemory<F>, at: Transpose, a: &mut SharedMemory<F>, bt: Transpose, b: &mut SharedMemory<F>, beta: &mut SharedMemory<F>, c: &mut SharedMemory<F>, m: usize, n: usize, k: usize) -> Result<(), ::error::Error> {
+        let operands = [("alpha", alpha.capacity()), ("a", a.capacity()), ("b", b.capacity()), ("beta", beta.capacity()), ("c", c.capacity())];
+        let detail = || format!("{} for m = {}, n = {} and k = {}", describe(&operands), m, n, k);
+        try!(validate::scalar(("alpha", alpha.capacity()))
+            .and_then(|_| validate::scalar(("beta", beta.capacity())))
+            .and_then(|_| validate::matrix_dims(("a", a.capacity()), m, k))
+            .and_then(|_| validate::matrix_dims(("b", b.capacity()), k, n))
+            .and_then(|_| validate::matrix_dims(("c", c.capacity()), m, n))
+            .map_err(|err| err.with_context("gemm", detail())));
+        match alpha.add_device(self.device()) { _ => try!(alpha.sync(self.device())) }
+        match a.add_device(self.device()) { _ => try!(a.sync(self.device())) }
+        match b.add_device(self.device()) { _ => try!(b.sync(self.device())) }
+        match beta.add_device(self.device()) { _ => try!(beta.sync(self.device())) }
+        match c.add_device(self.device()) { _ => try!(c.sync(self.device())) }
+        Ok(try!(
+            self.binary().gemm().compute(
+                try!(alpha.get(self.device()).ok_or(Error::MissingArgument(format!("Unable to resolve memory for `alpha`")))),
+                at,
+                try!(a.get(self.device()).ok_or(Error::MissingArgument(format!("Unable to resolve memory for `a`")))),
+                bt,
+                try!(b.get(self.device()).ok_or(Error::MissingArgument(format!("Unable to resolve memory for `b`")))),
+                try!(beta.get(self.device()).ok_or(Error::MissingArgument(format!("Unable to resolve memory for `beta`")))),
+                try!(c.get_mut(self.device())),
+                m, n, k,
+            ).map_err(|err| err.with_context("gemm", detail()))
+        ))
+    }
+
+    /// Computes `batch_count` independent matrix-matrix products aka. `alpha * op(A_i) * op(B_i) + beta * C_i`.
+    ///
+    /// `a`, `b` and `c` hold `batch_count` equally-sized matrices each, stored back to back.
+    /// The dimensions and the transposition follow `gemm` and apply to every matrix of the batch;
+    /// `alpha` and `beta` are shared by the whole batch.
+    /// Saves the resulting matrices into `c`.
+    fn gemm_batched(&self, alpha: &mut SharedMemory<F>, at: Transpose, a: &mut SharedMemory<F>, bt: Transpose, b: &mut SharedMemory<F>, beta: &mut SharedMemory<F>, c: &mut SharedMemory<F>, m: usize, n: usize, k: usize, batch_count: usize) -> Result<(), ::error::Error> {
+        let operands = [("alpha", alpha.capacity()), ("a", a.capacity()), ("b", b.capacity()), ("beta", beta.capacity()), ("c", c.capacity())];
+        let detail = || format!("{} for {} batches of m = {}, n = {} and k = {}", describe(&operands), batch_count, m, n, k);
+        try!(validate::scalar(("alpha", alpha.capacity()))
+            .and_then(|_| validate::scalar(("beta", beta.capacity())))
+            .and_then(|_| validate::batched_matrix_dims(("a", a.capacity()), batch_count, m, k))
+            .and_then(|_| validate::batched_matrix_dims(("b", b.capacity()), batch_count, k, n))
+            .and_then(|_| validate::batched_matrix_dims(("c", c.capacity()), batch_count, m, n))
+            .map_err(|err| err.with_context("gemm_batched", detail())));
+        match alpha.add_device(self.device()) { _ => try!(alpha.sync(self.device())) }
+        match a.add_device(self.device()) { _ => try!(a.sync(self.device())) }
+        match b.add_device(self.device()) { _ => try!(b.sync(self.device())) }
+        match beta.add_device(self.device()) { _ => try!(beta.sync(self.device())) }
+        match c.add_device(self.device()) { _ => try!(c.sync(self.device())) }
+        Ok(try!(
+            self.binary().gemm_batched().compute(
+                try!(alpha.get(self.device()).ok_or(Error::MissingArgument(format!("Unable to resolve memory for `alpha`")))),
+                at,
+                try!(a.get(self.device()).ok_or(Error::MissingArgument(format!("Unable to resolve memory for `a`")))),
+                bt,
+                try!(b.get(self.device()).ok_or(Error::MissingArgument(format!("Unable to resolve memory for `b`")))),
+                try!(beta.get(self.device()).ok_or(Error::MissingArgument(format!("Unable to resolve memory for `beta`")))),
+                try!(c.get_mut(self.device())),
+                m, n, k, batch_count,
+            ).map_err(|err| err.with_context("gemm_batched", detail()))
+        ))
+    }
+
+    /// Reduces the vector `x` into a single value by repeatedly applying `op`.
+    ///
+    /// Saves the result to `result`. An empty `x` results in the identity element of `op`,
+    /// e.g. `0` for `ReduceOp::Sum` and negative infinity for `ReduceOp::Max` on floats.
+    fn reduce(&self, x: &mut SharedMemory<F>, result: &mut SharedMemory<F>, op: ReduceOp) -> Result<(), ::error::Error> {
+        let operands = [("x", x.capacity()), ("result", result.capacity())];
+        let detail = || describe(&operands);
+        try!(validate::scalar(("result", result.capacity())).map_err(|err| err.with_context("reduce", detail())));
+        match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+        match result.add_device(self.device()) { _ => () }
+        Ok(try!(
+            self.binary().reduce().compute(
+                try!(x.get(self.device()).ok_or(Error::MissingArgument(format!("Unable to resolve memory for `x`")))),
+                try!(output(result, self.device(), 1)),
+                op,
+            ).map_err(|err| err.with_context("reduce", detail()))
+        ))
+    }
+
+    /// Applies the function `func` to every element of the vector `x`.
+    ///
+    /// Saves the resulting vector to `y`, which needs to have the same length as `x`.
+    /// The functions follow the semantics of libm outside of their domain, e.g. `UnaryFn::Log` of
+    /// a negative element yields NaN, of zero negative infinity and `UnaryFn::Sqrt` of a negative
+    /// element yields NaN.
+    fn map(&self, x: &mut SharedMemory<F>, y: &mut SharedMemory<F>, func: UnaryFn) -> Result<(), ::error::Error> {
+        let operands = [("x", x.capacity()), ("y", y.capacity())];
+        let detail = || describe(&operands);
+        try!(validate::same_len(("x", x.capacity()), ("y", y.capacity())).map_err(|err| err.with_context("map", detail())));
+        match x.add_device(self.device()) { _ => try!(x.sync(self.device())) }
+        match y.add_device(self.device()) { _ => () }
+        Ok(try!(
+            self.binary().map().compute(
+                try!(x.get(self.device()).ok_or(Error::MissingArgument(format!("Unable to resolve memory for `x`")))),
+                try!(output(y, self.device(), x.capacity())),
+                func,
+            ).map_err(|err| err.with_context("map", detail()))
+        ))
+    }
+
+    /// Combines the vectors `a` and `b` element by element with `op` aka. `c[i] = a[i] op b[i]`.
+    ///
+    /// Saves the resulting vector to `c`. All vectors need to have the same length.
+    /// Floating point division by zero follows IEEE 754 and yields infinity or NaN instead of an
+    /// error.
+    fn elementwise(&self, a: &mut SharedMemory<F>, b: &mut SharedMemory<F>, c: &mut SharedMemory<F>, op: BinaryOp) -> Result<(), ::error::Error> {
+        let operands = [("a", a.capacity()), ("b", b.capacity()), ("c", c.capacity())];
+        let detail = || describe(&operands);
+        try!(validate::same_len(("a", a.capacity()), ("b", b.capacity()))
+            .and_then(|_| validate::same_len(("a", a.capacity()), ("c", c.capacity())))
+            .map_err(|err| err.with_context("elementwise", detail())));
+        match a.add_device(self.device()) { _ => try!(a.sync(self.device())) }
+        match b.add_device(self.device()) { _ => try!(b.sync(self.device())) }
+        match c.add_device(self.device()) { _ => () }
+        Ok(try!(
+            self.binary().elementwise().compute(
+                try!(a.get(self.device()).ok_or(Error::MissingArgument(format!("Unable to resolve memory for `a`")))),
+                try!(b.get(self.device()).ok_or(Error::MissingArgument(format!("Unable to resolve memory for `b`")))),
+                try!(output(c, self.device(), a.capacity())),
+                op,
+            ).map_err(|err| err.with_context("elementwise", detail()))
+        ))
+    }
+
+    /// Returns whether the backend implements the operation `op` for `F`.
+    ///
+    /// Portable code can check for an operation, before calling it, and fall back otherwise.
+    fn supports(&self, op: BlasOp) -> bool {
+        self.binary().supports(op)
+    }
+
+    /// Returns the operations, which the backend implements for `F`.
+    fn capabilities(&self) -> Vec<BlasOp> {
+        BlasOp::all().iter().cloned().filter(|op| self.supports(*op)).collect()
+    }
+
     /// Returns the binary representation
     fn binary(&self) -> Self::B;
 
@@ -141,9 +420,11 @@ pub trait IBlas<F: Float> {
 }
 
 /// Describes the operation binding for a Blas Binary implementation.
-pub trait IBlasBinary<F: Float> {
+pub trait IBlasBinary<F: Element> {
     /// Describes the Asum Operation.
     type Asum: IOperationAsum<F>;
+    /// Describes the Iamax Operation.
+    type Iamax: IOperationIamax<F>;
     /// Describes the Axpy Operation.
     type Axpy: IOperationAxpy<F>;
     /// Describes the Copy Operation.
@@ -156,9 +437,23 @@ pub trait IBlasBinary<F: Float> {
     type Scale: IOperationScale<F>;
     /// Describes the Swap Operation.
     type Swap: IOperationSwap<F>;
+    /// Describes the Gemv Operation.
+    type Gemv: IOperationGemv<F>;
+    /// Describes the Gemm Operation.
+    type Gemm: IOperationGemm<F>;
+    /// Describes the GemmBatched Operation.
+    type GemmBatched: IOperationGemmBatched<F>;
+    /// Describes the Reduce Operation.
+    type Reduce: IOperationReduce<F>;
+    /// Describes the Map Operation.
+    type Map: IOperationMap<F>;
+    /// Describes the Elementwise Operation.
+    type Elementwise: IOperationElementwise<F>;
 
     /// Returns an initialized Asum operation.
     fn asum(&self) -> Self::Asum;
+    /// Returns an initialized Iamax operation.
+    fn iamax(&self) -> Self::Iamax;
     /// Returns an initialized Axpy operation.
     fn axpy(&self) -> Self::Axpy;
     /// Returns an initialized Copy operation.
@@ -171,48 +466,304 @@ pub trait IBlasBinary<F: Float> {
     fn scale(&self) -> Self::Scale;
     /// Returns an initialized Swap operation.
     fn swap(&self) -> Self::Swap;
+    /// Returns an initialized Gemv operation.
+    fn gemv(&self) -> Self::Gemv;
+    /// Returns an initialized Gemm operation.
+    fn gemm(&self) -> Self::Gemm;
+    /// Returns an initialized GemmBatched operation.
+    fn gemm_batched(&self) -> Self::GemmBatched;
+    /// Returns an initialized Reduce operation.
+    fn reduce(&self) -> Self::Reduce;
+    /// Returns an initialized Map operation.
+    fn map(&self) -> Self::Map;
+    /// Returns an initialized Elementwise operation.
+    fn elementwise(&self) -> Self::Elementwise;
+
+    /// Returns whether the binary implements the operation `op`.
+    ///
+    /// Asks the operation itself, see e.g. `IOperationAsum::is_available`.
+    fn supports(&self, op: BlasOp) -> bool {
+        match op {
+            BlasOp::Asum => IOperationAsum::<F>::is_available(&self.asum()),
+            BlasOp::Iamax => IOperationIamax::<F>::is_available(&self.iamax()),
+            BlasOp::Axpy => IOperationAxpy::<F>::is_available(&self.axpy()),
+            BlasOp::Copy => IOperationCopy::<F>::is_available(&self.copy()),
+            BlasOp::Dot => IOperationDot::<F>::is_available(&self.dot()),
+            BlasOp::Nrm2 => IOperationNrm2::<F>::is_available(&self.nrm2()),
+            BlasOp::Scale => IOperationScale::<F>::is_available(&self.scale()),
+            BlasOp::Swap => IOperationSwap::<F>::is_available(&self.swap()),
+            BlasOp::Gemv => IOperationGemv::<F>::is_available(&self.gemv()),
+            BlasOp::Gemm => IOperationGemm::<F>::is_available(&self.gemm()),
+            BlasOp::GemmBatched => IOperationGemmBatched::<F>::is_available(&self.gemm_batched()),
+            BlasOp::Reduce => IOperationReduce::<F>::is_available(&self.reduce()),
+            BlasOp::Map => IOperationMap::<F>::is_available(&self.map()),
+            BlasOp::Elementwise => IOperationElementwise::<F>::is_available(&self.elementwise()),
+        }
+    }
 }
 
 /// Describes a Asum Operation.
-pub trait IOperationAsum<F: Float> {
+pub trait IOperationAsum<F: Element> {
     /// Computes the Asum operation.
     fn compute(&self, x: &MemoryType, result: &mut MemoryType) -> Result<(), Error>;
+
+    /// Returns whether the Asum operation is implemented for `F`.
+    fn is_available(&self) -> bool {
+        true
+    }
+}
+
+/// Describes a Iamax Operation.
+pub trait IOperationIamax<F: Element> {
+    /// Computes the Iamax operation.
+    fn compute(&self, x: &MemoryType, result: &mut MemoryType) -> Result<(), Error>;
+
+    /// Returns whether the Iamax operation is implemented for `F`.
+    fn is_available(&self) -> bool {
+        true
+    }
 }
 
 /// Describes a Axpy Operation.
-pub trait IOperationAxpy<F: Float> {
+pub trait IOperationAxpy<F: Element> {
     /// Computes the Axpy operation.
     fn compute(&self, a: &MemoryType, x: & MemoryType, y: &mut MemoryType) -> Result<(), Error>;
+
+    /// Returns whether the Axpy operation is implemented for `F`.
+    fn is_available(&self) -> bool {
+        true
+    }
 }
 
 /// Describes a Copy Operation.
-pub trait IOperationCopy<F: Float> {
+pub trait IOperationCopy<F: Element> {
     /// Computes the Copy operation.
     fn compute(&self, x: &MemoryType, y: &mut MemoryType) -> Result<(), Error>;
+
+    /// Returns whether the Copy operation is implemented for `F`.
+    fn is_available(&self) -> bool {
+        true
+    }
 }
 
 /// Describes a Dot Operation.
-pub trait IOperationDot<F: Float> {
+pub trait IOperationDot<F: Element> {
     /// Computes the Dot operation.
     fn compute(&self, x: &MemoryType, y: &MemoryType, result: &mut MemoryType) -> Result<(), Error>;
+
+    /// Returns whether the Dot operation is implemented for `F`.
+    fn is_available(&self) -> bool {
+        true
+    }
 }
 
 /// Describes a Nrm2 Operation.
-pub trait IOperationNrm2<F: Float> {
+pub trait IOperationNrm2<F: Element> {
     /// Computes the Nrm2 operation.
     fn compute(&self, x: &MemoryType, result: &mut MemoryType) -> Result<(), Error>;
+
+    /// Returns whether the Nrm2 operation is implemented for `F`.
+    fn is_available(&self) -> bool {
+        true
+    }
 }
 
 /// Describes a Scale Operation.
-pub trait IOperationScale<F: Float> {
+pub trait IOperationScale<F: Element> {
     /// Computes the Scale operation.
     fn compute(&self, a: &MemoryType, x: &mut MemoryType) -> Result<(), Error>;
+
+    /// Returns whether the Scale operation is implemented for `F`.
+    fn is_available(&self) -> bool {
+        true
+    }
 }
 
 /// Describes a Swap Operation.
-pub trait IOperationSwap<F: Float> {
+pub trait IOperationSwap<F: Element> {
     /// Computes the Swap operation.
     fn compute(&self, x: &mut MemoryType, y: &mut MemoryType) -> Result<(), Error>;
+
+    /// Returns whether the Swap operation is implemented for `F`.
+    fn is_available(&self) -> bool {
+        true
+    }
+}
+
+/// Describes a Gemv Operation.
+pub trait IOperationGemv<F: Element> {
+    /// Computes the Gemv operation.
+    fn compute(&self, trans: Transpose, alpha: &MemoryType, a: &MemoryType, x: &MemoryType, beta: &MemoryType, y: &mut MemoryType, rows: usize, cols: usize) -> Result<(), Error>;
+
+    /// Returns whether the Gemv operation is implemented for `F`.
+    fn is_available(&self) -> bool {
+        true
+    }
+}
+
+/// Describes a Gemm Operation.
+pub trait IOperationGemm<F: Element> {
+    /// Computes the Gemm operation.
+    fn compute(&self, alpha: &MemoryType, at: Transpose, a: &MemoryType, bt: Transpose, b: &MemoryType, beta: &MemoryType, c: &mut MemoryType, m: usize, n: usize, k: usize) -> Result<(), Error>;
+
+    /// Returns whether the Gemm operation is implemented for `F`.
+    fn is_available(&self) -> bool {
+        true
+    }
+}
+
+/// Describes a GemmBatched Operation.
+pub trait IOperationGemmBatched<F: Element> {
+    /// Computes the GemmBatched operation.
+    fn compute(&self, alpha: &MemoryType, at: Transpose, a: &MemoryType, bt: Transpose, b: &MemoryType, beta: &MemoryType, c: &mut MemoryType, m: usize, n: usize, k: usize, batch_count: usize) -> Result<(), Error>;
+
+    /// Returns whether the GemmBatched operation is implemented for `F`.
+    fn is_available(&self) -> bool {
+        true
+    }
+}
+
+/// Describes a Reduce Operation.
+pub trait IOperationReduce<F: Element> {
+    /// Computes the Reduce operation.
+    fn compute(&self, x: &MemoryType, result: &mut MemoryType, op: ReduceOp) -> Result<(), Error>;
+
+    /// Returns whether the Reduce operation is implemented for `F`.
+    fn is_available(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+/// Names the BLAS operations, e.g. to check whether a backend supports them.
+pub enum BlasOp {
+    /// The `asum` operation.
+    Asum,
+    /// The `iamax` operation.
+    Iamax,
+    /// The `axpy` operation.
+    Axpy,
+    /// The `copy` operation.
+    Copy,
+    /// The `dot` operation.
+    Dot,
+    /// The `nrm2` operation.
+    Nrm2,
+    /// The `scale` operation.
+    Scale,
+    /// The `swap` operation.
+    Swap,
+    /// The `gemv` operation.
+    Gemv,
+    /// The `gemm` operation.
+    Gemm,
+    /// The `gemm_batched` operation.
+    GemmBatched,
+    /// The `reduce` operation.
+    Reduce,
+    /// The `map` operation.
+    Map,
+    /// The `elementwise` operation.
+    Elementwise,
+}
+
+impl BlasOp {
+    /// Returns all BLAS operations.
+    pub fn all() -> &'static [BlasOp] {
+        static ALL: [BlasOp; 14] = [
+            BlasOp::Asum, BlasOp::Iamax, BlasOp::Axpy, BlasOp::Copy, BlasOp::Dot, BlasOp::Nrm2,
+            BlasOp::Scale, BlasOp::Swap, BlasOp::Gemv, BlasOp::Gemm, BlasOp::GemmBatched,
+            BlasOp::Reduce, BlasOp::Map, BlasOp::Elementwise,
+        ];
+        &ALL
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// Defines the operation, with which `reduce` combines the elements of a vector.
+pub enum ReduceOp {
+    /// Adds up the elements.
+    Sum,
+    /// Takes the largest element.
+    Max,
+    /// Takes the smallest element.
+    Min,
+    /// Multiplies the elements.
+    Product,
+}
+
+/// Describes a Map Operation.
+pub trait IOperationMap<F: Element> {
+    /// Computes the Map operation.
+    fn compute(&self, x: &MemoryType, y: &mut MemoryType, func: UnaryFn) -> Result<(), Error>;
+
+    /// Returns whether the Map operation is implemented for `F`.
+    fn is_available(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// Defines the function, which `map` applies to every element of a vector.
+pub enum UnaryFn {
+    /// The exponential function `e^x`.
+    Exp,
+    /// The natural logarithm. Yields NaN for negative and negative infinity for zero elements.
+    Log,
+    /// The square root. Yields NaN for negative elements.
+    Sqrt,
+    /// The absolute value.
+    Abs,
+    /// The logistic function `1 / (1 + e^-x)`.
+    Sigmoid,
+    /// The hyperbolic tangent.
+    Tanh,
+    /// The rectified linear unit `max(x, 0)`.
+    Relu,
+}
+
+/// Describes a Elementwise Operation.
+pub trait IOperationElementwise<F: Element> {
+    /// Computes the Elementwise operation.
+    fn compute(&self, a: &MemoryType, b: &MemoryType, c: &mut MemoryType, op: BinaryOp) -> Result<(), Error>;
+
+    /// Returns whether the Elementwise operation is implemented for `F`.
+    fn is_available(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// Defines the operation, with which `elementwise` combines two vectors.
+pub enum BinaryOp {
+    /// Adds the elements.
+    Add,
+    /// Subtracts the elements of `b` from the elements of `a`.
+    Sub,
+    /// Multiplies the elements, also known as the Hadamard product.
+    Mul,
+    /// Divides the elements of `a` by the elements of `b`.
+    Div,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// Defines how a matrix gets interpreted by a Level 2 or Level 3 BLAS operation.
+pub enum Transpose {
+    /// Take the matrix as it is.
+    NoTrans,
+    /// Take the transpose of the matrix.
+    Trans,
+    /// Take the conjugate transpose of the matrix.
+    ///
+    /// Equals `Trans` for real numbers.
+    ConjTrans,
+}
+
+impl Transpose {
+    /// Returns whether the matrix gets transposed.
+    pub fn is_transposed(&self) -> bool {
+        *self != Transpose::NoTrans
+    }
 }
 
 #[derive(Debug)]
@@ -224,6 +775,24 @@ pub enum Error {
     MissingArgument(String),
     /// Failure related to an invalid argument.
     InvalidArgument(String),
+    /// Failure related to an operation, which is not supported by the device.
+    InvalidOperation(String),
+    /// Failure of the operation `op`, annotated with the shapes of its operands in `detail`.
+    Operation {
+        /// The name of the operation, e.g. `"gemm"`.
+        op: &'static str,
+        /// The shapes of the operands.
+        detail: String,
+        /// The underlying failure.
+        error: Box<Error>,
+    },
+}
+
+impl Error {
+    /// Wraps the error with the name of the operation `op` and the shapes of its operands.
+    pub fn with_context(self, op: &'static str, detail: String) -> Error {
+        Error::Operation { op: op, detail: detail, error: Box::new(self) }
+    }
 }
 
 impl ::std::fmt::Display for Error {
@@ -232,6 +801,8 @@ impl ::std::fmt::Display for Error {
             Error::Dot(ref err) => write!(f, "{:?}", err),
             Error::MissingArgument(ref err) => write!(f, "{:?}", err),
             Error::InvalidArgument(ref err) => write!(f, "{:?}", err),
+            Error::InvalidOperation(ref err) => write!(f, "{:?}", err),
+            Error::Operation { op, ref detail, ref error } => write!(f, "{} failed: {} ({})", op, error, detail),
         }
     }
 }
@@ -242,6 +813,8 @@ impl ::std::error::Error for Error {
             Error::Dot(ref err) => err,
             Error::MissingArgument(ref err) => err,
             Error::InvalidArgument(ref err) => err,
+            Error::InvalidOperation(ref err) => err,
+            Error::Operation { ref error, .. } => error.description(),
         }
     }
 
@@ -250,6 +823,18 @@ impl ::std::error::Error for Error {
             Error::Dot(_) => None,
             Error::MissingArgument(_) => None,
             Error::InvalidArgument(_) => None,
+            Error::InvalidOperation(_) => None,
+            Error::Operation { ref error, .. } => Some(&**error),
+        }
+    }
+
+    fn source(&self) -> Option<&(::std::error::Error + 'static)> {
+        match *self {
+            Error::Dot(_) => None,
+            Error::MissingArgument(_) => None,
+            Error::InvalidArgument(_) => None,
+            Error::InvalidOperation(_) => None,
+            Error::Operation { ref error, .. } => Some(&**error),
         }
     }
 }
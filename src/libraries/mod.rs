@@ -17,11 +17,35 @@
 //! Collenchyma ships with the most basic operations, but you should be able to easily write your
 //! own backend-agnostic operations, too.
 //!
+//! ## Writing a library
+//!
+//! A library follows the layout of [BLAS][blas-lib]:
+//!
+//! * an `IOperation...` trait per operation, which computes it on the `MemoryType`s of a device,
+//! * a binary trait like `IBlasBinary`, which returns the initialized operations,
+//! * the library trait like `IBlas`, whose provided methods synchronize the SharedMemory
+//!   arguments to the device and call the operations of the binary and
+//! * an [ILibrary][ilibrary] implementation per framework, which builds the binary for a device,
+//!   e.g. by compiling OpenCL kernels.
+//!
+//! `Backend::library` builds the binary once for the device of the Backend and returns a
+//! [LibraryBackend][library-backend], for which the library trait gets implemented. BLAS is the
+//! first implementor of this pattern: its binary is the binary of the framework, which every
+//! Backend builds on initialization, so `IBlas` is implemented for the Backend directly.
+//!
+//! [blas-lib]: ./blas/index.html
+//! [ilibrary]: ./trait.ILibrary.html
+//! [library-backend]: ../backend/struct.LibraryBackend.html
 //! [program]: ../program/index.html
 //! [blas]: http://www.netlib.org/blas/
 //! [cudnn]: https://developer.nvidia.com/cudnn
 
+use binary::IBinary;
+use device::DeviceType;
+use framework::IFramework;
 pub use self::numeric_helpers::Float;
+#[cfg(feature = "f16")]
+pub use half::f16;
 
 pub mod blas;
 /// Describes the Library numeric types and traits.
@@ -29,6 +53,34 @@ pub mod numeric_helpers {
     pub use num::traits::*;
 }
 
+/// Describes the element types, which the libraries operate on.
+///
+/// Implemented for all copyable numeric types, such as `f32`, `f64` and `i32`.
+pub trait Element: numeric_helpers::Num + Copy {}
+
+impl<T: numeric_helpers::Num + Copy> Element for T {}
+
+/// Defines a library, whose operations can be built for the devices of the framework `F`.
+pub trait ILibrary<F: IFramework> {
+    /// The binary, which provides the initialized operations of the library.
+    type B: IBinary + Clone;
+
+    /// Builds the binary of the library for the `device` of the `framework`.
+    fn new_binary(framework: &F, device: &DeviceType) -> Result<Self::B, ::framework::Error>;
+}
+
+#[derive(Debug, Copy, Clone)]
+/// Defines the BLAS library, whose binary is the binary of the framework.
+pub struct Blas;
+
+impl<F: IFramework> ILibrary<F> for Blas {
+    type B = F::B;
+
+    fn new_binary(framework: &F, device: &DeviceType) -> Result<F::B, ::framework::Error> {
+        framework.new_binary(device)
+    }
+}
+
 #[derive(Debug)]
 /// Defines a high-level library Error.
 pub enum Error {
@@ -56,6 +108,12 @@ impl ::std::error::Error for Error {
             Error::Blas(ref err) => Some(err),
         }
     }
+
+    fn source(&self) -> Option<&(::std::error::Error + 'static)> {
+        match *self {
+            Error::Blas(ref err) => Some(err),
+        }
+    }
 }
 
 impl From<Error> for ::error::Error {
@@ -0,0 +1,37 @@
+//! Provides the macros, which emit the `log` records of Collenchyma.
+//!
+//! With the `logging` feature, allocations, frees and memory synchronizations are logged at the
+//! `debug` level, kernel launches at the `trace` level and fallback paths at the `warn` level.
+//! Enable them downstream with e.g. `RUST_LOG=collenchyma=trace`.
+//!
+//! Without the feature, the macros expand to nothing.
+
+#[cfg(feature = "logging")]
+macro_rules! co_debug {
+    ($($arg:tt)*) => { debug!($($arg)*) }
+}
+
+#[cfg(not(feature = "logging"))]
+macro_rules! co_debug {
+    ($($arg:tt)*) => { () }
+}
+
+#[cfg(feature = "logging")]
+macro_rules! co_trace {
+    ($($arg:tt)*) => { trace!($($arg)*) }
+}
+
+#[cfg(not(feature = "logging"))]
+macro_rules! co_trace {
+    ($($arg:tt)*) => { () }
+}
+
+#[cfg(feature = "logging")]
+macro_rules! co_warn {
+    ($($arg:tt)*) => { warn!($($arg)*) }
+}
+
+#[cfg(not(feature = "logging"))]
+macro_rules! co_warn {
+    ($($arg:tt)*) => { () }
+}
@@ -8,21 +8,78 @@
 //! [shared_mem]: ../shared_mem/index.html
 
 use frameworks::native::flatbox::FlatBox;
+#[cfg(feature = "opencl")]
 use frameworks::opencl::memory::Memory;
+#[cfg(feature = "opencl")]
+use frameworks::opencl::Error as OpenCLError;
+#[cfg(feature = "opencl")]
+use libc::c_void;
+#[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+use frameworks::cuda::memory::Memory as CudaMemory;
+#[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+use frameworks::cuda::CUdeviceptr;
 
 /// Specifies Memory behavior accross frameworks.
 pub trait IMemory { }
 
 #[derive(Debug)]
 /// Container for all known IMemory implementations
+///
+/// Each variant frees its underlying allocation, when it goes out of scope.
 pub enum MemoryType {
-    /// A OpenCL Context
+    /// A Native FlatBox
     Native(FlatBox),
-    /// A OpenCL Context
+    #[cfg(feature = "opencl")]
+    /// A OpenCL Memory
     OpenCL(Memory),
+    #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+    /// A Cuda Memory
+    Cuda(CudaMemory),
 }
 
 impl MemoryType {
+    #[cfg(feature = "opencl")]
+    /// Wraps the OpenCL memory object `mem` of `size` bytes, which has been created outside of
+    /// Collenchyma, without copying it.
+    ///
+    /// With `retain`, the memory object is retained through `clRetainMemObject` and released
+    /// again, when the MemoryType is dropped. Otherwise it is borrowed and never released, so the
+    /// caller needs to keep it alive.
+    /// Returns an `InvalidBufferSize` error, if `size` exceeds the size of the memory object.
+    ///
+    /// ## Safety
+    ///
+    /// `mem` must be a live OpenCL memory object. The driver can not detect every dangling or
+    /// made-up handle, so querying or retaining those is undefined behavior.
+    pub unsafe fn from_raw_opencl(mem: *mut c_void, size: usize, retain: bool) -> Result<MemoryType, OpenCLError> {
+        Ok(MemoryType::OpenCL(try!(Memory::from_raw(mem, size, retain))))
+    }
+
+    #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+    /// Wraps the Cuda device pointer `ptr` to `size` bytes, which have been allocated outside
+    /// of Collenchyma, without copying them.
+    ///
+    /// The allocation is borrowed and never freed.
+    ///
+    /// ## Safety
+    ///
+    /// `ptr` must point to a live allocation of at least `size` bytes, which the caller keeps
+    /// alive for the lifetime of the MemoryType. Every later copy or kernel trusts `size`.
+    pub unsafe fn from_raw_cuda(ptr: CUdeviceptr, size: usize) -> MemoryType {
+        MemoryType::Cuda(CudaMemory::from_raw(ptr, size))
+    }
+
+    /// Returns the size of the allocated memory in bytes.
+    pub fn size(&self) -> usize {
+        match *self {
+            MemoryType::Native(ref mem) => mem.as_slice::<u8>().len(),
+            #[cfg(feature = "opencl")]
+            MemoryType::OpenCL(ref mem) => mem.size(),
+            #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+            MemoryType::Cuda(ref mem) => mem.size(),
+        }
+    }
+
     /// Extract the FlatBox if MemoryType is Native.
     pub fn as_native(&self) -> Option<&FlatBox> {
         match *self {
@@ -39,6 +96,7 @@ impl MemoryType {
         }
     }
 
+    #[cfg(feature = "opencl")]
     /// Extract the OpenCL Memory if MemoryType is OpenCL.
     pub fn as_opencl(&self) -> Option<&Memory> {
         match *self {
@@ -47,6 +105,7 @@ impl MemoryType {
         }
     }
 
+    #[cfg(feature = "opencl")]
     /// Extract the OpenCL Memory mutably if MemoryType is OpenCL.
     pub fn as_mut_opencl(&mut self) -> Option<&mut Memory> {
         match *self {
@@ -54,4 +113,22 @@ impl MemoryType {
             _ => None,
         }
     }
+
+    #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+    /// Extract the Cuda Memory if MemoryType is Cuda.
+    pub fn as_cuda(&self) -> Option<&CudaMemory> {
+        match *self {
+            MemoryType::Cuda(ref ret) => Some(ret),
+            _ => None,
+        }
+    }
+
+    #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+    /// Extract the Cuda Memory mutably if MemoryType is Cuda.
+    pub fn as_mut_cuda(&mut self) -> Option<&mut CudaMemory> {
+        match *self {
+            MemoryType::Cuda(ref mut ret) => Some(ret),
+            _ => None,
+        }
+    }
 }
@@ -0,0 +1,111 @@
+//! Provides a cache of freed device allocations.
+//!
+//! Allocating memory through `clCreateBuffer` or `cuMemAlloc` is expensive, which slows down
+//! iterative code, that frees and allocates buffers of the same sizes on every iteration. A
+//! MemoryPool keeps freed allocations by their size and hands them out again on the next
+//! allocation of the same size, instead of calling the driver.
+//!
+//! A Backend created with [Backend::with_pool][with_pool] attaches a MemoryPool to its OpenCL or
+//! Cuda device. The memory copies of a SharedMemory are then returned to the pool, when the
+//! SharedMemory is dropped. The Native framework allocates host memory, which is cheap, and is not
+//! pooled.
+//!
+//! [with_pool]: ../backend/struct.Backend.html#method.with_pool
+//!
+//! ## Examples
+//!
+//! ```
+//! # extern crate collenchyma as co;
+//! use co::memory_pool::MemoryPool;
+//!
+//! # fn main() {
+//! let pool = MemoryPool::new(1024);
+//! assert!(pool.give_back(256, vec![0u8; 256]).is_none());
+//! assert_eq!(256, pool.retained());
+//! assert!(pool.take(256).is_some());
+//! assert_eq!(0, pool.retained());
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug)]
+struct PoolState<M> {
+    max_retained: usize,
+    retained: usize,
+    free: HashMap<usize, Vec<M>>,
+}
+
+#[derive(Debug)]
+/// Caches freed allocations of type `M` by their size in bytes.
+///
+/// Clones of the MemoryPool share the cached allocations.
+pub struct MemoryPool<M> {
+    state: Arc<Mutex<PoolState<M>>>,
+}
+
+impl<M> Clone for MemoryPool<M> {
+    fn clone(&self) -> MemoryPool<M> {
+        MemoryPool { state: self.state.clone() }
+    }
+}
+
+impl<M> MemoryPool<M> {
+    /// Creates an empty MemoryPool, which retains at most `max_retained` bytes.
+    pub fn new(max_retained: usize) -> MemoryPool<M> {
+        MemoryPool {
+            state: Arc::new(Mutex::new(PoolState {
+                max_retained: max_retained,
+                retained: 0,
+                free: HashMap::new(),
+            }))
+        }
+    }
+
+    /// Returns a cached allocation of `size` bytes, if there is one.
+    pub fn take(&self, size: usize) -> Option<M> {
+        let mut state = self.state.lock().unwrap();
+        let memory = state.free.get_mut(&size).and_then(|free| free.pop());
+        if memory.is_some() {
+            state.retained -= size;
+        }
+        memory
+    }
+
+    /// Caches the allocation `memory` of `size` bytes for a later `take`.
+    ///
+    /// Hands `memory` back, if caching it would exceed the retained bytes of the pool, so that the
+    /// caller can free it.
+    pub fn give_back(&self, size: usize, memory: M) -> Option<M> {
+        let mut state = self.state.lock().unwrap();
+        match state.retained.checked_add(size) {
+            Some(retained) if size != 0 && retained <= state.max_retained => {
+                state.retained = retained;
+                state.free.entry(size).or_insert_with(Vec::new).push(memory);
+                None
+            },
+            _ => Some(memory)
+        }
+    }
+
+    /// Returns the number of bytes, which the pool currently retains.
+    pub fn retained(&self) -> usize {
+        self.state.lock().unwrap().retained
+    }
+
+    /// Returns the maximum number of bytes, which the pool retains.
+    pub fn max_retained(&self) -> usize {
+        self.state.lock().unwrap().max_retained
+    }
+
+    /// Frees all cached allocations.
+    pub fn drain(&self) {
+        let free = {
+            let mut state = self.state.lock().unwrap();
+            state.retained = 0;
+            ::std::mem::replace(&mut state.free, HashMap::new())
+        };
+        drop(free);
+    }
+}
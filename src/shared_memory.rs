@@ -38,61 +38,289 @@
 use std::collections::HashMap;
 use device::{IDevice, DeviceType};
 use memory::MemoryType;
+use stats;
 use std::marker::PhantomData;
-use std::{fmt, mem, error};
+use std::cell::Cell;
+use std::ops::{Deref, DerefMut};
+use std::{fmt, mem, error, slice};
+#[cfg(feature = "opencl")]
+use frameworks::opencl::{API as OpenCLAPI, Queue, Event};
+#[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+use frameworks::cuda::{API as CudaAPI, Stream, CUdeviceptr};
+#[cfg(any(feature = "opencl", feature = "cuda", feature = "cuda-runtime"))]
+use libc::c_void;
+
+/// Returns the number of bytes, a buffer of `len` elements of type `T` occupies.
+///
+/// `mem::size_of::<T>()` already includes the padding, which keeps consecutive elements aligned,
+/// so the result is the stride of `T` times `len`.
+///
+/// # Panics
+///
+/// Panics if the number of bytes overflows `usize`. Use [checked_buffer_bytes][checked] for a
+/// fallible version.
+/// [checked]: ./fn.checked_buffer_bytes.html
+pub fn buffer_bytes<T>(len: usize) -> usize {
+    match checked_buffer_bytes::<T>(len) {
+        Some(bytes) => bytes,
+        None => panic!("A buffer of {} elements of {} bytes overflows usize.", len, mem::size_of::<T>())
+    }
+}
+
+/// Returns the number of bytes, a buffer of `len` elements of type `T` occupies.
+///
+/// Returns `None` if the number of bytes overflows `usize`.
+pub fn checked_buffer_bytes<T>(len: usize) -> Option<usize> {
+    len.checked_mul(mem::size_of::<T>())
+}
+
+/// Allocates a memory copy of `len` elements, which occupy `size` bytes, on the `device`.
+///
+/// Unlike `DeviceType::alloc_memory`, allows empty copies, which SharedMemory of capacity 0 holds
+/// on the host. These are not tracked, as they do not hold on to any device memory.
+fn alloc_copy<T>(device: &DeviceType, len: usize, size: usize) -> Result<MemoryType, Error> {
+    match (device, size) {
+        (&DeviceType::Native(ref cpu), 0) => cpu.try_alloc_memory(size).map(MemoryType::Native).map_err(Error::MemoryAllocationFailed),
+        _ => device.alloc_memory_labeled(size, || format!("SharedMemory of {} elements of {} bytes", len, mem::size_of::<T>())).map_err(Error::MemoryAllocationFailed),
+    }
+}
 
 #[derive(Debug)]
 /// Container that handles synchronization of [Memory][1] of type `T`.
 /// [1]: ../memory/index.html
 pub struct SharedMemory<T> {
     latest_location: DeviceType,
+    latest_version: usize,
     copies: HashMap<DeviceType, MemoryType>,
+    versions: HashMap<DeviceType, usize>,
     cap: usize,
+    /// Whether the memory copies can be returned to the MemoryPool of their device on drop,
+    /// which is not the case for views and for SharedMemory, which has been viewed.
+    recyclable: Cell<bool>,
     phantom: PhantomData<T>,
 }
 
 impl<T> SharedMemory<T> {
     /// Create new SharedMemory by allocating [Memory][1] on a Device.
     /// [1]: ../memory/index.html
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` elements of type `T` exceed `usize::MAX` bytes. Use
+    /// [try_new][try_new] for a fallible version.
+    /// [try_new]: #method.try_new
     pub fn new(dev: &DeviceType, capacity: usize) -> SharedMemory<T> {
-        let mut copies = HashMap::<DeviceType, MemoryType>::new();
-        let copy: MemoryType;
-        let alloc_size = mem::size_of::<T>() * capacity;
-        match *dev {
-            DeviceType::Native(ref cpu) => copy = MemoryType::Native(cpu.alloc_memory(alloc_size)),
-            DeviceType::OpenCL(ref context) => copy = MemoryType::OpenCL(context.alloc_memory(alloc_size)),
+        match SharedMemory::try_new(dev, capacity) {
+            Ok(shared) => shared,
+            Err(err) => panic!("{}", err)
         }
+    }
+
+    /// Create new SharedMemory by allocating [Memory][1] on a Device.
+    /// [1]: ../memory/index.html
+    ///
+    /// Returns an error, if `capacity` elements of type `T` exceed `usize::MAX` bytes or if the
+    /// device fails to allocate the memory.
+    pub fn try_new(dev: &DeviceType, capacity: usize) -> Result<SharedMemory<T>, Error> {
+        let mut copies = HashMap::<DeviceType, MemoryType>::new();
+        let alloc_size = try!(checked_buffer_bytes::<T>(capacity).ok_or(Error::InvalidMemorySize(format!("SharedMemory of {} elements of {} bytes overflows the addressable memory.", capacity, mem::size_of::<T>()))));
+        let copy = try!(alloc_copy::<T>(dev, capacity, alloc_size));
         copies.insert(dev.clone(), copy);
-        SharedMemory {
+        let mut versions = HashMap::<DeviceType, usize>::new();
+        versions.insert(dev.clone(), 0);
+        Ok(SharedMemory {
             latest_location: dev.clone(),
+            latest_version: 0,
             copies: copies,
+            versions: versions,
             cap: capacity,
+            recyclable: Cell::new(true),
             phantom: PhantomData,
+        })
+    }
+
+    /// Creates a SharedMemory, whose copy on `dev` is the existing `memory`, without copying it.
+    ///
+    /// Together with [MemoryType::from_raw_opencl][opencl] and [from_raw_cuda][cuda], embeds
+    /// memory, which has been allocated outside of Collenchyma, e.g. by another library. The
+    /// `memory` is never returned to a MemoryPool and is not tracked as an allocation.
+    /// Returns an error, if `memory` does not belong to the framework of `dev` or if its size is
+    /// not a multiple of the size of `T`.
+    /// [opencl]: ../memory/enum.MemoryType.html#method.from_raw_opencl
+    /// [cuda]: ../memory/enum.MemoryType.html#method.from_raw_cuda
+    pub fn from_memory(dev: &DeviceType, memory: MemoryType) -> Result<SharedMemory<T>, Error> {
+        if !dev.owns_memory(&memory) {
+            return Err(Error::InvalidMemoryAllocation(format!("Unable to import {:?}, as it does not belong to the framework of device {:?}.", memory, dev)))
         }
+        let elem_size = mem::size_of::<T>();
+        if elem_size == 0 || memory.size() % elem_size != 0 {
+            return Err(Error::InvalidMemorySize(format!("The memory holds {} bytes, which are no multiple of the {} bytes of an element.", memory.size(), elem_size)))
+        }
+        let capacity = memory.size() / elem_size;
+        let mut copies = HashMap::<DeviceType, MemoryType>::new();
+        copies.insert(dev.clone(), memory);
+        let mut versions = HashMap::<DeviceType, usize>::new();
+        versions.insert(dev.clone(), 0);
+        Ok(SharedMemory {
+            latest_location: dev.clone(),
+            latest_version: 0,
+            copies: copies,
+            versions: versions,
+            cap: capacity,
+            recyclable: Cell::new(false),
+            phantom: PhantomData,
+        })
     }
 
     /// Synchronize memory from latest location to `destination`.
+    ///
+    /// Only copies the memory, if the copy on `destination` is outdated. Afterwards the copy on
+    /// `destination` holds the latest version.
     pub fn sync(&mut self, destination: &DeviceType) -> Result<(), Error> {
-        if &self.latest_location != destination {
+        if !self.is_latest(destination) {
             let latest = self.latest_location.clone();
             try!(self.sync_from_to(&latest, &destination));
+            self.versions.insert(destination.clone(), self.latest_version);
+            self.latest_location = destination.clone();
+        }
+        Ok(())
+    }
+
+    /// Starts to synchronize memory from the latest location to `destination` without blocking.
+    ///
+    /// Copies between the host and an OpenCL or Cuda device are issued on the OpenCL queue or the
+    /// Cuda stream of `transfer`, so that they overlap with work on other queues or streams, e.g.
+    /// to upload the next batch while the current batch is computed. All other copies, and
+    /// copies on a `TransferQueue::Native`, are carried out synchronously.
+    ///
+    /// The returned SyncHandle borrows the SharedMemory, which keeps the host memory alive and
+    /// unchanged, until the copy has been waited for. Afterwards `destination` holds the latest
+    /// version, like after `sync`.
+    ///
+    /// ## Safety
+    ///
+    /// The SyncHandle waits for the copy, when it gets dropped. It must not be leaked, e.g. with
+    /// `mem::forget`, as that ends the borrow while the copy still reads or writes the host
+    /// memory, which can then be changed or freed.
+    pub unsafe fn sync_async(&mut self, destination: &DeviceType, transfer: TransferQueue) -> Result<SyncHandle<T>, Error> {
+        let mut pending = Pending::Done;
+        if !self.is_latest(destination) {
+            let latest = self.latest_location.clone();
+            let (source_copy, mut destination_copy) = try!(self.aquire_copies(&latest, destination));
+            let issued = issue_async(&latest, &source_copy, destination, &mut destination_copy, &transfer);
+            if let Some(Ok(_)) = issued {
+                stats::record_transfer(&source_copy, &destination_copy, source_copy.size());
+            }
+            self.return_copies(&latest, source_copy, destination, destination_copy);
+            pending = match issued {
+                Some(result) => try!(result.map_err(Error::MemorySynchronizationFailed)),
+                None => {
+                    co_warn!("No asynchronous path from {} to {} memory, syncing synchronously", latest.framework_id(), destination.framework_id());
+                    try!(self.sync_from_to(&latest, destination));
+                    Pending::Done
+                }
+            };
+            self.versions.insert(destination.clone(), self.latest_version);
             self.latest_location = destination.clone();
         }
+        Ok(SyncHandle { _memory: self, pending: pending })
+    }
+
+    /// Moves the latest version from the `source` device to the `destination` device.
+    ///
+    /// Allocates a copy on `destination`, if there is none yet. Devices without a direct path
+    /// between them, e.g. Native and Cuda, exchange the data through a host buffer. Afterwards
+    /// `destination` holds the latest version.
+    /// Returns an error, if `source` and `destination` are the same device or if `source` does
+    /// not hold the latest version.
+    pub fn transfer(&mut self, source: &DeviceType, destination: &DeviceType) -> Result<(), Error> {
+        if source == destination {
+            return Err(Error::InvalidTransfer(format!("Unable to transfer memory from {:?} onto itself.", source)))
+        }
+        if !self.is_latest(source) {
+            return Err(Error::MissingSource(format!("SharedMemory does not hold the latest version on source device {:?}.", source)))
+        }
+        match self.add_device(destination) { _ => () }
+        let (source_copy, mut destination_copy) = try!(self.aquire_copies(source, destination));
+        let result = source.sync_between(&source_copy, destination, &mut destination_copy);
+        self.return_copies(source, source_copy, destination, destination_copy);
+        try!(result.map_err(Error::MemorySynchronizationFailed));
+        self.versions.insert(destination.clone(), self.latest_version);
+        self.latest_location = destination.clone();
         Ok(())
     }
 
     /// Get a reference to the memory copy on the provided `device`.
     ///
-    /// Returns `None` if there is no memory copy on the device.
+    /// Returns `None` if there is no memory copy on the device or if the copy is outdated, in
+    /// which case it needs to be synchronized first.
     pub fn get(&self, device: &DeviceType) -> Option<&MemoryType> {
-        self.copies.get(device)
+        if self.is_latest(device) {
+            self.copies.get(device)
+        } else {
+            None
+        }
     }
 
     /// Get a mutable reference to the memory copy on the provided `device`.
     ///
-    /// Returns `None` if there is no memory copy on the device.
-    pub fn get_mut(&mut self, device: &DeviceType) -> Option<&mut MemoryType> {
-        self.copies.get_mut(device)
+    /// Synchronizes an outdated copy first, so that it can be read and written. As the copy might
+    /// get written to, it becomes the only copy holding the latest version.
+    /// Returns an error, if there is no memory copy on the device or the synchronization failed.
+    pub fn get_mut(&mut self, device: &DeviceType) -> Result<&mut MemoryType, Error> {
+        if !self.copies.contains_key(device) {
+            return Err(Error::MissingDestination(format!("SharedMemory does not hold a copy on device {:?}.", device)))
+        }
+        try!(self.sync(device));
+        self.get_mut_write_only(device)
+    }
+
+    /// Get a mutable reference to the memory copy on the provided `device`, which is going to be
+    /// overwritten entirely.
+    ///
+    /// Unlike `get_mut`, an outdated copy is not synchronized, so its content is undefined until
+    /// it has been written. Use it for output operands, which are never read. The copy becomes
+    /// the only copy holding the latest version.
+    /// Returns an error, if there is no memory copy on the device.
+    pub fn get_mut_write_only(&mut self, device: &DeviceType) -> Result<&mut MemoryType, Error> {
+        if !self.copies.contains_key(device) {
+            return Err(Error::MissingDestination(format!("SharedMemory does not hold a copy on device {:?}.", device)))
+        }
+        self.latest_version += 1;
+        self.versions.insert(device.clone(), self.latest_version);
+        self.latest_location = device.clone();
+        Ok(self.copies.get_mut(device).unwrap())
+    }
+
+    #[cfg(feature = "opencl")]
+    /// Returns the raw OpenCL memory object of the copy on `device`, e.g. to pass it to a kernel,
+    /// which is launched outside of Collenchyma.
+    ///
+    /// Returns `None` like `get`, if there is no copy on `device` or if it is outdated, so `sync`
+    /// to `device` first. To write through the memory object, call `get_mut` on `device` first,
+    /// which makes its copy the only one holding the latest version.
+    /// The memory object stays owned by the SharedMemory: the caller must not release it and
+    /// must not use it after the SharedMemory has been dropped.
+    pub fn as_raw_opencl(&self, device: &DeviceType) -> Option<*mut c_void> {
+        self.get(device).and_then(|memory| memory.as_opencl()).map(|memory| memory.id_c())
+    }
+
+    #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+    /// Returns the raw Cuda device pointer of the copy on `device`, e.g. to pass it to a kernel,
+    /// which is launched outside of Collenchyma.
+    ///
+    /// Returns `None` like `get`, if there is no copy on `device` or if it is outdated, so `sync`
+    /// to `device` first. To write through the device pointer, call `get_mut` on `device` first,
+    /// which makes its copy the only one holding the latest version.
+    /// The allocation stays owned by the SharedMemory: the caller must not free it and must not
+    /// use it after the SharedMemory has been dropped.
+    pub fn as_raw_cuda(&self, device: &DeviceType) -> Option<CUdeviceptr> {
+        self.get(device).and_then(|memory| memory.as_cuda()).map(|memory| memory.id_c() as CUdeviceptr)
+    }
+
+    /// Returns whether the copy on `device` holds the latest version.
+    fn is_latest(&self, device: &DeviceType) -> bool {
+        self.versions.get(device) == Some(&self.latest_version)
     }
 
     /// Synchronize memory from `source` device to `destination` device.
@@ -100,20 +328,37 @@ impl<T> SharedMemory<T> {
         if source != destination {
             match self.aquire_copies(source, destination) {
                 Ok((source_copy, mut destination_copy)) => {
-                    match source.clone() {
+                    co_debug!("Syncing {} bytes from {} to {} memory", source_copy.size(), source.framework_id(), destination.framework_id());
+                    let result = match source.clone() {
                         DeviceType::Native(cpu) => {
-                            if let MemoryType::Native(ref src) = source_copy {
-                                cpu.sync_memory_to(&src, &mut destination_copy, destination)
+                            match source_copy {
+                                MemoryType::Native(ref src) => cpu.sync_memory_to(&src, &mut destination_copy, destination),
+                                _ => Err(::framework::Error::InvalidMemoryAllocation(format!("Unable to sync from {:?}, as it was not allocated by the framework of a Native device.", source_copy))),
                             }
                         },
+                        #[cfg(feature = "opencl")]
                         DeviceType::OpenCL(context) => {
-                            if let MemoryType::OpenCL(ref src) = source_copy {
-                                context.sync_memory_to(&src,&mut destination_copy, destination)
+                            match source_copy {
+                                MemoryType::OpenCL(ref src) => context.sync_memory_to(&src, &mut destination_copy, destination),
+                                _ => Err(::framework::Error::InvalidMemoryAllocation(format!("Unable to sync from {:?}, as it was not allocated by the framework of an OpenCL device.", source_copy))),
+                            }
+                        },
+                        #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+                        DeviceType::Cuda(context) => {
+                            match source_copy {
+                                MemoryType::Cuda(ref src) => context.sync_memory_to(&src, &mut destination_copy, destination),
+                                _ => Err(::framework::Error::InvalidMemoryAllocation(format!("Unable to sync from {:?}, as it was not allocated by the framework of a Cuda device.", source_copy))),
                             }
                         },
+                    };
+                    if result.is_ok() {
+                        stats::record_transfer(&source_copy, &destination_copy, source_copy.size());
                     }
                     self.return_copies(source, source_copy, destination, destination_copy);
-                    Ok(())
+                    match result {
+                        Ok(_) => Ok(()),
+                        Err(err) => Err(Error::MemorySynchronizationFailed(err)),
+                    }
                 },
                 Err(err) => Err(err),
             }
@@ -151,28 +396,297 @@ impl<T> SharedMemory<T> {
         match self.copies.get(device) {
             Some(_) => Err(Error::InvalidMemoryAllocation(format!("SharedMemory already tracks memory for this device. No memory allocation."))),
             None => {
-                let copy: MemoryType;
-                match *device {
-                    DeviceType::Native(ref cpu) => copy = MemoryType::Native(cpu.alloc_memory(mem::size_of::<T>())),
-                    DeviceType::OpenCL(ref context) => copy = MemoryType::OpenCL(context.alloc_memory(mem::size_of::<T>())),
+                let alloc_size = match checked_buffer_bytes::<T>(self.cap) {
+                    Some(bytes) => bytes,
+                    None => return Err(Error::InvalidMemorySize(format!("SharedMemory of {} elements of {} bytes overflows the addressable memory.", self.cap, mem::size_of::<T>())))
                 };
+                let copy = try!(alloc_copy::<T>(device, self.cap, alloc_size));
                 self.copies.insert(device.clone(), copy);
                 Ok(self)
             }
         }
     }
 
+    /// Returns a view of `len` elements, which starts `offset` elements into the SharedMemory.
+    ///
+    /// The view dereferences to a SharedMemory of its own, which does not allocate new memory, but
+    /// aliases the latest memory copy: a Native slice, an OpenCL sub-buffer or an offset Cuda
+    /// device pointer. It can be passed to every operation, which accepts a SharedMemory, e.g. to
+    /// compute on a row of a matrix. Synchronizing the view to another device allocates a
+    /// separate copy there.
+    ///
+    /// The view borrows the SharedMemory mutably. When it gets dropped after a write, its latest
+    /// version is synchronized back into the aliased copy, which then becomes the latest version
+    /// of this SharedMemory.
+    ///
+    /// On OpenCL the offset in bytes needs to be aligned to `CL_DEVICE_MEM_BASE_ADDR_ALIGN`.
+    /// Returns an error, if the view exceeds the capacity of the SharedMemory.
+    pub fn view(&mut self, offset: usize, len: usize) -> Result<SharedMemoryView<T>, Error> {
+        match offset.checked_add(len) {
+            Some(end) if end <= self.cap => (),
+            _ => return Err(Error::InvalidMemoryView(format!("A view of {} elements at offset {} exceeds the capacity of {} elements.", len, offset, self.cap)))
+        }
+        let device = &self.latest_location;
+        let latest = try!(self.copies.get(device).ok_or(Error::MissingSource(format!("SharedMemory does not hold a copy on the latest device {:?}.", device))));
+        let (offset_bytes, len_bytes) = (buffer_bytes::<T>(offset), buffer_bytes::<T>(len));
+        let view: MemoryType = match *latest {
+            MemoryType::Native(ref mem) => MemoryType::Native(try!(mem.view(offset_bytes, len_bytes).ok_or(Error::InvalidMemoryView(format!("A view of {} bytes at offset {} exceeds {:?}.", len_bytes, offset_bytes, mem))))),
+            #[cfg(feature = "opencl")]
+            MemoryType::OpenCL(ref mem) => match *device {
+                DeviceType::OpenCL(ref context) => MemoryType::OpenCL(try!(context.view_memory(mem, offset_bytes, len_bytes).map_err(|err| Error::MemoryAllocationFailed(From::from(err))))),
+                _ => return Err(Error::InvalidMemoryView(format!("Unable to view {:?}, as it was not allocated by an OpenCL device.", mem)))
+            },
+            #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+            MemoryType::Cuda(ref mem) => MemoryType::Cuda(try!(mem.view(offset_bytes, len_bytes).ok_or(Error::InvalidMemoryView(format!("A view of {} bytes at offset {} exceeds {:?}.", len_bytes, offset_bytes, mem))))),
+        };
+        self.recyclable.set(false);
+        let mut copies = HashMap::<DeviceType, MemoryType>::new();
+        copies.insert(device.clone(), view);
+        let mut versions = HashMap::<DeviceType, usize>::new();
+        versions.insert(device.clone(), 0);
+        let memory = SharedMemory {
+            latest_location: device.clone(),
+            latest_version: 0,
+            copies: copies,
+            versions: versions,
+            cap: len,
+            recyclable: Cell::new(false),
+            phantom: PhantomData,
+        };
+        Ok(SharedMemoryView { parent: self, memory: memory })
+    }
+
     /// Returns the device that contains the up-to-date memory copy.
+    ///
+    /// Other copies might hold the latest version as well, which `is_current_on` tells.
     pub fn latest_device(&self) -> &DeviceType {
         &self.latest_location
     }
 
+    /// Returns whether the copy on `device` holds the latest version, so that it can be read
+    /// through `get` without synchronizing it first.
+    ///
+    /// Returns `false` if there is no memory copy on the device. Neither synchronizes nor changes
+    /// the version of any copy.
+    pub fn is_current_on(&self, device: &DeviceType) -> bool {
+        self.copies.contains_key(device) && self.is_latest(device)
+    }
+
     /// Returns the number of elements for which the SharedMemory has been allocated.
     pub fn capacity(&self) -> usize {
         self.cap
     }
 }
 
+impl<T: Copy> SharedMemory<T> {
+    /// Allocates SharedMemory for the elements of `data` on the `device` and copies them into it.
+    ///
+    /// The copy on `device` holds the latest version. Returns an error, if the device fails to
+    /// allocate or to copy the memory.
+    pub fn from_slice(device: &DeviceType, data: &[T]) -> Result<SharedMemory<T>, Error> {
+        let mut shared = try!(SharedMemory::<T>::try_new(device, data.len()));
+        {
+            let host = unsafe { slice::from_raw_parts(data.as_ptr() as *const u8, mem::size_of_val(data)) };
+            let copy = try!(shared.get_mut_write_only(device));
+            try!(device.sync_in(host, copy).map_err(Error::MemorySynchronizationFailed));
+        }
+        Ok(shared)
+    }
+
+    /// Copies the latest version of the elements to the host.
+    ///
+    /// Reads the latest memory copy directly, without adding a copy on the host to the
+    /// SharedMemory. Returns an error, if the size of the latest copy does not match the capacity
+    /// of the SharedMemory in elements of `T`.
+    pub fn to_vec(&self) -> Result<Vec<T>, Error> {
+        let device = &self.latest_location;
+        let latest = try!(self.copies.get(device).ok_or(Error::MissingSource(format!("SharedMemory does not hold a copy on the latest device {:?}.", device))));
+        let bytes = buffer_bytes::<T>(self.cap);
+        if latest.size() != bytes {
+            return Err(Error::InvalidMemorySize(format!("The latest copy holds {} bytes, which are not {} elements of {} bytes.", latest.size(), self.cap, mem::size_of::<T>())))
+        }
+        let mut data = Vec::<T>::with_capacity(self.cap);
+        unsafe {
+            let host = slice::from_raw_parts_mut(data.as_mut_ptr() as *mut u8, bytes);
+            try!(device.sync_out(latest, host).map_err(Error::MemorySynchronizationFailed));
+            data.set_len(self.cap);
+        }
+        Ok(data)
+    }
+
+    /// Copies `len` elements of the latest version, starting at the element `offset`, to the host.
+    ///
+    /// Like `to_vec`, reads the latest memory copy directly, but only transfers the requested
+    /// range, e.g. to peek at a scalar result at the front of a large buffer.
+    /// Returns an error, if the range reaches past the capacity of the SharedMemory.
+    pub fn read_range(&self, offset: usize, len: usize) -> Result<Vec<T>, Error> {
+        match offset.checked_add(len) {
+            Some(end) if end <= self.cap => {},
+            _ => return Err(Error::InvalidMemoryView(format!("Unable to read {} elements at offset {} of a SharedMemory of {} elements.", len, offset, self.cap)))
+        }
+        let device = &self.latest_location;
+        let latest = try!(self.copies.get(device).ok_or(Error::MissingSource(format!("SharedMemory does not hold a copy on the latest device {:?}.", device))));
+        let bytes = buffer_bytes::<T>(len);
+        let mut data = Vec::<T>::with_capacity(len);
+        unsafe {
+            let host = slice::from_raw_parts_mut(data.as_mut_ptr() as *mut u8, bytes);
+            try!(device.sync_out_range(latest, offset * mem::size_of::<T>(), host).map_err(Error::MemorySynchronizationFailed));
+            data.set_len(len);
+        }
+        Ok(data)
+    }
+}
+
+impl<T> Drop for SharedMemory<T> {
+    /// Returns the memory copies to the MemoryPool of their device, if it has one.
+    fn drop(&mut self) {
+        if self.recyclable.get() {
+            for (device, memory) in self.copies.drain() {
+                device.recycle_memory(memory);
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+/// A range of a SharedMemory, which [SharedMemory::view][view] returns.
+/// [view]: ./struct.SharedMemory.html#method.view
+///
+/// Dereferences to a SharedMemory, which aliases the range. Writes through it become the latest
+/// version of the viewed SharedMemory, when the view gets dropped.
+pub struct SharedMemoryView<'a, T: 'a> {
+    parent: &'a mut SharedMemory<T>,
+    memory: SharedMemory<T>,
+}
+
+impl<'a, T> Deref for SharedMemoryView<'a, T> {
+    type Target = SharedMemory<T>;
+
+    fn deref(&self) -> &SharedMemory<T> {
+        &self.memory
+    }
+}
+
+impl<'a, T> DerefMut for SharedMemoryView<'a, T> {
+    fn deref_mut(&mut self) -> &mut SharedMemory<T> {
+        &mut self.memory
+    }
+}
+
+impl<'a, T> Drop for SharedMemoryView<'a, T> {
+    /// Synchronizes a written view back into the aliased copy and marks that copy as the latest
+    /// version of the viewed SharedMemory, which outdates its copies on other devices.
+    fn drop(&mut self) {
+        if self.memory.latest_version == 0 {
+            return
+        }
+        let device = self.parent.latest_location.clone();
+        if let Err(err) = self.memory.sync(&device) {
+            co_warn!("Unable to synchronize a view back to {} memory: {}", device.framework_id(), err);
+        }
+        self.parent.latest_version += 1;
+        self.parent.versions.insert(device, self.parent.latest_version);
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+/// Selects, where the copy of [SharedMemory::sync_async][sync_async] is issued.
+/// [sync_async]: ./struct.SharedMemory.html#method.sync_async
+pub enum TransferQueue {
+    /// Copies synchronously, before `sync_async` returns.
+    Native,
+    #[cfg(feature = "opencl")]
+    /// Enqueues the copy on an OpenCL command queue of the OpenCL device.
+    OpenCL(Queue),
+    #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+    /// Issues the copy on a Cuda stream of the Cuda device.
+    Cuda(Stream),
+}
+
+#[derive(Debug)]
+/// Defines a copy, which is still in flight.
+enum Pending {
+    Done,
+    #[cfg(feature = "opencl")]
+    OpenCL(Event),
+    #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+    Cuda(Stream),
+}
+
+impl Pending {
+    /// Blocks until the copy has been completed.
+    fn wait(&mut self) -> Result<(), Error> {
+        let result = match *self {
+            Pending::Done => Ok(()),
+            #[cfg(feature = "opencl")]
+            Pending::OpenCL(ref event) => event.wait().map_err(From::from),
+            #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+            Pending::Cuda(ref stream) => CudaAPI::synchronize_stream(stream).map_err(From::from),
+        };
+        *self = Pending::Done;
+        result.map_err(Error::MemorySynchronizationFailed)
+    }
+}
+
+#[derive(Debug)]
+#[must_use]
+/// Holds a SharedMemory, whose synchronization through `sync_async` is still in flight.
+///
+/// Waits for the copy, when it goes out of scope. Call `wait` to handle its errors. It must not be
+/// leaked, see the safety section of `sync_async`.
+pub struct SyncHandle<'a, T: 'a> {
+    /// Keeps the memory copies from being accessed or dropped, while the copy is in flight.
+    _memory: &'a mut SharedMemory<T>,
+    pending: Pending,
+}
+
+impl<'a, T> SyncHandle<'a, T> {
+    /// Blocks until the copy has been completed, which releases the SharedMemory.
+    pub fn wait(mut self) -> Result<(), Error> {
+        self.pending.wait()
+    }
+}
+
+impl<'a, T> Drop for SyncHandle<'a, T> {
+    #[allow(unused_must_use)]
+    fn drop(&mut self) {
+        self.pending.wait();
+    }
+}
+
+/// Issues an asynchronous copy from `source` on `source_device` to `destination` on
+/// `destination_device` on `transfer`.
+///
+/// Returns `None`, if there is no asynchronous path between the two memory copies.
+#[allow(unused_variables)]
+fn issue_async(source_device: &DeviceType, source: &MemoryType, destination_device: &DeviceType, destination: &mut MemoryType, transfer: &TransferQueue) -> Option<Result<Pending, ::framework::Error>> {
+    match (source, destination, transfer) {
+        #[cfg(feature = "opencl")]
+        (&MemoryType::Native(ref src), &mut MemoryType::OpenCL(ref mut dst), &TransferQueue::OpenCL(ref queue)) => {
+            let data = src.as_slice::<u8>();
+            Some(OpenCLAPI::enqueue_write_to_memory(queue, dst, data.as_ptr() as *const c_void, data.len()).map(Pending::OpenCL).map_err(From::from))
+        },
+        #[cfg(feature = "opencl")]
+        (&MemoryType::OpenCL(ref src), &mut MemoryType::Native(ref mut dst), &TransferQueue::OpenCL(ref queue)) => {
+            let data = dst.as_mut_slice::<u8>();
+            Some(OpenCLAPI::enqueue_read_from_memory(queue, src, data.as_mut_ptr() as *mut c_void, data.len()).map(Pending::OpenCL).map_err(From::from))
+        },
+        #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+        (&MemoryType::Native(ref src), &mut MemoryType::Cuda(ref dst), &TransferQueue::Cuda(ref stream)) => {
+            let context = match *destination_device { DeviceType::Cuda(ref context) => context, _ => return None };
+            let data = src.as_slice::<u8>();
+            Some(CudaAPI::mem_cpy_h_to_d_async(context, data.as_ptr() as *const c_void, dst, data.len(), stream).map(|_| Pending::Cuda(*stream)).map_err(From::from))
+        },
+        #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+        (&MemoryType::Cuda(ref src), &mut MemoryType::Native(ref mut dst), &TransferQueue::Cuda(ref stream)) => {
+            let context = match *source_device { DeviceType::Cuda(ref context) => context, _ => return None };
+            let data = dst.as_mut_slice::<u8>();
+            Some(CudaAPI::mem_cpy_d_to_h_async(context, src, data.as_mut_ptr() as *mut c_void, data.len(), stream).map(|_| Pending::Cuda(*stream)).map_err(From::from))
+        },
+        _ => None
+    }
+}
+
 /// Errors than can occur when synchronizing memory.
 #[derive(Debug)]
 pub enum Error {
@@ -182,6 +696,16 @@ pub enum Error {
     MissingDestination(String),
     /// No memory allocation on specified device happened.
     InvalidMemoryAllocation(String),
+    /// Framework error at memory synchronization.
+    MemorySynchronizationFailed(::framework::Error),
+    /// Framework error at memory allocation.
+    MemoryAllocationFailed(::framework::Error),
+    /// Transfer between the same device.
+    InvalidTransfer(String),
+    /// View beyond the bounds of the memory.
+    InvalidMemoryView(String),
+    /// Memory, whose size does not match the elements it is read as.
+    InvalidMemorySize(String),
 }
 
 impl fmt::Display for Error {
@@ -190,6 +714,11 @@ impl fmt::Display for Error {
             Error::MissingSource(ref err) => write!(f, "{:?}", err),
             Error::MissingDestination(ref err) => write!(f, "{:?}", err),
             Error::InvalidMemoryAllocation(ref err) => write!(f, "{:?}", err),
+            Error::MemorySynchronizationFailed(ref err) => write!(f, "{}", err),
+            Error::MemoryAllocationFailed(ref err) => write!(f, "{}", err),
+            Error::InvalidTransfer(ref err) => write!(f, "{:?}", err),
+            Error::InvalidMemoryView(ref err) => write!(f, "{:?}", err),
+            Error::InvalidMemorySize(ref err) => write!(f, "{:?}", err),
         }
     }
 }
@@ -200,6 +729,11 @@ impl error::Error for Error {
             Error::MissingSource(ref err) => err,
             Error::MissingDestination(ref err) => err,
             Error::InvalidMemoryAllocation(ref err) => err,
+            Error::MemorySynchronizationFailed(ref err) => err.description(),
+            Error::MemoryAllocationFailed(ref err) => err.description(),
+            Error::InvalidTransfer(ref err) => err,
+            Error::InvalidMemoryView(ref err) => err,
+            Error::InvalidMemorySize(ref err) => err,
         }
     }
 
@@ -208,6 +742,24 @@ impl error::Error for Error {
             Error::MissingSource(_) => None,
             Error::MissingDestination(_) => None,
             Error::InvalidMemoryAllocation(_) => None,
+            Error::MemorySynchronizationFailed(ref err) => Some(err),
+            Error::MemoryAllocationFailed(ref err) => Some(err),
+            Error::InvalidTransfer(_) => None,
+            Error::InvalidMemoryView(_) => None,
+            Error::InvalidMemorySize(_) => None,
+        }
+    }
+
+    fn source(&self) -> Option<&(error::Error + 'static)> {
+        match *self {
+            Error::MissingSource(_) => None,
+            Error::MissingDestination(_) => None,
+            Error::InvalidMemoryAllocation(_) => None,
+            Error::MemorySynchronizationFailed(ref err) => Some(err),
+            Error::MemoryAllocationFailed(ref err) => Some(err),
+            Error::InvalidTransfer(_) => None,
+            Error::InvalidMemoryView(_) => None,
+            Error::InvalidMemorySize(_) => None,
         }
     }
 }
@@ -0,0 +1,118 @@
+//! Provides counters of the work, which is issued to the devices.
+//!
+//! With the `stats` feature, the bytes transferred between host and devices, the kernel launches
+//! and the bytes of device memory allocated are counted, which helps to find out whether a program
+//! is bound by its transfers or by its computation. [Backend::stats][stats] returns the counters.
+//!
+//! The counters are relaxed atomics, which are shared by all Backends of the process. Without the
+//! feature, the counting compiles down to nothing.
+//!
+//! [stats]: ../backend/struct.Backend.html#method.stats
+
+use memory::MemoryType;
+#[cfg(feature = "stats")]
+use std::sync::atomic::{AtomicUsize, Ordering, ATOMIC_USIZE_INIT};
+
+#[cfg(feature = "stats")]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+/// Holds the counters of the work, which has been issued to the devices.
+pub struct BackendStats {
+    /// The number of bytes copied from the host to a device.
+    pub bytes_to_device: usize,
+    /// The number of bytes copied from a device to the host.
+    pub bytes_to_host: usize,
+    /// The number of kernels launched on a device.
+    pub kernel_launches: usize,
+    /// The number of bytes of device memory allocated, not counting reused pooled memory.
+    pub bytes_allocated: usize,
+}
+
+#[cfg(feature = "stats")]
+static BYTES_TO_DEVICE: AtomicUsize = ATOMIC_USIZE_INIT;
+#[cfg(feature = "stats")]
+static BYTES_TO_HOST: AtomicUsize = ATOMIC_USIZE_INIT;
+#[cfg(feature = "stats")]
+static KERNEL_LAUNCHES: AtomicUsize = ATOMIC_USIZE_INIT;
+#[cfg(feature = "stats")]
+static BYTES_ALLOCATED: AtomicUsize = ATOMIC_USIZE_INIT;
+
+/// Returns the current counters.
+#[cfg(feature = "stats")]
+pub fn snapshot() -> BackendStats {
+    BackendStats {
+        bytes_to_device: BYTES_TO_DEVICE.load(Ordering::Relaxed),
+        bytes_to_host: BYTES_TO_HOST.load(Ordering::Relaxed),
+        kernel_launches: KERNEL_LAUNCHES.load(Ordering::Relaxed),
+        bytes_allocated: BYTES_ALLOCATED.load(Ordering::Relaxed),
+    }
+}
+
+/// Sets all counters back to zero.
+#[cfg(feature = "stats")]
+pub fn reset() {
+    BYTES_TO_DEVICE.store(0, Ordering::Relaxed);
+    BYTES_TO_HOST.store(0, Ordering::Relaxed);
+    KERNEL_LAUNCHES.store(0, Ordering::Relaxed);
+    BYTES_ALLOCATED.store(0, Ordering::Relaxed);
+}
+
+/// Counts a copy of `bytes` from the memory `source` to the memory `destination`.
+///
+/// Only copies between the host and a device are counted.
+#[cfg(feature = "stats")]
+pub fn record_transfer(source: &MemoryType, destination: &MemoryType, bytes: usize) {
+    match (source.as_native().is_some(), destination.as_native().is_some()) {
+        (true, false) => record_to_device(bytes),
+        (false, true) => record_to_host(bytes),
+        _ => {}
+    }
+}
+
+/// Counts a copy of `bytes` from the host to a device.
+#[cfg(feature = "stats")]
+pub fn record_to_device(bytes: usize) {
+    BYTES_TO_DEVICE.fetch_add(bytes, Ordering::Relaxed);
+}
+
+/// Counts a copy of `bytes` from a device to the host.
+#[cfg(feature = "stats")]
+pub fn record_to_host(bytes: usize) {
+    BYTES_TO_HOST.fetch_add(bytes, Ordering::Relaxed);
+}
+
+/// Counts a kernel launch.
+#[cfg(feature = "stats")]
+pub fn record_launch() {
+    KERNEL_LAUNCHES.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Counts an allocation of `bytes` of device memory.
+#[cfg(feature = "stats")]
+pub fn record_alloc(bytes: usize) {
+    BYTES_ALLOCATED.fetch_add(bytes, Ordering::Relaxed);
+}
+
+/// Counts nothing without the `stats` feature.
+#[cfg(not(feature = "stats"))]
+#[inline(always)]
+pub fn record_transfer(_source: &MemoryType, _destination: &MemoryType, _bytes: usize) {}
+
+/// Counts nothing without the `stats` feature.
+#[cfg(not(feature = "stats"))]
+#[inline(always)]
+pub fn record_to_device(_bytes: usize) {}
+
+/// Counts nothing without the `stats` feature.
+#[cfg(not(feature = "stats"))]
+#[inline(always)]
+pub fn record_to_host(_bytes: usize) {}
+
+/// Counts nothing without the `stats` feature.
+#[cfg(not(feature = "stats"))]
+#[inline(always)]
+pub fn record_launch() {}
+
+/// Counts nothing without the `stats` feature.
+#[cfg(not(feature = "stats"))]
+#[inline(always)]
+pub fn record_alloc(_bytes: usize) {}
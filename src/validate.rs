@@ -0,0 +1,128 @@
+//! Provides the length and shape checks of the operands of library operations.
+//!
+//! Operations call the checks before synchronizing their operands, so that a kernel never gets
+//! launched on too small buffers and all libraries report mismatches with the same messages.
+//! Operands are passed as `(name, length)` pairs, with their length in elements.
+
+use libraries::blas::Error;
+
+/// Checks that the operands `a` and `b` have the same length.
+pub fn same_len(a: (&str, usize), b: (&str, usize)) -> Result<(), Error> {
+    if a.1 != b.1 {
+        return Err(Error::InvalidArgument(format!("`{}` ({}) and `{}` ({}) need to have the same length.", a.0, a.1, b.0, b.1)))
+    }
+    Ok(())
+}
+
+/// Checks that the operand `b` is at least as large as the operand `a`.
+pub fn at_least(a: (&str, usize), b: (&str, usize)) -> Result<(), Error> {
+    if b.1 < a.1 {
+        return Err(Error::InvalidArgument(format!("`{}` ({}) needs to be at least as large as `{}` ({}).", b.0, b.1, a.0, a.1)))
+    }
+    Ok(())
+}
+
+/// Checks that the vector `x` holds exactly `len` elements.
+pub fn vector_len(x: (&str, usize), len: usize) -> Result<(), Error> {
+    if x.1 != len {
+        return Err(Error::InvalidArgument(format!("`{}` ({}) needs to hold {} elements.", x.0, x.1, len)))
+    }
+    Ok(())
+}
+
+/// Checks that the scalar operand `a` holds at least one element.
+pub fn scalar(a: (&str, usize)) -> Result<(), Error> {
+    if a.1 < 1 {
+        return Err(Error::InvalidArgument(format!("`{}` ({}) needs to hold at least 1 element.", a.0, a.1)))
+    }
+    Ok(())
+}
+
+/// Checks that the matrix `a` holds exactly `rows` x `cols` elements.
+///
+/// Fails as well, if the number of elements overflows `usize`.
+pub fn matrix_dims(a: (&str, usize), rows: usize, cols: usize) -> Result<(), Error> {
+    match rows.checked_mul(cols) {
+        Some(len) if len == a.1 => Ok(()),
+        _ => Err(Error::InvalidArgument(format!("`{}` ({}) needs to hold {} x {} elements.", a.0, a.1, rows, cols)))
+    }
+}
+
+/// Checks that `a` holds exactly `batch_count` matrices of `rows` x `cols` elements.
+///
+/// Fails as well, if the number of elements overflows `usize`.
+pub fn batched_matrix_dims(a: (&str, usize), batch_count: usize, rows: usize, cols: usize) -> Result<(), Error> {
+    match rows.checked_mul(cols).and_then(|len| len.checked_mul(batch_count)) {
+        Some(len) if len == a.1 => Ok(()),
+        _ => Err(Error::InvalidArgument(format!("`{}` ({}) needs to hold {} matrices of {} x {} elements.", a.0, a.1, batch_count, rows, cols)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use libraries::blas::Error;
+    use super::*;
+
+    fn message(result: Result<(), Error>) -> String {
+        match result {
+            Err(Error::InvalidArgument(message)) => message,
+            other => panic!("expected an invalid argument, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn it_accepts_operands_of_the_same_length() {
+        assert!(same_len(("x", 0), ("y", 0)).is_ok());
+        assert!(same_len(("x", 5), ("y", 5)).is_ok());
+    }
+
+    #[test]
+    fn it_names_operands_of_different_lengths() {
+        let message = message(same_len(("x", 4), ("y", 5)));
+        assert_eq!("`x` (4) and `y` (5) need to have the same length.", message);
+    }
+
+    #[test]
+    fn it_accepts_larger_and_equal_targets() {
+        assert!(at_least(("x", 4), ("y", 4)).is_ok());
+        assert!(at_least(("x", 4), ("y", 5)).is_ok());
+        assert!(at_least(("x", 5), ("y", 4)).is_err());
+    }
+
+    #[test]
+    fn it_checks_the_vector_length_exactly() {
+        assert!(vector_len(("x", 3), 3).is_ok());
+        assert!(vector_len(("x", 2), 3).is_err());
+        assert!(vector_len(("x", 4), 3).is_err());
+    }
+
+    #[test]
+    fn it_checks_that_scalars_hold_an_element() {
+        assert!(scalar(("a", 1)).is_ok());
+        assert!(scalar(("a", 2)).is_ok());
+        let message = message(scalar(("a", 0)));
+        assert_eq!("`a` (0) needs to hold at least 1 element.", message);
+    }
+
+    #[test]
+    fn it_checks_the_matrix_dims() {
+        assert!(matrix_dims(("a", 6), 2, 3).is_ok());
+        assert!(matrix_dims(("a", 0), 0, 3).is_ok());
+        assert!(matrix_dims(("a", 0), 2, 0).is_ok());
+        let message = message(matrix_dims(("a", 5), 2, 3));
+        assert_eq!("`a` (5) needs to hold 2 x 3 elements.", message);
+    }
+
+    #[test]
+    fn it_rejects_matrix_dims_which_overflow() {
+        assert!(matrix_dims(("a", 0), ::std::usize::MAX, 2).is_err());
+        assert!(batched_matrix_dims(("a", 0), 2, ::std::usize::MAX, 1).is_err());
+    }
+
+    #[test]
+    fn it_checks_the_batched_matrix_dims() {
+        assert!(batched_matrix_dims(("a", 12), 2, 2, 3).is_ok());
+        assert!(batched_matrix_dims(("a", 0), 0, 2, 3).is_ok());
+        assert!(batched_matrix_dims(("a", 6), 2, 2, 3).is_err());
+    }
+}
@@ -4,12 +4,23 @@ extern crate libc;
 #[cfg(test)]
 mod backend_spec {
 
-    use co::backend::{Backend, BackendConfig};
-    use co::frameworks::{OpenCL, Native};
+    use co::error::Error;
+    use co::framework::Error as FrameworkError;
+    use co::backend::{Backend, BackendBuilder, BackendConfig, BackendSpec, BackendType};
+    use co::frameworks::Native;
+    #[cfg(feature = "opencl")]
+    use co::frameworks::OpenCL;
+    #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+    use co::frameworks::Cuda;
     use co::libraries::blas::*;
     use co::framework::IFramework;
+    use co::hardware::IHardware;
+    use co::memory::MemoryType;
+    use co::shared_memory::SharedMemory;
+    use std::time::Duration;
 
     #[test]
+    #[cfg(feature = "opencl")]
     fn it_works() {
         let framework = OpenCL::new();
         let hardwares = framework.hardwares();
@@ -17,4 +28,238 @@ mod backend_spec {
         let backend = Backend::new(backend_config);
         println!("{:?}", backend);
     }
+
+    #[test]
+    fn it_creates_config_with_all_hardwares() {
+        let framework = Native::new();
+        let hardwares = framework.hardwares();
+        assert!(Backend::new(BackendConfig::all(framework)).is_ok());
+        assert!(hardwares.len() > 0);
+    }
+
+    #[test]
+    fn it_creates_config_with_selected_hardwares() {
+        let framework = Native::new();
+        assert!(BackendConfig::with_devices(framework.clone(), &[0]).is_ok());
+        assert!(BackendConfig::with_devices(framework, &[42]).is_err());
+    }
+
+    #[test]
+    fn it_builds_a_backend_fluently() {
+        let backend = BackendBuilder::new().framework(Native::new()).build().unwrap();
+        assert_eq!(Native::new().hardwares().len(), backend.device_count());
+        let framework = Native::new();
+        let hardwares = framework.hardwares();
+        let backend = BackendBuilder::new().framework(framework).hardwares(hardwares).device_index(0).build().unwrap();
+        assert_eq!(1, backend.device_count());
+    }
+
+    #[test]
+    fn it_validates_the_backend_builder() {
+        assert!(BackendBuilder::<Native>::new().build().is_err());
+        assert!(BackendBuilder::new().framework(Native::new()).hardwares(vec![]).build().is_err());
+        assert!(BackendBuilder::new().framework(Native::new()).device_index(100).build().is_err());
+        match BackendBuilder::<Native>::new().build() {
+            Err(Error::Framework(FrameworkError::InvalidArgument(_))) => {},
+            other => panic!("expected an invalid argument, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn it_creates_backend_from_spec() {
+        let backend = Backend::<Native>::from_spec(&BackendSpec::new("NATIVE", vec![0])).unwrap();
+        assert_eq!(1, backend.device_count());
+        let backend = BackendType::from_spec(&BackendSpec::new("NATIVE", vec![])).unwrap();
+        assert_eq!("NATIVE", backend.framework_id());
+        assert!(Backend::<Native>::from_spec(&BackendSpec::new("OPENCL", vec![])).is_err());
+        assert!(BackendType::from_spec(&BackendSpec::new("NATIVE", vec![100])).is_err());
+        match BackendType::from_spec(&BackendSpec::new("UNKNOWN", vec![])) {
+            Err(Error::Framework(FrameworkError::InvalidArgument(_))) => {},
+            other => panic!("expected an invalid argument, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn it_reports_the_hardwares_of_its_device() {
+        let framework = Native::new();
+        let names: Vec<String> = framework.hardwares().iter().map(|hardware| hardware.name().unwrap()).collect();
+        let backend = Backend::new(BackendConfig::all(framework)).unwrap();
+        assert_eq!(names.len(), backend.device_count());
+        assert_eq!(names, backend.device_names());
+    }
+
+    #[test]
+    fn it_creates_default_backend() {
+        assert!(BackendType::default().is_ok());
+    }
+
+    #[test]
+    fn it_creates_backend_by_preference() {
+        let backend = BackendType::with_preference(&["UNKNOWN", "NATIVE"]).unwrap();
+        assert_eq!("NATIVE", backend.framework_id());
+        assert!(BackendType::with_preference(&["UNKNOWN"]).is_err());
+    }
+
+    #[test]
+    fn it_moves_native_backends_across_threads() {
+        let framework = Native::new();
+        let hardwares = framework.hardwares();
+        let backend = Backend::new(BackendConfig::new(framework, hardwares)).unwrap();
+        let handle = ::std::thread::spawn(move || backend.synchronize().is_ok());
+        assert!(handle.join().unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "native")]
+    fn it_shares_the_device_between_clones() {
+        let backend = Backend::new(BackendConfig::all(Native::new())).unwrap();
+        let clone = backend.clone();
+        assert_eq!(backend.device(), clone.device());
+        let mut a = SharedMemory::<f32>::new(backend.device(), 1);
+        a.get_mut(backend.device()).unwrap().as_mut_native().unwrap().as_mut_slice::<f32>()[0] = 2f32;
+        let mut x = SharedMemory::<f32>::new(backend.device(), 2);
+        x.get_mut(backend.device()).unwrap().as_mut_native().unwrap().as_mut_slice::<f32>().copy_from_slice(&[1f32, 2f32]);
+        clone.scale(&mut a, &mut x).unwrap();
+        assert_eq!(&[2f32, 4f32], x.get(backend.device()).unwrap().as_native().unwrap().as_slice::<f32>());
+    }
+
+    #[test]
+    #[cfg(feature = "opencl")]
+    fn it_shares_the_opencl_context_between_clones() {
+        let framework = OpenCL::new();
+        let hardwares = framework.hardwares()[0..1].to_vec();
+        let backend = Backend::new(BackendConfig::new(framework, hardwares)).unwrap();
+        let ntv = Native::new();
+        let cpu = ntv.new_device(ntv.hardwares()).unwrap();
+        let mut x = SharedMemory::<f32>::new(&cpu, 2);
+        if let &mut MemoryType::Native(ref mut mem) = x.get_mut(&cpu).unwrap() {
+            mem.as_mut_slice::<f32>().copy_from_slice(&[1f32, 2f32]);
+        }
+        {
+            let clone = backend.clone();
+            assert_eq!(backend.device(), clone.device());
+            x.add_device(clone.device()).unwrap();
+            x.sync(clone.device()).unwrap();
+        }
+        // the context outlives the clone, as long as the backend holds on to it
+        assert!(x.get(backend.device()).is_some());
+        x.sync(&cpu).unwrap();
+        if let &MemoryType::Native(ref mem) = x.get(&cpu).unwrap() {
+            assert_eq!(&[1f32, 2f32], mem.as_slice::<f32>());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "native")]
+    fn it_warms_up_the_backend() {
+        let backend = Backend::new(BackendConfig::all(Native::new())).unwrap();
+        assert!(backend.warm_up().is_ok());
+    }
+
+    #[test]
+    #[cfg(feature = "opencl")]
+    fn it_warms_up_the_opencl_backend() {
+        let framework = OpenCL::new();
+        let hardwares = framework.hardwares()[0..1].to_vec();
+        let backend = Backend::new(BackendConfig::new(framework, hardwares)).unwrap();
+        assert!(backend.warm_up().is_ok());
+    }
+
+    #[test]
+    #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+    fn it_warms_up_the_cuda_backend() {
+        let backend = Backend::new(BackendConfig::all(Cuda::new())).unwrap();
+        backend.warm_up().unwrap();
+        backend.warm_up().unwrap();
+    }
+
+    #[test]
+    fn it_synchronizes_the_backend_device() {
+        let backend = Backend::new(BackendConfig::all(Native::new())).unwrap();
+        assert!(backend.synchronize().is_ok());
+    }
+
+    #[test]
+    #[cfg(feature = "opencl")]
+    fn it_synchronizes_the_opencl_backend_device() {
+        let backend = Backend::new(BackendConfig::all(OpenCL::new())).unwrap();
+        assert!(backend.synchronize().is_ok());
+    }
+
+    #[test]
+    fn it_flushes_the_backend_device() {
+        let backend = Backend::new(BackendConfig::all(Native::new())).unwrap();
+        assert!(backend.flush().is_ok());
+    }
+
+    #[test]
+    #[cfg(feature = "opencl")]
+    fn it_flushes_the_opencl_backend_device() {
+        let backend = Backend::new(BackendConfig::all(OpenCL::new())).unwrap();
+        let mut x = SharedMemory::<f32>::new(backend.device(), 1024);
+        backend.device().fill(x.get_mut(backend.device()).unwrap(), 1f32).unwrap();
+        assert!(backend.flush().is_ok());
+        assert!(backend.synchronize().is_ok());
+    }
+
+    #[test]
+    fn it_synchronizes_the_backend_device_with_a_timeout() {
+        let backend = Backend::new(BackendConfig::all(Native::new())).unwrap();
+        assert_eq!(true, backend.synchronize_timeout(Duration::from_millis(0)).unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "opencl")]
+    fn it_synchronizes_the_opencl_backend_device_with_a_timeout() {
+        let backend = Backend::new(BackendConfig::all(OpenCL::new())).unwrap();
+        let mut x = SharedMemory::<f32>::new(backend.device(), 1024);
+        backend.device().fill(x.get_mut(backend.device()).unwrap(), 1f32).unwrap();
+        assert_eq!(true, backend.synchronize_timeout(Duration::from_secs(10)).unwrap());
+    }
+
+    #[test]
+    fn it_reports_the_framework_id() {
+        let framework = Native::new();
+        assert_eq!("NATIVE", framework.framework_id());
+        let backend = Backend::new(BackendConfig::all(framework)).unwrap();
+        assert_eq!("NATIVE", backend.framework_id());
+    }
+
+    #[test]
+    fn it_displays_the_framework_and_hardwares() {
+        let backend = Backend::new(BackendConfig::all(Native::new())).unwrap();
+        let names = backend.device_names().join(", ");
+        assert_eq!(format!("NATIVE: {}", names), backend.device().to_string());
+        assert_eq!(format!("Backend(NATIVE: {})", names), backend.to_string());
+    }
+
+    #[test]
+    fn it_validates_the_active_device() {
+        let mut backend = Backend::new(BackendConfig::all(Native::new())).unwrap();
+        assert_eq!(0, backend.active_device());
+        assert!(backend.set_active_device(0).is_ok());
+        assert!(backend.set_active_device(backend.device_count()).is_err());
+        assert_eq!(0, backend.active_device());
+    }
+
+    #[test]
+    #[cfg(feature = "opencl")]
+    fn it_switches_the_active_opencl_device() {
+        let mut backend = Backend::new(BackendConfig::all(OpenCL::new())).unwrap();
+        let last = backend.device_count() - 1;
+        backend.set_active_device(last).unwrap();
+        assert_eq!(last, backend.active_device());
+        let mut x = SharedMemory::<f32>::new(backend.device(), 4);
+        let mut a = SharedMemory::<f32>::new(backend.device(), 1);
+        backend.device().fill(x.get_mut(backend.device()).unwrap(), 2f32).unwrap();
+        backend.device().fill(a.get_mut(backend.device()).unwrap(), 3f32).unwrap();
+        backend.scale(&mut a, &mut x).unwrap();
+        backend.synchronize().unwrap();
+        let cpu = Native::new().new_device(Native::new().hardwares()).unwrap();
+        x.add_device(&cpu).unwrap();
+        x.sync(&cpu).unwrap();
+        if let Some(&MemoryType::Native(ref mem)) = x.get(&cpu) {
+            assert_eq!(&[6f32; 4], mem.as_slice::<f32>());
+        }
+    }
 }
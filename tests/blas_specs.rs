@@ -2,16 +2,21 @@ extern crate collenchyma as co;
 extern crate libc;
 extern crate num;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "native"))]
 mod blas_spec {
 
     use co::backend::{Backend, BackendConfig};
     use co::framework::IFramework;
-    use co::frameworks::{OpenCL, Native};
+    use co::frameworks::Native;
+    #[cfg(feature = "opencl")]
+    use co::frameworks::OpenCL;
     use co::libraries::blas::*;
+    use co::frameworks::native::libraries as native_libraries;
     use co::memory::MemoryType;
     use co::shared_memory::SharedMemory;
     use num::traits::{cast, NumCast, Float};
+    #[cfg(feature = "f16")]
+    use co::libraries::f16;
 
     fn get_native_backend() -> Backend<Native> {
         let framework = Native::new();
@@ -20,6 +25,14 @@ mod blas_spec {
         Backend::new(backend_config).unwrap()
     }
 
+    #[cfg(feature = "opencl")]
+    fn get_opencl_backend() -> Backend<OpenCL> {
+        let framework = OpenCL::new();
+        let hardwares = framework.hardwares()[0..1].to_vec();
+        let backend_config = BackendConfig::new(framework, hardwares);
+        Backend::new(backend_config).unwrap()
+    }
+
     fn write_to_memory<T: Copy>(mem: &mut MemoryType, data: &[T]) {
         if let &mut MemoryType::Native(ref mut mem) = mem {
             let mut mem_buffer = mem.as_mut_slice::<T>();
@@ -96,6 +109,42 @@ mod blas_spec {
         (x, y)
     }
 
+    fn get_gemm_memory<T: Float, B: IFramework + Clone>(backend: &Backend<B>, a_data: &[i32]) -> (SharedMemory<T>, SharedMemory<T>, SharedMemory<T>, SharedMemory<T>, SharedMemory<T>){
+        let mut alpha = SharedMemory::<T>::new(backend.device(), 1);
+        write_to_memory(alpha.get_mut(backend.device()).unwrap(), &[cast::<i32, T>(1).unwrap()]);
+
+        let mut a = SharedMemory::<T>::new(backend.device(), 6);
+        let a_values: Vec<T> = a_data.iter().map(|v| cast::<i32, T>(*v).unwrap()).collect();
+        write_to_memory(a.get_mut(backend.device()).unwrap(), &a_values);
+
+        let mut b = SharedMemory::<T>::new(backend.device(), 6);
+        let b_values: Vec<T> = (7..13).map(|v| cast::<i32, T>(v).unwrap()).collect();
+        write_to_memory(b.get_mut(backend.device()).unwrap(), &b_values);
+
+        let mut beta = SharedMemory::<T>::new(backend.device(), 1);
+        write_to_memory(beta.get_mut(backend.device()).unwrap(), &[cast::<i32, T>(0).unwrap()]);
+
+        let mut c = SharedMemory::<T>::new(backend.device(), 4);
+        write_to_memory(c.get_mut(backend.device()).unwrap(), &[cast::<i32, T>(0).unwrap(); 4]);
+        (alpha, a, b, beta, c)
+    }
+
+    fn get_gemm_batched_memory<T: Float, B: IFramework + Clone>(backend: &Backend<B>) -> (SharedMemory<T>, SharedMemory<T>, SharedMemory<T>, SharedMemory<T>, SharedMemory<T>){
+        let (alpha, _, _, beta, _) = get_gemm_memory::<T, B>(backend, &[0; 6]);
+
+        let mut a = SharedMemory::<T>::new(backend.device(), 8);
+        let a_values: Vec<T> = [1, 2, 3, 4, 1, 0, 0, 1].iter().map(|v| cast::<i32, T>(*v).unwrap()).collect();
+        write_to_memory(a.get_mut(backend.device()).unwrap(), &a_values);
+
+        let mut b = SharedMemory::<T>::new(backend.device(), 8);
+        let b_values: Vec<T> = [5, 6, 7, 8, 2, 3, 4, 5].iter().map(|v| cast::<i32, T>(*v).unwrap()).collect();
+        write_to_memory(b.get_mut(backend.device()).unwrap(), &b_values);
+
+        let mut c = SharedMemory::<T>::new(backend.device(), 8);
+        write_to_memory(c.get_mut(backend.device()).unwrap(), &[cast::<i32, T>(0).unwrap(); 8]);
+        (alpha, a, b, beta, c)
+    }
+
     #[test]
     fn it_computes_correct_asum_on_native_for_f32() {
         let backend = get_native_backend();
@@ -118,6 +167,27 @@ mod blas_spec {
         backend.asum(&mut x, &mut result).unwrap();
     }
 
+    #[test]
+    fn it_computes_correct_iamax_on_native_for_f32() {
+        let backend = get_native_backend();
+        let mut x = SharedMemory::<f32>::new(backend.device(), 4);
+        write_to_memory(x.get_mut(backend.device()).unwrap(), &[1f32, -3f32, 3f32, 2f32]);
+        let mut result = SharedMemory::<i32>::new(backend.device(), 1);
+
+        backend.iamax(&mut x, &mut result).unwrap();
+        if let Some(mem) = result.get(backend.device()).unwrap().as_native() { assert_eq!(&[1i32], mem.as_slice::<i32>()) }
+    }
+
+    #[test]
+    fn it_computes_iamax_of_an_empty_vector_on_native() {
+        let backend = get_native_backend();
+        let mut x = SharedMemory::<f64>::new(backend.device(), 0);
+        let mut result = SharedMemory::<i32>::new(backend.device(), 1);
+
+        backend.iamax(&mut x, &mut result).unwrap();
+        if let Some(mem) = result.get(backend.device()).unwrap().as_native() { assert_eq!(&[-1i32], mem.as_slice::<i32>()) }
+    }
+
     #[test]
     fn it_computes_correct_axpy_on_native_for_f32() {
         let backend = get_native_backend();
@@ -140,6 +210,37 @@ mod blas_spec {
         backend.axpy(&mut a, &mut x, &mut y).unwrap();
     }
 
+    #[test]
+    fn it_rejects_axpy_with_different_lengths() {
+        let backend = get_native_backend();
+        let (mut a, mut x, _) = get_axpy_memory::<f32, Native>(&backend);
+        let mut y = SharedMemory::<f32>::new(backend.device(), 2);
+
+        assert!(backend.axpy(&mut a, &mut x, &mut y).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "opencl")]
+    fn it_rejects_axpy_and_scale_without_a_scalar_on_opencl() {
+        let native = get_native_backend();
+        let backend = get_opencl_backend();
+        let (_, mut x, mut y) = get_axpy_memory::<f32, Native>(&native);
+        let mut a = SharedMemory::<f32>::new(native.device(), 0);
+
+        assert!(backend.axpy(&mut a, &mut x, &mut y).is_err());
+        assert!(backend.scale(&mut a, &mut x).is_err());
+    }
+
+    #[test]
+    fn it_rejects_axpy_and_scale_without_a_scalar_on_native() {
+        let backend = get_native_backend();
+        let (_, mut x, mut y) = get_axpy_memory::<f32, Native>(&backend);
+        let mut a = SharedMemory::<f32>::new(backend.device(), 0);
+
+        assert!(backend.axpy(&mut a, &mut x, &mut y).is_err());
+        assert!(backend.scale(&mut a, &mut x).is_err());
+    }
+
     #[test]
     fn it_computes_correct_copy_on_native_for_f32() {
         let backend = get_native_backend();
@@ -151,6 +252,15 @@ mod blas_spec {
         backend.copy(&mut x, &mut y).unwrap();
     }
 
+    #[test]
+    fn it_rejects_copy_into_smaller_vector() {
+        let backend = get_native_backend();
+        let (mut x, _) = get_copy_memory::<f32, Native>(&backend);
+        let mut y = SharedMemory::<f32>::new(backend.device(), 2);
+
+        assert!(backend.copy(&mut x, &mut y).is_err());
+    }
+
     #[test]
     fn it_computes_correct_copy_on_native_for_f64() {
         let backend = get_native_backend();
@@ -173,6 +283,24 @@ mod blas_spec {
         backend.dot(&mut x, &mut y, &mut result).unwrap();
     }
 
+    #[test]
+    fn it_rejects_dot_with_different_lengths() {
+        let backend = get_native_backend();
+        let (mut x, _, mut result) = get_dot_memory::<f32, Native>(&backend);
+        let mut y = SharedMemory::<f32>::new(backend.device(), 2);
+
+        assert!(backend.dot(&mut x, &mut y, &mut result).is_err());
+    }
+
+    #[test]
+    fn it_rejects_dot_without_a_result_on_native() {
+        let backend = get_native_backend();
+        let (mut x, mut y, _) = get_dot_memory::<f32, Native>(&backend);
+        let mut result = SharedMemory::<f32>::new(backend.device(), 0);
+
+        assert!(backend.dot(&mut x, &mut y, &mut result).is_err());
+    }
+
     #[test]
     fn it_computes_correct_dot_on_native_for_f64() {
         let backend = get_native_backend();
@@ -208,6 +336,47 @@ mod blas_spec {
         backend.nrm2(&mut x, &mut result).unwrap();
     }
 
+    #[test]
+    fn it_computes_nrm2_without_overflow_on_native() {
+        let backend = get_native_backend();
+        let mut x = SharedMemory::<f32>::new(backend.device(), 2);
+        write_to_memory(x.get_mut(backend.device()).unwrap(), &[3e20f32, 4e20f32]);
+        let mut result = SharedMemory::<f32>::new(backend.device(), 1);
+
+        backend.nrm2(&mut x, &mut result).unwrap();
+        if let Some(mem) = result.get(backend.device()).unwrap().as_native() {
+            let nrm2 = mem.as_slice::<f32>()[0];
+            assert!(nrm2.is_finite());
+            assert!((nrm2 - 5e20f32).abs() / 5e20f32 < 1e-6);
+        }
+    }
+
+    #[test]
+    fn it_computes_level1_operations_on_long_vectors_on_native() {
+        let backend = get_native_backend();
+        let n = 3 * native_libraries::DEFAULT_THRESHOLD + 1;
+        let mut x = SharedMemory::<f64>::new(backend.device(), n);
+        write_to_memory(x.get_mut(backend.device()).unwrap(), &vec![-1f64; n]);
+        let mut y = SharedMemory::<f64>::new(backend.device(), n);
+        write_to_memory(y.get_mut(backend.device()).unwrap(), &vec![2f64; n]);
+        let mut a = SharedMemory::<f64>::new(backend.device(), 1);
+        write_to_memory(a.get_mut(backend.device()).unwrap(), &[3f64]);
+        let mut result = SharedMemory::<f64>::new(backend.device(), 1);
+
+        backend.asum(&mut x, &mut result).unwrap();
+        if let Some(mem) = result.get(backend.device()).unwrap().as_native() { assert_eq!(&[n as f64], mem.as_slice::<f64>()) }
+        backend.dot(&mut x, &mut y, &mut result).unwrap();
+        if let Some(mem) = result.get(backend.device()).unwrap().as_native() { assert_eq!(&[-2f64 * n as f64], mem.as_slice::<f64>()) }
+        backend.nrm2(&mut y, &mut result).unwrap();
+        if let Some(mem) = result.get(backend.device()).unwrap().as_native() {
+            assert!((mem.as_slice::<f64>()[0] - 2f64 * (n as f64).sqrt()).abs() < 1e-6);
+        }
+        backend.axpy(&mut a, &mut x, &mut y).unwrap();
+        if let Some(mem) = y.get(backend.device()).unwrap().as_native() { assert!(mem.as_slice::<f64>().iter().all(|yi| *yi == -1f64)) }
+        backend.scale(&mut a, &mut y).unwrap();
+        if let Some(mem) = y.get(backend.device()).unwrap().as_native() { assert!(mem.as_slice::<f64>().iter().all(|yi| *yi == -3f64)) }
+    }
+
     /// SCALE
 
     #[test]
@@ -221,6 +390,17 @@ mod blas_spec {
         backend.scale(&mut x, &mut y).unwrap();
     }
 
+    #[test]
+    fn it_computes_correct_scale_on_a_view_on_native() {
+        let backend = get_native_backend();
+        let (mut a, mut x) = get_scale_memory::<f32, Native>(&backend);
+        {
+            let mut tail = x.view(1, 2).unwrap();
+            backend.scale(&mut a, &mut tail).unwrap();
+        }
+        if let Some(mem) = x.get(backend.device()).unwrap().as_native() { assert_eq!(&[1f32, 4f32, 6f32], mem.as_slice::<f32>()) }
+    }
+
     #[test]
     fn it_computes_correct_scale_on_native_for_f64() {
         let backend = get_native_backend();
@@ -232,6 +412,16 @@ mod blas_spec {
         backend.scale(&mut x, &mut y).unwrap();
     }
 
+    #[test]
+    fn it_scales_empty_vectors_on_native() {
+        let backend = get_native_backend();
+        let mut a = SharedMemory::<f32>::new(backend.device(), 1);
+        write_to_memory(a.get_mut(backend.device()).unwrap(), &[2f32]);
+        let mut x = SharedMemory::<f32>::new(backend.device(), 0);
+
+        backend.scale(&mut a, &mut x).unwrap();
+    }
+
     /// SWAP
 
     #[test]
@@ -257,4 +447,518 @@ mod blas_spec {
         }
         backend.swap(&mut x, &mut y).unwrap();
     }
+
+    #[test]
+    fn it_rejects_swap_with_different_lengths() {
+        let backend = get_native_backend();
+        let (mut x, _) = get_swap_memory::<f32, Native>(&backend);
+        let mut y = SharedMemory::<f32>::new(backend.device(), 2);
+
+        assert!(backend.swap(&mut x, &mut y).is_err());
+    }
+
+    #[test]
+    fn it_supports_every_operation_on_native() {
+        let backend = get_native_backend();
+        assert!(backend.supports::<f32>(BlasOp::Gemm));
+        assert_eq!(BlasOp::all().len(), IBlas::<f64>::capabilities(&backend).len());
+    }
+
+    /// OPENCL
+
+    #[test]
+    #[cfg(feature = "opencl")]
+    fn it_reports_the_precision_coverage_of_opencl() {
+        let backend = get_opencl_backend();
+        let supports_fp64 = OpenCL::new().hardwares()[0].supports_fp64();
+        assert!(backend.supports::<f32>(BlasOp::Gemm));
+        assert_eq!(supports_fp64, backend.supports::<f64>(BlasOp::Gemm));
+        assert!(IBlas::<f64>::capabilities(&backend).contains(&BlasOp::Copy));
+    }
+
+    #[test]
+    #[cfg(feature = "opencl")]
+    fn it_computes_double_precision_ops_only_if_supported_on_opencl() {
+        let native = get_native_backend();
+        let backend = get_opencl_backend();
+        let (mut x, mut result) = get_asum_memory::<f64, Native>(&native);
+        if !backend.supports::<f64>(BlasOp::Asum) {
+            assert!(backend.asum(&mut x, &mut result).is_err());
+            let (mut a, mut x, mut y) = get_axpy_memory::<f64, Native>(&native);
+            assert!(backend.axpy(&mut a, &mut x, &mut y).is_err());
+            let mut x = SharedMemory::<f64>::new(native.device(), 3);
+            let mut index = SharedMemory::<i32>::new(native.device(), 1);
+            assert!(backend.iamax(&mut x, &mut index).is_err());
+            return
+        }
+        backend.asum(&mut x, &mut result).unwrap();
+        result.sync(native.device()).unwrap();
+        if let Some(mem) = result.get(native.device()).unwrap().as_native() { assert_eq!(&[6f64], mem.as_slice::<f64>()) }
+    }
+
+    #[test]
+    #[cfg(feature = "opencl")]
+    fn it_computes_correct_axpy_and_gemm_on_opencl_for_f64() {
+        let native = get_native_backend();
+        let backend = get_opencl_backend();
+        if !backend.supports::<f64>(BlasOp::Gemm) {
+            return
+        }
+        let (mut a, mut x, mut y) = get_axpy_memory::<f64, Native>(&native);
+        backend.axpy(&mut a, &mut x, &mut y).unwrap();
+        y.sync(native.device()).unwrap();
+        if let Some(mem) = y.get(native.device()).unwrap().as_native() { assert_eq!(&[3f64, 6f64, 9f64], mem.as_slice::<f64>()) }
+
+        let (mut alpha, mut a, mut b, mut beta, mut c) = get_gemm_batched_memory::<f64, Native>(&native);
+        backend.gemm_batched(&mut alpha, Transpose::NoTrans, &mut a, Transpose::NoTrans, &mut b, &mut beta, &mut c, 2, 2, 2, 2).unwrap();
+        c.sync(native.device()).unwrap();
+        if let Some(mem) = c.get(native.device()).unwrap().as_native() { assert_eq!(&[19f64, 22f64, 43f64, 50f64, 2f64, 3f64, 4f64, 5f64], mem.as_slice::<f64>()) }
+    }
+
+    #[test]
+    #[cfg(feature = "opencl")]
+    fn it_computes_correct_dot_on_opencl_for_f32() {
+        let native = get_native_backend();
+        let backend = get_opencl_backend();
+        let (mut x, mut y, mut result) = get_dot_memory::<f32, Native>(&native);
+
+        backend.dot(&mut x, &mut y, &mut result).unwrap();
+        result.sync(native.device()).unwrap();
+        if let Some(mem) = result.get(native.device()).unwrap().as_native() { assert_eq!(&[14f32], mem.as_slice::<f32>()) }
+    }
+
+    #[test]
+    #[cfg(feature = "opencl")]
+    fn it_computes_correct_iamax_on_opencl_for_f32() {
+        let native = get_native_backend();
+        let backend = get_opencl_backend();
+        let mut values = vec![1f32; 100];
+        values[70] = -5f32;
+        values[90] = 5f32;
+        let mut x = SharedMemory::<f32>::new(native.device(), 100);
+        write_to_memory(x.get_mut(native.device()).unwrap(), &values);
+        let mut result = SharedMemory::<i32>::new(native.device(), 1);
+
+        backend.iamax(&mut x, &mut result).unwrap();
+        result.sync(native.device()).unwrap();
+        if let Some(mem) = result.get(native.device()).unwrap().as_native() { assert_eq!(&[70i32], mem.as_slice::<i32>()) }
+    }
+
+    #[test]
+    #[cfg(feature = "opencl")]
+    fn it_computes_correct_dot_on_opencl_for_non_power_of_two_lengths() {
+        let native = get_native_backend();
+        let backend = get_opencl_backend();
+        let mut x = SharedMemory::<f32>::new(native.device(), 100);
+        write_to_memory(x.get_mut(native.device()).unwrap(), &vec![1f32; 100]);
+        let mut y = SharedMemory::<f32>::new(native.device(), 100);
+        write_to_memory(y.get_mut(native.device()).unwrap(), &vec![2f32; 100]);
+        let mut result = SharedMemory::<f32>::new(native.device(), 1);
+
+        backend.dot(&mut x, &mut y, &mut result).unwrap();
+        result.sync(native.device()).unwrap();
+        if let Some(mem) = result.get(native.device()).unwrap().as_native() { assert_eq!(&[200f32], mem.as_slice::<f32>()) }
+    }
+
+    /// GEMV
+
+    fn get_gemv_memory<T: Float, B: IFramework + Clone>(backend: &Backend<B>, x_data: &[i32], y_len: usize) -> (SharedMemory<T>, SharedMemory<T>, SharedMemory<T>, SharedMemory<T>, SharedMemory<T>){
+        let mut alpha = SharedMemory::<T>::new(backend.device(), 1);
+        write_to_memory(alpha.get_mut(backend.device()).unwrap(), &[cast::<i32, T>(1).unwrap()]);
+
+        let mut a = SharedMemory::<T>::new(backend.device(), 6);
+        let a_values: Vec<T> = (1..7).map(|v| cast::<i32, T>(v).unwrap()).collect();
+        write_to_memory(a.get_mut(backend.device()).unwrap(), &a_values);
+
+        let mut x = SharedMemory::<T>::new(backend.device(), x_data.len());
+        let x_values: Vec<T> = x_data.iter().map(|v| cast::<i32, T>(*v).unwrap()).collect();
+        write_to_memory(x.get_mut(backend.device()).unwrap(), &x_values);
+
+        let mut beta = SharedMemory::<T>::new(backend.device(), 1);
+        write_to_memory(beta.get_mut(backend.device()).unwrap(), &[cast::<i32, T>(1).unwrap()]);
+
+        let mut y = SharedMemory::<T>::new(backend.device(), y_len);
+        let y_values: Vec<T> = (0..y_len).map(|_| cast::<i32, T>(1).unwrap()).collect();
+        write_to_memory(y.get_mut(backend.device()).unwrap(), &y_values);
+        (alpha, a, x, beta, y)
+    }
+
+    #[test]
+    fn it_computes_correct_gemv_on_native_for_f32() {
+        let backend = get_native_backend();
+        let (mut alpha, mut a, mut x, mut beta, mut y) = get_gemv_memory::<f32, Native>(&backend, &[1, 1, 1], 2);
+
+        backend.gemv(Transpose::NoTrans, &mut alpha, &mut a, &mut x, &mut beta, &mut y, 2, 3).unwrap();
+        if let Some(mem) = y.get(backend.device()).unwrap().as_native() { assert_eq!(&[7f32, 16f32], mem.as_slice::<f32>()) }
+    }
+
+    #[test]
+    fn it_computes_correct_transposed_gemv_on_native_for_f64() {
+        let backend = get_native_backend();
+        let (mut alpha, mut a, mut x, mut beta, mut y) = get_gemv_memory::<f64, Native>(&backend, &[1, 1], 3);
+
+        backend.gemv(Transpose::Trans, &mut alpha, &mut a, &mut x, &mut beta, &mut y, 2, 3).unwrap();
+        if let Some(mem) = y.get(backend.device()).unwrap().as_native() { assert_eq!(&[6f64, 8f64, 10f64], mem.as_slice::<f64>()) }
+    }
+
+    #[test]
+    fn it_rejects_gemv_with_mismatching_dimensions() {
+        let backend = get_native_backend();
+        let (mut alpha, mut a, mut x, mut beta, mut y) = get_gemv_memory::<f32, Native>(&backend, &[1, 1], 2);
+
+        assert!(backend.gemv(Transpose::NoTrans, &mut alpha, &mut a, &mut x, &mut beta, &mut y, 2, 3).is_err());
+    }
+
+    /// I32
+
+    fn i32_memory(backend: &Backend<Native>, data: &[i32]) -> SharedMemory<i32> {
+        let mut mem = SharedMemory::<i32>::new(backend.device(), data.len());
+        write_to_memory(mem.get_mut(backend.device()).unwrap(), data);
+        mem
+    }
+
+    #[test]
+    fn it_computes_correct_integer_operations_on_native() {
+        let backend = get_native_backend();
+        let mut a = i32_memory(&backend, &[2]);
+        let mut x = i32_memory(&backend, &[1, 2, 3]);
+        let mut y = i32_memory(&backend, &[1, -2, 3]);
+
+        backend.axpy(&mut a, &mut x, &mut y).unwrap();
+        if let Some(mem) = y.get(backend.device()).unwrap().as_native() { assert_eq!(&[3, 2, 9], mem.as_slice::<i32>()) }
+        backend.swap(&mut x, &mut y).unwrap();
+        if let Some(mem) = x.get(backend.device()).unwrap().as_native() { assert_eq!(&[3, 2, 9], mem.as_slice::<i32>()) }
+        backend.copy(&mut y, &mut x).unwrap();
+        if let Some(mem) = x.get(backend.device()).unwrap().as_native() { assert_eq!(&[1, 2, 3], mem.as_slice::<i32>()) }
+        let mut result = i32_memory(&backend, &[0]);
+        assert!(backend.nrm2(&mut x, &mut result).is_err());
+    }
+
+    /// REDUCE
+
+    fn reduce_with<T: Float>(backend: &Backend<Native>, data: &[T], op: ReduceOp) -> T {
+        let mut x = SharedMemory::<T>::new(backend.device(), data.len());
+        write_to_memory(x.get_mut(backend.device()).unwrap(), data);
+        let mut result = SharedMemory::<T>::new(backend.device(), 1);
+
+        backend.reduce(&mut x, &mut result, op).unwrap();
+        let value = result.get(backend.device()).unwrap().as_native().unwrap().as_slice::<T>()[0];
+        value
+    }
+
+    #[test]
+    fn it_computes_correct_reductions_on_native() {
+        let backend = get_native_backend();
+        let data = [2f32, -3f32, 4f32];
+
+        assert_eq!(3f32, reduce_with(&backend, &data, ReduceOp::Sum));
+        assert_eq!(4f32, reduce_with(&backend, &data, ReduceOp::Max));
+        assert_eq!(-3f32, reduce_with(&backend, &data, ReduceOp::Min));
+        assert_eq!(-24f64, reduce_with(&backend, &[2f64, -3f64, 4f64], ReduceOp::Product));
+    }
+
+    #[test]
+    fn it_reduces_empty_vectors_to_the_identity_on_native() {
+        let backend = get_native_backend();
+
+        assert_eq!(0f32, reduce_with::<f32>(&backend, &[], ReduceOp::Sum));
+        assert_eq!(f32::neg_infinity(), reduce_with::<f32>(&backend, &[], ReduceOp::Max));
+        assert_eq!(f32::infinity(), reduce_with::<f32>(&backend, &[], ReduceOp::Min));
+        assert_eq!(1f32, reduce_with::<f32>(&backend, &[], ReduceOp::Product));
+    }
+
+    #[test]
+    #[cfg(feature = "opencl")]
+    fn it_computes_correct_reductions_on_opencl_for_f32() {
+        let native = get_native_backend();
+        let backend = get_opencl_backend();
+        let mut x = SharedMemory::<f32>::new(native.device(), 100);
+        let data: Vec<f32> = (1..101).map(|v| v as f32).collect();
+        write_to_memory(x.get_mut(native.device()).unwrap(), &data);
+        let mut result = SharedMemory::<f32>::new(native.device(), 1);
+
+        for &(op, expected) in &[(ReduceOp::Sum, 5050f32), (ReduceOp::Max, 100f32), (ReduceOp::Min, 1f32)] {
+            backend.reduce(&mut x, &mut result, op).unwrap();
+            result.sync(native.device()).unwrap();
+            if let Some(mem) = result.get(native.device()).unwrap().as_native() { assert_eq!(&[expected], mem.as_slice::<f32>()) }
+        }
+    }
+
+    /// MAP
+
+    fn map_with<T: Float>(backend: &Backend<Native>, data: &[T], func: UnaryFn) -> Vec<T> {
+        let mut x = SharedMemory::<T>::new(backend.device(), data.len());
+        write_to_memory(x.get_mut(backend.device()).unwrap(), data);
+        let mut y = SharedMemory::<T>::new(backend.device(), data.len());
+
+        backend.map(&mut x, &mut y, func).unwrap();
+        let values = y.get(backend.device()).unwrap().as_native().unwrap().as_slice::<T>().to_vec();
+        values
+    }
+
+    #[test]
+    fn it_computes_correct_map_on_native() {
+        let backend = get_native_backend();
+        let data = [-1f32, 0f32, 4f32];
+
+        assert_eq!(vec![1f32, 0f32, 4f32], map_with(&backend, &data, UnaryFn::Abs));
+        assert_eq!(vec![0f32, 0f32, 4f32], map_with(&backend, &data, UnaryFn::Relu));
+        assert_eq!(vec![1f64, 2f64], map_with(&backend, &[1f64, 4f64], UnaryFn::Sqrt));
+        assert_eq!(vec![0.5f32], map_with(&backend, &[0f32], UnaryFn::Sigmoid));
+        assert_eq!(vec![1f32], map_with(&backend, &[0f32], UnaryFn::Exp));
+        assert_eq!(vec![0f32], map_with(&backend, &[0f32], UnaryFn::Tanh));
+    }
+
+    #[test]
+    fn it_follows_libm_outside_of_the_domain_on_native() {
+        let backend = get_native_backend();
+        let logs = map_with(&backend, &[-1f32, 0f32], UnaryFn::Log);
+
+        assert!(logs[0].is_nan());
+        assert_eq!(f32::neg_infinity(), logs[1]);
+        assert!(map_with(&backend, &[-1f32], UnaryFn::Sqrt)[0].is_nan());
+    }
+
+    #[test]
+    fn it_rejects_map_with_different_lengths() {
+        let backend = get_native_backend();
+        let mut x = SharedMemory::<f32>::new(backend.device(), 3);
+        let mut y = SharedMemory::<f32>::new(backend.device(), 2);
+
+        assert!(backend.map(&mut x, &mut y, UnaryFn::Exp).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "opencl")]
+    fn it_computes_correct_map_on_opencl_for_f32() {
+        let native = get_native_backend();
+        let backend = get_opencl_backend();
+        let mut x = SharedMemory::<f32>::new(native.device(), 3);
+        write_to_memory(x.get_mut(native.device()).unwrap(), &[-1f32, 0f32, 4f32]);
+        let mut y = SharedMemory::<f32>::new(native.device(), 3);
+
+        backend.map(&mut x, &mut y, UnaryFn::Relu).unwrap();
+        y.sync(native.device()).unwrap();
+        if let Some(mem) = y.get(native.device()).unwrap().as_native() { assert_eq!(&[0f32, 0f32, 4f32], mem.as_slice::<f32>()) }
+    }
+
+    /// ELEMENTWISE
+
+    fn elementwise_with<T: Float>(backend: &Backend<Native>, a_data: &[T], b_data: &[T], op: BinaryOp) -> Vec<T> {
+        let mut a = SharedMemory::<T>::new(backend.device(), a_data.len());
+        write_to_memory(a.get_mut(backend.device()).unwrap(), a_data);
+        let mut b = SharedMemory::<T>::new(backend.device(), b_data.len());
+        write_to_memory(b.get_mut(backend.device()).unwrap(), b_data);
+        let mut c = SharedMemory::<T>::new(backend.device(), a_data.len());
+
+        backend.elementwise(&mut a, &mut b, &mut c, op).unwrap();
+        let values = c.get(backend.device()).unwrap().as_native().unwrap().as_slice::<T>().to_vec();
+        values
+    }
+
+    #[test]
+    fn it_computes_correct_elementwise_operations_on_native() {
+        let backend = get_native_backend();
+        let a = [6f32, 4f32];
+        let b = [2f32, 8f32];
+
+        assert_eq!(vec![8f32, 12f32], elementwise_with(&backend, &a, &b, BinaryOp::Add));
+        assert_eq!(vec![4f32, -4f32], elementwise_with(&backend, &a, &b, BinaryOp::Sub));
+        assert_eq!(vec![12f64, 32f64], elementwise_with(&backend, &[6f64, 4f64], &[2f64, 8f64], BinaryOp::Mul));
+        assert_eq!(vec![3f32, 0.5f32], elementwise_with(&backend, &a, &b, BinaryOp::Div));
+    }
+
+    #[test]
+    fn it_divides_by_zero_following_ieee_on_native() {
+        let backend = get_native_backend();
+        let c = elementwise_with(&backend, &[1f32, 0f32], &[0f32, 0f32], BinaryOp::Div);
+
+        assert_eq!(f32::infinity(), c[0]);
+        assert!(c[1].is_nan());
+    }
+
+    #[test]
+    fn it_rejects_elementwise_operations_with_different_lengths() {
+        let backend = get_native_backend();
+        let mut a = SharedMemory::<f32>::new(backend.device(), 3);
+        let mut b = SharedMemory::<f32>::new(backend.device(), 3);
+        let mut c = SharedMemory::<f32>::new(backend.device(), 2);
+
+        assert!(backend.elementwise(&mut a, &mut b, &mut c, BinaryOp::Add).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "opencl")]
+    fn it_computes_correct_elementwise_operations_on_opencl_for_f32() {
+        let native = get_native_backend();
+        let backend = get_opencl_backend();
+        let mut a = SharedMemory::<f32>::new(native.device(), 2);
+        write_to_memory(a.get_mut(native.device()).unwrap(), &[6f32, 4f32]);
+        let mut b = SharedMemory::<f32>::new(native.device(), 2);
+        write_to_memory(b.get_mut(native.device()).unwrap(), &[2f32, 8f32]);
+        let mut c = SharedMemory::<f32>::new(native.device(), 2);
+
+        backend.elementwise(&mut a, &mut b, &mut c, BinaryOp::Mul).unwrap();
+        c.sync(native.device()).unwrap();
+        if let Some(mem) = c.get(native.device()).unwrap().as_native() { assert_eq!(&[12f32, 32f32], mem.as_slice::<f32>()) }
+    }
+
+    /// GEMM
+
+    #[test]
+    fn it_computes_correct_gemm_on_native_for_f32() {
+        let backend = get_native_backend();
+        let (mut alpha, mut a, mut b, mut beta, mut c) = get_gemm_memory::<f32, Native>(&backend, &[1, 2, 3, 4, 5, 6]);
+
+        backend.gemm(&mut alpha, Transpose::NoTrans, &mut a, Transpose::NoTrans, &mut b, &mut beta, &mut c, 2, 2, 3).unwrap();
+        if let Some(mem) = c.get(backend.device()).unwrap().as_native() { assert_eq!(&[58f32, 64f32, 139f32, 154f32], mem.as_slice::<f32>()) }
+    }
+
+    #[test]
+    fn it_computes_correct_gemm_with_transposed_a_on_native_for_f64() {
+        let backend = get_native_backend();
+        let (mut alpha, mut a, mut b, mut beta, mut c) = get_gemm_memory::<f64, Native>(&backend, &[1, 4, 2, 5, 3, 6]);
+
+        backend.gemm(&mut alpha, Transpose::Trans, &mut a, Transpose::NoTrans, &mut b, &mut beta, &mut c, 2, 2, 3).unwrap();
+        if let Some(mem) = c.get(backend.device()).unwrap().as_native() { assert_eq!(&[58f64, 64f64, 139f64, 154f64], mem.as_slice::<f64>()) }
+    }
+
+    #[test]
+    fn it_rejects_gemm_with_mismatching_dimensions() {
+        let backend = get_native_backend();
+        let (mut alpha, mut a, mut b, mut beta, mut c) = get_gemm_memory::<f32, Native>(&backend, &[1, 2, 3, 4, 5, 6]);
+
+        assert!(backend.gemm(&mut alpha, Transpose::NoTrans, &mut a, Transpose::NoTrans, &mut b, &mut beta, &mut c, 3, 2, 3).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "opencl")]
+    fn it_rejects_gemv_and_gemm_without_scalars_on_opencl() {
+        let native = get_native_backend();
+        let backend = get_opencl_backend();
+        let mut empty = SharedMemory::<f32>::new(native.device(), 0);
+
+        let (mut alpha, mut a, mut x, mut beta, mut y) = get_gemv_memory::<f32, Native>(&native, &[1, 1, 1], 2);
+        assert!(backend.gemv(Transpose::NoTrans, &mut empty, &mut a, &mut x, &mut beta, &mut y, 2, 3).is_err());
+        assert!(backend.gemv(Transpose::NoTrans, &mut alpha, &mut a, &mut x, &mut empty, &mut y, 2, 3).is_err());
+
+        let (mut alpha, mut a, mut b, mut beta, mut c) = get_gemm_memory::<f32, Native>(&native, &[1, 2, 3, 4, 5, 6]);
+        assert!(backend.gemm(&mut empty, Transpose::NoTrans, &mut a, Transpose::NoTrans, &mut b, &mut beta, &mut c, 2, 2, 3).is_err());
+        assert!(backend.gemm(&mut alpha, Transpose::NoTrans, &mut a, Transpose::NoTrans, &mut b, &mut empty, &mut c, 2, 2, 3).is_err());
+    }
+
+    #[test]
+    fn it_rejects_gemv_and_gemm_without_scalars_on_native() {
+        let backend = get_native_backend();
+        let mut empty = SharedMemory::<f32>::new(backend.device(), 0);
+
+        let (mut alpha, mut a, mut x, mut beta, mut y) = get_gemv_memory::<f32, Native>(&backend, &[1, 1, 1], 2);
+        assert!(backend.gemv(Transpose::NoTrans, &mut empty, &mut a, &mut x, &mut beta, &mut y, 2, 3).is_err());
+        assert!(backend.gemv(Transpose::NoTrans, &mut alpha, &mut a, &mut x, &mut empty, &mut y, 2, 3).is_err());
+
+        let (mut alpha, mut a, mut b, mut beta, mut c) = get_gemm_memory::<f32, Native>(&backend, &[1, 2, 3, 4, 5, 6]);
+        assert!(backend.gemm(&mut empty, Transpose::NoTrans, &mut a, Transpose::NoTrans, &mut b, &mut beta, &mut c, 2, 2, 3).is_err());
+        assert!(backend.gemm(&mut alpha, Transpose::NoTrans, &mut a, Transpose::NoTrans, &mut b, &mut empty, &mut c, 2, 2, 3).is_err());
+    }
+
+    #[test]
+    fn it_names_the_operation_and_its_operands_in_errors() {
+        let backend = get_native_backend();
+        let (mut alpha, mut a, mut b, mut beta, mut c) = get_gemm_memory::<f32, Native>(&backend, &[1, 2, 3, 4, 5, 6]);
+
+        match backend.gemm(&mut alpha, Transpose::NoTrans, &mut a, Transpose::NoTrans, &mut b, &mut beta, &mut c, 3, 2, 3) {
+            Err(co::error::Error::Operation(co::libraries::Error::Blas(Error::Operation { op, detail, .. }))) => {
+                assert_eq!("gemm", op);
+                assert!(detail.contains("`a` holds 6 elements"));
+                assert!(detail.contains("m = 3, n = 2 and k = 3"));
+            },
+            other => panic!("Expected a gemm error with context, got {:?}", other)
+        }
+    }
+
+    #[test]
+    fn it_computes_correct_gemm_batched_on_native_for_f32() {
+        let backend = get_native_backend();
+        let (mut alpha, mut a, mut b, mut beta, mut c) = get_gemm_batched_memory::<f32, Native>(&backend);
+
+        backend.gemm_batched(&mut alpha, Transpose::NoTrans, &mut a, Transpose::NoTrans, &mut b, &mut beta, &mut c, 2, 2, 2, 2).unwrap();
+        if let Some(mem) = c.get(backend.device()).unwrap().as_native() { assert_eq!(&[19f32, 22f32, 43f32, 50f32, 2f32, 3f32, 4f32, 5f32], mem.as_slice::<f32>()) }
+    }
+
+    #[test]
+    #[cfg(feature = "opencl")]
+    fn it_computes_correct_gemm_batched_on_opencl_for_f32() {
+        let native = get_native_backend();
+        let backend = get_opencl_backend();
+        let (mut alpha, mut a, mut b, mut beta, mut c) = get_gemm_batched_memory::<f32, Native>(&native);
+
+        backend.gemm_batched(&mut alpha, Transpose::NoTrans, &mut a, Transpose::NoTrans, &mut b, &mut beta, &mut c, 2, 2, 2, 2).unwrap();
+        c.sync(native.device()).unwrap();
+        if let Some(mem) = c.get(native.device()).unwrap().as_native() { assert_eq!(&[19f32, 22f32, 43f32, 50f32, 2f32, 3f32, 4f32, 5f32], mem.as_slice::<f32>()) }
+    }
+
+    #[test]
+    fn it_rejects_gemm_batched_with_mismatching_batch_count() {
+        let backend = get_native_backend();
+        let (mut alpha, mut a, mut b, mut beta, mut c) = get_gemm_batched_memory::<f32, Native>(&backend);
+
+        assert!(backend.gemm_batched(&mut alpha, Transpose::NoTrans, &mut a, Transpose::NoTrans, &mut b, &mut beta, &mut c, 2, 2, 2, 3).is_err());
+    }
+
+    /// F16
+
+    #[cfg(feature = "f16")]
+    fn half_memory(backend: &Backend<Native>, data: &[f32]) -> SharedMemory<f16> {
+        let mut mem = SharedMemory::<f16>::new(backend.device(), data.len());
+        let halfs: Vec<f16> = data.iter().map(|datum| f16::from_f32(*datum)).collect();
+        write_to_memory(mem.get_mut(backend.device()).unwrap(), &halfs);
+        mem
+    }
+
+    #[cfg(feature = "f16")]
+    fn read_half_memory(backend: &Backend<Native>, mem: &SharedMemory<f16>) -> Vec<f32> {
+        mem.get(backend.device()).unwrap().as_native().unwrap().as_slice::<f16>().iter().map(|datum| datum.to_f32()).collect()
+    }
+
+    #[test]
+    #[cfg(feature = "f16")]
+    fn it_computes_correct_half_precision_operations_on_native() {
+        let backend = get_native_backend();
+        let mut a = half_memory(&backend, &[2f32]);
+        let mut x = half_memory(&backend, &[1f32, 2f32, 3f32]);
+        let mut y = half_memory(&backend, &[1f32, 2f32, 3f32]);
+        assert_eq!(6, y.get(backend.device()).unwrap().size());
+
+        backend.axpy_f16(&mut a, &mut x, &mut y).unwrap();
+        assert_eq!(vec![3f32, 6f32, 9f32], read_half_memory(&backend, &y));
+        backend.scale_f16(&mut a, &mut y).unwrap();
+        assert_eq!(vec![6f32, 12f32, 18f32], read_half_memory(&backend, &y));
+        backend.copy_f16(&mut x, &mut y).unwrap();
+        assert_eq!(vec![1f32, 2f32, 3f32], read_half_memory(&backend, &y));
+    }
+
+    #[test]
+    #[cfg(all(feature = "f16", feature = "opencl"))]
+    fn it_computes_half_precision_operations_only_if_supported_on_opencl() {
+        let native = get_native_backend();
+        let backend = get_opencl_backend();
+        let mut a = half_memory(&native, &[2f32]);
+        let mut x = half_memory(&native, &[1f32, 2f32, 3f32]);
+        let mut y = half_memory(&native, &[1f32, 2f32, 3f32]);
+        if !OpenCL::new().hardwares()[0].supports_fp16() {
+            assert!(backend.axpy_f16(&mut a, &mut x, &mut y).is_err());
+            assert!(backend.scale_f16(&mut a, &mut y).is_err());
+            return
+        }
+
+        backend.axpy_f16(&mut a, &mut x, &mut y).unwrap();
+        y.sync(native.device()).unwrap();
+        assert_eq!(vec![3f32, 6f32, 9f32], read_half_memory(&native, &y));
+        backend.scale_f16(&mut a, &mut y).unwrap();
+        y.sync(native.device()).unwrap();
+        assert_eq!(vec![6f32, 12f32, 18f32], read_half_memory(&native, &y));
+        backend.copy_f16(&mut x, &mut y).unwrap();
+        y.sync(native.device()).unwrap();
+        assert_eq!(vec![1f32, 2f32, 3f32], read_half_memory(&native, &y));
+    }
 }
@@ -0,0 +1,67 @@
+extern crate collenchyma as co;
+
+#[cfg(test)]
+mod error_spec {
+
+    use co::error::Error;
+    use co::framework::Error as FrameworkError;
+    use co::frameworks::native::Error as NativeError;
+    #[cfg(feature = "opencl")]
+    use co::frameworks::opencl::Error as OpenCLError;
+    use co::shared_memory::Error as SharedMemoryError;
+    use co::libraries::Error as LibraryError;
+    use co::libraries::blas::Error as BlasError;
+    use std::error::Error as StdError;
+
+    #[test]
+    fn it_chains_the_framework_error_as_source() {
+        let err: Error = From::from(FrameworkError::NotSupported(format!("test")));
+        let source = err.source().unwrap();
+        match source.downcast_ref::<FrameworkError>() {
+            Some(&FrameworkError::NotSupported(_)) => {},
+            _ => panic!("expected the source to be a framework error"),
+        }
+    }
+
+    #[test]
+    fn it_walks_the_source_chain_to_the_native_error() {
+        let err: Error = From::from(FrameworkError::from(NativeError::Other(format!("test"))));
+        let framework_err = err.source().unwrap();
+        let native_err = framework_err.source().unwrap();
+        assert!(native_err.downcast_ref::<NativeError>().is_some());
+    }
+
+    #[test]
+    #[cfg(feature = "opencl")]
+    fn it_walks_the_source_chain_through_shared_memory_to_the_opencl_error() {
+        let sync_err = SharedMemoryError::MemorySynchronizationFailed(FrameworkError::from(OpenCLError::InvalidValue(format!("test"))));
+        let err: Error = From::from(sync_err);
+        let shared_memory_err = err.source().unwrap();
+        match shared_memory_err.downcast_ref::<SharedMemoryError>() {
+            Some(&SharedMemoryError::MemorySynchronizationFailed(_)) => {},
+            _ => panic!("expected the source to be a shared memory error"),
+        }
+        let framework_err = shared_memory_err.source().unwrap();
+        match framework_err.downcast_ref::<FrameworkError>() {
+            Some(&FrameworkError::OpenCL(_)) => {},
+            _ => panic!("expected the source to be an OpenCL framework error"),
+        }
+        assert!(framework_err.source().unwrap().downcast_ref::<OpenCLError>().is_some());
+    }
+
+    #[test]
+    fn it_walks_the_source_chain_through_an_operation_to_the_blas_error() {
+        let blas_err = BlasError::InvalidArgument(format!("test")).with_context("dot", format!("x: 1, y: 2"));
+        let err: Error = From::from(LibraryError::from(blas_err));
+        let library_err = err.source().unwrap();
+        let operation_err = library_err.source().unwrap();
+        match operation_err.downcast_ref::<BlasError>() {
+            Some(&BlasError::Operation { .. }) => {},
+            _ => panic!("expected the source to be a BLAS operation error"),
+        }
+        match operation_err.source().unwrap().downcast_ref::<BlasError>() {
+            Some(&BlasError::InvalidArgument(_)) => {},
+            _ => panic!("expected the operation to wrap the invalid argument"),
+        }
+    }
+}
@@ -0,0 +1,273 @@
+extern crate collenchyma as co;
+extern crate libc;
+
+#[cfg(all(test, any(feature = "cuda", feature = "cuda-runtime")))]
+mod framework_cuda_spec {
+
+    use co::backend::{Backend, BackendConfig};
+    use co::framework::IFramework;
+    use co::frameworks::Cuda;
+    use co::hardware::IHardware;
+    use co::frameworks::cuda::{API, Memory, Context, ContextOwnership};
+    use co::frameworks::cuda::context::{CTX_SCHED_BLOCKING_SYNC, CTX_SCHED_SPIN, CTX_MAP_HOST};
+    use co::device::DeviceType;
+    use co::framework::Error as FrameworkError;
+    use co::frameworks::cuda::Error as CudaError;
+
+    #[test]
+    fn it_works() {
+        let frm = Cuda::new();
+        println!("{:?}", frm.hardwares());
+    }
+
+    #[test]
+    fn it_returns_hardwares_in_a_stable_order() {
+        let frm = Cuda::new();
+        let ids: Vec<isize> = frm.hardwares().iter().map(|hardware| hardware.id()).collect();
+        let ids_again: Vec<isize> = frm.hardwares().iter().map(|hardware| hardware.id()).collect();
+        assert_eq!(ids, ids_again);
+    }
+
+    #[test]
+    fn it_enumerates_every_device_by_ordinal() {
+        let frm = Cuda::new();
+        let count = API::device_count().unwrap();
+        assert_eq!(frm.hardwares().len(), count);
+        for (ordinal, hardware) in frm.hardwares().iter().enumerate() {
+            assert_eq!(hardware.id(), API::get_device(ordinal).unwrap().id());
+        }
+        assert!(API::get_device(count).is_err());
+    }
+
+    #[test]
+    fn it_reports_the_driver_version() {
+        let version = API::driver_version().unwrap();
+        assert!(version >= 1000);
+    }
+
+    #[test]
+    fn it_creates_backends_with_context_flags() {
+        let backend = Backend::<Cuda>::with_context_flags(BackendConfig::all(Cuda::new()), CTX_SCHED_BLOCKING_SYNC | CTX_MAP_HOST).unwrap();
+        assert!(backend.synchronize().is_ok());
+        match Backend::<Cuda>::with_context_flags(BackendConfig::all(Cuda::new()), CTX_SCHED_BLOCKING_SYNC | CTX_SCHED_SPIN) {
+            Err(::co::error::Error::Framework(FrameworkError::Cuda(CudaError::InvalidValue(_)))) => {},
+            other => panic!("expected an invalid value error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn it_creates_a_backend_with_the_primary_context() {
+        let frm = Cuda::new();
+        let context = Context::from_primary(frm.hardwares()[0].clone()).unwrap();
+        assert_eq!(ContextOwnership::Primary, context.ownership());
+        let backend = Backend::<Cuda>::new_with_context(frm, context).unwrap();
+        assert!(backend.synchronize().is_ok());
+    }
+
+    #[test]
+    #[cfg(feature = "cuda")]
+    fn it_adopts_an_existing_context_without_destroying_it() {
+        let frm = Cuda::new();
+        let owner = Context::new(frm.hardwares()[0].clone()).unwrap();
+        let borrowed = unsafe { Context::from_raw(owner.id_c(), frm.hardwares()[0].clone(), false) }.unwrap();
+        drop(borrowed);
+        assert!(API::synchronize_context(&owner).is_ok());
+    }
+
+    #[test]
+    fn it_creates_and_drops_many_contexts() {
+        let frm = Cuda::new();
+        for _ in 0..256 {
+            let hardwares = frm.hardwares()[0..1].to_vec();
+            let backend_config = BackendConfig::new(frm.clone(), hardwares);
+            let backend = Backend::new(backend_config);
+            assert!(backend.is_ok());
+        }
+    }
+
+    #[test]
+    fn it_refuses_to_create_a_device_without_hardware() {
+        let frm = Cuda::new();
+        match frm.new_device(vec![]) {
+            Err(FrameworkError::MissingHardware(_)) => {},
+            other => panic!("expected missing hardware, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn it_reports_memory_info() {
+        let frm = Cuda::new();
+        let hardwares = frm.hardwares()[0..1].to_vec();
+        if let DeviceType::Cuda(ctx) = frm.new_device(hardwares).unwrap() {
+            let (free, total) = API::memory_info(&ctx).unwrap();
+            assert!(total > 0);
+            assert!(free <= total);
+        }
+    }
+
+    #[test]
+    fn it_shares_memory_between_clones() {
+        let frm = Cuda::new();
+        let hardwares = frm.hardwares()[0..1].to_vec();
+        if let DeviceType::Cuda(ctx) = frm.new_device(hardwares).unwrap() {
+            let mem = Memory::new(&ctx, 1024).unwrap();
+            let mem_clone = mem.clone();
+            assert_eq!(mem.id_c(), mem_clone.id_c());
+            assert_eq!(2, mem.ref_count());
+            drop(mem_clone);
+            assert_eq!(1, mem.ref_count());
+        }
+    }
+
+    #[test]
+    fn it_allocates_aligned_memory() {
+        let frm = Cuda::new();
+        let device = frm.new_device(frm.hardwares()[0..1].to_vec()).unwrap();
+        for &align in &[64, 256, 4096] {
+            let memory = device.alloc_memory_aligned(100, align).unwrap();
+            assert_eq!(100, memory.size());
+            assert_eq!(0, memory.as_cuda().unwrap().id_c() as usize % align);
+        }
+        assert!(device.alloc_memory_aligned(100, 24).is_err());
+    }
+
+    #[test]
+    fn it_frees_memory_without_a_current_context() {
+        let frm = Cuda::new();
+        let hardwares = frm.hardwares()[0..1].to_vec();
+        if let DeviceType::Cuda(ctx) = frm.new_device(hardwares).unwrap() {
+            let size = 64 * 1024 * 1024;
+            let (free_before, _) = API::memory_info(&ctx).unwrap();
+            let mem = Memory::new(&ctx, size).unwrap();
+            // no context is current to the thread, when the memory is dropped
+            drop(mem);
+            let (free_after, _) = API::memory_info(&ctx).unwrap();
+            assert!(free_after + size / 2 > free_before);
+        }
+    }
+
+    #[test]
+    fn it_allocates_pinned_host_memory() {
+        let frm = Cuda::new();
+        let hardwares = frm.hardwares()[0..1].to_vec();
+        if let DeviceType::Cuda(ctx) = frm.new_device(hardwares).unwrap() {
+            let mut mem = API::allocate_host_pinned(&ctx, 16).unwrap();
+            assert_eq!(16, mem.len());
+            mem[0] = 42;
+            assert_eq!(42, mem[0]);
+        }
+    }
+
+    #[test]
+    fn it_allocates_mapped_host_memory_if_supported() {
+        let frm = Cuda::new();
+        let hardwares = frm.hardwares()[0..1].to_vec();
+        if let DeviceType::Cuda(ctx) = frm.new_device(hardwares).unwrap() {
+            match API::allocate_mapped(&ctx, 16) {
+                Ok(mem) => {
+                    assert_eq!(16, mem.len());
+                    assert!(mem.device_ptr() != 0);
+                },
+                Err(CudaError::NotSupported(_)) => {},
+                Err(err) => panic!("unexpected error: {}", err),
+            }
+        }
+    }
+
+    #[test]
+    fn it_converts_cuda_errors_into_framework_errors() {
+        let err: FrameworkError = From::from(CudaError::Other(format!("test")));
+        match err {
+            FrameworkError::Cuda(_) => {},
+            _ => panic!("expected a Cuda framework error"),
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "cuda")]
+    fn it_loads_modules_from_ptx() {
+        let frm = Cuda::new();
+        let hardwares = frm.hardwares()[0..1].to_vec();
+        if let DeviceType::Cuda(ctx) = frm.new_device(hardwares).unwrap() {
+            let ptx = ".version 3.0\n.target sm_20\n.address_size 64\n.visible .entry noop() { ret; }\n";
+            let module = API::load_module_from_ptx(&ctx, ptx).unwrap();
+            let function = module.get_function("noop").unwrap();
+            assert!(API::launch_kernel(&ctx, &function, (1, 1, 1), (1, 1, 1), 0, None, &mut []).is_ok());
+            match module.get_function("missing") {
+                Err(CudaError::NotFound(_)) => {},
+                _ => panic!("expected a NotFound error"),
+            }
+            assert!(API::unload_module(&ctx, module).is_ok());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "cuda")]
+    fn it_returns_the_jit_log_for_invalid_ptx() {
+        let frm = Cuda::new();
+        let hardwares = frm.hardwares()[0..1].to_vec();
+        if let DeviceType::Cuda(ctx) = frm.new_device(hardwares).unwrap() {
+            match API::load_module_from_ptx(&ctx, ".version 3.0\n.target sm_20\nbroken") {
+                Err(CudaError::InvalidPtx(log)) => assert!(log.len() > 0),
+                _ => panic!("expected an InvalidPtx error"),
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "cuda-runtime")]
+    fn it_refuses_to_load_modules_with_the_runtime_api() {
+        let frm = Cuda::new();
+        let hardwares = frm.hardwares()[0..1].to_vec();
+        if let DeviceType::Cuda(ctx) = frm.new_device(hardwares).unwrap() {
+            match API::load_module_from_ptx(&ctx, ".version 3.0\n.target sm_20\n") {
+                Err(CudaError::NotSupported(_)) => {},
+                _ => panic!("expected a NotSupported error"),
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "cuda-runtime")]
+    fn it_shares_the_primary_context_between_contexts_of_a_device() {
+        let frm = Cuda::new();
+        let ctx_a = Context::new(frm.hardwares()[0].clone()).unwrap();
+        let ctx_b = Context::from_primary(frm.hardwares()[0].clone()).unwrap();
+        let mut mem = Memory::new(&ctx_a, 4).unwrap();
+        API::write_to_memory(&ctx_b, &mut mem, [3u8; 4].as_ptr() as *const libc::c_void, 4).unwrap();
+        drop(ctx_b);
+        let mut host = [0u8; 4];
+        API::read_from_memory(&ctx_a, &mem, host.as_mut_ptr() as *mut libc::c_void, 4).unwrap();
+        assert_eq!([3u8; 4], host);
+    }
+
+    #[test]
+    fn it_interleaves_calls_on_two_contexts() {
+        let frm = Cuda::new();
+        let hardwares = frm.hardwares()[0..1].to_vec();
+        if let (DeviceType::Cuda(ctx_a), DeviceType::Cuda(ctx_b)) = (frm.new_device(hardwares.clone()).unwrap(), frm.new_device(hardwares).unwrap()) {
+            let mut mem_a = Memory::new(&ctx_a, 4).unwrap();
+            let mut mem_b = Memory::new(&ctx_b, 4).unwrap();
+            API::write_to_memory(&ctx_a, &mut mem_a, [1u8; 4].as_ptr() as *const libc::c_void, 4).unwrap();
+            API::write_to_memory(&ctx_b, &mut mem_b, [2u8; 4].as_ptr() as *const libc::c_void, 4).unwrap();
+            let mut host = [0u8; 4];
+            API::read_from_memory(&ctx_a, &mem_a, host.as_mut_ptr() as *mut libc::c_void, 4).unwrap();
+            assert_eq!([1u8; 4], host);
+            {
+                let _guard = API::make_current(&ctx_b).unwrap();
+                API::read_from_memory(&ctx_a, &mem_a, host.as_mut_ptr() as *mut libc::c_void, 4).unwrap();
+                assert_eq!([1u8; 4], host);
+            }
+            API::read_from_memory(&ctx_b, &mem_b, host.as_mut_ptr() as *mut libc::c_void, 4).unwrap();
+            assert_eq!([2u8; 4], host);
+        }
+    }
+
+    #[test]
+    fn it_reports_the_status_code_of_errors() {
+        let err = CudaError::InvalidContext(format!("test"));
+        assert_eq!(Some(201), err.code());
+        assert_eq!("CUDA_ERROR_INVALID_CONTEXT (201): test", format!("{}", err));
+        assert_eq!(None, CudaError::Other(format!("test")).code());
+    }
+}
@@ -0,0 +1,63 @@
+extern crate collenchyma as co;
+
+#[cfg(all(test, feature = "test-util"))]
+mod framework_mock_spec {
+
+    use co::backend::{Backend, BackendConfig};
+    use co::framework::IFramework;
+    use co::frameworks::Mock;
+    use co::libraries::blas::*;
+    use co::memory::MemoryType;
+    use co::shared_memory::SharedMemory;
+
+    fn filled(backend: &Backend<Mock>, data: &[f32]) -> SharedMemory<f32> {
+        let mut mem = SharedMemory::<f32>::new(backend.device(), data.len());
+        if let &mut MemoryType::Native(ref mut flat) = mem.get_mut(backend.device()).unwrap() {
+            flat.as_mut_slice::<f32>().copy_from_slice(data);
+        }
+        mem
+    }
+
+    #[test]
+    fn it_records_the_issued_operations() {
+        let mock = Mock::new();
+        let backend = Backend::new(BackendConfig::all(mock.clone())).unwrap();
+        let mut a = filled(&backend, &[2f32]);
+        let mut x = filled(&backend, &[1f32, 2f32]);
+        let mut y = filled(&backend, &[0f32, 0f32]);
+        backend.axpy(&mut a, &mut x, &mut y).unwrap();
+        backend.reduce(&mut y, &mut a, ReduceOp::Max).unwrap();
+        let calls = mock.calls();
+        assert_eq!(2, calls.len());
+        assert_eq!("axpy", calls[0].operation);
+        assert_eq!(vec![2f64], calls[0].arguments[0]);
+        assert_eq!(vec![1f64, 2f64], calls[0].arguments[1]);
+        assert_eq!("reduce", calls[1].operation);
+        assert_eq!(vec![format!("Max")], calls[1].options);
+        mock.clear_calls();
+        assert!(mock.calls().is_empty());
+    }
+
+    #[test]
+    fn it_computes_the_issued_operations_on_the_host() {
+        let backend = Backend::new(BackendConfig::all(Mock::new())).unwrap();
+        let mut a = filled(&backend, &[2f32]);
+        let mut x = filled(&backend, &[1f32, 2f32]);
+        backend.scale(&mut a, &mut x).unwrap();
+        if let &MemoryType::Native(ref flat) = x.get(backend.device()).unwrap() {
+            assert_eq!(&[2f32, 4f32], flat.as_slice::<f32>());
+        }
+    }
+
+    #[test]
+    fn it_fails_the_next_operation_on_request() {
+        let mock = Mock::new();
+        let backend = Backend::new(BackendConfig::all(mock.clone())).unwrap();
+        let mut x = filled(&backend, &[1f32, 2f32]);
+        let mut result = filled(&backend, &[0f32]);
+        mock.fail_next(Error::InvalidOperation(format!("injected")));
+        assert!(backend.asum(&mut x, &mut result).is_err());
+        assert!(backend.asum(&mut x, &mut result).is_ok());
+        assert_eq!(2, mock.calls().len());
+    }
+}
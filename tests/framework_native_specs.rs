@@ -4,11 +4,209 @@ extern crate collenchyma as co;
 mod framework_native_spec {
 
     use co::framework::IFramework;
+    use co::hardware::{IHardware, HardwareType, Partition};
     use co::frameworks::Native;
+    use co::framework::Error as FrameworkError;
+    use co::frameworks::native::Error as NativeError;
+    #[cfg(feature = "opencl")]
+    use co::frameworks::OpenCL;
 
     #[test]
     fn it_works() {
         let frm = Native::new();
         assert_eq!(frm.hardwares().len(), 1);
     }
+
+    #[test]
+    fn it_initializes_without_panicking() {
+        let frm = Native::try_new().unwrap();
+        assert_eq!(frm.hardwares().len(), 1);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn it_reports_the_memory_of_the_host() {
+        let frm = Native::new();
+        assert!(frm.hardwares()[0].memory().unwrap() > 0);
+    }
+
+    #[test]
+    fn it_filters_hardwares_by_type() {
+        let frm = Native::new();
+        assert_eq!(frm.hardwares_of_type(HardwareType::CPU).len(), 1);
+        assert!(frm.hardwares_of_type(HardwareType::GPU).is_empty());
+    }
+
+    #[test]
+    fn it_iterates_lazily_over_hardwares() {
+        let frm = Native::new();
+        assert_eq!(frm.hardwares().len(), frm.hardware_iter().count());
+        let cpu = frm.hardware_iter().find(|hardware| hardware.hardware_type() == Some(HardwareType::CPU));
+        assert_eq!(frm.hardwares()[0].id(), cpu.unwrap().id());
+    }
+
+    #[test]
+    fn it_converts_native_errors_into_framework_errors() {
+        let err: FrameworkError = From::from(NativeError::Other(format!("test")));
+        match err {
+            FrameworkError::Native(_) => {},
+            _ => panic!("expected a Native framework error"),
+        }
+    }
+
+    #[test]
+    fn it_allocates_and_frees_memory_through_the_device_type() {
+        let frm = Native::new();
+        let device = frm.new_device(frm.hardwares()).unwrap();
+        let memory = device.alloc_memory(16).unwrap();
+        assert_eq!(4, memory.as_native().unwrap().as_slice::<f32>().len());
+        assert!(memory.as_opencl().is_none());
+        assert!(memory.as_cuda().is_none());
+        assert!(device.free_memory(memory).is_ok());
+        assert!(device.alloc_memory(0).is_err());
+    }
+
+    #[test]
+    fn it_rejects_allocations_beyond_the_address_space() {
+        let frm = Native::new();
+        let device = frm.new_device(frm.hardwares()).unwrap();
+        match device.alloc_memory(::std::usize::MAX) {
+            Err(FrameworkError::Native(NativeError::InvalidMemory(_))) => {},
+            other => panic!("expected an invalid memory error, got {:?}", other),
+        }
+        match device.alloc_memory_aligned(::std::usize::MAX, 64) {
+            Err(FrameworkError::Native(NativeError::InvalidMemory(_))) => {},
+            other => panic!("expected an invalid memory error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn it_allocates_aligned_memory_through_the_device_type() {
+        let frm = Native::new();
+        let device = frm.new_device(frm.hardwares()).unwrap();
+        let memory = device.alloc_memory_aligned(100, 256).unwrap();
+        let slice = memory.as_native().unwrap().as_slice::<u8>();
+        assert_eq!(100, slice.len());
+        assert_eq!(0, slice.as_ptr() as usize % 256);
+        assert!(device.alloc_memory_aligned(100, 24).is_err());
+        assert!(device.alloc_memory_aligned(100, 0).is_err());
+    }
+
+    #[test]
+    fn it_copies_bytes_in_and_out_of_device_memory() {
+        let frm = Native::new();
+        let device = frm.new_device(frm.hardwares()).unwrap();
+        let mut memory = device.alloc_memory(4).unwrap();
+        device.sync_in(&[1u8, 2, 3, 4], &mut memory).unwrap();
+        let mut host = [0u8; 4];
+        device.sync_out(&memory, &mut host).unwrap();
+        assert_eq!([1u8, 2, 3, 4], host);
+        assert!(device.sync_in(&[1u8, 2], &mut memory).is_err());
+    }
+
+    #[test]
+    fn it_copies_memory_between_device_memories() {
+        let frm = Native::new();
+        let device = frm.new_device(frm.hardwares()).unwrap();
+        let mut src = device.alloc_memory(4).unwrap();
+        let mut dst = device.alloc_memory(4).unwrap();
+        device.sync_in(&[1u8, 2, 3, 4], &mut src).unwrap();
+        device.sync_between(&src, &device, &mut dst).unwrap();
+        assert_eq!(&[1u8, 2, 3, 4], dst.as_native().unwrap().as_slice::<u8>());
+        let mut too_small = device.alloc_memory(2).unwrap();
+        assert!(device.sync_between(&src, &device, &mut too_small).is_err());
+    }
+
+    #[test]
+    fn it_sets_and_fills_device_memory() {
+        let frm = Native::new();
+        let device = frm.new_device(frm.hardwares()).unwrap();
+        let mut memory = device.alloc_memory(8).unwrap();
+        device.memset(&mut memory, 7).unwrap();
+        assert_eq!(&[7u8; 8], memory.as_native().unwrap().as_slice::<u8>());
+        device.fill(&mut memory, 1.5f32).unwrap();
+        assert_eq!(&[1.5f32, 1.5f32], memory.as_native().unwrap().as_slice::<f32>());
+        let mut odd = device.alloc_memory(6).unwrap();
+        assert!(device.fill(&mut odd, 1f32).is_err());
+    }
+
+    #[test]
+    fn it_does_not_partition_native_hardware() {
+        let ntv = Native::new();
+        match ntv.hardwares()[0].create_sub_devices(Partition::Equally(1)) {
+            Err(FrameworkError::NotSupported(_)) => {},
+            _ => panic!("expected a NotSupported error"),
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "opencl")]
+    fn it_refuses_to_copy_memory_of_another_framework() {
+        let ntv = Native::new();
+        let cpu = ntv.new_device(ntv.hardwares()).unwrap();
+        let cl = OpenCL::new();
+        let cl_device = cl.new_device(cl.hardwares()[0..1].to_vec()).unwrap();
+        let src = cl_device.alloc_memory(4).unwrap();
+        let mut dst = cl_device.alloc_memory(4).unwrap();
+        assert!(!cpu.owns_memory(&src));
+        assert!(cpu.sync_between(&src, &cpu, &mut dst).is_err());
+        assert!(cpu.sync_between(&src, &cl_device, &mut dst).is_err());
+        assert!(cpu.free_memory(src).is_err());
+    }
+}
+
+#[cfg(all(test, feature = "test-util"))]
+mod framework_native_failure_spec {
+
+    use co::backend::{Backend, BackendConfig};
+    use co::framework::IFramework;
+    use co::framework::Error as FrameworkError;
+    use co::frameworks::Native;
+    use co::frameworks::native::Failure;
+    use co::shared_memory::{SharedMemory, Error as SharedMemoryError};
+    #[cfg(feature = "opencl")]
+    use co::frameworks::OpenCL;
+
+    #[test]
+    fn it_fails_the_next_allocation_on_request() {
+        let backend = Backend::new(BackendConfig::all(Native::new())).unwrap();
+        backend.inject_failure(Failure::Alloc, FrameworkError::InvalidMemoryAllocation(format!("injected")));
+        assert!(backend.device().alloc_memory(4).is_err());
+        assert!(backend.device().alloc_memory(4).is_ok());
+    }
+
+    #[test]
+    fn it_returns_injected_allocation_failures_from_shared_memory() {
+        let backend = Backend::new(BackendConfig::all(Native::new())).unwrap();
+        backend.inject_failure(Failure::Alloc, FrameworkError::InvalidMemoryAllocation(format!("injected")));
+        match SharedMemory::<f32>::try_new(backend.device(), 4) {
+            Err(SharedMemoryError::MemoryAllocationFailed(FrameworkError::InvalidMemoryAllocation(_))) => {},
+            _ => panic!("expected the injected error"),
+        }
+        assert!(SharedMemory::<f32>::try_new(backend.device(), 4).is_ok());
+    }
+
+    #[test]
+    fn it_returns_injected_allocation_failures_from_from_slice() {
+        let backend = Backend::new(BackendConfig::all(Native::new())).unwrap();
+        backend.inject_failure(Failure::Alloc, FrameworkError::InvalidMemoryAllocation(format!("injected")));
+        assert!(SharedMemory::<f32>::from_slice(backend.device(), &[1f32, 2f32]).is_err());
+        assert_eq!(vec![1f32, 2f32], SharedMemory::<f32>::from_slice(backend.device(), &[1f32, 2f32]).unwrap().to_vec().unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "opencl")]
+    fn it_fails_the_next_sync_on_request() {
+        let backend = Backend::new(BackendConfig::all(Native::new())).unwrap();
+        let cl = OpenCL::new();
+        let cl_device = cl.new_device(cl.hardwares()[0..1].to_vec()).unwrap();
+        let mut mem = SharedMemory::<f32>::new(backend.device(), 4);
+        mem.add_device(&cl_device).unwrap();
+        backend.inject_failure(Failure::Sync, FrameworkError::NotSupported(format!("injected")));
+        match mem.sync(&cl_device) {
+            Err(SharedMemoryError::MemorySynchronizationFailed(FrameworkError::NotSupported(_))) => {},
+            _ => panic!("expected the injected error"),
+        }
+        assert!(mem.sync(&cl_device).is_ok());
+    }
 }
@@ -1,13 +1,19 @@
 extern crate collenchyma as co;
 extern crate libc;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "opencl"))]
 mod framework_opencl_spec {
 
     use co::framework::IFramework;
     use co::frameworks::OpenCL;
+    use co::hardware::{IHardware, Partition};
+    use co::framework::Error as FrameworkError;
     use co::device::DeviceType;
     use co::frameworks::opencl::memory::*;
+    use co::frameworks::opencl::{API, DeviceInfo, Event, Program, KernelArg, QueueFlags, QUEUE_OUT_OF_ORDER_EXEC_MODE_ENABLE};
+    use co::memory::MemoryType;
+    use co::frameworks::opencl::program::templated_source;
+    use co::frameworks::opencl::Error as OpenCLError;
 
     #[test]
     fn it_works() {
@@ -15,6 +21,46 @@ mod framework_opencl_spec {
         println!("{:?}", frm.hardwares());
     }
 
+    #[test]
+    fn it_reads_device_infos_by_their_size() {
+        assert_eq!(Some(256), DeviceInfo::new(vec![0, 1, 0, 0]).to_usize());
+        assert_eq!(Some(256), DeviceInfo::new(vec![0, 1, 0, 0, 0, 0, 0, 0]).to_usize());
+        assert_eq!(None, DeviceInfo::new(vec![0, 1]).to_usize());
+        assert_eq!(None, DeviceInfo::new(Vec::new()).to_usize());
+    }
+
+    #[test]
+    fn it_returns_hardwares_in_a_stable_order() {
+        let frm = OpenCL::new();
+        let ids: Vec<isize> = frm.hardwares().iter().map(|hardware| hardware.id()).collect();
+        let ids_again: Vec<isize> = frm.hardwares().iter().map(|hardware| hardware.id()).collect();
+        assert_eq!(ids, ids_again);
+    }
+
+    #[test]
+    fn it_ranks_hardwares_by_compute_units() {
+        let frm = OpenCL::new();
+        let ranked = frm.hardwares_ranked();
+        assert_eq!(frm.hardwares().len(), ranked.len());
+        for pair in ranked.windows(2) {
+            assert!(pair[0].compute_units().unwrap_or(0) >= pair[1].compute_units().unwrap_or(0));
+        }
+        let by_memory = frm.hardwares_ranked_by(|hardware| hardware.memory());
+        for pair in by_memory.windows(2) {
+            assert!(pair[0].memory() >= pair[1].memory());
+        }
+    }
+
+    #[test]
+    fn it_lists_and_selects_platforms() {
+        let platforms = OpenCL::platforms().unwrap();
+        assert!(!platforms.is_empty());
+        assert!(!platforms[0].name.is_empty());
+        let frm = OpenCL::with_platform(0).unwrap();
+        assert!(!frm.hardwares().is_empty());
+        assert!(OpenCL::with_platform(platforms.len()).is_err());
+    }
+
     #[test]
     fn it_creates_context() {
         let frm = OpenCL::new();
@@ -22,14 +68,251 @@ mod framework_opencl_spec {
         println!("{:?}", frm.new_device(hardwares));
     }
 
-    // #[test]
-    // #[allow(unused_must_use)]
-    // fn it_allocates_memory() {
-    //     let vec_a = vec![0isize, 1, 2, -3, 4, 5, 6, 7];
-    //     let frm = OpenCL::new();
-    //     if let DeviceType::OpenCL(ctx) = frm.new_device(frm.hardwares()[0..1].to_vec()).unwrap() {
-    //         // OpenCL memory
-    //         Memory::new(ctx.id_c(), vec_a.len());
-    //     }
-    // }
+    #[test]
+    fn it_creates_context_with_queue_flags() {
+        let frm = OpenCL::new();
+        let hardware = frm.hardwares()[0].clone();
+        let supported = hardware.queue_flags().unwrap();
+        match frm.new_device_with_queue_flags(vec![hardware], QUEUE_OUT_OF_ORDER_EXEC_MODE_ENABLE) {
+            Ok(DeviceType::OpenCL(context)) => {
+                assert!(supported.contains(QUEUE_OUT_OF_ORDER_EXEC_MODE_ENABLE));
+                assert!(context.queue().unwrap().flags().unwrap().contains(QUEUE_OUT_OF_ORDER_EXEC_MODE_ENABLE));
+            },
+            Ok(_) => panic!("expected an OpenCL device"),
+            Err(_) => assert!(!supported.contains(QUEUE_OUT_OF_ORDER_EXEC_MODE_ENABLE)),
+        }
+        match frm.new_device(frm.hardwares()[0..1].to_vec()).unwrap() {
+            DeviceType::OpenCL(context) => assert_eq!(QueueFlags::empty(), context.queue().unwrap().flags().unwrap()),
+            _ => panic!("expected an OpenCL device"),
+        }
+    }
+
+    #[test]
+    fn it_allocates_memory() {
+        let vec_a = vec![0isize, 1, 2, -3, 4, 5, 6, 7];
+        let frm = OpenCL::new();
+        if let DeviceType::OpenCL(ctx) = frm.new_device(frm.hardwares()[0..1].to_vec()).unwrap() {
+            // OpenCL memory
+            let mem = Memory::new(&ctx, vec_a.len()).unwrap();
+            assert_eq!(vec_a.len(), mem.size());
+        }
+    }
+
+    #[test]
+    fn it_allocates_aligned_memory_up_to_the_base_address_alignment() {
+        let frm = OpenCL::new();
+        let hardware = frm.hardwares()[0].clone();
+        let device = frm.new_device(vec![hardware.clone()]).unwrap();
+        let base_align = hardware.mem_base_addr_align().unwrap();
+        let memory = device.alloc_memory_aligned(100, base_align).unwrap();
+        assert_eq!(100, memory.size());
+        assert!(device.alloc_memory_aligned(100, base_align * 2).is_err());
+        assert!(device.alloc_memory_aligned(100, 24).is_err());
+    }
+
+    #[test]
+    fn it_returns_allocation_failures_instead_of_panicking() {
+        let frm = OpenCL::new();
+        let device = frm.new_device(frm.hardwares()[0..1].to_vec()).unwrap();
+        assert!(device.alloc_memory(::std::usize::MAX / 2).is_err());
+    }
+
+    #[test]
+    fn it_builds_the_blas_program() {
+        let frm = OpenCL::new();
+        let device = frm.new_device(frm.hardwares()[0..1].to_vec()).unwrap();
+        assert!(frm.new_binary(&device).is_ok());
+    }
+
+    #[test]
+    fn it_builds_the_blas_program_with_build_options() {
+        let mut frm = OpenCL::new();
+        frm.set_build_options("-cl-fast-relaxed-math -cl-mad-enable");
+        let device = frm.new_device(frm.hardwares()[0..1].to_vec()).unwrap();
+        assert!(frm.new_binary(&device).is_ok());
+        if let DeviceType::OpenCL(ref ctx) = device {
+            match Program::with_build_options(ctx, "-cl-no-such-option") {
+                Err(OpenCLError::InvalidBuildOptions(log)) => assert!(log.contains("-cl-no-such-option")),
+                // some drivers report unknown options as a failing build
+                Err(OpenCLError::BuildProgramFailure(_)) => {},
+                _ => panic!("expected an InvalidBuildOptions error"),
+            }
+        }
+    }
+
+    #[test]
+    fn it_builds_fp64_kernels_only_if_supported() {
+        let frm = OpenCL::new();
+        let hardwares = frm.hardwares()[0..1].to_vec();
+        let supports_fp64 = hardwares[0].supports_fp64();
+        let device = frm.new_device(hardwares).unwrap();
+        let program = frm.new_binary(&device).unwrap();
+        assert_eq!(supports_fp64, program.blas_double.is_some());
+        assert_eq!(supports_fp64, program.blas_kernels::<f64>().is_some());
+    }
+
+    #[test]
+    fn it_instantiates_kernel_templates_per_element_type() {
+        let template = "__kernel void $Pfill(__global $T *x) { x[0] = ($T)1; }";
+        assert_eq!("__kernel void sfill(__global float *x) { x[0] = (float)1; }", templated_source::<f32>(template));
+        let double = templated_source::<f64>(template);
+        assert!(double.starts_with("#pragma OPENCL EXTENSION cl_khr_fp64 : enable\n"));
+        assert!(double.ends_with("__kernel void dfill(__global double *x) { x[0] = (double)1; }"));
+    }
+
+    #[test]
+    fn it_builds_and_runs_custom_kernels() {
+        let frm = OpenCL::new();
+        if let DeviceType::OpenCL(ctx) = frm.new_device(frm.hardwares()[0..1].to_vec()).unwrap() {
+            let source = "__kernel void fill(__global float *x, const float value) { x[get_global_id(0)] = value; }";
+            let program = Program::from_source(&ctx, source, &["fill"]).unwrap();
+            assert!(program.kernel("fill").is_some());
+            let memory = MemoryType::OpenCL(Memory::new(&ctx, 16).unwrap());
+            assert!(program.run("fill", &[KernelArg::Mem(&memory), KernelArg::Scalar(1f32)], &[4], &[]).is_ok());
+            assert!(program.run("missing", &[], &[4], &[]).is_err());
+        }
+    }
+
+    #[test]
+    fn it_orders_commands_through_events() {
+        let frm = OpenCL::new();
+        if let DeviceType::OpenCL(ctx) = frm.new_device(frm.hardwares()[0..1].to_vec()).unwrap() {
+            let source = "__kernel void fill(__global float *x, const float value) { x[get_global_id(0)] = value; }";
+            let program = Program::from_source(&ctx, source, &["fill"]).unwrap();
+            let src = MemoryType::OpenCL(Memory::new(&ctx, 16).unwrap());
+            let dst = Memory::new(&ctx, 16).unwrap();
+            let fill = program.run_with_events("fill", &[KernelArg::Mem(&src), KernelArg::Scalar(2f32)], &[4], &[], &[]).unwrap();
+            let copy = API::enqueue_copy_buffer(ctx.queue().unwrap(), src.as_opencl().unwrap(), &dst, 16, &[&fill]).unwrap();
+            Event::wait_all(&[&fill, &copy]).unwrap();
+            let mut host = [0f32; 4];
+            API::read_from_memory(ctx.queue().unwrap(), &dst, host.as_mut_ptr() as *mut libc::c_void, 16).unwrap();
+            assert_eq!([2f32; 4], host);
+        }
+    }
+
+    #[test]
+    fn it_fills_device_memory() {
+        let frm = OpenCL::new();
+        let device = frm.new_device(frm.hardwares()[0..1].to_vec()).unwrap();
+        let mut memory = device.alloc_memory(16).unwrap();
+        device.fill(&mut memory, 3f32).unwrap();
+        if let DeviceType::OpenCL(ref ctx) = device {
+            let mut host = [0f32; 4];
+            API::read_from_memory(ctx.queue().unwrap(), memory.as_opencl().unwrap(), host.as_mut_ptr() as *mut libc::c_void, 16).unwrap();
+            assert_eq!([3f32; 4], host);
+        }
+    }
+
+    #[test]
+    fn it_fills_device_memory_repeatedly() {
+        let frm = OpenCL::new();
+        let device = frm.new_device(frm.hardwares()[0..1].to_vec()).unwrap();
+        let clone = device.clone();
+        let mut memory = device.alloc_memory(16).unwrap();
+        for value in 0..3 {
+            device.fill(&mut memory, value as f32).unwrap();
+            clone.fill(&mut memory, value as f32 + 1f32).unwrap();
+            if let DeviceType::OpenCL(ref ctx) = device {
+                let mut host = [0f32; 4];
+                API::read_from_memory(ctx.queue().unwrap(), memory.as_opencl().unwrap(), host.as_mut_ptr() as *mut libc::c_void, 16).unwrap();
+                assert_eq!([value as f32 + 1f32; 4], host);
+            }
+        }
+    }
+
+    #[test]
+    fn it_partitions_hardware_into_sub_devices() {
+        let frm = OpenCL::new();
+        let hardware = frm.hardwares()[0].clone();
+        match hardware.create_sub_devices(Partition::Equally(1)) {
+            Ok(sub_devices) => {
+                assert!(!sub_devices.is_empty());
+                for sub_device in &sub_devices {
+                    assert_eq!(Some(1), sub_device.compute_units());
+                    assert!(sub_device.is_sub_device());
+                }
+                assert!(!hardware.is_sub_device());
+                assert!(frm.new_device(sub_devices[0..1].to_vec()).is_ok());
+            },
+            // not every device can be partitioned
+            Err(FrameworkError::OpenCL(OpenCLError::DevicePartitionFailed(_))) |
+            Err(FrameworkError::OpenCL(OpenCLError::InvalidValue(_))) => {},
+            Err(err) => panic!("unexpected error: {:?}", err),
+        }
+    }
+
+    #[test]
+    fn it_clamps_work_group_sizes_to_the_kernel_maximum() {
+        let frm = OpenCL::new();
+        let hardwares = frm.hardwares()[0..1].to_vec();
+        let device = hardwares[0].clone();
+        if let DeviceType::OpenCL(ctx) = frm.new_device(hardwares).unwrap() {
+            let source = "__kernel void fill(__global float *x, const float value) { x[get_global_id(0)] = value; }";
+            let program = Program::from_source(&ctx, source, &["fill"]).unwrap();
+            let kernel = program.kernel("fill").unwrap();
+            let max = program.kernel_work_group_size(kernel, &device).unwrap();
+            assert!(max > 0);
+            assert!(max <= device.max_work_group_size().unwrap());
+            assert_eq!(max, kernel.clamp_work_group_size(max + 1).unwrap());
+            assert_eq!(1, kernel.clamp_work_group_size(1).unwrap());
+        }
+    }
+
+    #[test]
+    fn it_rejects_mismatching_kernel_arguments() {
+        let frm = OpenCL::new();
+        if let DeviceType::OpenCL(ctx) = frm.new_device(frm.hardwares()[0..1].to_vec()).unwrap() {
+            let source = "__kernel void fill(__global float *x, const float value) { x[get_global_id(0)] = value; }";
+            let program = Program::from_source(&ctx, source, &["fill"]).unwrap();
+            match program.set_args("fill", &[KernelArg::Scalar(1f32)]) {
+                Err(OpenCLError::InvalidKernelArgs(_)) => {},
+                _ => panic!("expected an InvalidKernelArgs error"),
+            }
+        }
+    }
+
+    #[test]
+    fn it_returns_the_build_log_for_invalid_source() {
+        let frm = OpenCL::new();
+        if let DeviceType::OpenCL(ctx) = frm.new_device(frm.hardwares()[0..1].to_vec()).unwrap() {
+            match Program::from_source(&ctx, "__kernel void broken( {", &["broken"]) {
+                Err(OpenCLError::BuildProgramFailure(log)) => assert!(log.len() > 0),
+                _ => panic!("expected a BuildProgramFailure"),
+            }
+        }
+    }
+
+    #[test]
+    fn it_reports_the_status_code_of_errors() {
+        let err = OpenCLError::InvalidContext(format!("test"));
+        assert_eq!(Some(-34), err.code());
+        assert_eq!("CL_INVALID_CONTEXT (-34): test", format!("{}", err));
+        assert_eq!(None, OpenCLError::Other(format!("test")).code());
+        assert_eq!(Some(-11), OpenCLError::BuildProgramFailure(format!("test")).code());
+    }
+
+    #[test]
+    fn it_maps_status_codes_to_errors() {
+        assert!(OpenCLError::from_status(0, "clCreateContext").is_none());
+        match OpenCLError::from_status(-34, "clCreateContext") {
+            Some(OpenCLError::InvalidContext(_)) => {},
+            _ => panic!("expected an InvalidContext error"),
+        }
+        match OpenCLError::from_status(-4242, "clCreateContext") {
+            Some(OpenCLError::Other(msg)) => assert!(msg.contains("-4242")),
+            _ => panic!("expected an Other error"),
+        }
+    }
+
+    #[test]
+    fn it_maps_status_codes_of_failed_calls_to_errors() {
+        match OpenCLError::from_failed_status(-5, "clCreateBuffer") {
+            OpenCLError::OutOfResources(_) => {},
+            _ => panic!("expected an OutOfResources error"),
+        }
+        match OpenCLError::from_failed_status(0, "clCreateBuffer") {
+            OpenCLError::Other(msg) => assert!(msg.contains("clCreateBuffer")),
+            _ => panic!("expected an Other error"),
+        }
+    }
 }
@@ -1,7 +1,7 @@
 extern crate collenchyma as co;
 extern crate libc;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "opencl"))]
 mod hardware_spec {
     use co::hardware::{IHardware, HardwareType};
     use co::frameworks::opencl::Device;
@@ -0,0 +1,109 @@
+extern crate collenchyma as co;
+
+#[cfg(test)]
+mod library_spec {
+
+    use co::backend::{Backend, BackendConfig, LibraryBackend};
+    use co::binary::IBinary;
+    use co::device::DeviceType;
+    use co::error::Error;
+    use co::framework::{IFramework, Error as FrameworkError};
+    use co::frameworks::Native;
+    use co::libraries::ILibrary;
+    use co::memory::MemoryType;
+    use co::operation::IOperation;
+    use co::shared_memory::{SharedMemory, Error as SharedMemoryError};
+
+    // A tiny custom library, which doubles the elements of a vector, laid out like BLAS.
+
+    /// Describes the Double Operation.
+    trait IOperationDouble {
+        fn compute(&self, x: &mut MemoryType) -> Result<(), Error>;
+    }
+
+    /// Describes the binary of the library.
+    trait IDoubleBinary {
+        type Double: IOperationDouble;
+
+        fn double(&self) -> Self::Double;
+    }
+
+    /// Describes the library.
+    trait IDouble {
+        type B: IDoubleBinary + IBinary;
+
+        fn double(&self, x: &mut SharedMemory<f32>) -> Result<(), Error> {
+            match x.add_device(self.device()) { _ => () }
+            let x_mem = try!(x.get_mut(self.device()));
+            self.binary().double().compute(x_mem)
+        }
+
+        fn binary(&self) -> Self::B;
+
+        fn device(&self) -> &DeviceType;
+    }
+
+    #[derive(Debug, Copy, Clone)]
+    /// The Native binary and operation of the library.
+    struct NativeDouble;
+
+    impl IBinary for NativeDouble {}
+
+    impl IOperation for NativeDouble {}
+
+    impl IOperationDouble for NativeDouble {
+        fn compute(&self, x: &mut MemoryType) -> Result<(), Error> {
+            let x_slice = try!(x.as_mut_native().ok_or(SharedMemoryError::MissingDestination(format!("Unable to receive native memory for `x`.")))).as_mut_slice::<f32>();
+            for xi in x_slice.iter_mut() {
+                *xi = *xi * 2f32;
+            }
+            Ok(())
+        }
+    }
+
+    impl IDoubleBinary for NativeDouble {
+        type Double = NativeDouble;
+
+        fn double(&self) -> NativeDouble {
+            *self
+        }
+    }
+
+    /// Registers the library for the Native framework.
+    struct Double;
+
+    impl ILibrary<Native> for Double {
+        type B = NativeDouble;
+
+        fn new_binary(_framework: &Native, _device: &DeviceType) -> Result<NativeDouble, FrameworkError> {
+            Ok(NativeDouble)
+        }
+    }
+
+    impl IDouble for LibraryBackend<Native, Double> {
+        type B = NativeDouble;
+
+        fn binary(&self) -> NativeDouble {
+            self.binary()
+        }
+
+        fn device(&self) -> &DeviceType {
+            self.device()
+        }
+    }
+
+    #[test]
+    fn it_computes_a_custom_library_operation() {
+        let backend = Backend::new(BackendConfig::all(Native::new())).unwrap();
+        let library = backend.library::<Double>().unwrap();
+        let mut x = SharedMemory::<f32>::new(backend.device(), 3);
+        if let &mut MemoryType::Native(ref mut mem) = x.get_mut(backend.device()).unwrap() {
+            mem.as_mut_slice::<f32>().copy_from_slice(&[1f32, 2f32, 3f32]);
+        }
+
+        library.double(&mut x).unwrap();
+        if let &MemoryType::Native(ref mem) = x.get(library.device()).unwrap() {
+            assert_eq!(&[2f32, 4f32, 6f32], mem.as_slice::<f32>());
+        }
+    }
+}
@@ -0,0 +1,56 @@
+extern crate collenchyma as co;
+
+#[cfg(test)]
+mod memory_pool_spec {
+
+    use co::memory_pool::MemoryPool;
+    #[cfg(feature = "opencl")]
+    use co::backend::{Backend, BackendConfig};
+    #[cfg(feature = "opencl")]
+    use co::frameworks::OpenCL;
+    #[cfg(feature = "opencl")]
+    use co::shared_memory::SharedMemory;
+
+    #[test]
+    fn it_hands_out_allocations_of_the_same_size() {
+        let pool = MemoryPool::new(1024);
+        assert!(pool.give_back(16, vec![1u8; 16]).is_none());
+        assert!(pool.take(32).is_none());
+        assert_eq!(Some(vec![1u8; 16]), pool.take(16));
+        assert!(pool.take(16).is_none());
+    }
+
+    #[test]
+    fn it_does_not_retain_more_than_its_cap() {
+        let pool = MemoryPool::new(24);
+        assert!(pool.give_back(16, vec![0u8; 16]).is_none());
+        assert_eq!(Some(vec![0u8; 16]), pool.give_back(16, vec![0u8; 16]));
+        assert_eq!(16, pool.retained());
+    }
+
+    #[test]
+    fn it_drains_all_allocations() {
+        let pool = MemoryPool::new(1024);
+        pool.give_back(16, vec![0u8; 16]);
+        pool.give_back(32, vec![0u8; 32]);
+        pool.clone().drain();
+        assert_eq!(0, pool.retained());
+        assert!(pool.take(16).is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "opencl")]
+    fn it_recycles_dropped_shared_memory_on_opencl() {
+        let backend = Backend::<OpenCL>::with_pool(BackendConfig::all(OpenCL::new()), 1 << 20).unwrap();
+        drop(SharedMemory::<f32>::new(backend.device(), 256));
+        assert_eq!(1024, backend.pooled_memory());
+        let mem = SharedMemory::<f32>::new(backend.device(), 256);
+        assert_eq!(0, backend.pooled_memory());
+        drop(mem.view(0, 16).unwrap());
+        drop(mem);
+        assert_eq!(0, backend.pooled_memory());
+        drop(SharedMemory::<f32>::new(backend.device(), 256));
+        backend.drain_pool();
+        assert_eq!(0, backend.pooled_memory());
+    }
+}
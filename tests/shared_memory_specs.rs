@@ -6,8 +6,16 @@ mod shared_memory_spec {
 
     use co::framework::IFramework;
     use co::frameworks::Native;
+    #[cfg(feature = "opencl")]
+    use co::frameworks::OpenCL;
+    #[cfg(feature = "opencl")]
+    use co::frameworks::opencl::Memory;
+    #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+    use co::frameworks::Cuda;
 
     use co::memory::MemoryType;
+    #[cfg(feature = "opencl")]
+    use co::device::DeviceType;
 
     use co::shared_memory::*;
 
@@ -29,4 +37,483 @@ mod shared_memory_spec {
         let shared_data = &mut SharedMemory::<f32>::new(&cpu_dev, 10);
         assert_eq!(&cpu_dev, shared_data.latest_device());
     }
+
+    #[test]
+    fn it_round_trips_host_data() {
+        let ntv = Native::new();
+        let cpu = ntv.new_device(ntv.hardwares()).unwrap();
+        let data = vec![1f64, 2f64, 3f64];
+        let shared_data = SharedMemory::from_slice(&cpu, &data).unwrap();
+        assert_eq!(3, shared_data.capacity());
+        assert_eq!(data, shared_data.to_vec().unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "opencl")]
+    fn it_round_trips_host_data_through_opencl() {
+        let cl = OpenCL::new();
+        let cl_device = cl.new_device(cl.hardwares()[0..1].to_vec()).unwrap();
+        let data: Vec<i32> = (0..64).collect();
+        let shared_data = SharedMemory::from_slice(&cl_device, &data).unwrap();
+        assert_eq!(&cl_device, shared_data.latest_device());
+        assert_eq!(data, shared_data.to_vec().unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "opencl")]
+    fn it_imports_an_external_opencl_buffer() {
+        let cl = OpenCL::new();
+        let cl_device = cl.new_device(cl.hardwares()[0..1].to_vec()).unwrap();
+        let external = match cl_device {
+            DeviceType::OpenCL(ref context) => Memory::new(context, 16).unwrap(),
+            _ => unreachable!(),
+        };
+        assert!(unsafe { MemoryType::from_raw_opencl(external.id_c(), 32, false) }.is_err());
+        let mut memory = unsafe { MemoryType::from_raw_opencl(external.id_c(), 16, true) }.unwrap();
+        drop(external);
+        cl_device.sync_in(&[1u8; 16], &mut memory).unwrap();
+        let shared_data = SharedMemory::<u32>::from_memory(&cl_device, memory).unwrap();
+        assert_eq!(4, shared_data.capacity());
+        assert_eq!(vec![0x01010101u32; 4], shared_data.to_vec().unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "opencl")]
+    fn it_exports_the_raw_opencl_buffer_of_the_latest_copy() {
+        let ntv = Native::new();
+        let cpu = ntv.new_device(ntv.hardwares()).unwrap();
+        let cl = OpenCL::new();
+        let cl_device = cl.new_device(cl.hardwares()[0..1].to_vec()).unwrap();
+        let mut shared_data = SharedMemory::<f32>::new(&cl_device, 4);
+        let raw = shared_data.as_raw_opencl(&cl_device).unwrap();
+        assert_eq!(Some(raw), shared_data.get(&cl_device).unwrap().as_opencl().map(|memory| memory.id_c()));
+        assert!(shared_data.as_raw_opencl(&cpu).is_none());
+        shared_data.add_device(&cpu).unwrap();
+        shared_data.get_mut(&cpu).unwrap();
+        assert!(shared_data.as_raw_opencl(&cl_device).is_none());
+    }
+
+    #[test]
+    fn it_refuses_to_import_memory_of_partial_elements() {
+        let ntv = Native::new();
+        let cpu = ntv.new_device(ntv.hardwares()).unwrap();
+        let memory = cpu.alloc_memory(6).unwrap();
+        assert!(SharedMemory::<u32>::from_memory(&cpu, memory).is_err());
+    }
+
+    #[test]
+    fn it_reads_a_range_of_host_data() {
+        let ntv = Native::new();
+        let cpu = ntv.new_device(ntv.hardwares()).unwrap();
+        let data = vec![1f32, 2f32, 3f32, 4f32];
+        let shared_data = SharedMemory::from_slice(&cpu, &data).unwrap();
+        assert_eq!(vec![2f32, 3f32], shared_data.read_range(1, 2).unwrap());
+        assert!(shared_data.read_range(3, 2).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "opencl")]
+    fn it_reads_a_range_of_host_data_through_opencl() {
+        let cl = OpenCL::new();
+        let cl_device = cl.new_device(cl.hardwares()[0..1].to_vec()).unwrap();
+        let data: Vec<i32> = (0..64).collect();
+        let shared_data = SharedMemory::from_slice(&cl_device, &data).unwrap();
+        assert_eq!(vec![0], shared_data.read_range(0, 1).unwrap());
+        assert_eq!(data[60..].to_vec(), shared_data.read_range(60, 4).unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "opencl")]
+    fn it_syncs_from_native_to_opencl_and_back() {
+        let cl = OpenCL::new();
+        let ntv = Native::new();
+        let cpu = ntv.new_device(ntv.hardwares()).unwrap();
+        let cl_device = cl.new_device(cl.hardwares()[0..1].to_vec()).unwrap();
+        let data: Vec<u8> = (0..32).collect();
+        let shared_data = &mut SharedMemory::<u8>::new(&cpu, data.len());
+        if let &mut MemoryType::Native(ref mut mem) = shared_data.get_mut(&cpu).unwrap() {
+            mem.as_mut_slice::<u8>().clone_from_slice(&data);
+        }
+        shared_data.add_device(&cl_device).unwrap();
+        shared_data.sync(&cl_device).unwrap();
+        assert_eq!(&cl_device, shared_data.latest_device());
+        // mark the OpenCL copy as the only up-to-date copy, so that the data has to come back from the device.
+        shared_data.get_mut(&cl_device).unwrap();
+        assert!(shared_data.get(&cpu).is_none());
+        shared_data.sync(&cpu).unwrap();
+        assert_eq!(&cpu, shared_data.latest_device());
+        if let &MemoryType::Native(ref mem) = shared_data.get(&cpu).unwrap() {
+            assert_eq!(&data[..], mem.as_slice::<u8>());
+        }
+    }
+
+    #[test]
+    #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+    fn it_syncs_from_native_to_cuda_and_back() {
+        let cu = Cuda::new();
+        let ntv = Native::new();
+        let cpu = ntv.new_device(ntv.hardwares()).unwrap();
+        let cu_device = cu.new_device(cu.hardwares()[0..1].to_vec()).unwrap();
+        let data: Vec<u8> = (0..32).collect();
+        let shared_data = &mut SharedMemory::<u8>::new(&cpu, data.len());
+        if let &mut MemoryType::Native(ref mut mem) = shared_data.get_mut(&cpu).unwrap() {
+            mem.as_mut_slice::<u8>().clone_from_slice(&data);
+        }
+        shared_data.add_device(&cu_device).unwrap();
+        shared_data.sync(&cu_device).unwrap();
+        assert_eq!(&cu_device, shared_data.latest_device());
+        // mark the Cuda copy as the only up-to-date copy, so that the data has to come back from the device.
+        shared_data.get_mut(&cu_device).unwrap();
+        assert!(shared_data.get(&cpu).is_none());
+        shared_data.sync(&cpu).unwrap();
+        if let &MemoryType::Native(ref mem) = shared_data.get(&cpu).unwrap() {
+            assert_eq!(&data[..], mem.as_slice::<u8>());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "opencl")]
+    fn it_syncs_asynchronously_from_native_to_opencl_and_back() {
+        let cl = OpenCL::new();
+        let ntv = Native::new();
+        let cpu = ntv.new_device(ntv.hardwares()).unwrap();
+        let cl_device = cl.new_device(cl.hardwares()[0..1].to_vec()).unwrap();
+        let queue = match cl_device {
+            DeviceType::OpenCL(ref context) => *context.queue().unwrap(),
+            _ => unreachable!()
+        };
+        let data: Vec<u8> = (0..32).collect();
+        let shared_data = &mut SharedMemory::<u8>::new(&cpu, data.len());
+        if let &mut MemoryType::Native(ref mut mem) = shared_data.get_mut(&cpu).unwrap() {
+            mem.as_mut_slice::<u8>().copy_from_slice(&data);
+        }
+        shared_data.add_device(&cl_device).unwrap();
+        unsafe { shared_data.sync_async(&cl_device, TransferQueue::OpenCL(queue)) }.unwrap().wait().unwrap();
+        assert_eq!(&cl_device, shared_data.latest_device());
+        shared_data.get_mut(&cl_device).unwrap();
+        unsafe { shared_data.sync_async(&cpu, TransferQueue::OpenCL(queue)) }.unwrap().wait().unwrap();
+        if let &MemoryType::Native(ref mem) = shared_data.get(&cpu).unwrap() {
+            assert_eq!(&data[..], mem.as_slice::<u8>());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "opencl")]
+    fn it_syncs_synchronously_on_a_native_transfer_queue() {
+        let cl = OpenCL::new();
+        let ntv = Native::new();
+        let cpu = ntv.new_device(ntv.hardwares()).unwrap();
+        let cl_device = cl.new_device(cl.hardwares()[0..1].to_vec()).unwrap();
+        let shared_data = &mut SharedMemory::<f32>::new(&cpu, 8);
+        shared_data.add_device(&cl_device).unwrap();
+        drop(unsafe { shared_data.sync_async(&cl_device, TransferQueue::Native) }.unwrap());
+        assert_eq!(&cl_device, shared_data.latest_device());
+        assert!(shared_data.get(&cl_device).is_some());
+    }
+
+    #[test]
+    #[cfg(feature = "opencl")]
+    fn it_keeps_copies_current_after_sync() {
+        let cl = OpenCL::new();
+        let ntv = Native::new();
+        let cpu = ntv.new_device(ntv.hardwares()).unwrap();
+        let cl_device = cl.new_device(cl.hardwares()[0..1].to_vec()).unwrap();
+        let shared_data = &mut SharedMemory::<f32>::new(&cpu, 10);
+        shared_data.add_device(&cl_device).unwrap();
+        assert!(shared_data.get(&cl_device).is_none());
+        shared_data.sync(&cl_device).unwrap();
+        // both copies hold the latest version, reading them again needs no synchronization.
+        assert!(shared_data.get(&cl_device).is_some());
+        assert!(shared_data.get(&cpu).is_some());
+        shared_data.sync(&cl_device).unwrap();
+        assert!(shared_data.get(&cpu).is_some());
+    }
+
+    #[test]
+    #[cfg(feature = "opencl")]
+    fn it_reports_the_current_copies_without_syncing() {
+        let cl = OpenCL::new();
+        let ntv = Native::new();
+        let cpu = ntv.new_device(ntv.hardwares()).unwrap();
+        let cl_device = cl.new_device(cl.hardwares()[0..1].to_vec()).unwrap();
+        let shared_data = &mut SharedMemory::<f32>::new(&cpu, 10);
+        assert!(!shared_data.is_current_on(&cl_device));
+        shared_data.add_device(&cl_device).unwrap();
+        assert!(shared_data.is_current_on(&cpu));
+        assert!(!shared_data.is_current_on(&cl_device));
+        shared_data.sync(&cl_device).unwrap();
+        assert!(shared_data.is_current_on(&cpu));
+        assert!(shared_data.is_current_on(&cl_device));
+        shared_data.get_mut(&cl_device).unwrap();
+        assert!(!shared_data.is_current_on(&cpu));
+        assert_eq!(&cl_device, shared_data.latest_device());
+    }
+
+    #[test]
+    #[cfg(feature = "opencl")]
+    fn it_returns_an_error_from_get_mut_without_a_copy() {
+        let cl = OpenCL::new();
+        let ntv = Native::new();
+        let cpu = ntv.new_device(ntv.hardwares()).unwrap();
+        let cl_device = cl.new_device(cl.hardwares()[0..1].to_vec()).unwrap();
+        let shared_data = &mut SharedMemory::<f32>::new(&cpu, 10);
+        match shared_data.get_mut(&cl_device) {
+            Err(Error::MissingDestination(_)) => {},
+            _ => panic!("expected a MissingDestination error"),
+        }
+        match shared_data.get_mut_write_only(&cl_device) {
+            Err(Error::MissingDestination(_)) => {},
+            _ => panic!("expected a MissingDestination error"),
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "opencl")]
+    fn it_makes_a_write_only_copy_the_latest_without_syncing() {
+        let cl = OpenCL::new();
+        let ntv = Native::new();
+        let cpu = ntv.new_device(ntv.hardwares()).unwrap();
+        let cl_device = cl.new_device(cl.hardwares()[0..1].to_vec()).unwrap();
+        let shared_data = &mut SharedMemory::<f32>::new(&cpu, 10);
+        shared_data.add_device(&cl_device).unwrap();
+        shared_data.get_mut_write_only(&cl_device).unwrap();
+        assert!(shared_data.is_current_on(&cl_device));
+        assert!(!shared_data.is_current_on(&cpu));
+    }
+
+    #[test]
+    #[cfg(feature = "opencl")]
+    fn it_allocates_full_capacity_on_added_device() {
+        let cl = OpenCL::new();
+        let ntv = Native::new();
+        let cpu = ntv.new_device(ntv.hardwares()).unwrap();
+        let cl_device = cl.new_device(cl.hardwares()[0..1].to_vec()).unwrap();
+        let shared_data = &mut SharedMemory::<f32>::new(&cpu, 10);
+        shared_data.add_device(&cl_device).unwrap();
+        if let &MemoryType::OpenCL(ref mem) = shared_data.get(&cl_device).unwrap() {
+            assert_eq!(10 * 4, mem.size());
+        }
+    }
+
+    #[test]
+    fn it_computes_buffer_bytes() {
+        assert_eq!(40, buffer_bytes::<f32>(10));
+        assert_eq!(80, buffer_bytes::<f64>(10));
+        assert_eq!(0, buffer_bytes::<f32>(0));
+        assert_eq!(Some(40), checked_buffer_bytes::<i32>(10));
+        assert_eq!(None, checked_buffer_bytes::<f32>(usize::max_value()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn it_refuses_to_allocate_overflowing_buffers() {
+        let ntv = Native::new();
+        let cpu = ntv.new_device(ntv.hardwares()).unwrap();
+        SharedMemory::<f64>::new(&cpu, usize::max_value() / 4);
+    }
+
+    #[test]
+    fn it_returns_an_error_for_overflowing_buffers() {
+        let ntv = Native::new();
+        let cpu = ntv.new_device(ntv.hardwares()).unwrap();
+        match SharedMemory::<f64>::try_new(&cpu, usize::max_value() / 4) {
+            Err(Error::InvalidMemorySize(_)) => {},
+            _ => panic!("expected an InvalidMemorySize error"),
+        }
+    }
+
+    #[test]
+    fn it_refuses_to_transfer_onto_the_same_device() {
+        let ntv = Native::new();
+        let cpu = ntv.new_device(ntv.hardwares()).unwrap();
+        let shared_data = &mut SharedMemory::<f32>::new(&cpu, 10);
+        assert!(shared_data.transfer(&cpu, &cpu).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "opencl")]
+    fn it_transfers_between_devices() {
+        let cl = OpenCL::new();
+        let ntv = Native::new();
+        let cpu = ntv.new_device(ntv.hardwares()).unwrap();
+        let cl_device = cl.new_device(cl.hardwares()[0..1].to_vec()).unwrap();
+        let data: Vec<u8> = (0..32).collect();
+        let shared_data = &mut SharedMemory::<u8>::new(&cpu, data.len());
+        if let &mut MemoryType::Native(ref mut mem) = shared_data.get_mut(&cpu).unwrap() {
+            mem.as_mut_slice::<u8>().clone_from_slice(&data);
+        }
+        shared_data.transfer(&cpu, &cl_device).unwrap();
+        assert_eq!(&cl_device, shared_data.latest_device());
+        shared_data.get_mut(&cl_device).unwrap();
+        assert!(shared_data.transfer(&cpu, &cl_device).is_err());
+        shared_data.transfer(&cl_device, &cpu).unwrap();
+        if let &MemoryType::Native(ref mem) = shared_data.get(&cpu).unwrap() {
+            assert_eq!(&data[..], mem.as_slice::<u8>());
+        }
+    }
+
+    #[test]
+    #[cfg(any(feature = "cuda", feature = "cuda-runtime"))]
+    fn it_transfers_between_native_and_cuda() {
+        let cu = Cuda::new();
+        let ntv = Native::new();
+        let cpu = ntv.new_device(ntv.hardwares()).unwrap();
+        let cu_device = cu.new_device(cu.hardwares()[0..1].to_vec()).unwrap();
+        let data: Vec<u8> = (0..32).collect();
+        let shared_data = &mut SharedMemory::<u8>::new(&cpu, data.len());
+        if let &mut MemoryType::Native(ref mut mem) = shared_data.get_mut(&cpu).unwrap() {
+            mem.as_mut_slice::<u8>().clone_from_slice(&data);
+        }
+        shared_data.transfer(&cpu, &cu_device).unwrap();
+        assert_eq!(&cu_device, shared_data.latest_device());
+        shared_data.get_mut(&cu_device).unwrap();
+        shared_data.transfer(&cu_device, &cpu).unwrap();
+        if let &MemoryType::Native(ref mem) = shared_data.get(&cpu).unwrap() {
+            assert_eq!(&data[..], mem.as_slice::<u8>());
+        }
+    }
+
+    #[test]
+    fn it_writes_through_a_view() {
+        let ntv = Native::new();
+        let cpu = ntv.new_device(ntv.hardwares()).unwrap();
+        let shared_data = &mut SharedMemory::<f32>::new(&cpu, 6);
+        if let &mut MemoryType::Native(ref mut mem) = shared_data.get_mut(&cpu).unwrap() {
+            mem.as_mut_slice::<f32>().copy_from_slice(&[0f32, 1f32, 2f32, 3f32, 4f32, 5f32]);
+        }
+        {
+            let view = &mut shared_data.view(2, 3).unwrap();
+            assert_eq!(3, view.capacity());
+            if let &mut MemoryType::Native(ref mut mem) = view.get_mut(&cpu).unwrap() {
+                assert_eq!(&[2f32, 3f32, 4f32], mem.as_slice::<f32>());
+                mem.as_mut_slice::<f32>()[0] = 7f32;
+            }
+        }
+        if let &MemoryType::Native(ref mem) = shared_data.get(&cpu).unwrap() {
+            assert_eq!(&[0f32, 1f32, 7f32, 3f32, 4f32, 5f32], mem.as_slice::<f32>());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "opencl")]
+    fn it_outdates_other_copies_after_writing_through_a_view() {
+        let cl = OpenCL::new();
+        let ntv = Native::new();
+        let cpu = ntv.new_device(ntv.hardwares()).unwrap();
+        let cl_device = cl.new_device(cl.hardwares()[0..1].to_vec()).unwrap();
+        let shared_data = &mut SharedMemory::<f32>::new(&cpu, 6);
+        shared_data.add_device(&cl_device).unwrap();
+        shared_data.sync(&cl_device).unwrap();
+        shared_data.sync(&cpu).unwrap();
+        assert!(shared_data.is_current_on(&cl_device));
+        {
+            let view = &mut shared_data.view(0, 2).unwrap();
+            view.get_mut(&cpu).unwrap();
+        }
+        assert_eq!(&cpu, shared_data.latest_device());
+        assert!(!shared_data.is_current_on(&cl_device));
+    }
+
+    #[test]
+    fn it_refuses_views_beyond_the_capacity() {
+        let ntv = Native::new();
+        let cpu = ntv.new_device(ntv.hardwares()).unwrap();
+        let shared_data = &mut SharedMemory::<f32>::new(&cpu, 6);
+        assert!(shared_data.view(4, 3).is_err());
+        assert!(shared_data.view(usize::max_value(), 2).is_err());
+        assert!(shared_data.view(6, 0).is_ok());
+    }
+
+    #[test]
+    #[cfg(feature = "opencl")]
+    fn it_refuses_misaligned_views_on_opencl() {
+        let cl = OpenCL::new();
+        let cl_device = cl.new_device(cl.hardwares()[0..1].to_vec()).unwrap();
+        let shared_data = &mut SharedMemory::<u8>::new(&cl_device, 1024);
+        assert!(shared_data.view(1, 16).is_err());
+        assert_eq!(16, shared_data.view(0, 16).unwrap().capacity());
+    }
+}
+
+#[cfg(all(test, feature = "alloc_tracking"))]
+mod shared_memory_alloc_tracking_spec {
+
+    use co::backend::{Backend, BackendConfig};
+    use co::frameworks::Native;
+    #[cfg(feature = "opencl")]
+    use co::frameworks::OpenCL;
+    use co::shared_memory::SharedMemory;
+
+    #[test]
+    fn it_reports_live_allocations_until_dropped() {
+        let backend = Backend::new(BackendConfig::all(Native::new())).unwrap();
+        let is_tracked = |backend: &Backend<Native>| backend.memory_report().iter().any(|info| info.size == 4 * 1237);
+        let mem = SharedMemory::<f32>::new(backend.device(), 1237);
+        assert!(is_tracked(&backend));
+        let view = mem.view(0, 7).unwrap();
+        assert_eq!(1, backend.memory_report().iter().filter(|info| info.size == 4 * 1237).count());
+        drop(view);
+        drop(mem);
+        assert!(!is_tracked(&backend));
+    }
+
+    #[test]
+    fn it_reports_labeled_device_allocations() {
+        let backend = Backend::new(BackendConfig::all(Native::new())).unwrap();
+        let memory = backend.device().alloc_memory_labeled(4211, || format!("accumulator")).unwrap();
+        assert!(backend.memory_report().iter().any(|info| info.size == 4211 && info.label == "accumulator"));
+        drop(memory);
+        assert!(!backend.memory_report().iter().any(|info| info.size == 4211));
+    }
+
+    #[test]
+    #[cfg(feature = "opencl")]
+    fn it_reports_pooled_allocations() {
+        let backend = Backend::new(BackendConfig::all(OpenCL::new())).unwrap();
+        let mut device = backend.device().clone();
+        device.enable_pool(1 << 20);
+        let memory = device.alloc_memory_labeled(4213, || format!("activations")).unwrap();
+        device.recycle_memory(memory);
+        assert!(backend.memory_report().iter().any(|info| info.size == 4213 && info.label == "pooled"));
+        device.drain_pool();
+        assert!(!backend.memory_report().iter().any(|info| info.size == 4213));
+    }
+}
+
+#[cfg(all(test, feature = "stats", feature = "opencl"))]
+mod shared_memory_stats_spec {
+
+    use co::backend::{Backend, BackendConfig};
+    use co::frameworks::{Native, OpenCL};
+    use co::framework::IFramework;
+    use co::shared_memory::SharedMemory;
+
+    #[test]
+    fn it_counts_transfers_and_allocations() {
+        let backend = Backend::new(BackendConfig::all(OpenCL::new())).unwrap();
+        let native = Native::new();
+        let cpu = native.new_device(native.hardwares()).unwrap();
+        backend.reset_stats();
+        let mut mem = SharedMemory::<f32>::new(&cpu, 256);
+        mem.add_device(backend.device()).unwrap();
+        mem.sync(backend.device()).unwrap();
+        mem.get_mut(backend.device()).unwrap();
+        mem.sync(&cpu).unwrap();
+        let stats = backend.stats();
+        assert!(stats.bytes_to_device >= 4 * 256);
+        assert!(stats.bytes_to_host >= 4 * 256);
+        assert!(stats.bytes_allocated >= 4 * 256);
+    }
+
+    #[test]
+    fn it_does_not_transfer_write_only_copies() {
+        let backend = Backend::new(BackendConfig::all(OpenCL::new())).unwrap();
+        let native = Native::new();
+        let cpu = native.new_device(native.hardwares()).unwrap();
+        let mut mem = SharedMemory::<f32>::new(&cpu, 256);
+        mem.add_device(backend.device()).unwrap();
+        backend.reset_stats();
+        mem.get_mut_write_only(backend.device()).unwrap();
+        assert_eq!(0, backend.stats().bytes_to_device);
+    }
 }